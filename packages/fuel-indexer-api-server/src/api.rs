@@ -1,37 +1,47 @@
 use crate::{
     auth::AuthenticationMiddleware,
+    rate_limit::RateLimitMiddleware,
+    tls::{load_tls_config, serve_tls},
     uses::{
-        get_nonce, health_check, metrics, query_graph, register_indexer_assets,
-        revert_indexer, stop_indexer, verify_signature,
+        get_nonce, graphql_playground, health_check, indexer_assets, indexer_stats,
+        indexer_status, introspect_graph, metrics, pause_indexer, query_graph, query_sql,
+        register_indexer_assets, replay_indexer, resume_indexer, revert_indexer,
+        rollback_indexer, stop_indexer, subscribe_graph, verify_signature, StreamSlots,
     },
 };
 use async_std::sync::{Arc, RwLock};
 use axum::{
-    extract::{Extension, Json},
-    http::StatusCode,
+    extract::{multipart::MultipartError, Extension, Json},
+    http::{HeaderName, HeaderValue, Request, StatusCode},
     response::{IntoResponse, Response},
     routing::{delete, get, post, put},
     Error as AxumError, Router,
 };
 use fuel_crypto::Error as FuelCryptoError;
-use fuel_indexer_database::{IndexerConnectionPool, IndexerDatabaseError};
-use fuel_indexer_lib::{config::IndexerConfig, utils::ServiceRequest};
+use fuel_indexer_database::{
+    types::IndexAssetType, IndexerConnectionPool, IndexerDatabaseError,
+};
+use fuel_indexer_lib::{
+    config::{auth::MessageEncoding, GraphQLConfig, IndexerConfig},
+    utils::ServiceRequest,
+};
 use fuel_indexer_schema::db::{
     graphql::GraphqlError, manager::SchemaManager, IndexerSchemaError,
 };
 use hyper::{Error as HyperError, Method};
 use jsonwebtoken::errors::Error as JsonWebTokenError;
 use serde_json::json;
-use std::{net::SocketAddr, time::Instant};
+use std::{net::SocketAddr, str::FromStr, time::Instant};
 use thiserror::Error;
 use tokio::sync::mpsc::{error::SendError, Sender};
 use tower_http::{
     cors::{Any, CorsLayer},
     limit::RequestBodyLimitLayer,
-    trace::{DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, TraceLayer},
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::{DefaultOnRequest, DefaultOnResponse, MakeSpan, TraceLayer},
     LatencyUnit,
 };
-use tracing::{error, Level};
+use tracing::{error, info_span, Level, Span};
 
 pub type ApiResult<T> = core::result::Result<T, ApiError>;
 
@@ -41,6 +51,8 @@ pub enum HttpError {
     BadRequest,
     #[error("Unauthorized request.")]
     Unauthorized,
+    #[error("Forbidden request.")]
+    Forbidden,
     #[error("Not not found. {0:#?}")]
     NotFound(String),
     #[error("Error.")]
@@ -69,8 +81,42 @@ pub enum ApiError {
     HyperError(#[from] HyperError),
     #[error("FuelCrypto error: {0:?}")]
     FuelCrypto(#[from] FuelCryptoError),
+    #[error("Signature is not of a {0:?} message.")]
+    SignatureEncoding(MessageEncoding),
     #[error("JsonWebTokenError: {0:?}")]
     JsonWebTokenError(#[from] JsonWebTokenError),
+    #[error("Query exceeded the row limit of {0}.")]
+    QueryRowLimitExceeded(usize),
+    #[error("Invalid pagination: {0}")]
+    InvalidPagination(String),
+    #[error("Rate limited, retry in {0}s.")]
+    RateLimited(u64),
+    #[error("All {0} query streams are taken, retry later.")]
+    TooManyStreams(usize),
+    #[error(
+        "Subscriptions require the API server to run alongside the indexer service."
+    )]
+    SubscriptionsUnavailable,
+    #[error("Indexer({0}) may still be running, stop it before rolling back.")]
+    IndexerRunning(String),
+    #[error("Invalid rollback: {0}")]
+    InvalidRollback(String),
+    #[error("Invalid replay: {0}")]
+    InvalidReplay(String),
+    #[error("Invalid SQL query: {0}")]
+    InvalidSql(String),
+    #[error("Invalid assets: {0}")]
+    InvalidAssets(String),
+    #[error("The {} asset exceeds the max size of {1} bytes.", .0.as_ref())]
+    AssetTooLarge(IndexAssetType, usize),
+    #[error("Multipart error: {0}")]
+    Multipart(#[from] MultipartError),
+    #[error("IO error: {0:?}")]
+    Io(#[from] std::io::Error),
+    #[error("TLS error: {0}")]
+    Tls(String),
+    #[error("Invalid CORS configuration: {0}")]
+    Cors(String),
 }
 
 impl Default for ApiError {
@@ -79,10 +125,11 @@ impl Default for ApiError {
     }
 }
 
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
+impl ApiError {
+    /// The status and details an error is reported to clients with.
+    pub(crate) fn into_status_and_details(self) -> (StatusCode, String) {
         let generic_details = "Internal server error.".to_string();
-        let (status, details) = match self {
+        match self {
             Self::JsonWebTokenError(e) => (
                 StatusCode::BAD_REQUEST,
                 format!("Could not process JWT: {e}"),
@@ -90,10 +137,26 @@ impl IntoResponse for ApiError {
             ApiError::Http(HttpError::Unauthorized) => {
                 (StatusCode::UNAUTHORIZED, "Unauthorized.".to_string())
             }
+            ApiError::Http(HttpError::Forbidden) => {
+                (StatusCode::FORBIDDEN, "Forbidden.".to_string())
+            }
+            ApiError::Http(HttpError::BadRequest) => {
+                (StatusCode::BAD_REQUEST, "Bad request.".to_string())
+            }
+            ApiError::Http(HttpError::NotFound(details)) => {
+                (StatusCode::NOT_FOUND, details)
+            }
             ApiError::Sqlx(e) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 format!("Database error: {e}."),
             ),
+            ApiError::Database(IndexerDatabaseError::QueryTimeout(timeout)) => (
+                StatusCode::GATEWAY_TIMEOUT,
+                format!(
+                    "Query exceeded the timeout of {}s and was cancelled.",
+                    timeout.as_secs()
+                ),
+            ),
             ApiError::Database(e) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 format!("Database error: {e}."),
@@ -101,22 +164,152 @@ impl IntoResponse for ApiError {
             ApiError::FuelCrypto(e) => {
                 (StatusCode::BAD_REQUEST, format!("Crypto error: {e}."))
             }
+            ApiError::SignatureEncoding(encoding) => (
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "No signer could be recovered from the signature of a {} message. Check that the wallet signs messages the way the indexer's signature_encoding expects.",
+                    encoding.as_ref()
+                ),
+            ),
+            ApiError::QueryRowLimitExceeded(limit) => (
+                StatusCode::BAD_REQUEST,
+                format!("Query would return more than {limit} rows. Narrow the query with filters."),
+            ),
+            ApiError::InvalidPagination(details) => (StatusCode::BAD_REQUEST, details),
+            e @ ApiError::IndexerRunning(_) => (StatusCode::CONFLICT, format!("{e}")),
+            ApiError::InvalidRollback(details) => (StatusCode::BAD_REQUEST, details),
+            ApiError::InvalidReplay(details) => (StatusCode::BAD_REQUEST, details),
+            e @ ApiError::InvalidSql(_) => (StatusCode::BAD_REQUEST, format!("{e}")),
+            e @ ApiError::InvalidAssets(_) => (StatusCode::BAD_REQUEST, format!("{e}")),
+            e @ ApiError::AssetTooLarge(..) => {
+                (StatusCode::PAYLOAD_TOO_LARGE, format!("{e}"))
+            }
+            e @ ApiError::Multipart(_) => (StatusCode::BAD_REQUEST, format!("{e}")),
+            e @ ApiError::SubscriptionsUnavailable => {
+                (StatusCode::SERVICE_UNAVAILABLE, format!("{e}"))
+            }
+            e @ ApiError::TooManyStreams(_) => {
+                (StatusCode::SERVICE_UNAVAILABLE, format!("{e}"))
+            }
+            ApiError::RateLimited(retry_after) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                format!("Too many requests. Retry in {retry_after}s."),
+            ),
+            // Every error of the query builder is a mistake in the query.
+            ApiError::Graphql(e) => (StatusCode::BAD_REQUEST, format!("{e}")),
             _ => (StatusCode::INTERNAL_SERVER_ERROR, generic_details),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let field = match &self {
+            ApiError::Graphql(e) => e.field().map(str::to_string),
+            _ => None,
         };
+        let (status, details) = self.into_status_and_details();
 
         error!("{status:?} - {details}");
 
-        (
-            status,
-            Json(json!({
-                "success": "false",
-                "details": details,
-            })),
+        let mut body = json!({
+            "success": "false",
+            "details": details,
+        });
+        if let Some(field) = field {
+            body["field"] = json!(field);
+        }
+
+        (status, Json(body)).into_response()
+    }
+}
+
+/// Header that carries the correlation ID of a request. A client may set it
+/// to its own ID; otherwise a UUID is generated. It is echoed in the response.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Room for the boundaries and part headers of an asset upload, on top of
+/// the assets themselves.
+const MULTIPART_OVERHEAD: usize = 16384; // 16KB
+
+/// Opens a span for each request, tagged with its correlation `request_id`,
+/// so that every log line emitted while handling it can be correlated.
+#[derive(Clone, Debug, Default)]
+struct RequestSpan;
+
+impl<B> MakeSpan<B> for RequestSpan {
+    fn make_span(&mut self, request: &Request<B>) -> Span {
+        let request_id = request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|id| id.to_str().ok())
+            .unwrap_or_default();
+        info_span!(
+            "request",
+            request_id = %request_id,
+            method = %request.method(),
+            uri = %request.uri(),
+            version = ?request.version(),
+            headers = ?request.headers(),
         )
-            .into_response()
     }
 }
 
+/// Build the CORS policy of the API from its config. Cross-origin requests
+/// are refused unless some origins are allowed, and `*` allows any origin,
+/// method or header.
+fn cors_layer(config: &GraphQLConfig) -> ApiResult<CorsLayer> {
+    fn is_any(list: &[String]) -> bool {
+        list.iter().any(|item| item == "*")
+    }
+
+    let mut cors = CorsLayer::new();
+
+    if is_any(&config.cors_allowed_origins) {
+        cors = cors.allow_origin(Any);
+    } else if !config.cors_allowed_origins.is_empty() {
+        let origins = config
+            .cors_allowed_origins
+            .iter()
+            .map(|origin| {
+                HeaderValue::from_str(origin)
+                    .map_err(|e| ApiError::Cors(format!("'{origin}': {e}")))
+            })
+            .collect::<ApiResult<Vec<_>>>()?;
+        cors = cors.allow_origin(origins);
+    }
+
+    if is_any(&config.cors_allowed_methods) {
+        cors = cors.allow_methods(Any);
+    } else {
+        let methods = config
+            .cors_allowed_methods
+            .iter()
+            .map(|method| {
+                Method::from_str(&method.to_uppercase())
+                    .map_err(|e| ApiError::Cors(format!("'{method}': {e}")))
+            })
+            .collect::<ApiResult<Vec<_>>>()?;
+        cors = cors.allow_methods(methods);
+    }
+
+    if is_any(&config.cors_allowed_headers) {
+        cors = cors.allow_headers(Any);
+    } else {
+        let headers = config
+            .cors_allowed_headers
+            .iter()
+            .map(|header| {
+                HeaderName::from_str(header)
+                    .map_err(|e| ApiError::Cors(format!("'{header}': {e}")))
+            })
+            .collect::<ApiResult<Vec<_>>>()?;
+        cors = cors.allow_headers(headers);
+    }
+
+    Ok(cors)
+}
+
 pub struct GraphQlApi;
 
 impl GraphQlApi {
@@ -129,31 +322,74 @@ impl GraphQlApi {
         let schema_manager = Arc::new(RwLock::new(sm));
         let max_body_size = config.graphql_api.max_body_size;
         let start_time = Arc::new(Instant::now());
+        let cors = cors_layer(&config.graphql_api)?;
+        let request_id_header = HeaderName::from_static(REQUEST_ID_HEADER);
 
         let graph_route = Router::new()
             .route("/:namespace/:identifier", post(query_graph))
+            .route("/:namespace/:identifier/sql", post(query_sql))
+            .route("/:namespace/:identifier/subscribe", get(subscribe_graph))
+            .route("/:namespace/:identifier/schema", get(introspect_graph))
+            .layer(Extension(tx.clone()))
             .layer(Extension(schema_manager.clone()))
             .layer(Extension(pool.clone()))
-            .layer(RequestBodyLimitLayer::new(max_body_size));
+            .layer(Extension(config.clone()))
+            .layer(Extension(StreamSlots::from_config(&config)))
+            .layer(RequestBodyLimitLayer::new(max_body_size))
+            .layer(RateLimitMiddleware::from(&config))
+            .layer(AuthenticationMiddleware::from(&config));
 
-        let index_routes = Router::new()
+        let playground_route = Router::new()
+            .route("/:namespace/:identifier", get(graphql_playground))
+            .layer(Extension(schema_manager.clone()));
+
+        // Each asset of an upload is checked against its own limit, so the
+        // request as a whole may hold all of them at their limits.
+        let max_upload_size = config.graphql_api.max_wasm_size
+            + config.graphql_api.max_manifest_size
+            + config.graphql_api.max_schema_size
+            + MULTIPART_OVERHEAD;
+
+        let upload_route = Router::new()
             .route("/:namespace/:identifier", post(register_indexer_assets))
             .layer(AuthenticationMiddleware::from(&config))
             .layer(Extension(tx.clone()))
             .layer(Extension(schema_manager))
             .layer(Extension(pool.clone()))
+            .layer(Extension(config.clone()))
+            .layer(RequestBodyLimitLayer::new(max_upload_size));
+
+        let index_routes = Router::new()
             .route("/:namespace/:identifier", delete(stop_indexer))
             .route("/:namespace/:identifier", put(revert_indexer))
+            .route("/:namespace/:identifier/assets", get(indexer_assets))
+            .route("/:namespace/:identifier/stats", get(indexer_stats))
+            .route("/:namespace/:identifier/pause", post(pause_indexer))
+            .route("/:namespace/:identifier/resume", post(resume_indexer))
+            .route(
+                "/:namespace/:identifier/rollback/:height",
+                post(rollback_indexer),
+            )
+            .route(
+                "/:namespace/:identifier/replay/:from/:to",
+                post(replay_indexer),
+            )
             .layer(AuthenticationMiddleware::from(&config))
-            .layer(Extension(tx))
+            .layer(Extension(tx.clone()))
             .layer(Extension(pool.clone()))
-            .layer(RequestBodyLimitLayer::new(max_body_size));
+            .layer(Extension(config.clone()))
+            .layer(RequestBodyLimitLayer::new(max_body_size))
+            .merge(upload_route);
 
         let root_routes = Router::new()
             .route("/health", get(health_check))
             .layer(Extension(config.clone()))
             .layer(Extension(pool.clone()))
             .layer(Extension(start_time))
+            .route("/status", get(indexer_status))
+            .layer(Extension(tx))
+            .layer(Extension(config.clone()))
+            .layer(Extension(pool.clone()))
             .route("/metrics", get(metrics));
 
         let auth_routes = Router::new()
@@ -167,13 +403,14 @@ impl GraphQlApi {
             .nest("/", root_routes)
             .nest("/index", index_routes)
             .nest("/graph", graph_route)
+            .nest("/playground", playground_route)
             .nest("/auth", auth_routes);
 
         let app = Router::new()
             .nest("/api", api_routes)
             .layer(
                 TraceLayer::new_for_http()
-                    .make_span_with(DefaultMakeSpan::new().include_headers(true))
+                    .make_span_with(RequestSpan)
                     .on_request(DefaultOnRequest::new().level(Level::INFO))
                     .on_response(
                         DefaultOnResponse::new()
@@ -181,20 +418,28 @@ impl GraphQlApi {
                             .latency_unit(LatencyUnit::Micros),
                     ),
             )
-            .layer(
-                CorsLayer::new()
-                    .allow_methods(vec![Method::GET, Method::POST])
-                    .allow_origin(Any {}),
-            );
+            .layer(PropagateRequestIdLayer::new(request_id_header.clone()))
+            // Outside of the trace layer, so that the span of each request
+            // has its correlation ID.
+            .layer(SetRequestIdLayer::new(request_id_header, MakeRequestUuid))
+            // Outermost, so that preflight requests are answered before they
+            // reach the authentication middleware.
+            .layer(cors);
 
         Ok(app)
     }
 
+    /// Serve `app`, over HTTPS if a TLS certificate and key are configured.
     pub async fn run(config: IndexerConfig, app: Router) -> ApiResult<()> {
-        let listen_on: SocketAddr = config.graphql_api.into();
+        let listen_on: SocketAddr = config.graphql_api.clone().into();
+
+        if let Some((cert, key)) = config.graphql_api.tls() {
+            let tls_config = load_tls_config(cert, key)?;
+            return serve_tls(listen_on, app, tls_config).await;
+        }
 
         axum::Server::bind(&listen_on)
-            .serve(app.into_make_service())
+            .serve(app.into_make_service_with_connect_info::<SocketAddr>())
             .await?;
 
         Ok(())
@@ -205,13 +450,8 @@ impl GraphQlApi {
         pool: IndexerConnectionPool,
         tx: Option<Sender<ServiceRequest>>,
     ) -> ApiResult<()> {
-        let listen_on: SocketAddr = config.graphql_api.clone().into();
-        let app = GraphQlApi::build(config, pool, tx).await?;
+        let app = GraphQlApi::build(config.clone(), pool, tx).await?;
 
-        axum::Server::bind(&listen_on)
-            .serve(app.into_make_service())
-            .await?;
-
-        Ok(())
+        GraphQlApi::run(config, app).await
     }
 }
@@ -1,13 +1,58 @@
 use axum::http::Request;
-use fuel_indexer_lib::config::{
-    auth::{AuthenticationStrategy, Claims},
-    IndexerConfig,
+use fuel_indexer_lib::{
+    config::{
+        auth::{AuthenticationStrategy, Claims, Role},
+        IndexerConfig,
+    },
+    defaults,
+    utils::sha256_digest,
 };
 use jsonwebtoken::{decode, DecodingKey, Validation};
-use std::task::{Context, Poll};
+use std::{
+    task::{Context, Poll},
+    time::{SystemTime, UNIX_EPOCH},
+};
 use tower::{Layer, Service};
 use tracing::error;
 
+/// Compare two byte strings in time that depends only on their lengths.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Claims for a request presenting `key`, if it hashes to the configured
+/// `api_key_hash`. The subject is the key's hash, so that the key itself is
+/// never stored or echoed back. The key belongs to the operator, so it is an
+/// admin key.
+fn api_key_claims(config: &IndexerConfig, key: &str) -> Option<Claims> {
+    let expected = config.authentication.api_key_hash.as_ref()?;
+    let digest = sha256_digest(&key);
+
+    if key.is_empty()
+        || !constant_time_eq(digest.as_bytes(), expected.to_lowercase().as_bytes())
+    {
+        return None;
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as usize;
+
+    Some(Claims {
+        sub: digest,
+        iss: config.authentication.jwt_issuer.clone().unwrap_or_default(),
+        iat: now,
+        exp: now
+            + config
+                .authentication
+                .jwt_expiry
+                .unwrap_or(defaults::JWT_EXPIRY_SECS),
+        role: Role::Admin,
+        address: None,
+    })
+}
+
 #[derive(Clone)]
 struct MiddlewareState {
     config: IndexerConfig,
@@ -87,7 +132,18 @@ where
                     }
                     return self.inner.call(req);
                 }
-                _ => {
+                Some(AuthenticationStrategy::ApiKey) => {
+                    let claims = match api_key_claims(config, header) {
+                        Some(claims) => claims,
+                        None => {
+                            error!("Invalid API key.");
+                            Claims::unauthenticated()
+                        }
+                    };
+                    req.extensions_mut().insert(claims);
+                    return self.inner.call(req);
+                }
+                None => {
                     error!("Unsupported authentication strategy.");
                     unimplemented!();
                 }
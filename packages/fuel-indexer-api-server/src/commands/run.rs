@@ -1,6 +1,9 @@
 use crate::api::GraphQlApi;
-use fuel_indexer_database::{queries, IndexerConnectionPool};
-use fuel_indexer_lib::config::{ApiServerArgs, IndexerConfig};
+use fuel_indexer_database::{queries, ConnectionPoolConfig, IndexerConnectionPool};
+use fuel_indexer_lib::{
+    config::{ApiServerArgs, IndexerConfig},
+    utils::bin_utils::init_logging,
+};
 use tracing::info;
 
 pub async fn exec(args: ApiServerArgs) -> anyhow::Result<()> {
@@ -9,9 +12,15 @@ pub async fn exec(args: ApiServerArgs) -> anyhow::Result<()> {
         None => IndexerConfig::from(args),
     };
 
+    init_logging(config.json_logging).await?;
+
     info!("Configuration: {:?}", config);
 
-    let pool = IndexerConnectionPool::connect(&config.database.to_string()).await?;
+    let pool = IndexerConnectionPool::connect_with_config(
+        &config.database.to_string(),
+        ConnectionPoolConfig::from(&config),
+    )
+    .await?;
 
     if config.run_migrations {
         let mut c = pool.acquire().await?;
@@ -3,6 +3,9 @@
 pub mod api;
 pub(crate) mod auth;
 pub(crate) mod models;
+pub(crate) mod rate_limit;
+pub(crate) mod sql;
+pub(crate) mod tls;
 mod uses;
 
 pub mod cli;
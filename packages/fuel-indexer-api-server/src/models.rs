@@ -1,7 +1,22 @@
+use fuel_indexer_lib::config::auth::Role;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct VerifySignatureRequest {
     pub signature: String,
     pub message: String,
+    /// Role to issue the token with, defaulting to the most the signer may hold.
+    #[serde(default)]
+    pub role: Option<Role>,
+}
+
+/// Query parameters of a request to remove an indexer.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct RemoveIndexerParams {
+    /// Drop the indexer's tables along with its registration.
+    #[serde(default)]
+    pub purge: bool,
+    /// List what would be dropped, without removing anything.
+    #[serde(default)]
+    pub dry_run: bool,
 }
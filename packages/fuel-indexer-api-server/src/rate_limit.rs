@@ -0,0 +1,142 @@
+use crate::api::ApiError;
+use axum::{
+    extract::ConnectInfo,
+    http::{header::RETRY_AFTER, HeaderValue, Request},
+    response::{IntoResponse, Response},
+};
+use fuel_indexer_lib::config::{auth::Claims, IndexerConfig};
+use std::{
+    collections::HashMap,
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Instant,
+};
+use tower::{Layer, Service};
+
+/// Number of tracked clients above which idle buckets are dropped.
+const MAX_IDLE_BUCKETS: usize = 10000;
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+#[derive(Clone)]
+struct RateLimiter {
+    rate: f64,
+    burst: f64,
+    buckets: Arc<Mutex<HashMap<String, TokenBucket>>>,
+}
+
+impl RateLimiter {
+    /// Take a token from the client's bucket, or return the number of
+    /// seconds until one becomes available.
+    fn check(&self, key: &str) -> Result<(), u64> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().expect("Rate limiter lock poisoned.");
+
+        if buckets.len() > MAX_IDLE_BUCKETS {
+            let refill_secs = self.burst / self.rate;
+            buckets.retain(|_, bucket| {
+                now.duration_since(bucket.last_refill).as_secs_f64() < refill_secs
+            });
+        }
+
+        let bucket = buckets.entry(key.to_string()).or_insert(TokenBucket {
+            tokens: self.burst,
+            last_refill: now,
+        });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(((1.0 - bucket.tokens) / self.rate).ceil().max(1.0) as u64)
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitMiddleware {
+    limiter: Option<RateLimiter>,
+}
+
+impl From<&IndexerConfig> for RateLimitMiddleware {
+    fn from(config: &IndexerConfig) -> Self {
+        let rate = config.graphql_api.query_rate_limit;
+        let limiter = (rate > 0).then(|| RateLimiter {
+            rate: rate as f64,
+            burst: config.graphql_api.query_rate_limit_burst.max(1) as f64,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        });
+
+        Self { limiter }
+    }
+}
+
+impl<S> Layer<S> for RateLimitMiddleware {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: Option<RateLimiter>,
+}
+
+/// Rate limit authenticated clients by their subject, and everyone else by
+/// their source IP.
+fn client_key<B>(req: &Request<B>) -> String {
+    if let Some(claims) = req.extensions().get::<Claims>() {
+        if !claims.sub.is_empty() {
+            return format!("sub:{}", claims.sub);
+        }
+    }
+
+    match req.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => format!("ip:{}", addr.ip()),
+        None => "ip:unknown".to_string(),
+    }
+}
+
+impl<S, B> Service<Request<B>> for RateLimitService<S>
+where
+    S: Service<Request<B>, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        if let Some(limiter) = &self.limiter {
+            if let Err(retry_after) = limiter.check(&client_key(&req)) {
+                let mut response = ApiError::RateLimited(retry_after).into_response();
+                response
+                    .headers_mut()
+                    .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+                return Box::pin(async move { Ok(response) });
+            }
+        }
+
+        Box::pin(self.inner.call(req))
+    }
+}
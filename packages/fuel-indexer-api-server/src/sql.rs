@@ -0,0 +1,178 @@
+use crate::api::{ApiError, ApiResult};
+use fuel_indexer_database::DbType;
+use sqlparser::{
+    ast::{visit_relations, visit_statements, Ident, ObjectName, SetExpr, Statement},
+    dialect::{Dialect, PostgreSqlDialect, SQLiteDialect},
+    parser::Parser,
+};
+use std::{collections::HashSet, ops::ControlFlow};
+
+/// Check that `query` is a single `SELECT` that only reads from `tables`, the
+/// tables of the indexer `namespace.identifier`, and return it as it will be run.
+///
+/// Tables are named as they are in the database, e.g. `my_project_my_indexer.block`
+/// on Postgres, where they may also be left unqualified, and
+/// `my_project_my_indexer_block` on SQLite. Common table expressions declared at
+/// the top of the query may be read from as well.
+pub(crate) fn read_only_query(
+    query: &str,
+    db_type: DbType,
+    namespace: &str,
+    identifier: &str,
+    tables: &[String],
+) -> ApiResult<String> {
+    let dialect: Box<dyn Dialect> = match db_type {
+        DbType::Postgres => Box::new(PostgreSqlDialect {}),
+        DbType::Sqlite => Box::new(SQLiteDialect {}),
+    };
+
+    let mut statements = Parser::parse_sql(dialect.as_ref(), query)
+        .map_err(|e| ApiError::InvalidSql(format!("{e}")))?;
+    if statements.len() != 1 {
+        return Err(ApiError::InvalidSql(
+            "Expected a single statement.".to_string(),
+        ));
+    }
+    let statement = statements.remove(0);
+
+    let query = match &statement {
+        Statement::Query(query) => query,
+        _ => {
+            return Err(ApiError::InvalidSql(
+                "Only SELECT statements may be run.".to_string(),
+            ))
+        }
+    };
+
+    if selects_into(&query.body) {
+        return Err(ApiError::InvalidSql(
+            "SELECT INTO writes a table, and may not be run.".to_string(),
+        ));
+    }
+
+    // Statements nested in the query, e.g. an INSERT in a common table
+    // expression, would write to the database.
+    if let ControlFlow::Break(()) = visit_statements(&statement, |s| match s {
+        Statement::Query(_) => ControlFlow::Continue(()),
+        _ => ControlFlow::Break(()),
+    }) {
+        return Err(ApiError::InvalidSql(
+            "Only SELECT statements may be run.".to_string(),
+        ));
+    }
+
+    let schema = format!("{namespace}_{identifier}");
+    let mut allowed = HashSet::new();
+    for table in tables {
+        let table = table.trim_matches('"');
+        match db_type {
+            DbType::Postgres => {
+                allowed.insert(format!("{schema}.{table}"));
+                allowed.insert(table.to_string());
+            }
+            DbType::Sqlite => {
+                allowed.insert(format!("{schema}_{table}"));
+            }
+        }
+    }
+    if let Some(with) = &query.with {
+        for cte in with.cte_tables.iter() {
+            allowed.insert(normalize_ident(&cte.alias.name));
+        }
+    }
+
+    if let ControlFlow::Break(relation) = visit_relations(&statement, |relation| {
+        let name = normalize_name(relation);
+        if allowed.contains(&name) {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(relation.to_string())
+        }
+    }) {
+        return Err(ApiError::InvalidSql(format!(
+            "'{relation}' is not a table of the indexer '{namespace}.{identifier}'."
+        )));
+    }
+
+    Ok(statement.to_string())
+}
+
+/// Whether any `SELECT` of `body` writes its rows into a new table.
+fn selects_into(body: &SetExpr) -> bool {
+    match body {
+        SetExpr::Select(select) => select.into.is_some(),
+        SetExpr::SetOperation { left, right, .. } => {
+            selects_into(left) || selects_into(right)
+        }
+        SetExpr::Query(query) => selects_into(&query.body),
+        _ => false,
+    }
+}
+
+/// Unquoted identifiers are case-insensitive, so they are compared in lowercase.
+fn normalize_ident(ident: &Ident) -> String {
+    match ident.quote_style {
+        Some(_) => ident.value.clone(),
+        None => ident.value.to_lowercase(),
+    }
+}
+
+fn normalize_name(name: &ObjectName) -> String {
+    name.0
+        .iter()
+        .map(normalize_ident)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(query: &str, db_type: DbType) -> ApiResult<String> {
+        let tables = vec!["block".to_string(), "\"Thing\"".to_string()];
+        read_only_query(query, db_type, "my_project", "my_indexer", &tables)
+    }
+
+    #[test]
+    fn test_read_only_query_accepts_selects_of_indexer_tables() {
+        for query in [
+            "SELECT id, height FROM my_project_my_indexer.block WHERE height > 10",
+            "SELECT count(*) FROM block",
+            "SELECT * FROM \"Thing\" t JOIN block b ON b.id = t.id",
+            "WITH recent AS (SELECT * FROM block ORDER BY height DESC LIMIT 5) SELECT * FROM recent",
+            "SELECT id FROM block WHERE id IN (SELECT id FROM my_project_my_indexer.\"Thing\")",
+        ] {
+            assert!(check(query, DbType::Postgres).is_ok(), "{query}");
+        }
+
+        assert!(
+            check("SELECT * FROM my_project_my_indexer_block", DbType::Sqlite).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_read_only_query_rejects_writes_and_other_tables() {
+        for query in [
+            "DELETE FROM block",
+            "DROP TABLE block",
+            "UPDATE block SET height = 0",
+            "SELECT * INTO copy FROM block",
+            "SELECT * FROM block; DELETE FROM block",
+            "WITH x AS (INSERT INTO block (id) VALUES (1) RETURNING *) SELECT * FROM x",
+            "SELECT * FROM other_project_other_indexer.block",
+            "SELECT * FROM pg_catalog.pg_user",
+            "SELECT * FROM block WHERE id IN (SELECT id FROM graph_registry_columns)",
+            "SELECT * FROM \"thing\"",
+            "not sql",
+        ] {
+            assert!(
+                matches!(check(query, DbType::Postgres), Err(ApiError::InvalidSql(_))),
+                "{query}"
+            );
+        }
+
+        // SQLite tables are only known by their prefixed names.
+        assert!(check("SELECT * FROM block", DbType::Sqlite).is_err());
+    }
+}
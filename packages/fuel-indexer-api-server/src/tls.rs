@@ -0,0 +1,132 @@
+use crate::api::{ApiError, ApiResult};
+use axum::{body::Body, extract::ConnectInfo, http::Request, Router};
+use hyper::{server::conn::Http, service::service_fn};
+use rustls_pemfile::Item;
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    net::SocketAddr,
+    path::Path,
+    sync::Arc,
+};
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpListener, TcpStream},
+};
+use tokio_rustls::{
+    rustls::{Certificate, PrivateKey, ServerConfig},
+    TlsAcceptor,
+};
+use tower::Service;
+use tracing::{debug, info, warn};
+
+/// Content type of the record that opens every TLS connection.
+const TLS_HANDSHAKE_RECORD: u8 = 0x16;
+
+/// Sent to clients that speak plain HTTP to the HTTPS listener.
+const PLAIN_HTTP_RESPONSE: &[u8] = b"HTTP/1.1 400 Bad Request\r\n\
+Content-Type: text/plain\r\n\
+Content-Length: 43\r\n\
+Connection: close\r\n\
+\r\n\
+This server only accepts HTTPS connections.";
+
+/// Build the TLS config of the API server from a PEM certificate chain and
+/// the PEM private key of its leaf certificate.
+pub(crate) fn load_tls_config(cert: &Path, key: &Path) -> ApiResult<ServerConfig> {
+    let certs = rustls_pemfile::certs(&mut BufReader::new(File::open(cert)?))?
+        .into_iter()
+        .map(Certificate)
+        .collect::<Vec<_>>();
+
+    if certs.is_empty() {
+        return Err(ApiError::Tls(format!(
+            "No certificate found in {}.",
+            cert.display()
+        )));
+    }
+
+    let key = rustls_pemfile::read_all(&mut BufReader::new(File::open(key)?))?
+        .into_iter()
+        .find_map(|item| match item {
+            Item::RSAKey(key) | Item::PKCS8Key(key) | Item::ECKey(key) => {
+                Some(PrivateKey(key))
+            }
+            _ => None,
+        })
+        .ok_or_else(|| {
+            ApiError::Tls(format!("No private key found in {}.", key.display()))
+        })?;
+
+    let mut config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|e| ApiError::Tls(e.to_string()))?;
+
+    // Subscriptions upgrade to WebSockets, which hyper only supports over
+    // HTTP/1.1.
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+    Ok(config)
+}
+
+/// Serve `app` over HTTPS on `listen_on` until the listener fails.
+pub(crate) async fn serve_tls(
+    listen_on: SocketAddr,
+    app: Router,
+    config: ServerConfig,
+) -> ApiResult<()> {
+    let listener = TcpListener::bind(listen_on).await?;
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+
+    info!("Serving the GraphQL API over HTTPS on {listen_on}.");
+
+    loop {
+        let (stream, remote_addr) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!("Failed to accept connection: {e}.");
+                continue;
+            }
+        };
+
+        let (acceptor, app) = (acceptor.clone(), app.clone());
+        tokio::spawn(async move {
+            if let Err(e) = serve_connection(stream, remote_addr, acceptor, app).await {
+                debug!("Connection from {remote_addr} closed: {e}.");
+            }
+        });
+    }
+}
+
+async fn serve_connection(
+    mut stream: TcpStream,
+    remote_addr: SocketAddr,
+    acceptor: TlsAcceptor,
+    app: Router,
+) -> io::Result<()> {
+    // Plain HTTP clients are refused outright, rather than left to time out
+    // on a handshake they will never complete.
+    let mut first = [0u8; 1];
+    if stream.peek(&mut first).await? == 1 && first[0] != TLS_HANDSHAKE_RECORD {
+        stream.write_all(PLAIN_HTTP_RESPONSE).await?;
+        return stream.shutdown().await;
+    }
+
+    let stream = acceptor.accept(stream).await?;
+
+    // Handlers and the rate limiter read the client's address from the
+    // request, as they do when served by `axum::Server`.
+    let service = service_fn(move |mut req: Request<Body>| {
+        req.extensions_mut().insert(ConnectInfo(remote_addr));
+        app.clone().call(req)
+    });
+
+    Http::new()
+        .http1_only(true)
+        .serve_connection(stream, service)
+        .with_upgrades()
+        .await
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+}
@@ -1,49 +1,122 @@
 use crate::{
     api::{ApiError, ApiResult, HttpError},
-    models::VerifySignatureRequest,
+    models::{RemoveIndexerParams, VerifySignatureRequest},
+    sql,
 };
 use async_std::sync::{Arc, RwLock};
 use axum::{
-    body::Body,
-    extract::{multipart::Multipart, Extension, Json, Path},
-    http::{Request, StatusCode},
-    response::{IntoResponse, Response},
+    body::{Body, StreamBody},
+    extract::{
+        multipart::Multipart,
+        ws::{close_code, CloseFrame, Message as WsMessage, WebSocket, WebSocketUpgrade},
+        Extension, Json, Path, Query as UrlQuery,
+    },
+    http::{header::ACCEPT, HeaderMap, Request, StatusCode},
+    response::{Html, IntoResponse, Response},
 };
-use fuel_crypto::{Message, Signature};
+use fuel_crypto::{Message, PublicKey, Signature};
 use fuel_indexer_database::{
     queries,
-    types::{IndexAsset, IndexAssetType},
-    IndexerConnectionPool,
+    types::{IndexAsset, IndexAssetType, QueryParam, UserQuery},
+    IndexerConnection, IndexerConnectionPool, IndexerDatabaseError,
 };
 use fuel_indexer_lib::{
     config::{
-        auth::{AuthenticationStrategy, Claims},
+        auth::{AuthenticationStrategy, Claims, Role},
         IndexerConfig,
     },
     defaults,
     utils::{
-        AssetReloadRequest, FuelNodeHealthResponse, IndexRevertRequest, IndexStopRequest,
-        ServiceRequest, ServiceStatus,
+        AssetReloadRequest, AssetValidateRequest, EntityNotification,
+        EntitySubscribeRequest, FuelNodeHealthResponse, IndexPauseRequest,
+        IndexReplayRequest, IndexResumeRequest, IndexRevertRequest, IndexStatusRequest,
+        IndexStopRequest, ServiceRequest, ServiceStatus,
     },
 };
 use fuel_indexer_schema::db::{
-    graphql::GraphqlQueryBuilder, manager::SchemaManager, tables::Schema,
+    graphql::{Cursor, GraphqlQueryBuilder},
+    manager::SchemaManager,
+    tables::Schema,
 };
-use hyper::Client;
-use hyper_rustls::HttpsConnectorBuilder;
+use fuel_indexer_types::Address;
+use hyper::{client::HttpConnector, Client};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
 use jsonwebtoken::{encode, EncodingKey, Header};
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::{
+    collections::{BTreeSet, HashMap},
+    convert::Infallible,
     str::FromStr,
     time::{Instant, SystemTime, UNIX_EPOCH},
 };
-use tokio::sync::mpsc::Sender;
-use tracing::error;
+use tokio::{
+    sync::{
+        broadcast::{
+            self,
+            error::{RecvError, TryRecvError},
+        },
+        mpsc::{self, Sender},
+        oneshot, OwnedSemaphorePermit, Semaphore,
+    },
+    time::{timeout, Duration},
+};
+use tower_http::request_id::RequestId;
+use tracing::{error, info, warn};
 
 #[cfg(feature = "metrics")]
 use fuel_indexer_metrics::{encode_metrics_response, METRICS};
 
+/// Response header carrying the number of rows returned by a GraphQL query.
+pub const ROW_COUNT_HEADER: &str = "x-row-count";
+
+/// Response header carrying the version of the schema a GraphQL query was run
+/// against, which changes whenever the indexer's schema does.
+pub const SCHEMA_VERSION_HEADER: &str = "x-schema-version";
+
+/// Media type of newline-delimited JSON, which a GraphQL query can ask for in
+/// its `Accept` header to have its rows streamed rather than returned at once.
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Number of rows read from the database at a time for a streamed query.
+const STREAM_BATCH_SIZE: usize = 1000;
+
+/// Slots that streamed queries take while they are read from the database,
+/// each holding a connection for as long as its client takes to read the rows.
+/// Clones share the same slots.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct StreamSlots {
+    slots: Option<Arc<Semaphore>>,
+    max: usize,
+}
+
+impl StreamSlots {
+    /// `max_concurrent_streams` slots, or no limit if it's zero.
+    pub(crate) fn from_config(config: &IndexerConfig) -> Self {
+        let max = config.graphql_api.max_concurrent_streams;
+        Self {
+            slots: (max > 0).then(|| Arc::new(Semaphore::new(max))),
+            max,
+        }
+    }
+
+    /// Take a slot, which is freed once the returned permit is dropped, or
+    /// fail if they are all taken.
+    fn try_acquire(&self) -> ApiResult<Option<OwnedSemaphorePermit>> {
+        match &self.slots {
+            Some(slots) => slots
+                .clone()
+                .try_acquire_owned()
+                .map(Some)
+                .map_err(|_| ApiError::TooManyStreams(self.max)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Page of the GraphQL playground, which finds the graph to query from its own URL.
+const PLAYGROUND_HTML: &str = include_str!("./playground.html");
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct Query {
     pub query: String,
@@ -55,31 +128,379 @@ pub(crate) async fn query_graph(
     Path((namespace, identifier)): Path<(String, String)>,
     Extension(pool): Extension<IndexerConnectionPool>,
     Extension(manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(config): Extension<IndexerConfig>,
+    Extension(streams): Extension<StreamSlots>,
+    headers: HeaderMap,
     Json(query): Json<Query>,
-) -> ApiResult<axum::Json<Value>> {
+) -> ApiResult<Response> {
     match manager
         .read()
         .await
         .load_schema(&namespace, &identifier)
         .await
     {
-        Ok(schema) => match run_query(query, schema, &pool).await {
-            Ok(response) => Ok(axum::Json(response)),
-            Err(e) => {
-                error!("query_graph error: {e}");
-                Err(e)
-            }
-        },
+        Ok(schema) => {
+            let version = schema.version.clone();
+            let result = if accepts_ndjson(&headers) {
+                stream_query(query, schema, &pool, &config, &streams)
+                    .await
+                    .map(|body| {
+                        (
+                            [
+                                ("content-type", NDJSON_CONTENT_TYPE.to_string()),
+                                (SCHEMA_VERSION_HEADER, version),
+                            ],
+                            body,
+                        )
+                            .into_response()
+                    })
+            } else {
+                run_query(query, schema, &pool, &config)
+                    .await
+                    .map(|response| {
+                        let row_count = response["data"].as_array().map_or(0, Vec::len);
+                        (
+                            [
+                                (ROW_COUNT_HEADER, row_count.to_string()),
+                                (SCHEMA_VERSION_HEADER, version),
+                            ],
+                            axum::Json(response),
+                        )
+                            .into_response()
+                    })
+            };
+
+            result.map_err(|e| {
+                error!(uid = %format!("{namespace}.{identifier}"), "query_graph error: {e}");
+                e
+            })
+        }
         Err(_e) => Err(ApiError::Http(HttpError::NotFound(format!(
             "The graph '{namespace}.{identifier}' was not found."
         )))),
     }
 }
 
-pub(crate) async fn get_fuel_status(config: &IndexerConfig) -> ServiceStatus {
-    #[cfg(feature = "metrics")]
-    METRICS.web.health.requests.inc();
+/// Whether a request asks for newline-delimited JSON in its `Accept` header.
+fn accepts_ndjson(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media_type| {
+            media_type.split(';').next().map(str::trim) == Some(NDJSON_CONTENT_TYPE)
+        })
+}
+
+/// Body of a raw SQL query against an indexer's tables.
+#[derive(Clone, Debug, Deserialize)]
+pub struct SqlQuery {
+    pub query: String,
+}
+
+/// Run a read-only SQL query against the tables of an indexer, on behalf of an
+/// admin, returning each row as a JSON object of its columns.
+pub(crate) async fn query_sql(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(config): Extension<IndexerConfig>,
+    Extension(claims): Extension<Claims>,
+    Json(body): Json<SqlQuery>,
+) -> ApiResult<([(&'static str, String); 1], axum::Json<Value>)> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    if !claims.has_role(Role::Admin) {
+        return Err(ApiError::Http(HttpError::Forbidden));
+    }
+
+    let schema = manager
+        .read()
+        .await
+        .load_schema(&namespace, &identifier)
+        .await
+        .map_err(|_| {
+            ApiError::Http(HttpError::NotFound(format!(
+                "The graph '{namespace}.{identifier}' was not found."
+            )))
+        })?;
+
+    let tables: Vec<String> = schema.tables.values().cloned().collect();
+    let query = sql::read_only_query(
+        &body.query,
+        pool.database_type(),
+        &namespace,
+        &identifier,
+        &tables,
+    )?;
+
+    let timeout = Duration::from_secs(config.graphql_api.query_timeout_secs);
+    let mut conn = pool.acquire().await?;
+    let rows = match queries::run_read_only_query(
+        &mut conn,
+        &namespace,
+        &identifier,
+        query.clone(),
+        timeout,
+    )
+    .await
+    {
+        Ok(Value::Array(rows)) => rows,
+        Ok(_) => Vec::new(),
+        Err(e @ IndexerDatabaseError::QueryTimeout(_)) => {
+            warn!(
+                "SQL query cancelled after {}s, raise query_timeout_secs if it should be allowed: {query}",
+                timeout.as_secs()
+            );
+            return Err(e.into());
+        }
+        Err(e) => {
+            error!(uid = %format!("{namespace}.{identifier}"), "query_sql error: {e}");
+            return Err(e.into());
+        }
+    };
+
+    Ok((
+        [(ROW_COUNT_HEADER, rows.len().to_string())],
+        axum::Json(Value::Array(rows)),
+    ))
+}
+
+/// Describe the entities of a graph, their fields, and the scalar types those
+/// fields use, so that GraphQL tooling can explore it.
+pub(crate) async fn introspect_graph(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(manager): Extension<Arc<RwLock<SchemaManager>>>,
+) -> ApiResult<axum::Json<Value>> {
+    let schema = manager
+        .read()
+        .await
+        .load_schema(&namespace, &identifier)
+        .await
+        .map_err(|_| {
+            ApiError::Http(HttpError::NotFound(format!(
+                "The graph '{namespace}.{identifier}' was not found."
+            )))
+        })?;
+
+    let mut names: Vec<&String> = schema.types.iter().collect();
+    names.sort();
+
+    let mut entities = Vec::new();
+    let mut scalars = BTreeSet::new();
+    for name in names {
+        let mut fields: Vec<(&String, &String)> = schema
+            .fields
+            .get(name)
+            .into_iter()
+            .flatten()
+            // The `object` column holds the serialized entity, and isn't a field.
+            .filter(|(_, typ)| typ.as_str() != "__")
+            .collect();
+        fields.sort();
+
+        for (_, typ) in fields.iter() {
+            let base = typ.trim_matches(|c| matches!(c, '[' | ']' | '!'));
+            if !schema.check_type(base) {
+                scalars.insert(base.to_string());
+            }
+        }
+
+        if *name != schema.query {
+            // Descriptions are only listed for the types and fields that have one.
+            let descriptions = schema.descriptions.get(name.as_str());
+            let fields = fields
+                .iter()
+                .map(|(field, typ)| {
+                    let mut field_json = json!({ "name": field, "type": typ });
+                    if let Some(d) = descriptions.and_then(|d| d.fields.get(*field)) {
+                        field_json["description"] = json!(d);
+                    }
+                    field_json
+                })
+                .collect::<Vec<_>>();
+
+            let mut entity = json!({ "name": name, "fields": fields });
+            if let Some(d) = descriptions.and_then(|d| d.description.as_ref()) {
+                entity["description"] = json!(d);
+            }
+            entities.push(entity);
+        }
+    }
+
+    Ok(Json(json!({
+        "namespace": schema.namespace,
+        "identifier": schema.identifier,
+        "version": schema.version,
+        "query": schema.query,
+        "entities": entities,
+        "scalars": scalars,
+    })))
+}
+
+/// Serve a page for writing and running queries against a graph, listing the
+/// entities of the graph alongside.
+///
+/// The page is served without a token. The token entered on it is sent along
+/// with the requests it makes instead.
+pub(crate) async fn graphql_playground(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(manager): Extension<Arc<RwLock<SchemaManager>>>,
+) -> ApiResult<Html<&'static str>> {
+    manager
+        .read()
+        .await
+        .load_schema(&namespace, &identifier)
+        .await
+        .map_err(|_| {
+            ApiError::Http(HttpError::NotFound(format!(
+                "The graph '{namespace}.{identifier}' was not found."
+            )))
+        })?;
+
+    Ok(Html(PLAYGROUND_HTML))
+}
+
+/// The first message a subscriber sends: the entity to watch, and the query
+/// to re-run whenever objects of that entity are committed.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Subscription {
+    pub entity: String,
+    pub query: String,
+}
+
+pub(crate) async fn subscribe_graph(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(tx): Extension<Option<Sender<ServiceRequest>>>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(config): Extension<IndexerConfig>,
+    Extension(claims): Extension<Claims>,
+    ws: WebSocketUpgrade,
+) -> ApiResult<Response> {
+    if !claims.has_role(Role::ReadOnly) {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let tx = tx.ok_or(ApiError::SubscriptionsUnavailable)?;
+
+    let schema = manager
+        .read()
+        .await
+        .load_schema(&namespace, &identifier)
+        .await
+        .map_err(|_| {
+            ApiError::Http(HttpError::NotFound(format!(
+                "The graph '{namespace}.{identifier}' was not found."
+            )))
+        })?;
+
+    // Subscribe before upgrading, so that no commit made while the client
+    // sends its subscription is missed.
+    let (reply, rx) = oneshot::channel();
+    tx.send(ServiceRequest::EntitySubscribe(EntitySubscribeRequest {
+        reply,
+    }))
+    .await?;
+    let notifications = rx.await.map_err(|_| ApiError::SubscriptionsUnavailable)?;
 
+    Ok(ws.on_upgrade(move |socket| {
+        run_subscription(socket, notifications, schema, pool, config)
+    }))
+}
+
+/// Send the subscriber the result of its query, then again after every
+/// commit of the entity it watches, until it disconnects.
+///
+/// Subscribers that fall more than `ENTITY_NOTIFICATION_CHANNEL_SIZE`
+/// notifications behind are disconnected rather than buffered for.
+async fn run_subscription(
+    mut socket: WebSocket,
+    mut notifications: broadcast::Receiver<EntityNotification>,
+    schema: Schema,
+    pool: IndexerConnectionPool,
+    config: IndexerConfig,
+) {
+    let subscription: Subscription = match socket.recv().await {
+        Some(Ok(WsMessage::Text(text))) => match serde_json::from_str(&text) {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                close_subscription(socket, close_code::INVALID, e.to_string()).await;
+                return;
+            }
+        },
+        _ => return,
+    };
+
+    let entity = subscription.entity.to_lowercase();
+    if !schema.types.iter().any(|t| t.to_lowercase() == entity) {
+        let reason = format!("Entity '{}' was not found.", subscription.entity);
+        close_subscription(socket, close_code::INVALID, reason).await;
+        return;
+    }
+
+    let query = Query {
+        query: subscription.query,
+        params: String::new(),
+    };
+
+    loop {
+        let response =
+            match run_query(query.clone(), schema.clone(), &pool, &config).await {
+                Ok(response) => response,
+                Err(e) => json!({ "success": "false", "details": e.to_string() }),
+            };
+
+        if socket
+            .send(WsMessage::Text(response.to_string()))
+            .await
+            .is_err()
+        {
+            return;
+        }
+
+        // Wait for a commit of the watched entity, answering pings meanwhile.
+        loop {
+            tokio::select! {
+                notification = notifications.recv() => match notification {
+                    Ok(n) if n.namespace == schema.namespace
+                        && n.identifier == schema.identifier
+                        && n.entity == entity => break,
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Dropping subscriber to {entity} that fell {skipped} notifications behind.");
+                        let reason = "Subscriber fell too far behind.".to_string();
+                        close_subscription(socket, close_code::AGAIN, reason).await;
+                        return;
+                    }
+                    Err(RecvError::Closed) => return,
+                },
+                message = socket.recv() => match message {
+                    Some(Ok(WsMessage::Close(_))) | Some(Err(_)) | None => return,
+                    Some(Ok(_)) => continue,
+                },
+            }
+        }
+
+        // The query is re-run from scratch, so any commits that queued up
+        // behind this one are covered by the same result.
+        while let Ok(_) | Err(TryRecvError::Lagged(_)) = notifications.try_recv() {}
+    }
+}
+
+async fn close_subscription(mut socket: WebSocket, code: u16, reason: String) {
+    let _ = socket
+        .send(WsMessage::Close(Some(CloseFrame {
+            code,
+            reason: reason.into(),
+        })))
+        .await;
+}
+
+fn fuel_node_client() -> Client<HttpsConnector<HttpConnector>> {
     let https = HttpsConnectorBuilder::new()
         .with_native_roots()
         .https_or_http()
@@ -87,8 +508,14 @@ pub(crate) async fn get_fuel_status(config: &IndexerConfig) -> ServiceStatus {
         .enable_http2()
         .build();
 
-    let client = Client::builder().build::<_, hyper::Body>(https);
-    match client
+    Client::builder().build::<_, hyper::Body>(https)
+}
+
+pub(crate) async fn get_fuel_status(config: &IndexerConfig) -> ServiceStatus {
+    #[cfg(feature = "metrics")]
+    METRICS.web.health.requests.inc();
+
+    match fuel_node_client()
         .get(config.to_owned().fuel_node.health_check_uri())
         .await
     {
@@ -125,55 +552,373 @@ pub(crate) async fn health_check(
     })))
 }
 
+/// Run a query under the configured `query_timeout_secs`, logging any query
+/// that gets cancelled so the timeout can be tuned.
+///
+/// Each row of the result is one JSON value, so the rows are always returned
+/// as a list, even when the database hands back a single value.
+async fn run_query_with_timeout(
+    conn: &mut IndexerConnection,
+    query: String,
+    params: Vec<QueryParam>,
+    config: &IndexerConfig,
+) -> ApiResult<Vec<Value>> {
+    let timeout = Duration::from_secs(config.graphql_api.query_timeout_secs);
+    match queries::run_query(conn, query.clone(), params, timeout).await {
+        Ok(Value::Array(rows)) => Ok(rows),
+        Ok(Value::Null) => Ok(Vec::new()),
+        Ok(row) => Ok(vec![row]),
+        Err(e @ IndexerDatabaseError::QueryTimeout(_)) => {
+            warn!(
+                "Query cancelled after {}s, raise query_timeout_secs if it should be allowed: {query}",
+                timeout.as_secs()
+            );
+            Err(e.into())
+        }
+        Err(e) => {
+            error!("Error querying database: {e}.");
+            Err(e.into())
+        }
+    }
+}
+
+/// The height of the latest block known to the Fuel node, if it can be reached.
+pub(crate) async fn get_fuel_head_height(config: &IndexerConfig) -> Option<u64> {
+    let request = Request::post(config.to_owned().fuel_node.graphql_uri())
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({ "query": "{ chain { latestBlock { header { height } } } }" })
+                .to_string(),
+        ))
+        .ok()?;
+
+    let response = match fuel_node_client().request(request).await {
+        Ok(r) => r,
+        Err(e) => {
+            error!("Failed to fetch fuel head height: {e}.");
+            return None;
+        }
+    };
+
+    let body_bytes = hyper::body::to_bytes(response.into_body()).await.ok()?;
+    let body: Value = serde_json::from_slice(&body_bytes).ok()?;
+
+    // fuel-core serializes U64 scalars as strings.
+    match &body["data"]["chain"]["latestBlock"]["header"]["height"] {
+        Value::String(height) => height.parse().ok(),
+        Value::Number(height) => height.as_u64(),
+        _ => None,
+    }
+}
+
+/// Whether each indexer's future is still running, as reported by the service.
+///
+/// Returns `None` when the API server is not running alongside a service.
+async fn get_indexer_liveness(
+    tx: Option<Sender<ServiceRequest>>,
+) -> ApiResult<Option<HashMap<String, bool>>> {
+    let tx = match tx {
+        Some(tx) => tx,
+        None => return Ok(None),
+    };
+
+    let (reply, rx) = oneshot::channel();
+    tx.send(ServiceRequest::IndexStatus(IndexStatusRequest { reply }))
+        .await?;
+
+    // The service may be busy starting an indexer before it gets to the request.
+    let wait = Duration::from_secs(defaults::SERVICE_REPLY_TIMEOUT_SECS);
+    match timeout(wait, rx).await {
+        Ok(Ok(statuses)) => Ok(Some(statuses)),
+        _ => {
+            error!("Indexer service did not report indexer liveness.");
+            Ok(None)
+        }
+    }
+}
+
+pub(crate) async fn indexer_status(
+    Extension(config): Extension<IndexerConfig>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(tx): Extension<Option<Sender<ServiceRequest>>>,
+) -> ApiResult<axum::Json<Value>> {
+    let head_block_height = get_fuel_head_height(&config).await;
+    let liveness = get_indexer_liveness(tx).await?;
+
+    let mut conn = pool.acquire().await?;
+    let mut indexers = Vec::new();
+    for index in queries::registered_indices(&mut conn).await? {
+        let uid = index.uid();
+        let last_block_height = queries::last_block_height_for_indexer(
+            &mut conn,
+            &index.namespace,
+            &index.identifier,
+        )
+        .await?;
+        let lag = head_block_height
+            .zip(last_block_height)
+            .map(|(head, last)| head.saturating_sub(last));
+        let alive = liveness
+            .as_ref()
+            .map(|statuses| statuses.get(&uid).copied().unwrap_or(false));
+
+        indexers.push(json!({
+            "uid": uid,
+            "last_block_height": last_block_height,
+            "head_block_height": head_block_height,
+            "lag": lag,
+            "alive": alive,
+        }));
+    }
+
+    Ok(Json(json!({ "indexers": indexers })))
+}
+
 pub(crate) async fn stop_indexer(
     Path((namespace, identifier)): Path<(String, String)>,
+    UrlQuery(params): UrlQuery<RemoveIndexerParams>,
     Extension(tx): Extension<Option<Sender<ServiceRequest>>>,
     Extension(pool): Extension<IndexerConnectionPool>,
     Extension(claims): Extension<Claims>,
+    Extension(request_id): Extension<RequestId>,
 ) -> ApiResult<axum::Json<Value>> {
     if claims.is_unauthenticated() {
         return Err(ApiError::Http(HttpError::Unauthorized));
     }
 
+    if !claims.has_role(Role::Admin) {
+        return Err(ApiError::Http(HttpError::Forbidden));
+    }
+
     let mut conn = pool.acquire().await?;
 
+    if params.dry_run {
+        queries::index_id_for(&mut conn, &namespace, &identifier)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::RowNotFound => ApiError::Http(HttpError::NotFound(format!(
+                    "Indexer({namespace}.{identifier}) is not registered."
+                ))),
+                e => e.into(),
+            })?;
+
+        let tables = if params.purge {
+            queries::indexer_tables(&mut conn, &namespace, &identifier).await?
+        } else {
+            Vec::new()
+        };
+
+        return Ok(Json(json!({
+            "dry_run": true,
+            "dropped_tables": tables,
+        })));
+    }
+
     let _ = queries::start_transaction(&mut conn).await?;
 
-    if let Err(e) = queries::remove_indexer(&mut conn, &namespace, &identifier).await {
-        queries::revert_transaction(&mut conn).await?;
+    match remove_indexer_records(&mut conn, &namespace, &identifier, params.purge).await {
+        Err(e) => {
+            queries::revert_transaction(&mut conn).await?;
 
-        error!("Failed to remove Indexer({namespace}.{identifier}): {e}");
+            error!(
+                uid = %format!("{namespace}.{identifier}"),
+                "Failed to remove Indexer({namespace}.{identifier}): {e}"
+            );
 
-        return Err(ApiError::Sqlx(sqlx::Error::RowNotFound));
-    } else {
-        queries::commit_transaction(&mut conn).await?;
+            return Err(ApiError::Sqlx(sqlx::Error::RowNotFound));
+        }
+        Ok(tables) => {
+            queries::commit_transaction(&mut conn).await?;
 
-        if let Some(tx) = tx {
-            tx.send(ServiceRequest::IndexStop(IndexStopRequest {
-                namespace,
-                identifier,
-            }))
-            .await?;
+            if let Some(tx) = tx {
+                tx.send(ServiceRequest::IndexStop(IndexStopRequest {
+                    namespace,
+                    identifier,
+                    request_id: correlation_id(&request_id),
+                }))
+                .await?;
 
-            return Ok(Json(json!({
-                "success": "true"
-            })));
+                return Ok(Json(json!({
+                    "success": "true",
+                    "dropped_tables": tables,
+                })));
+            }
         }
     }
 
     Err(ApiError::default())
 }
 
+pub(crate) async fn pause_indexer(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(tx): Extension<Option<Sender<ServiceRequest>>>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(request_id): Extension<RequestId>,
+) -> ApiResult<axum::Json<Value>> {
+    let request = ServiceRequest::IndexPause(IndexPauseRequest {
+        namespace: namespace.clone(),
+        identifier: identifier.clone(),
+        request_id: correlation_id(&request_id),
+    });
+
+    send_indexer_request(&namespace, &identifier, request, tx, pool, claims).await
+}
+
+pub(crate) async fn resume_indexer(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(tx): Extension<Option<Sender<ServiceRequest>>>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(request_id): Extension<RequestId>,
+) -> ApiResult<axum::Json<Value>> {
+    let request = ServiceRequest::IndexResume(IndexResumeRequest {
+        namespace: namespace.clone(),
+        identifier: identifier.clone(),
+        request_id: correlation_id(&request_id),
+    });
+
+    send_indexer_request(&namespace, &identifier, request, tx, pool, claims).await
+}
+
+/// Have a running indexer handle blocks `[from, to]` again with its current
+/// module, after the block it is handling. Only blocks the indexer has already
+/// committed can be replayed.
+pub(crate) async fn replay_indexer(
+    Path((namespace, identifier, from, to)): Path<(String, String, u64, u64)>,
+    Extension(tx): Extension<Option<Sender<ServiceRequest>>>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(request_id): Extension<RequestId>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    if !claims.has_role(Role::Admin) {
+        return Err(ApiError::Http(HttpError::Forbidden));
+    }
+
+    let uid = format!("{namespace}.{identifier}");
+    let mut conn = pool.acquire().await?;
+    let last_block_height =
+        queries::last_block_height_for_indexer(&mut conn, &namespace, &identifier)
+            .await?
+            .ok_or_else(|| {
+                ApiError::InvalidReplay(format!(
+                    "Indexer({uid}) has not committed any blocks."
+                ))
+            })?;
+
+    if from == 0 || from > to || to > last_block_height {
+        return Err(ApiError::InvalidReplay(format!(
+            "[{from}, {to}] is not a block range between 1 and the last committed Block({last_block_height})."
+        )));
+    }
+
+    let request = ServiceRequest::IndexReplay(IndexReplayRequest {
+        namespace,
+        identifier,
+        from,
+        to,
+        request_id: correlation_id(&request_id),
+    });
+
+    if let Some(tx) = tx {
+        tx.send(request).await?;
+
+        return Ok(Json(json!({
+            "success": "true",
+            "from": from,
+            "to": to,
+        })));
+    }
+
+    Err(ApiError::default())
+}
+
+/// Send `request` about a registered indexer to the service, on behalf of an
+/// admin. Pausing and resuming leave the indexer's registration and assets as
+/// they are.
+async fn send_indexer_request(
+    namespace: &str,
+    identifier: &str,
+    request: ServiceRequest,
+    tx: Option<Sender<ServiceRequest>>,
+    pool: IndexerConnectionPool,
+    claims: Claims,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    if !claims.has_role(Role::Admin) {
+        return Err(ApiError::Http(HttpError::Forbidden));
+    }
+
+    let mut conn = pool.acquire().await?;
+    queries::index_id_for(&mut conn, namespace, identifier)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => ApiError::Http(HttpError::NotFound(format!(
+                "Indexer({namespace}.{identifier}) is not registered."
+            ))),
+            e => e.into(),
+        })?;
+
+    if let Some(tx) = tx {
+        tx.send(request).await?;
+
+        return Ok(Json(json!({
+            "success": "true"
+        })));
+    }
+
+    Err(ApiError::default())
+}
+
+/// Correlation ID of a request, which is passed on to the service requests it
+/// triggers so that the indexer service's logs carry it too.
+fn correlation_id(request_id: &RequestId) -> Option<String> {
+    request_id.header_value().to_str().ok().map(str::to_string)
+}
+
+/// Deregister an indexer and, if `purge` is set, drop its tables, returning
+/// the tables that were dropped.
+async fn remove_indexer_records(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    purge: bool,
+) -> sqlx::Result<Vec<String>> {
+    let mut tables = Vec::new();
+    if purge {
+        tables = queries::indexer_tables(conn, namespace, identifier).await?;
+        queries::purge_indexer(conn, namespace, identifier).await?;
+    }
+
+    queries::remove_indexer(conn, namespace, identifier).await?;
+
+    Ok(tables)
+}
+
 pub(crate) async fn revert_indexer(
     Path((namespace, identifier)): Path<(String, String)>,
     Extension(tx): Extension<Option<Sender<ServiceRequest>>>,
     Extension(pool): Extension<IndexerConnectionPool>,
     Extension(claims): Extension<Claims>,
+    Extension(request_id): Extension<RequestId>,
 ) -> ApiResult<axum::Json<Value>> {
     if claims.is_unauthenticated() {
         return Err(ApiError::Http(HttpError::Unauthorized));
     }
 
+    if !claims.has_role(Role::Deployer) {
+        return Err(ApiError::Http(HttpError::Forbidden));
+    }
+
+    check_may_modify(&pool, &claims, &namespace, &identifier).await?;
+
     let mut conn = pool.acquire().await?;
     let asset = queries::penultimate_asset_for_index(
         &mut conn,
@@ -189,6 +934,7 @@ pub(crate) async fn revert_indexer(
             penultimate_asset_bytes: asset.bytes,
             namespace,
             identifier,
+            request_id: correlation_id(&request_id),
         }))
         .await?;
 
@@ -200,90 +946,343 @@ pub(crate) async fn revert_indexer(
     Err(ApiError::default())
 }
 
+/// List every registered version of an indexer's assets, along with each
+/// version's digest and upload time.
+pub(crate) async fn indexer_assets(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+    let index_id = queries::index_id_for(&mut conn, &namespace, &identifier)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => ApiError::Http(HttpError::NotFound(format!(
+                "Indexer({namespace}.{identifier}) is not registered."
+            ))),
+            e => e.into(),
+        })?;
+
+    let wasm =
+        queries::assets_for_index(&mut conn, &index_id, IndexAssetType::Wasm).await?;
+    let manifest =
+        queries::assets_for_index(&mut conn, &index_id, IndexAssetType::Manifest).await?;
+    let schema =
+        queries::assets_for_index(&mut conn, &index_id, IndexAssetType::Schema).await?;
+
+    Ok(Json(json!({
+        "namespace": namespace,
+        "identifier": identifier,
+        "wasm": wasm,
+        "manifest": manifest,
+        "schema": schema,
+    })))
+}
+
+/// The running totals of an indexer's work, which are kept across restarts.
+pub(crate) async fn indexer_stats(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+    let stats = queries::indexer_stats(&mut conn, &namespace, &identifier)
+        .await?
+        .ok_or_else(|| {
+            ApiError::Http(HttpError::NotFound(format!(
+                "No stats are recorded for Indexer({namespace}.{identifier})."
+            )))
+        })?;
+    let last_block_height =
+        queries::last_block_height_for_indexer(&mut conn, &namespace, &identifier)
+            .await
+            .unwrap_or(None);
+
+    Ok(Json(json!({
+        "namespace": stats.namespace,
+        "identifier": stats.identifier,
+        "registered_at": stats.registered_at,
+        "blocks_processed": stats.blocks_processed,
+        "objects_written": stats.objects_written,
+        "last_block_height": last_block_height,
+    })))
+}
+
+/// Delete the objects an indexer wrote at or above a block height, and reset
+/// its last block height, so that a resumable indexer re-processes the blocks
+/// from there once it is started again.
+pub(crate) async fn rollback_indexer(
+    Path((namespace, identifier, height)): Path<(String, String, u64)>,
+    Extension(tx): Extension<Option<Sender<ServiceRequest>>>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(config): Extension<IndexerConfig>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    if !claims.has_role(Role::Admin) {
+        return Err(ApiError::Http(HttpError::Forbidden));
+    }
+
+    // A running indexer would keep writing objects above the rollback height.
+    let uid = format!("{namespace}.{identifier}");
+    if tx.is_some() {
+        let running = match get_indexer_liveness(tx).await? {
+            Some(statuses) => statuses.get(&uid).copied().unwrap_or(false),
+            None => true,
+        };
+        if running {
+            return Err(ApiError::IndexerRunning(uid));
+        }
+    }
+
+    let mut conn = pool.acquire().await?;
+    let last_block_height =
+        queries::last_block_height_for_indexer(&mut conn, &namespace, &identifier)
+            .await?
+            .ok_or_else(|| {
+                ApiError::InvalidRollback(format!(
+                    "Indexer({uid}) has not committed any blocks."
+                ))
+            })?;
+
+    if height == 0 || height > last_block_height {
+        return Err(ApiError::InvalidRollback(format!(
+            "Block({height}) is not between 1 and the last committed Block({last_block_height})."
+        )));
+    }
+
+    // Writes are only recorded for the last `max_reorg_depth` blocks.
+    if last_block_height - height >= config.max_reorg_depth {
+        return Err(ApiError::InvalidRollback(format!(
+            "Block({height}) is more than the max reorg depth of {} blocks behind the last committed Block({last_block_height}).",
+            config.max_reorg_depth
+        )));
+    }
+
+    // Writes are recorded at the first block of the batch that made them, so
+    // roll back from the start of the batch spanning `height`.
+    let height =
+        queries::object_writes_batch_start(&mut conn, &namespace, &identifier, height)
+            .await?
+            .unwrap_or(height);
+
+    queries::start_transaction(&mut conn).await?;
+
+    let result = async {
+        let count =
+            queries::revert_to_block(&mut conn, &namespace, &identifier, height).await?;
+        queries::set_last_block_height(&mut conn, &namespace, &identifier, height - 1)
+            .await?;
+        Ok::<usize, sqlx::Error>(count)
+    }
+    .await;
+
+    match result {
+        Ok(count) => {
+            queries::commit_transaction(&mut conn).await?;
+
+            info!(
+                uid = %uid,
+                block_height = height,
+                "Rolled back Indexer({uid}) to Block({height}), deleting {count} objects."
+            );
+
+            Ok(Json(json!({
+                "success": "true",
+                "block_height": height,
+                "objects_deleted": count,
+            })))
+        }
+        Err(e) => {
+            queries::revert_transaction(&mut conn).await?;
+
+            error!(uid = %uid, "Failed to roll back Indexer({uid}): {e}");
+
+            Err(e.into())
+        }
+    }
+}
+
+/// The latest WASM module registered for an indexer, if it is registered.
+async fn registered_wasm(
+    pool: &IndexerConnectionPool,
+    namespace: &str,
+    identifier: &str,
+) -> ApiResult<Option<Vec<u8>>> {
+    let mut conn = pool.acquire().await?;
+    let index_id = match queries::index_id_for(&mut conn, namespace, identifier).await {
+        Ok(index_id) => index_id,
+        Err(sqlx::Error::RowNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    match queries::latest_asset_for_index(&mut conn, &index_id, IndexAssetType::Wasm)
+        .await
+    {
+        Ok(asset) => Ok(Some(asset.bytes)),
+        Err(sqlx::Error::RowNotFound) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
 pub(crate) async fn register_indexer_assets(
     Path((namespace, identifier)): Path<(String, String)>,
     Extension(tx): Extension<Option<Sender<ServiceRequest>>>,
     Extension(schema_manager): Extension<Arc<RwLock<SchemaManager>>>,
     Extension(claims): Extension<Claims>,
     Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(config): Extension<IndexerConfig>,
+    Extension(request_id): Extension<RequestId>,
     multipart: Option<Multipart>,
 ) -> ApiResult<axum::Json<Value>> {
     if claims.is_unauthenticated() {
         return Err(ApiError::Http(HttpError::Unauthorized));
     }
 
+    if !claims.has_role(Role::Deployer) {
+        return Err(ApiError::Http(HttpError::Forbidden));
+    }
+
+    // Only the deployer of an existing indexer may update it.
+    check_may_modify(&pool, &claims, &namespace, &identifier).await?;
+
     if let Some(mut multipart) = multipart {
+        let mut uploads = Vec::new();
+
+        while let Some(mut field) = multipart.next_field().await? {
+            let name = field.name().unwrap_or("").to_string();
+            let asset_type =
+                IndexAssetType::from_str(&name).expect("Invalid asset type.");
+            let max_size = match asset_type {
+                IndexAssetType::Wasm => config.graphql_api.max_wasm_size,
+                IndexAssetType::Manifest => config.graphql_api.max_manifest_size,
+                IndexAssetType::Schema => config.graphql_api.max_schema_size,
+            };
+
+            // Read the field a chunk at a time, so that an oversized asset is
+            // rejected without buffering all of it.
+            let mut data = Vec::new();
+            while let Some(chunk) = field.chunk().await? {
+                if data.len() + chunk.len() > max_size {
+                    return Err(ApiError::AssetTooLarge(asset_type, max_size));
+                }
+                data.extend_from_slice(&chunk);
+            }
+
+            uploads.push((asset_type, data));
+        }
+
+        // Reject a module that the service could not run before registering
+        // anything, rather than when the indexer is reloaded.
+        if let Some(tx) = &tx {
+            let find = |asset_type: IndexAssetType| {
+                uploads
+                    .iter()
+                    .find(|(t, _)| *t == asset_type)
+                    .map(|(_, data)| data.clone())
+            };
+            let manifest = find(IndexAssetType::Manifest);
+
+            // A manifest uploaded on its own is checked against the module
+            // that is already registered.
+            let wasm = match find(IndexAssetType::Wasm) {
+                Some(wasm) => Some(wasm),
+                None if manifest.is_some() => {
+                    registered_wasm(&pool, &namespace, &identifier).await?
+                }
+                None => None,
+            };
+
+            if let Some(wasm) = wasm {
+                let schema = find(IndexAssetType::Schema)
+                    .map(|data| String::from_utf8_lossy(&data).into_owned());
+
+                let (reply, rx) = oneshot::channel();
+                tx.send(ServiceRequest::AssetValidate(AssetValidateRequest {
+                    namespace: namespace.clone(),
+                    identifier: identifier.clone(),
+                    wasm,
+                    schema,
+                    manifest,
+                    reply,
+                    request_id: correlation_id(&request_id),
+                }))
+                .await?;
+
+                match rx.await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => return Err(ApiError::InvalidAssets(e)),
+                    Err(_) => {
+                        error!("Indexer service did not report whether the assets are valid.");
+                        return Err(ApiError::default());
+                    }
+                }
+            }
+        }
+
         let mut conn = pool.acquire().await?;
 
         let _ = queries::start_transaction(&mut conn).await?;
 
         let mut assets: Vec<IndexAsset> = Vec::new();
+        let mut unchanged = true;
 
-        while let Some(field) = multipart.next_field().await.unwrap() {
-            let name = field.name().unwrap_or("").to_string();
-            let data = field.bytes().await.unwrap_or_default();
-            let asset_type =
-                IndexAssetType::from_str(&name).expect("Invalid asset type.");
+        for (asset_type, data) in uploads {
+            let (asset, asset_unchanged) = queries::register_index_asset(
+                &mut conn,
+                &namespace,
+                &identifier,
+                data.clone(),
+                asset_type.clone(),
+                Some(&claims.sub),
+            )
+            .await?;
 
-            let asset: IndexAsset = match asset_type {
-                IndexAssetType::Wasm | IndexAssetType::Manifest => {
-                    queries::register_index_asset(
-                        &mut conn,
+            if asset_type == IndexAssetType::Schema && !asset_unchanged {
+                schema_manager
+                    .write()
+                    .await
+                    .new_schema(
                         &namespace,
                         &identifier,
-                        data.to_vec(),
-                        asset_type,
-                        Some(&claims.sub),
-                    )
-                    .await?
-                }
-                IndexAssetType::Schema => {
-                    match queries::register_index_asset(
+                        &String::from_utf8_lossy(&data),
                         &mut conn,
-                        &namespace,
-                        &identifier,
-                        data.to_vec(),
-                        IndexAssetType::Schema,
-                        Some(&claims.sub),
                     )
-                    .await
-                    {
-                        Ok(result) => {
-                            schema_manager
-                                .write()
-                                .await
-                                .new_schema(
-                                    &namespace,
-                                    &identifier,
-                                    &String::from_utf8_lossy(&data),
-                                    &mut conn,
-                                )
-                                .await?;
-
-                            result
-                        }
-                        Err(e) => {
-                            return Err(e.into());
-                        }
-                    }
-                }
-            };
+                    .await?;
+            }
 
+            unchanged &= asset_unchanged;
             assets.push(asset);
         }
 
         let _ = queries::commit_transaction(&mut conn).await?;
 
-        if let Some(tx) = tx {
-            tx.send(ServiceRequest::AssetReload(AssetReloadRequest {
-                namespace,
-                identifier,
-            }))
-            .await?;
+        // Identical assets leave the running indexer as it is.
+        if !unchanged {
+            if let Some(tx) = tx {
+                tx.send(ServiceRequest::AssetReload(AssetReloadRequest {
+                    namespace,
+                    identifier,
+                    request_id: correlation_id(&request_id),
+                }))
+                .await?;
+            }
         }
 
         return Ok(Json(json!({
             "success": "true",
+            "unchanged": unchanged,
             "assets": assets,
         })));
     }
@@ -300,6 +1299,40 @@ pub(crate) async fn get_nonce(
     Ok(Json(json!(nonce)))
 }
 
+/// Fail with `403 Forbidden` unless `claims` may modify the indexer
+/// `namespace.identifier`, if it is registered.
+async fn check_may_modify(
+    pool: &IndexerConnectionPool,
+    claims: &Claims,
+    namespace: &str,
+    identifier: &str,
+) -> ApiResult<()> {
+    let mut conn = pool.acquire().await?;
+    if let Some(index) =
+        queries::index_is_registered(&mut conn, namespace, identifier).await?
+    {
+        let deployer = index
+            .pubkey
+            .and_then(|pubkey| PublicKey::from_str(&pubkey).ok())
+            .map(|pk| pubkey_address(&pk));
+
+        if !claims.may_modify(deployer.as_ref()) {
+            error!(
+                "{} may not modify {namespace}.{identifier}, which it did not deploy.",
+                claims.sub
+            );
+            return Err(ApiError::Http(HttpError::Forbidden));
+        }
+    }
+
+    Ok(())
+}
+
+/// The address owned by `pk`.
+fn pubkey_address(pk: &PublicKey) -> Address {
+    Address::new(*pk.hash())
+}
+
 pub(crate) async fn verify_signature(
     Extension(config): Extension<IndexerConfig>,
     Extension(pool): Extension<IndexerConnectionPool>,
@@ -318,14 +1351,34 @@ pub(crate) async fn verify_signature(
                 let mut buff: [u8; 64] = [0u8; 64];
                 buff.copy_from_slice(&payload.signature.as_bytes()[..64]);
                 let sig = Signature::from_bytes(buff);
-                let msg = Message::new(payload.message);
-                let pk = sig.recover(&msg)?;
+                let encoding = config.authentication.signature_encoding;
+                let msg = Message::new(encoding.encode(&payload.message));
+                let pk = sig.recover(&msg).map_err(|e| {
+                    error!("Failed to recover signer of {encoding:?} message: {e}.");
+                    ApiError::SignatureEncoding(encoding)
+                })?;
 
                 let now = SystemTime::now()
                     .duration_since(UNIX_EPOCH)
                     .unwrap()
                     .as_secs() as usize;
 
+                let max_role = if config
+                    .authentication
+                    .admin_pubkeys
+                    .contains(&pk.to_string())
+                {
+                    Role::Admin
+                } else {
+                    Role::Deployer
+                };
+                let role = payload.role.unwrap_or(max_role);
+
+                if role > max_role {
+                    error!("{pk} may not be issued a {role:?} token.");
+                    return Err(ApiError::Http(HttpError::Forbidden));
+                }
+
                 let claims = Claims {
                     sub: pk.to_string(),
                     iss: config.authentication.jwt_issuer.unwrap_or_default(),
@@ -335,6 +1388,8 @@ pub(crate) async fn verify_signature(
                             .authentication
                             .jwt_expiry
                             .unwrap_or(defaults::JWT_EXPIRY_SECS),
+                    role,
+                    address: Some(pubkey_address(&pk)),
                 };
 
                 if let Err(e) = sig.verify(&pk, &msg) {
@@ -358,7 +1413,11 @@ pub(crate) async fn verify_signature(
 
                 Ok(Json(json!({ "token": token })))
             }
-            _ => {
+            Some(AuthenticationStrategy::ApiKey) => {
+                error!("Signatures are not used with API key authentication.");
+                Err(ApiError::Http(HttpError::BadRequest))
+            }
+            None => {
                 error!("Unsupported authentication strategy.");
                 unimplemented!();
             }
@@ -368,28 +1427,253 @@ pub(crate) async fn verify_signature(
     }
 }
 
+/// Run a GraphQL query, returning its rows under `data`, along with an
+/// `errors` list of the root fields that failed, if any. Paginated queries
+/// return their page under `data` instead.
 pub async fn run_query(
     query: Query,
     schema: Schema,
     pool: &IndexerConnectionPool,
+    config: &IndexerConfig,
 ) -> ApiResult<Value> {
-    let builder = GraphqlQueryBuilder::new(&schema, &query.query)?;
+    let builder = GraphqlQueryBuilder::new(&schema, &query.query)?
+        .max_depth(config.graphql_api.max_query_depth)
+        .max_complexity(config.graphql_api.max_query_complexity);
     let query = builder.build()?;
+    let mut user_queries = query.parse(&schema);
 
-    let queries = query.as_sql(&schema, pool.database_type()).join(";\n");
+    if user_queries.iter().any(|q| q.pagination.is_some()) {
+        if user_queries.len() != 1 {
+            return Err(ApiError::InvalidPagination(
+                "Pagination is only supported on a single root field.".to_string(),
+            ));
+        }
+
+        return run_paginated_query(user_queries.remove(0), pool, config).await;
+    }
+
+    // Fetch one row past the limit so that an over-limit query can be
+    // detected without scanning the rest of the table.
+    let max_rows = config.graphql_api.max_query_rows;
+    let db_type = pool.database_type();
 
     let mut conn = pool.acquire().await?;
 
-    match queries::run_query(&mut conn, queries).await {
-        Ok(ans) => {
-            let row: Value = serde_json::from_value(ans)?;
-            Ok(row)
+    // Queries with bound parameters can't be sent as a single multi-statement
+    // query, so each root field is queried on its own.
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    for user_query in user_queries.iter_mut() {
+        let query = format!("{} LIMIT {}", user_query.to_sql(&db_type), max_rows + 1);
+        let params = user_query.params(&db_type);
+
+        // A root field that fails is reported alongside the results of the
+        // others, GraphQL-style, rather than failing the whole query.
+        match run_query_with_timeout(&mut conn, query, params, config).await {
+            Ok(field_rows) => rows.extend(field_rows),
+            Err(e) => {
+                // Quoted table names are reported without their quotes.
+                let entity = user_query.entity_name.trim_matches('"').to_string();
+                errors.push((entity, e));
+                continue;
+            }
         }
-        Err(e) => {
-            error!("Error querying database: {e}.");
-            Err(e.into())
+
+        if rows.len() > max_rows {
+            return Err(ApiError::QueryRowLimitExceeded(max_rows));
         }
     }
+
+    if errors.is_empty() {
+        return Ok(json!({ "data": rows }));
+    }
+
+    // With nothing to return, the query fails as a whole.
+    if errors.len() == user_queries.len() {
+        return Err(errors.remove(0).1);
+    }
+
+    let errors = errors
+        .into_iter()
+        .map(|(entity, e)| {
+            let (_, message) = e.into_status_and_details();
+            json!({ "message": message, "path": [entity] })
+        })
+        .collect::<Vec<Value>>();
+
+    Ok(json!({ "data": rows, "errors": errors }))
+}
+
+/// Run a GraphQL query, streaming its rows as newline-delimited JSON while they
+/// are read from the database, rather than collecting them into one response.
+///
+/// Each line is a row, as it would be listed in a JSON response. A root field
+/// that fails is reported on a line of its own, as `{"errors": [...]}`, and the
+/// rows of the other fields are still streamed. Since the rows are never all in
+/// memory, streamed queries aren't held to `max_query_rows`, but they can't be
+/// paginated either.
+///
+/// A stream holds a database connection until its rows are read, so at most
+/// `max_concurrent_streams` queries are streamed at once, and a stream that
+/// its client hasn't read within `stream_timeout_secs` is cut off.
+async fn stream_query(
+    query: Query,
+    schema: Schema,
+    pool: &IndexerConnectionPool,
+    config: &IndexerConfig,
+    streams: &StreamSlots,
+) -> ApiResult<Response> {
+    let builder = GraphqlQueryBuilder::new(&schema, &query.query)?
+        .max_depth(config.graphql_api.max_query_depth)
+        .max_complexity(config.graphql_api.max_query_complexity);
+    let query = builder.build()?;
+    let mut user_queries = query.parse(&schema);
+
+    if user_queries.iter().any(|q| q.pagination.is_some()) {
+        return Err(ApiError::InvalidPagination(
+            "Paginated queries can't be streamed.".to_string(),
+        ));
+    }
+
+    let slot = streams.try_acquire()?;
+    let db_type = pool.database_type();
+    let timeout = Duration::from_secs(config.graphql_api.query_timeout_secs);
+    let stream_timeout_secs = config.graphql_api.stream_timeout_secs;
+    let mut conn = pool.acquire().await?;
+
+    // Rows are handed over a bounded channel, so the database is only read as
+    // fast as the client takes the response.
+    let (lines, rx) = mpsc::channel::<Value>(STREAM_BATCH_SIZE);
+
+    tokio::spawn(async move {
+        let _slot = slot;
+        let stream = async {
+            for user_query in user_queries.iter_mut() {
+                let query = user_query.to_sql(&db_type);
+                let params = user_query.params(&db_type);
+
+                let result = queries::stream_query(
+                    &mut conn,
+                    query,
+                    params,
+                    timeout,
+                    STREAM_BATCH_SIZE,
+                    |row| {
+                        let lines = lines.clone();
+                        async move { lines.send(row).await.is_ok() }
+                    },
+                )
+                .await;
+
+                if let Err(e) = result {
+                    error!("Error streaming query: {e}.");
+                    let entity = user_query.entity_name.trim_matches('"').to_string();
+                    let (_, message) = ApiError::from(e).into_status_and_details();
+                    let errors =
+                        json!({ "errors": [{ "message": message, "path": [entity] }] });
+                    if lines.send(errors).await.is_err() {
+                        break;
+                    }
+                }
+
+                // The client went away, so the rest of the query is of no use.
+                if lines.is_closed() {
+                    break;
+                }
+            }
+        };
+
+        if stream_timeout_secs == 0 {
+            return stream.await;
+        }
+
+        // Dropping the stream rolls back its transaction, and the connection
+        // goes back to the pool.
+        let deadline = Duration::from_secs(stream_timeout_secs);
+        if tokio::time::timeout(deadline, stream).await.is_err() {
+            warn!("Streamed query was not read within {stream_timeout_secs}s, cutting it off.");
+            let message = format!(
+                "Query was not read within the stream timeout of {stream_timeout_secs}s and was cut off."
+            );
+            // The client is slow to read, so don't wait on it to make room.
+            let _ = lines.try_send(json!({ "errors": [{ "message": message }] }));
+        }
+    });
+
+    let rows = futures::stream::unfold(rx, |mut rx| async move {
+        let row = rx.recv().await?;
+        Some((Ok::<_, Infallible>(format!("{row}\n")), rx))
+    });
+
+    Ok(StreamBody::new(rows).into_response())
+}
+
+/// Run a single paginated root query, returning a page of results along with
+/// `pageInfo` describing how to fetch the next page, and the `totalCount` of
+/// rows matching the query across all pages.
+async fn run_paginated_query(
+    mut user_query: UserQuery,
+    pool: &IndexerConnectionPool,
+    config: &IndexerConfig,
+) -> ApiResult<Value> {
+    let pagination = user_query
+        .pagination
+        .as_mut()
+        .expect("Paginated query is missing pagination.");
+
+    let first = pagination
+        .first
+        .unwrap_or(config.graphql_api.default_page_size as u64);
+
+    if first > defaults::MAX_PAGE_SIZE as u64 {
+        return Err(ApiError::InvalidPagination(format!(
+            "Page size {first} exceeds the maximum of {}.",
+            defaults::MAX_PAGE_SIZE
+        )));
+    }
+
+    pagination.first = Some(first);
+    let offset = pagination.offset;
+
+    let db_type = pool.database_type();
+    let query = user_query.to_sql(&db_type);
+    let params = user_query.params(&db_type);
+    let mut conn = pool.acquire().await?;
+
+    let mut rows = run_query_with_timeout(&mut conn, query, params, config).await?;
+
+    // Every row carries the same count; an empty page past the end of the
+    // results has none, so the rows of previous pages are all there is.
+    let total_count = rows
+        .first()
+        .and_then(|row| row["total_count"].as_u64())
+        .unwrap_or(offset);
+
+    // One row past the page size is fetched to tell whether another page exists.
+    let has_next_page = rows.len() as u64 > first;
+    rows.truncate(first as usize);
+
+    let end_cursor = rows.last().and_then(|row| row["id"].as_i64()).map(|id| {
+        Cursor {
+            id,
+            offset: offset + rows.len() as u64,
+        }
+        .encode()
+    });
+
+    let data = rows
+        .into_iter()
+        .map(|mut row| row["node"].take())
+        .collect::<Vec<Value>>();
+
+    Ok(json!({
+        "data": data,
+        "totalCount": total_count,
+        "pageInfo": {
+            "hasNextPage": has_next_page,
+            "endCursor": end_cursor,
+        },
+    }))
 }
 
 pub async fn metrics(_req: Request<Body>) -> impl IntoResponse {
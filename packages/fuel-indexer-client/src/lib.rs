@@ -0,0 +1,278 @@
+#![deny(unused_crate_dependencies)]
+
+pub mod models;
+
+pub use crate::models::{
+    HealthResponse, IndexerAssets, Nonce, RegisterResponse, RegisteredAsset, StopParams,
+    StopResponse, VerifySignatureRequest,
+};
+
+use crate::models::{ErrorResponse, QueryRequest, VerifySignatureResponse};
+use fuel_indexer_lib::config::auth::Role;
+use reqwest::{
+    header::AUTHORIZATION,
+    multipart::{Form, Part},
+    RequestBuilder, StatusCode,
+};
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+pub type ClientResult<T> = Result<T, ClientError>;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("Request error: {0:?}")]
+    Request(#[from] reqwest::Error),
+    #[error("Serialization error: {0:?}")]
+    Serde(#[from] serde_json::Error),
+    #[error("API server returned {status}: {details}")]
+    Api {
+        status: StatusCode,
+        details: String,
+        /// The field of a GraphQL query that the error is about, if any.
+        field: Option<String>,
+    },
+}
+
+/// Async client for the endpoints of the API server.
+///
+/// Endpoints that need authentication send the client's token, which is either
+/// set up front with `with_token`, or issued by `authenticate`.
+#[derive(Clone, Debug)]
+pub struct IndexerClient {
+    client: reqwest::Client,
+    url: String,
+    token: Option<String>,
+}
+
+impl IndexerClient {
+    /// A client for the API server at `url`, e.g. `http://localhost:29987`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.into().trim_end_matches('/').to_string(),
+            token: None,
+        }
+    }
+
+    /// Send `token` with each request, e.g. an API key or a token issued earlier.
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Get a nonce to sign, to be issued a token by `verify_signature`.
+    pub async fn nonce(&self) -> ClientResult<Nonce> {
+        let request = self.client.get(self.endpoint("auth/nonce"));
+        self.send(request).await
+    }
+
+    /// Exchange the signature of a nonce for a token.
+    pub async fn verify_signature(
+        &self,
+        request: &VerifySignatureRequest,
+    ) -> ClientResult<String> {
+        let request = self
+            .client
+            .post(self.endpoint("auth/signature"))
+            .json(request);
+        let response: VerifySignatureResponse = self.send(request).await?;
+        Ok(response.token)
+    }
+
+    /// Sign a fresh nonce with `sign`, which returns the signature of the nonce
+    /// it is given, and use the token issued for it from then on.
+    pub async fn authenticate<F>(&mut self, sign: F) -> ClientResult<&str>
+    where
+        F: FnOnce(&str) -> String,
+    {
+        self.authenticate_as(sign, None).await
+    }
+
+    /// Like `authenticate`, but asks for a token with `role`, rather than the
+    /// most the signer may hold.
+    pub async fn authenticate_as<F>(
+        &mut self,
+        sign: F,
+        role: Option<Role>,
+    ) -> ClientResult<&str>
+    where
+        F: FnOnce(&str) -> String,
+    {
+        let nonce = self.nonce().await?;
+        let token = self
+            .verify_signature(&VerifySignatureRequest {
+                signature: sign(&nonce.uid),
+                message: nonce.uid,
+                role,
+            })
+            .await?;
+
+        Ok(self.token.insert(token).as_str())
+    }
+
+    pub async fn health(&self) -> ClientResult<HealthResponse> {
+        let request = self.client.get(self.endpoint("health"));
+        self.send(request).await
+    }
+
+    /// Register `assets` for an indexer, deploying it if it isn't registered.
+    pub async fn register(
+        &self,
+        namespace: &str,
+        identifier: &str,
+        assets: IndexerAssets,
+    ) -> ClientResult<RegisterResponse> {
+        let IndexerAssets {
+            manifest,
+            schema,
+            wasm,
+        } = assets;
+
+        let mut form = Form::new();
+        for (name, data) in [("manifest", manifest), ("schema", schema), ("wasm", wasm)] {
+            if let Some(data) = data {
+                form = form.part(name, Part::bytes(data).file_name(name));
+            }
+        }
+
+        let request = self
+            .client
+            .post(self.index_endpoint(namespace, identifier))
+            .multipart(form);
+        self.send(request).await
+    }
+
+    /// Stop an indexer and remove its registration.
+    pub async fn stop(
+        &self,
+        namespace: &str,
+        identifier: &str,
+        params: &StopParams,
+    ) -> ClientResult<StopResponse> {
+        let request = self
+            .client
+            .delete(self.index_endpoint(namespace, identifier))
+            .query(params);
+        self.send(request).await
+    }
+
+    /// Revert an indexer to its previous module.
+    pub async fn revert(&self, namespace: &str, identifier: &str) -> ClientResult<()> {
+        let request = self.client.put(self.index_endpoint(namespace, identifier));
+        self.send::<serde_json::Value>(request).await?;
+        Ok(())
+    }
+
+    /// Run a GraphQL query against the graph of an indexer.
+    ///
+    /// A query returns a list of rows, or, when paginated, an object of the
+    /// rows under `data` and the page under `page_info`.
+    pub async fn query<T: DeserializeOwned>(
+        &self,
+        namespace: &str,
+        identifier: &str,
+        query: &str,
+    ) -> ClientResult<T> {
+        let request = self
+            .client
+            .post(self.endpoint(&format!("graph/{namespace}/{identifier}")))
+            .json(&QueryRequest { query, params: "" });
+        self.send(request).await
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}/api/{path}", self.url)
+    }
+
+    fn index_endpoint(&self, namespace: &str, identifier: &str) -> String {
+        self.endpoint(&format!("index/{namespace}/{identifier}"))
+    }
+
+    /// Send `request` with the client's token, and parse the response, or the
+    /// error the API server returned instead.
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> ClientResult<T> {
+        let request = match &self.token {
+            Some(token) => request.header(AUTHORIZATION, token),
+            None => request,
+        };
+
+        let response = request.send().await?;
+        let status = response.status();
+        let body = response.bytes().await?;
+
+        if !status.is_success() {
+            return Err(api_error(status, &body));
+        }
+
+        Ok(serde_json::from_slice(&body)?)
+    }
+}
+
+/// The error of a failed response, with the details the API server gave, or
+/// else the body as it is.
+fn api_error(status: StatusCode, body: &[u8]) -> ClientError {
+    match serde_json::from_slice::<ErrorResponse>(body) {
+        Ok(ErrorResponse { details, field }) => ClientError::Api {
+            status,
+            details,
+            field,
+        },
+        Err(_) => ClientError::Api {
+            status,
+            details: String::from_utf8_lossy(body).into_owned(),
+            field: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_builds_endpoints() {
+        let client = IndexerClient::new("http://localhost:29987/");
+
+        assert_eq!(
+            client.endpoint("health"),
+            "http://localhost:29987/api/health"
+        );
+        assert_eq!(
+            client.index_endpoint("fuel_indexer", "hello_index"),
+            "http://localhost:29987/api/index/fuel_indexer/hello_index"
+        );
+    }
+
+    #[test]
+    fn test_api_error_keeps_details_of_error_responses() {
+        let body = br#"{"success":"false","details":"Unrecognized Field in \"Thing\": \"foo\"","field":"foo"}"#;
+        match api_error(StatusCode::BAD_REQUEST, body) {
+            ClientError::Api {
+                status,
+                details,
+                field,
+            } => {
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+                assert_eq!(details, r#"Unrecognized Field in "Thing": "foo""#);
+                assert_eq!(field.as_deref(), Some("foo"));
+            }
+            e => panic!("Unexpected error: {e:?}"),
+        }
+
+        match api_error(StatusCode::PAYLOAD_TOO_LARGE, b"length limit exceeded") {
+            ClientError::Api { details, field, .. } => {
+                assert_eq!(details, "length limit exceeded");
+                assert_eq!(field, None);
+            }
+            e => panic!("Unexpected error: {e:?}"),
+        }
+    }
+}
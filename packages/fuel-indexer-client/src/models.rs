@@ -0,0 +1,91 @@
+use fuel_indexer_lib::{config::auth::Role, utils::ServiceStatus};
+use serde::{Deserialize, Serialize};
+
+/// A nonce to be signed, to be issued a token.
+#[derive(Deserialize, Clone, Debug)]
+pub struct Nonce {
+    pub uid: String,
+    /// Unix timestamp (in seconds) after which the nonce can't be used.
+    pub expiry: i64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct VerifySignatureRequest {
+    pub signature: String,
+    pub message: String,
+    /// Role to issue the token with, defaulting to the most the signer may hold.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<Role>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub(crate) struct VerifySignatureResponse {
+    pub token: String,
+}
+
+/// Assets of an indexer to register. Assets left out keep their registered
+/// version.
+#[derive(Clone, Debug, Default)]
+pub struct IndexerAssets {
+    pub manifest: Option<Vec<u8>>,
+    pub schema: Option<Vec<u8>>,
+    pub wasm: Option<Vec<u8>>,
+}
+
+/// A registered version of an indexer asset.
+#[derive(Deserialize, Clone, Debug)]
+pub struct RegisteredAsset {
+    pub id: i64,
+    pub index_id: i64,
+    pub version: i32,
+    pub digest: String,
+    /// Unix timestamp (in seconds) at which the asset was uploaded.
+    pub created_at: i64,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct RegisterResponse {
+    /// Whether every asset was identical to its registered version, in which
+    /// case the running indexer was left as it is.
+    pub unchanged: bool,
+    pub assets: Vec<RegisteredAsset>,
+}
+
+/// Query parameters of a request to stop an indexer.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct StopParams {
+    /// Drop the indexer's tables along with its registration.
+    pub purge: bool,
+    /// List what would be dropped, without removing anything.
+    pub dry_run: bool,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct StopResponse {
+    /// Tables that were dropped, or that would be for a dry run.
+    #[serde(default)]
+    pub dropped_tables: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub(crate) struct QueryRequest<'a> {
+    pub query: &'a str,
+    pub params: &'a str,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct HealthResponse {
+    pub fuel_core_status: ServiceStatus,
+    pub database_status: ServiceStatus,
+    #[serde(rename = "uptime(seconds)")]
+    pub uptime_secs: String,
+}
+
+/// Body of an error response.
+#[derive(Deserialize, Clone, Debug)]
+pub(crate) struct ErrorResponse {
+    pub details: String,
+    /// The field of a GraphQL query that the error is about, if any.
+    #[serde(default)]
+    pub field: Option<String>,
+}
@@ -1,3 +1,4 @@
+use crate::QueryParam;
 use strum::{AsRefStr, EnumString};
 
 #[derive(Debug, EnumString, AsRefStr, Default)]
@@ -38,3 +39,13 @@ impl Index {
         }
     }
 }
+
+/// The value of a field's `@default` directive, which the database stores in
+/// place of a missing value of the field's column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnDefault {
+    /// The value as bound in place of a null parameter of the column.
+    pub param: QueryParam,
+    /// The value as an SQL literal, for the `DEFAULT` clause of the column.
+    pub literal: String,
+}
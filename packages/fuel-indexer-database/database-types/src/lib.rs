@@ -90,13 +90,13 @@ pub struct Columns {
 }
 
 impl NewColumn {
-    pub fn sql_fragment(&self) -> String {
+    pub fn sql_fragment(&self, db_type: &DbType, naming: &NamingStrategy) -> String {
         let null_frag = if self.nullable { "" } else { "not null" };
         let unique_frag = if self.unique { "unique" } else { "" };
         format!(
             "{} {} {} {}",
-            self.column_name,
-            self.sql_type(),
+            naming.column_name(&self.column_name),
+            self.sql_type(db_type),
             null_frag,
             unique_frag
         )
@@ -104,37 +104,71 @@ impl NewColumn {
         .to_string()
     }
 
-    fn sql_type(&self) -> &str {
+    fn sql_type(&self, db_type: &DbType) -> String {
         match ColumnType::from(self.column_type.as_str()) {
-            ColumnType::ID => "bigint primary key",
-            ColumnType::Address => "varchar(64)",
-            ColumnType::Bytes4 => "varchar(8)",
-            ColumnType::Bytes8 => "varchar(16)",
-            ColumnType::Bytes32 => "varchar(64)",
-            ColumnType::AssetId => "varchar(64)",
-            ColumnType::ContractId => "varchar(64)",
-            ColumnType::Salt => "varchar(64)",
-            ColumnType::Int4 => "integer",
-            ColumnType::Int8 => "bigint",
-            ColumnType::Int16 => "numeric",
-            ColumnType::UInt4 => "integer",
-            ColumnType::UInt8 => "bigint",
-            ColumnType::UInt16 => "numeric",
-            ColumnType::Timestamp => "timestamp",
-            ColumnType::Object => "bytea",
-            ColumnType::Blob => "varchar(10485760)",
-            ColumnType::ForeignKey => {
-                panic!("ForeignKey ColumnType is a reference type only.")
+            ColumnType::ID => "bigint primary key".to_string(),
+            _ => value_sql_type(&self.column_type, &self.graphql_type, db_type),
+        }
+    }
+}
+
+/// SQL type of the values of a column of the given type.
+fn value_sql_type(column_type: &str, graphql_type: &str, db_type: &DbType) -> String {
+    match ColumnType::from(column_type) {
+        // The item type of an array column is that of its GraphQL list.
+        ColumnType::Array => match db_type {
+            DbType::Postgres => {
+                let item_type = graphql_type.replace(['[', ']', '!'], "");
+                let item_sql_type = scalar_sql_type(ColumnType::from(item_type.as_str()));
+                format!("{item_sql_type}[]")
             }
-            ColumnType::Json => "Json",
-            ColumnType::MessageId => "varchar(64)",
-            ColumnType::Charfield => "varchar(255)",
-            ColumnType::Identity => "varchar(66)",
-            ColumnType::Boolean => "boolean",
+            // SQLite has no array type, so arrays are stored as JSON.
+            DbType::Sqlite => "json".to_string(),
+        },
+        column_type => scalar_sql_type(column_type).to_string(),
+    }
+}
+
+fn scalar_sql_type(column_type: ColumnType) -> &'static str {
+    match column_type {
+        ColumnType::ID => "bigint",
+        ColumnType::Address => "varchar(64)",
+        ColumnType::Bytes4 => "varchar(8)",
+        ColumnType::Bytes8 => "varchar(16)",
+        ColumnType::Bytes32 => "varchar(64)",
+        ColumnType::AssetId => "varchar(64)",
+        ColumnType::ContractId => "varchar(64)",
+        ColumnType::Salt => "varchar(64)",
+        ColumnType::Int4 => "integer",
+        ColumnType::Int8 => "bigint",
+        ColumnType::Int16 => "numeric",
+        ColumnType::UInt4 => "integer",
+        ColumnType::UInt8 => "bigint",
+        ColumnType::UInt16 => "numeric",
+        ColumnType::Decimal => "numeric",
+        ColumnType::Timestamp => "timestamp",
+        ColumnType::Object => "bytea",
+        ColumnType::Blob => "varchar(10485760)",
+        ColumnType::Bytes => "bytea",
+        ColumnType::ForeignKey => {
+            panic!("ForeignKey ColumnType is a reference type only.")
         }
+        ColumnType::Json => "Json",
+        ColumnType::MessageId => "varchar(64)",
+        ColumnType::Charfield => "varchar(255)",
+        ColumnType::Identity => "varchar(66)",
+        ColumnType::Boolean => "boolean",
+        ColumnType::Enum => "varchar(255)",
+        ColumnType::Array => panic!("Array ColumnType is not a scalar type."),
     }
 }
 
+#[derive(Debug)]
+pub struct IndexedColumn {
+    pub table_name: String,
+    pub column_name: String,
+}
+
 #[derive(Debug)]
 pub struct ColumnInfo {
     pub type_id: i64,
@@ -142,6 +176,23 @@ pub struct ColumnInfo {
     pub column_position: i32,
     pub column_name: String,
     pub column_type: String,
+    pub graphql_type: String,
+}
+
+impl ColumnInfo {
+    /// SQL type of the column's values, e.g. to cast a bound parameter to.
+    ///
+    /// Length modifiers are left out, since casting to e.g. `varchar(64)` would
+    /// silently truncate longer values rather than rejecting them on write.
+    pub fn value_sql_type(&self, db_type: &DbType) -> String {
+        let sql_type = value_sql_type(&self.column_type, &self.graphql_type, db_type);
+        match (sql_type.find('('), sql_type.find(')')) {
+            (Some(start), Some(end)) => {
+                format!("{}{}", &sql_type[..start], &sql_type[end + 1..])
+            }
+            _ => sql_type,
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -169,6 +220,10 @@ pub enum ColumnType {
     Object = 20,
     UInt16 = 21,
     Int16 = 22,
+    Enum = 23,
+    Array = 24,
+    Decimal = 25,
+    Bytes = 26,
 }
 
 impl From<ColumnType> for i32 {
@@ -197,6 +252,10 @@ impl From<ColumnType> for i32 {
             ColumnType::Object => 20,
             ColumnType::UInt16 => 21,
             ColumnType::Int16 => 22,
+            ColumnType::Enum => 23,
+            ColumnType::Array => 24,
+            ColumnType::Decimal => 25,
+            ColumnType::Bytes => 26,
         }
     }
 }
@@ -233,6 +292,10 @@ impl From<i32> for ColumnType {
             20 => ColumnType::Object,
             21 => ColumnType::Int16,
             22 => ColumnType::UInt16,
+            23 => ColumnType::Enum,
+            24 => ColumnType::Array,
+            25 => ColumnType::Decimal,
+            26 => ColumnType::Bytes,
             _ => panic!("Invalid column type."),
         }
     }
@@ -264,6 +327,10 @@ impl From<&str> for ColumnType {
             "Object" => ColumnType::Object,
             "UInt16" => ColumnType::UInt16,
             "Int16" => ColumnType::Int16,
+            "Enum" => ColumnType::Enum,
+            "Array" => ColumnType::Array,
+            "Decimal" => ColumnType::Decimal,
+            "Bytes" => ColumnType::Bytes,
             _ => panic!("Invalid column type: '{name}'"),
         }
     }
@@ -277,6 +344,8 @@ pub struct IndexAsset {
     pub digest: String,
     #[serde(skip_serializing)]
     pub bytes: Vec<u8>,
+    /// Unix timestamp (in seconds) at which the asset was uploaded.
+    pub created_at: i64,
 }
 
 #[derive(Debug)]
@@ -314,16 +383,117 @@ impl RegisteredIndex {
 pub enum DbType {
     #[default]
     Postgres,
+    Sqlite,
 }
 
 impl DbType {
     pub fn table_name(&self, namespace: &str, table_name: &str) -> String {
         match self {
             DbType::Postgres => format!("{namespace}.{table_name}"),
+            // SQLite has no schemas, so tables are namespaced by prefix instead,
+            // inside the quotes of a quoted table name.
+            DbType::Sqlite => match table_name.strip_prefix('"') {
+                Some(unquoted) => format!("\"{namespace}_{unquoted}"),
+                None => format!("{namespace}_{table_name}"),
+            },
+        }
+    }
+
+    /// SQL expression for the current time, as recorded in timestamp columns.
+    pub fn current_timestamp(&self) -> &'static str {
+        match self {
+            DbType::Postgres => "now()",
+            DbType::Sqlite => "CURRENT_TIMESTAMP",
+        }
+    }
+
+    /// Name of the SQL function that builds a JSON object from key/value pairs.
+    pub fn json_object_fn(&self) -> &'static str {
+        match self {
+            DbType::Postgres => "json_build_object",
+            DbType::Sqlite => "json_object",
         }
     }
 }
 
+/// How the tables and columns of entities are named after their GraphQL types
+/// and fields, as set by the `@naming` directive of a schema.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, EnumString, AsRefStr)]
+pub enum NamingStrategy {
+    /// Tables are named by their lowercase type name, and columns by their
+    /// field name, unquoted, which the database folds to lowercase.
+    #[default]
+    Lowercase,
+    /// Type and field names are converted to snake case, e.g. the field
+    /// `firstName` of `BlockHeader` is stored as `block_header.first_name`.
+    SnakeCase,
+    /// Type and field names are kept as they are, in quoted identifiers.
+    Verbatim,
+}
+
+impl NamingStrategy {
+    /// Table of the type named `type_name`, as an SQL identifier.
+    pub fn table_name(&self, type_name: &str) -> String {
+        match self {
+            NamingStrategy::Lowercase => type_name.to_lowercase(),
+            NamingStrategy::SnakeCase => to_snake_case(type_name),
+            NamingStrategy::Verbatim => format!("\"{type_name}\""),
+        }
+    }
+
+    /// Column of the field named `field_name`, as an SQL identifier.
+    pub fn column_name(&self, field_name: &str) -> String {
+        match self {
+            NamingStrategy::Lowercase => field_name.to_string(),
+            NamingStrategy::SnakeCase => to_snake_case(field_name),
+            NamingStrategy::Verbatim => format!("\"{field_name}\""),
+        }
+    }
+
+    /// Name that the database stores the column of the field named
+    /// `field_name` under, as it is listed in the database's catalog.
+    pub fn stored_column_name(&self, field_name: &str) -> String {
+        match self {
+            NamingStrategy::Lowercase => field_name.to_lowercase(),
+            NamingStrategy::SnakeCase => to_snake_case(field_name),
+            NamingStrategy::Verbatim => field_name.to_string(),
+        }
+    }
+
+    /// A name from the database's catalog, as an SQL identifier.
+    pub fn quote(&self, stored_name: &str) -> String {
+        match self {
+            NamingStrategy::Verbatim => format!("\"{stored_name}\""),
+            _ => stored_name.to_string(),
+        }
+    }
+}
+
+/// Convert a GraphQL name to snake case, e.g. `firstName` to `first_name`, and
+/// `HTTPServer` to `http_server`.
+pub fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).map_or(false, |n| n.is_lowercase());
+            // A word starts at an uppercase letter that follows a lowercase one or
+            // a digit, or that ends a run of capitals, as `S` does in `HTTPServer`.
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_is_lowercase)
+            {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+
+    snake
+}
+
 pub trait CreateStatement {
     fn create_statement(&self) -> String;
 }
@@ -340,7 +510,9 @@ pub struct ColumnIndex {
 
 impl ColumnIndex {
     pub fn name(&self) -> String {
-        format!("{}_{}_idx", &self.table_name, &self.column_name)
+        // Quoted table and column names are quoted as a whole, not as part of
+        // the name of the index.
+        format!("{}_{}_idx", &self.table_name, &self.column_name).replace('"', "")
     }
 }
 
@@ -352,10 +524,12 @@ impl CreateStatement for ColumnIndex {
         }
 
         match self.db_type {
+            // Index names only need to be unique within the schema of the
+            // table, and already existing indexes are kept when a schema is reloaded.
             DbType::Postgres => {
                 let _ = write!(
                     frag,
-                    "INDEX {} ON {}.{} USING {} ({});",
+                    "INDEX IF NOT EXISTS {} ON {}.{} USING {} ({});",
                     self.name(),
                     self.namespace,
                     self.table_name,
@@ -363,6 +537,17 @@ impl CreateStatement for ColumnIndex {
                     self.column_name
                 );
             }
+            DbType::Sqlite => {
+                // Index names are global to a SQLite database, so they are
+                // prefixed with the namespace just like table names.
+                let _ = write!(
+                    frag,
+                    "INDEX IF NOT EXISTS {} ON {} ({});",
+                    self.db_type.table_name(&self.namespace, &self.name()),
+                    self.db_type.table_name(&self.namespace, &self.table_name),
+                    self.column_name
+                );
+            }
         }
 
         frag
@@ -430,6 +615,7 @@ impl ForeignKey {
             self.reference_table_name,
             self.reference_column_name
         )
+        .replace('"', "")
     }
 }
 
@@ -450,10 +636,35 @@ impl CreateStatement for ForeignKey {
                     self.on_update.as_ref()
                 )
             }
+            // SQLite can't add a constraint to an existing table, so this is a
+            // column constraint, to be appended to the referencing column's definition.
+            DbType::Sqlite => {
+                format!(
+                    "REFERENCES {}({}) ON DELETE {} ON UPDATE {} DEFERRABLE INITIALLY DEFERRED",
+                    self.db_type
+                        .table_name(&self.namespace, &self.reference_table_name),
+                    self.reference_column_name,
+                    self.on_delete.as_ref(),
+                    self.on_update.as_ref()
+                )
+            }
         }
     }
 }
 
+/// Implicit column of every entity table recording when an object was first written.
+pub const CREATED_AT_COLUMN: &str = "created_at";
+
+/// Implicit column of every entity table recording when an object was last written.
+pub const UPDATED_AT_COLUMN: &str = "updated_at";
+
+/// Implicit column of every entity table recording when an object was removed.
+pub const DELETED_AT_COLUMN: &str = "deleted_at";
+
+/// Implicit column of the tables of entities marked `@onlyNewer`, recording the
+/// block at which an object was last written.
+pub const WRITTEN_AT_BLOCK_COLUMN: &str = "written_at_block";
+
 //
 pub struct IdCol {}
 impl IdCol {
@@ -468,17 +679,137 @@ impl IdCol {
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum QueryElement {
-    Field { key: String, value: String },
-    ObjectOpeningBoundary { key: String },
+    Field {
+        key: String,
+        value: String,
+    },
+    /// A field of a `Bytes` column, which is returned hex-encoded.
+    BytesField {
+        key: String,
+        value: String,
+    },
+    ObjectOpeningBoundary {
+        key: String,
+    },
     ObjectClosingBoundary,
+    /// Start of a list of related entities, resolved by a correlated subquery.
+    ListOpeningBoundary {
+        key: String,
+    },
+    /// End of a list of related entities; `condition` relates the rows of `table`
+    /// to the row of the enclosing entity.
+    ListClosingBoundary {
+        table: String,
+        condition: String,
+    },
 }
 
-// TODO: Adjust filter to allow for more complex filtering
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub struct QueryFilter {
-    pub key: String,
-    pub relation: String,
-    pub value: String,
+/// Comparison operators of a `where` filter.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Comparison {
+    Eq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl Comparison {
+    pub fn as_sql(&self) -> &str {
+        match self {
+            Comparison::Eq => "=",
+            Comparison::Gt => ">",
+            Comparison::Gte => ">=",
+            Comparison::Lt => "<",
+            Comparison::Lte => "<=",
+        }
+    }
+}
+
+/// A value provided by a user query or written to a column, bound as a
+/// parameter rather than being written into the SQL text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryParam {
+    Int(i64),
+    Float(f64),
+    String(String),
+    Boolean(bool),
+    Bytes(Vec<u8>),
+    Null,
+}
+
+/// A condition on the columns of the queried entity.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryFilter {
+    Comparison {
+        key: String,
+        relation: Comparison,
+        value: QueryParam,
+    },
+    In {
+        key: String,
+        values: Vec<QueryParam>,
+    },
+    IsNull {
+        key: String,
+    },
+    And(Vec<QueryFilter>),
+    Or(Vec<QueryFilter>),
+}
+
+impl QueryFilter {
+    /// Build the SQL condition of this filter on the columns of `table`, pushing
+    /// its values onto `params` and referring to them as `$1`, `$2`, etc.
+    ///
+    /// Keys are column names that have been checked against the schema, so
+    /// only values need to be bound.
+    pub fn to_sql(&self, table: &str, params: &mut Vec<QueryParam>) -> String {
+        let mut bind = |value: &QueryParam| {
+            params.push(value.clone());
+            format!("${}", params.len())
+        };
+
+        match self {
+            QueryFilter::Comparison {
+                key,
+                relation,
+                value,
+            } => format!("{table}.{key} {} {}", relation.as_sql(), bind(value)),
+            QueryFilter::In { key, values } => {
+                if values.is_empty() {
+                    return "1 = 0".to_string();
+                }
+                let values = values.iter().map(bind).collect::<Vec<String>>();
+                format!("{table}.{key} IN ({})", values.join(", "))
+            }
+            QueryFilter::IsNull { key } => format!("{table}.{key} IS NULL"),
+            QueryFilter::And(filters) => {
+                Self::group_to_sql(filters, "AND", "1 = 1", table, params)
+            }
+            QueryFilter::Or(filters) => {
+                Self::group_to_sql(filters, "OR", "1 = 0", table, params)
+            }
+        }
+    }
+
+    fn group_to_sql(
+        filters: &[QueryFilter],
+        operator: &str,
+        empty: &str,
+        table: &str,
+        params: &mut Vec<QueryParam>,
+    ) -> String {
+        if filters.is_empty() {
+            return empty.to_string();
+        }
+
+        let conditions = filters
+            .iter()
+            .map(|f| f.to_sql(table, params))
+            .collect::<Vec<String>>();
+
+        format!("({})", conditions.join(&format!(" {operator} ")))
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -495,118 +826,356 @@ pub struct QueryJoinNode {
     pub dependents: HashMap<String, JoinCondition>,
 }
 
+/// Cursor-based pagination of a root query, ordered by `id`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct QueryPagination {
+    /// Page size; `None` until the server's default page size is applied.
+    pub first: Option<u64>,
+    /// Only rows with an `id` greater than this are returned.
+    pub after_id: Option<i64>,
+    /// Number of rows returned by previous pages.
+    pub offset: u64,
+}
+
+/// Direction in which a root query is sorted on a field.
 #[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+    /// In the order of the listed values of the field, e.g. the IDs of an
+    /// `ids` argument.
+    Listed(Vec<i64>),
+}
+
+impl SortOrder {
+    pub fn as_sql(&self) -> &str {
+        match self {
+            SortOrder::Asc | SortOrder::Listed(_) => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
+
+/// A sort key of a root query, on a column of the queried entity.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct QuerySort {
+    pub key: String,
+    pub order: SortOrder,
+}
+
+impl QuerySort {
+    /// Build the SQL of this sort key on the columns of `table`. Listed values
+    /// are ranked by their position in the list.
+    pub fn to_sql(&self, table: &str) -> String {
+        match &self.order {
+            SortOrder::Listed(values) if !values.is_empty() => {
+                let positions = values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, value)| format!("WHEN {value} THEN {i}"))
+                    .collect::<Vec<String>>();
+                format!(
+                    "CASE {table}.{} {} END {}",
+                    self.key,
+                    positions.join(" "),
+                    self.order.as_sql()
+                )
+            }
+            order => format!("{table}.{} {}", self.key, order.as_sql()),
+        }
+    }
+}
+
+/// Aggregate function of an `<entity>_aggregate` root query.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AggregateFunction {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl AggregateFunction {
+    /// Name of the aggregate in GraphQL queries and their results.
+    pub fn name(&self) -> &str {
+        match self {
+            AggregateFunction::Count => "count",
+            AggregateFunction::Sum => "sum",
+            AggregateFunction::Avg => "avg",
+            AggregateFunction::Min => "min",
+            AggregateFunction::Max => "max",
+        }
+    }
+
+    pub fn as_sql(&self) -> &str {
+        match self {
+            AggregateFunction::Count => "COUNT",
+            AggregateFunction::Sum => "SUM",
+            AggregateFunction::Avg => "AVG",
+            AggregateFunction::Min => "MIN",
+            AggregateFunction::Max => "MAX",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct UserQuery {
     pub elements: Vec<QueryElement>,
     pub joins: HashMap<String, QueryJoinNode>,
     pub namespace_identifier: String,
     pub entity_name: String,
     pub filters: Vec<QueryFilter>,
+    pub sort: Vec<QuerySort>,
+    pub pagination: Option<QueryPagination>,
 }
 
 impl UserQuery {
-    // TODO: Add proper parsing for filtering
+    /// Build the SQL for this query. Filter values are referred to as `$1`,
+    /// `$2`, etc., and must be bound in the order returned by `params`.
     pub fn to_sql(&mut self, db_type: &DbType) -> String {
         // Different database solutions have unique ways of
         // constructing JSON-formatted queries and results.
-        match db_type {
-            DbType::Postgres => {
-                let elements = self.parse_query_elements(db_type);
+        let json_object = db_type.json_object_fn();
 
-                let _filters: Vec<String> = self
-                    .filters
-                    .iter()
-                    .map(|f| format!("{} {} {}", f.key, f.relation, f.value))
-                    .collect();
-
-                let elements_string = elements.join("");
-
-                let sorted_joins = self.get_topologically_sorted_joins();
-
-                let mut last_seen_primary_key_table = "".to_string();
-                let mut joins: Vec<String> = Vec::new();
-
-                for sj in sorted_joins {
-                    if sj.primary_key_table == last_seen_primary_key_table {
-                        if let Some(elem) = joins.last_mut() {
-                            let join_condition = format!(
-                                "{}.{} = {}.{}",
-                                sj.referencing_key_table,
-                                sj.referencing_key_col,
-                                sj.primary_key_table,
-                                sj.primary_key_col
-                            );
-                            *elem = format!("{elem} AND {join_condition}")
-                        }
-                    } else {
-                        joins.push(format!(
-                            "INNER JOIN {} ON {}.{} = {}.{}",
-                            sj.primary_key_table,
-                            sj.referencing_key_table,
-                            sj.referencing_key_col,
-                            sj.primary_key_table,
-                            sj.primary_key_col
-                        ));
-                        last_seen_primary_key_table = sj.primary_key_table;
+        let elements = self.parse_query_elements(db_type);
+
+        let table = db_type.table_name(&self.namespace_identifier, &self.entity_name);
+        let (mut conditions, _) = self.filter_conditions(&table);
+
+        let elements_string = self.table_reference(db_type, &elements.join(""));
+
+        let sorted_joins = self.get_topologically_sorted_joins();
+
+        let mut last_seen_primary_key_table = "".to_string();
+        let mut joins: Vec<String> = Vec::new();
+
+        for sj in sorted_joins {
+            let referencing_key_table =
+                self.table_reference(db_type, &sj.referencing_key_table);
+            let primary_key_table = self.table_reference(db_type, &sj.primary_key_table);
+
+            if sj.primary_key_table == last_seen_primary_key_table {
+                if let Some(elem) = joins.last_mut() {
+                    let join_condition = format!(
+                        "{}.{} = {}.{}",
+                        referencing_key_table,
+                        sj.referencing_key_col,
+                        primary_key_table,
+                        sj.primary_key_col
+                    );
+                    *elem = format!("{elem} AND {join_condition}")
+                }
+            } else {
+                joins.push(format!(
+                    "INNER JOIN {} ON {}.{} = {}.{}",
+                    primary_key_table,
+                    referencing_key_table,
+                    sj.referencing_key_col,
+                    primary_key_table,
+                    sj.primary_key_col
+                ));
+                last_seen_primary_key_table = sj.primary_key_table;
+            }
+        }
+
+        let mut sort = self
+            .sort
+            .iter()
+            .map(|s| s.to_sql(&table))
+            .collect::<Vec<String>>();
+
+        match &self.pagination {
+            // Paginated rows are wrapped with their `id`, so that a cursor
+            // for the next page can be built from the last row, and with the
+            // total number of matching rows. One row past the page size is
+            // fetched to tell whether a next page exists.
+            Some(pagination) => {
+                // `COUNT(*) OVER()` is taken before the `LIMIT` applies. Pages
+                // after an `id` cursor leave out the rows of previous pages,
+                // so those are added back in.
+                let total_count = match pagination.after_id {
+                    Some(_) if sort.is_empty() => {
+                        format!("COUNT(*) OVER() + {}", pagination.offset)
                     }
+                    _ => "COUNT(*) OVER()".to_string(),
+                };
+
+                let mut query = format!(
+                    "SELECT {json_object}('id', {table}.id, 'total_count', {total_count}, 'node', {json_object}({elements_string})) FROM {table}"
+                );
+
+                if !joins.is_empty() {
+                    query = format!("{query} {}", joins.join(" "));
                 }
 
-                format!(
-                    "SELECT json_build_object({}) FROM {}.{} {}",
+                // Pages ordered by `id` alone start after the `id` of the cursor.
+                // Pages sorted on other fields are skipped with an offset instead,
+                // with `id` breaking ties so that rows keep a stable order.
+                match pagination.after_id {
+                    Some(id) if sort.is_empty() => {
+                        conditions.insert(0, format!("{table}.id > {id}"))
+                    }
+                    _ => {}
+                }
+
+                if !conditions.is_empty() {
+                    query = format!("{query} WHERE {}", conditions.join(" AND "));
+                }
+
+                sort.push(format!("{table}.id ASC"));
+                query = format!("{query} ORDER BY {}", sort.join(", "));
+
+                if let Some(first) = pagination.first {
+                    query = format!("{query} LIMIT {}", first + 1);
+                }
+
+                if !self.sort.is_empty() && pagination.offset > 0 {
+                    query = format!("{query} OFFSET {}", pagination.offset);
+                }
+
+                query
+            }
+            None => {
+                let mut query = format!(
+                    "SELECT {}({}) FROM {} {}",
+                    json_object,
                     elements_string,
-                    self.namespace_identifier,
-                    self.entity_name,
+                    table,
                     joins.join(" ")
-                )
+                );
+
+                if !conditions.is_empty() {
+                    query = format!(
+                        "{} WHERE {}",
+                        query.trim_end(),
+                        conditions.join(" AND ")
+                    );
+                }
+
+                if !sort.is_empty() {
+                    query = format!("{} ORDER BY {}", query.trim_end(), sort.join(", "));
+                }
+
+                query
             }
         }
     }
 
+    /// Values to bind to the parameters of the SQL built by `to_sql`, in order.
+    pub fn params(&self, db_type: &DbType) -> Vec<QueryParam> {
+        let table = db_type.table_name(&self.namespace_identifier, &self.entity_name);
+        let (_, params) = self.filter_conditions(&table);
+        params
+    }
+
+    fn filter_conditions(&self, table: &str) -> (Vec<String>, Vec<QueryParam>) {
+        let mut params = Vec::new();
+        let conditions = self
+            .filters
+            .iter()
+            .map(|f| f.to_sql(table, &mut params))
+            .collect();
+
+        (conditions, params)
+    }
+
+    /// Rewrite the `namespace.table` references built by the query parser
+    /// into the table naming scheme of the given database.
+    fn table_reference(&self, db_type: &DbType, reference: &str) -> String {
+        match db_type {
+            DbType::Postgres => reference.to_string(),
+            DbType::Sqlite => reference
+                .replace(
+                    &format!("{}.\"", self.namespace_identifier),
+                    &db_type.table_name(&self.namespace_identifier, "\""),
+                )
+                .replace(
+                    &format!("{}.", self.namespace_identifier),
+                    &db_type.table_name(&self.namespace_identifier, ""),
+                ),
+        }
+    }
+
     fn parse_query_elements(&self, db_type: &DbType) -> Vec<String> {
         let mut peekable_elements = self.elements.iter().peekable();
 
         let mut elements = Vec::new();
 
-        match db_type {
-            DbType::Postgres => {
-                while let Some(e) = peekable_elements.next() {
-                    match e {
-                        // Set the key for this JSON element to the name of the entity field
-                        // and the value to the corresponding database table so that it can
-                        // be successfully retrieved.
-                        QueryElement::Field { key, value } => {
-                            elements.push(format!("'{key}', {value}"));
-
-                            // If the next element is not a closing boundary, then a comma should
-                            // be added so that the resultant SQL query can be properly constructed.
-                            if let Some(next_element) = peekable_elements.peek() {
-                                match next_element {
-                                    QueryElement::Field { .. }
-                                    | QueryElement::ObjectOpeningBoundary { .. } => {
-                                        elements.push(", ".to_string());
-                                    }
-                                    _ => {}
-                                }
+        while let Some(e) = peekable_elements.next() {
+            match e {
+                // Set the key for this JSON element to the name of the entity field
+                // and the value to the corresponding database table so that it can
+                // be successfully retrieved.
+                QueryElement::Field { key, value }
+                | QueryElement::BytesField { key, value } => {
+                    match (e, db_type) {
+                        (QueryElement::BytesField { .. }, DbType::Postgres) => {
+                            elements.push(format!("'{key}', encode({value}, 'hex')"))
+                        }
+                        (QueryElement::BytesField { .. }, DbType::Sqlite) => {
+                            elements.push(format!("'{key}', lower(hex({value}))"))
+                        }
+                        _ => elements.push(format!("'{key}', {value}")),
+                    }
+
+                    // If the next element is not a closing boundary, then a comma should
+                    // be added so that the resultant SQL query can be properly constructed.
+                    if let Some(next_element) = peekable_elements.peek() {
+                        match next_element {
+                            QueryElement::Field { .. }
+                            | QueryElement::BytesField { .. }
+                            | QueryElement::ObjectOpeningBoundary { .. }
+                            | QueryElement::ListOpeningBoundary { .. } => {
+                                elements.push(", ".to_string());
                             }
+                            _ => {}
                         }
+                    }
+                }
+
+                // Set a nested JSON object as the value for this entity field.
+                QueryElement::ObjectOpeningBoundary { key } => {
+                    elements.push(format!("'{key}', {}(", db_type.json_object_fn()))
+                }
 
-                        // Set a nested JSON object as the value for this entity field.
-                        QueryElement::ObjectOpeningBoundary { key } => {
-                            elements.push(format!("'{key}', json_build_object("))
+                // Set a JSON array of the related entities as the value for this
+                // entity field. Each related row is built as a JSON object.
+                QueryElement::ListOpeningBoundary { key } => match db_type {
+                    DbType::Postgres => elements.push(format!(
+                        "'{key}', (SELECT COALESCE(json_agg(json_build_object("
+                    )),
+                    DbType::Sqlite => elements.push(format!(
+                        "'{key}', json((SELECT json_group_array(json_object("
+                    )),
+                },
+
+                QueryElement::ObjectClosingBoundary
+                | QueryElement::ListClosingBoundary { .. } => {
+                    match e {
+                        QueryElement::ListClosingBoundary { table, condition } => {
+                            match db_type {
+                                // json_agg returns NULL rather than an empty array
+                                // when there are no related rows.
+                                DbType::Postgres => elements.push(format!(
+                                    ")), '[]'::json) FROM {table} WHERE {condition})"
+                                )),
+                                DbType::Sqlite => elements
+                                    .push(format!(")) FROM {table} WHERE {condition}))")),
+                            }
                         }
+                        _ => elements.push(")".to_string()),
+                    }
 
-                        QueryElement::ObjectClosingBoundary => {
-                            elements.push(")".to_string());
-
-                            if let Some(next_element) = peekable_elements.peek() {
-                                match next_element {
-                                    QueryElement::Field { .. }
-                                    | QueryElement::ObjectOpeningBoundary { .. } => {
-                                        elements.push(", ".to_string());
-                                    }
-                                    _ => {}
-                                }
+                    if let Some(next_element) = peekable_elements.peek() {
+                        match next_element {
+                            QueryElement::Field { .. }
+                            | QueryElement::BytesField { .. }
+                            | QueryElement::ObjectOpeningBoundary { .. }
+                            | QueryElement::ListOpeningBoundary { .. } => {
+                                elements.push(", ".to_string());
                             }
+                            _ => {}
                         }
                     }
                 }
@@ -649,6 +1218,29 @@ impl UserQuery {
     }
 }
 
+/// A block that an indexer skipped or dead-lettered after its handler failed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FailedBlock {
+    pub namespace: String,
+    pub identifier: String,
+    pub block_height: u64,
+    pub strategy: String,
+    pub error: String,
+    pub block_data: Option<Vec<u8>>,
+}
+
+/// Running totals of an indexer's work, kept across restarts.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct IndexerStats {
+    pub namespace: String,
+    pub identifier: String,
+    /// Unix timestamp (in seconds) at which the indexer was first registered,
+    /// or first committed a block if it was registered before stats were kept.
+    pub registered_at: i64,
+    pub blocks_processed: u64,
+    pub objects_written: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Nonce {
     pub uid: String,
@@ -692,6 +1284,8 @@ mod tests {
             namespace_identifier: "".to_string(),
             entity_name: "".to_string(),
             filters: Vec::new(),
+            sort: Vec::new(),
+            pagination: None,
         };
 
         let expected = vec![
@@ -762,15 +1356,325 @@ mod tests {
             ]),
             namespace_identifier: "name_ident".to_string(),
             entity_name: "entity_name".to_string(),
-            filters: vec![QueryFilter {
+            filters: vec![QueryFilter::Comparison {
+                key: "a".to_string(),
+                relation: Comparison::Eq,
+                value: QueryParam::Int(123),
+            }],
+            sort: Vec::new(),
+            pagination: None,
+        };
+
+        let expected = "SELECT json_build_object('hash', name_ident.block.hash, 'tx', json_build_object('hash', name_ident.tx.hash), 'height', name_ident.block.height) FROM name_ident.entity_name INNER JOIN name_ident.block ON name_ident.tx.block = name_ident.block.id WHERE name_ident.entity_name.a = $1"
+            .to_string();
+        assert_eq!(expected, uq.to_sql(&DbType::Postgres));
+        assert_eq!(vec![QueryParam::Int(123)], uq.params(&DbType::Postgres));
+    }
+
+    #[test]
+    fn test_user_query_to_sql_for_sqlite() {
+        let elements = vec![
+            QueryElement::Field {
+                key: "hash".to_string(),
+                value: "name_ident.block.hash".to_string(),
+            },
+            QueryElement::ObjectOpeningBoundary {
+                key: "tx".to_string(),
+            },
+            QueryElement::Field {
+                key: "hash".to_string(),
+                value: "name_ident.tx.hash".to_string(),
+            },
+            QueryElement::ObjectClosingBoundary,
+            QueryElement::Field {
+                key: "height".to_string(),
+                value: "name_ident.block.height".to_string(),
+            },
+        ];
+
+        let mut uq = UserQuery {
+            elements,
+            joins: HashMap::from([
+                (
+                    "name_ident.block".to_string(),
+                    QueryJoinNode {
+                        dependencies: HashMap::new(),
+                        dependents: HashMap::from([(
+                            "name_ident.tx".to_string(),
+                            JoinCondition {
+                                referencing_key_table: "name_ident.tx".to_string(),
+                                referencing_key_col: "block".to_string(),
+                                primary_key_table: "name_ident.block".to_string(),
+                                primary_key_col: "id".to_string(),
+                            },
+                        )]),
+                    },
+                ),
+                (
+                    "name_ident.tx".to_string(),
+                    QueryJoinNode {
+                        dependents: HashMap::new(),
+                        dependencies: HashMap::from([(
+                            "name_ident.block".to_string(),
+                            JoinCondition {
+                                referencing_key_table: "name_ident.tx".to_string(),
+                                referencing_key_col: "block".to_string(),
+                                primary_key_table: "name_ident.block".to_string(),
+                                primary_key_col: "id".to_string(),
+                            },
+                        )]),
+                    },
+                ),
+            ]),
+            namespace_identifier: "name_ident".to_string(),
+            entity_name: "entity_name".to_string(),
+            filters: vec![QueryFilter::Comparison {
                 key: "a".to_string(),
-                relation: "=".to_string(),
-                value: "123".to_string(),
+                relation: Comparison::Eq,
+                value: QueryParam::Int(123),
+            }],
+            sort: Vec::new(),
+            pagination: None,
+        };
+
+        let expected = "SELECT json_object('hash', name_ident_block.hash, 'tx', json_object('hash', name_ident_tx.hash), 'height', name_ident_block.height) FROM name_ident_entity_name INNER JOIN name_ident_block ON name_ident_tx.block = name_ident_block.id WHERE name_ident_entity_name.a = $1"
+            .to_string();
+        assert_eq!(expected, uq.to_sql(&DbType::Sqlite));
+    }
+
+    #[test]
+    fn test_user_query_to_sql_for_sqlite_with_quoted_names() {
+        let mut uq = UserQuery {
+            elements: vec![QueryElement::Field {
+                key: "firstName".to_string(),
+                value: "name_ident.\"BlockHeader\".\"firstName\"".to_string(),
+            }],
+            joins: HashMap::new(),
+            namespace_identifier: "name_ident".to_string(),
+            entity_name: "\"BlockHeader\"".to_string(),
+            filters: vec![QueryFilter::Comparison {
+                key: "\"firstName\"".to_string(),
+                relation: Comparison::Eq,
+                value: QueryParam::String("a".to_string()),
+            }],
+            sort: Vec::new(),
+            pagination: None,
+        };
+
+        let expected = "SELECT json_object('firstName', \"name_ident_BlockHeader\".\"firstName\") FROM \"name_ident_BlockHeader\" WHERE \"name_ident_BlockHeader\".\"firstName\" = $1";
+        assert_eq!(expected, uq.to_sql(&DbType::Sqlite));
+    }
+
+    #[test]
+    fn test_naming_strategy_names_tables_and_columns() {
+        assert_eq!(to_snake_case("BlockHeader"), "block_header");
+        assert_eq!(to_snake_case("firstName"), "first_name");
+        assert_eq!(to_snake_case("HTTPServer"), "http_server");
+        assert_eq!(to_snake_case("hash32Bytes"), "hash32_bytes");
+        assert_eq!(to_snake_case("block_header"), "block_header");
+
+        let lowercase = NamingStrategy::Lowercase;
+        assert_eq!(lowercase.table_name("BlockHeader"), "blockheader");
+        assert_eq!(lowercase.column_name("firstName"), "firstName");
+        assert_eq!(lowercase.stored_column_name("firstName"), "firstname");
+
+        let verbatim = NamingStrategy::Verbatim;
+        assert_eq!(verbatim.table_name("BlockHeader"), "\"BlockHeader\"");
+        assert_eq!(verbatim.column_name("firstName"), "\"firstName\"");
+        assert_eq!(verbatim.stored_column_name("firstName"), "firstName");
+        assert_eq!(verbatim.quote("firstName"), "\"firstName\"");
+
+        assert_eq!(
+            DbType::Sqlite.table_name("name_ident", "\"BlockHeader\""),
+            "\"name_ident_BlockHeader\""
+        );
+    }
+
+    #[test]
+    fn test_user_query_to_sql_with_list_of_related_entities() {
+        let mut uq = UserQuery {
+            elements: vec![
+                QueryElement::Field {
+                    key: "height".to_string(),
+                    value: "name_ident.block.height".to_string(),
+                },
+                QueryElement::ListOpeningBoundary {
+                    key: "transactions".to_string(),
+                },
+                QueryElement::Field {
+                    key: "hash".to_string(),
+                    value: "name_ident.tx.hash".to_string(),
+                },
+                QueryElement::ListClosingBoundary {
+                    table: "name_ident.tx".to_string(),
+                    condition: "name_ident.tx.block = name_ident.block.id".to_string(),
+                },
+            ],
+            joins: HashMap::new(),
+            namespace_identifier: "name_ident".to_string(),
+            entity_name: "block".to_string(),
+            filters: Vec::new(),
+            sort: Vec::new(),
+            pagination: None,
+        };
+
+        let expected = "SELECT json_build_object('height', name_ident.block.height, 'transactions', (SELECT COALESCE(json_agg(json_build_object('hash', name_ident.tx.hash)), '[]'::json) FROM name_ident.tx WHERE name_ident.tx.block = name_ident.block.id)) FROM name_ident.block "
+            .to_string();
+        assert_eq!(expected, uq.to_sql(&DbType::Postgres));
+
+        let expected = "SELECT json_object('height', name_ident_block.height, 'transactions', json((SELECT json_group_array(json_object('hash', name_ident_tx.hash)) FROM name_ident_tx WHERE name_ident_tx.block = name_ident_block.id))) FROM name_ident_block "
+            .to_string();
+        assert_eq!(expected, uq.to_sql(&DbType::Sqlite));
+    }
+
+    #[test]
+    fn test_user_query_to_sql_with_pagination() {
+        let mut uq = UserQuery {
+            elements: vec![QueryElement::Field {
+                key: "hash".to_string(),
+                value: "name_ident.block.hash".to_string(),
+            }],
+            joins: HashMap::new(),
+            namespace_identifier: "name_ident".to_string(),
+            entity_name: "block".to_string(),
+            filters: Vec::new(),
+            sort: Vec::new(),
+            pagination: Some(QueryPagination {
+                first: Some(10),
+                after_id: Some(42),
+                offset: 20,
+            }),
+        };
+
+        let expected = "SELECT json_build_object('id', name_ident.block.id, 'total_count', COUNT(*) OVER() + 20, 'node', json_build_object('hash', name_ident.block.hash)) FROM name_ident.block WHERE name_ident.block.id > 42 ORDER BY name_ident.block.id ASC LIMIT 11"
+            .to_string();
+        assert_eq!(expected, uq.to_sql(&DbType::Postgres));
+    }
+
+    #[test]
+    fn test_user_query_to_sql_with_sort() {
+        let mut uq = UserQuery {
+            elements: vec![QueryElement::Field {
+                key: "hash".to_string(),
+                value: "name_ident.block.hash".to_string(),
+            }],
+            joins: HashMap::new(),
+            namespace_identifier: "name_ident".to_string(),
+            entity_name: "block".to_string(),
+            filters: Vec::new(),
+            sort: vec![
+                QuerySort {
+                    key: "height".to_string(),
+                    order: SortOrder::Desc,
+                },
+                QuerySort {
+                    key: "hash".to_string(),
+                    order: SortOrder::Asc,
+                },
+            ],
+            pagination: None,
+        };
+
+        let expected = "SELECT json_build_object('hash', name_ident.block.hash) FROM name_ident.block ORDER BY name_ident.block.height DESC, name_ident.block.hash ASC"
+            .to_string();
+        assert_eq!(expected, uq.to_sql(&DbType::Postgres));
+
+        uq.pagination = Some(QueryPagination {
+            first: Some(10),
+            after_id: Some(42),
+            offset: 20,
+        });
+
+        let expected = "SELECT json_build_object('id', name_ident.block.id, 'total_count', COUNT(*) OVER(), 'node', json_build_object('hash', name_ident.block.hash)) FROM name_ident.block ORDER BY name_ident.block.height DESC, name_ident.block.hash ASC, name_ident.block.id ASC LIMIT 11 OFFSET 20"
+            .to_string();
+        assert_eq!(expected, uq.to_sql(&DbType::Postgres));
+    }
+
+    #[test]
+    fn test_user_query_to_sql_with_listed_ids() {
+        let mut uq = UserQuery {
+            elements: vec![QueryElement::Field {
+                key: "hash".to_string(),
+                value: "name_ident.block.hash".to_string(),
             }],
+            joins: HashMap::new(),
+            namespace_identifier: "name_ident".to_string(),
+            entity_name: "block".to_string(),
+            filters: vec![QueryFilter::In {
+                key: "id".to_string(),
+                values: vec![QueryParam::Int(3), QueryParam::Int(1)],
+            }],
+            sort: vec![QuerySort {
+                key: "id".to_string(),
+                order: SortOrder::Listed(vec![3, 1]),
+            }],
+            pagination: None,
         };
 
-        let expected = "SELECT json_build_object('hash', name_ident.block.hash, 'tx', json_build_object('hash', name_ident.tx.hash), 'height', name_ident.block.height) FROM name_ident.entity_name INNER JOIN name_ident.block ON name_ident.tx.block = name_ident.block.id"
+        let expected = "SELECT json_build_object('hash', name_ident.block.hash) FROM name_ident.block WHERE name_ident.block.id IN ($1, $2) ORDER BY CASE name_ident.block.id WHEN 3 THEN 0 WHEN 1 THEN 1 END ASC"
             .to_string();
         assert_eq!(expected, uq.to_sql(&DbType::Postgres));
+        assert_eq!(
+            uq.params(&DbType::Postgres),
+            vec![QueryParam::Int(3), QueryParam::Int(1)]
+        );
+    }
+
+    #[test]
+    fn test_user_query_to_sql_with_nested_filters() {
+        let mut uq = UserQuery {
+            elements: vec![QueryElement::Field {
+                key: "hash".to_string(),
+                value: "name_ident.block.hash".to_string(),
+            }],
+            joins: HashMap::new(),
+            namespace_identifier: "name_ident".to_string(),
+            entity_name: "block".to_string(),
+            filters: vec![
+                QueryFilter::In {
+                    key: "hash".to_string(),
+                    values: vec![
+                        QueryParam::String("aa".to_string()),
+                        QueryParam::String("bb".to_string()),
+                    ],
+                },
+                QueryFilter::Or(vec![
+                    QueryFilter::Comparison {
+                        key: "height".to_string(),
+                        relation: Comparison::Lt,
+                        value: QueryParam::Int(10),
+                    },
+                    QueryFilter::And(vec![
+                        QueryFilter::Comparison {
+                            key: "height".to_string(),
+                            relation: Comparison::Gte,
+                            value: QueryParam::Int(100),
+                        },
+                        QueryFilter::IsNull {
+                            key: "producer".to_string(),
+                        },
+                    ]),
+                ]),
+            ],
+            sort: Vec::new(),
+            pagination: Some(QueryPagination {
+                first: Some(10),
+                after_id: Some(42),
+                offset: 20,
+            }),
+        };
+
+        let expected = "SELECT json_object('id', name_ident_block.id, 'total_count', COUNT(*) OVER() + 20, 'node', json_object('hash', name_ident_block.hash)) FROM name_ident_block WHERE name_ident_block.id > 42 AND name_ident_block.hash IN ($1, $2) AND (name_ident_block.height < $3 OR (name_ident_block.height >= $4 AND name_ident_block.producer IS NULL)) ORDER BY name_ident_block.id ASC LIMIT 11"
+            .to_string();
+        assert_eq!(expected, uq.to_sql(&DbType::Sqlite));
+        assert_eq!(
+            vec![
+                QueryParam::String("aa".to_string()),
+                QueryParam::String("bb".to_string()),
+                QueryParam::Int(10),
+                QueryParam::Int(100),
+            ],
+            uq.params(&DbType::Sqlite)
+        );
     }
 }
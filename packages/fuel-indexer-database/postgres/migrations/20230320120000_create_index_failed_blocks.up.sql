@@ -0,0 +1,9 @@
+create table if not exists index_failed_blocks (
+   id bigserial primary key,
+   namespace varchar(32) not null,
+   identifier varchar(32) not null,
+   block_height bigint not null,
+   strategy varchar(32) not null,
+   error text not null,
+   block_data bytea
+);
@@ -0,0 +1,11 @@
+create table if not exists index_object_writes (
+   id bigserial primary key,
+   namespace varchar(32) not null,
+   identifier varchar(32) not null,
+   block_height bigint not null,
+   table_name varchar(255) not null,
+   object_id bigint not null
+);
+
+create index if not exists index_object_writes_block_height
+   on index_object_writes (namespace, identifier, block_height);
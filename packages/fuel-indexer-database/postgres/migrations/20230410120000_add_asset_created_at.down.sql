@@ -0,0 +1,3 @@
+alter table index_asset_registry_wasm drop column if exists created_at;
+alter table index_asset_registry_schema drop column if exists created_at;
+alter table index_asset_registry_manifest drop column if exists created_at;
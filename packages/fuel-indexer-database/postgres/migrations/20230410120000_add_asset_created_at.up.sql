@@ -0,0 +1,3 @@
+alter table index_asset_registry_wasm add column if not exists created_at bigint not null default 0;
+alter table index_asset_registry_schema add column if not exists created_at bigint not null default 0;
+alter table index_asset_registry_manifest add column if not exists created_at bigint not null default 0;
@@ -2,8 +2,15 @@
 
 use fuel_indexer_database_types::*;
 use fuel_indexer_lib::utils::sha256_digest;
-use sqlx::{pool::PoolConnection, postgres::PgRow, types::JsonValue, Postgres, Row};
-use std::time::{SystemTime, UNIX_EPOCH};
+use sqlx::{
+    pool::PoolConnection,
+    postgres::{PgArguments, PgRow},
+    query::Query,
+    types::JsonValue,
+    Connection, Postgres, Row,
+};
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tracing::info;
 
 #[cfg(feature = "metrics")]
@@ -11,6 +18,21 @@ use fuel_indexer_metrics::METRICS;
 
 const NONCE_EXPIRY: u64 = 3600; // 1 hour
 
+/// Bind `param` to the next parameter of `query`.
+fn bind_param<'q>(
+    query: Query<'q, Postgres, PgArguments>,
+    param: QueryParam,
+) -> Query<'q, Postgres, PgArguments> {
+    match param {
+        QueryParam::Int(v) => query.bind(v),
+        QueryParam::Float(v) => query.bind(v),
+        QueryParam::String(v) => query.bind(v),
+        QueryParam::Boolean(v) => query.bind(v),
+        QueryParam::Bytes(v) => query.bind(v),
+        QueryParam::Null => query.bind(None::<String>),
+    }
+}
+
 pub async fn put_object(
     conn: &mut PoolConnection<Postgres>,
     query: String,
@@ -28,20 +50,95 @@ pub async fn put_object(
     Ok(result.rows_affected() as usize)
 }
 
+/// Run a multi-row upsert, binding `params` to its parameters in order.
+pub async fn put_objects(
+    conn: &mut PoolConnection<Postgres>,
+    query: String,
+    params: Vec<QueryParam>,
+) -> sqlx::Result<usize> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.put_objects_calls.inc();
+
+    let mut query = sqlx::query(&query);
+    for param in params {
+        query = bind_param(query, param);
+    }
+    let result = query.execute(conn).await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
 pub async fn get_object(
     conn: &mut PoolConnection<Postgres>,
     query: String,
+    object_id: i64,
 ) -> sqlx::Result<Vec<u8>> {
     #[cfg(feature = "metrics")]
     METRICS.db.postgres.get_object_calls.inc();
 
-    let mut builder = sqlx::QueryBuilder::new(query);
+    let row = sqlx::query(&query).bind(object_id).fetch_one(conn).await?;
 
-    let query = builder.build();
+    Ok(row.get(0))
+}
 
-    let row = query.fetch_one(conn).await?;
+pub async fn get_object_by(
+    conn: &mut PoolConnection<Postgres>,
+    query: String,
+    params: Vec<QueryParam>,
+) -> sqlx::Result<Option<Vec<u8>>> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.get_object_by_calls.inc();
 
-    Ok(row.get(0))
+    let mut query = sqlx::query(&query);
+    for param in params {
+        query = bind_param(query, param);
+    }
+
+    Ok(query.fetch_optional(conn).await?.map(|row| row.get(0)))
+}
+
+pub async fn soft_delete_object(
+    conn: &mut PoolConnection<Postgres>,
+    table_name: &str,
+    object_id: i64,
+    block_height: Option<i64>,
+) -> sqlx::Result<usize> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.soft_delete_object_calls.inc();
+
+    let written_at_block = block_height
+        .map(|height| format!(", {WRITTEN_AT_BLOCK_COLUMN} = {height}"))
+        .unwrap_or_default();
+    let result = sqlx::query(&format!(
+        "UPDATE {table_name} SET {DELETED_AT_COLUMN} = now(), {UPDATED_AT_COLUMN} = now(){written_at_block}
+        WHERE id = $1 AND {DELETED_AT_COLUMN} IS NULL"
+    ))
+    .bind(object_id)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+pub async fn get_object_ids(
+    conn: &mut PoolConnection<Postgres>,
+    query: String,
+    params: Vec<QueryParam>,
+) -> sqlx::Result<Vec<i64>> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.get_object_ids_calls.inc();
+
+    let mut query = sqlx::query(&query);
+    for param in params {
+        query = bind_param(query, param);
+    }
+
+    Ok(query
+        .fetch_all(conn)
+        .await?
+        .iter()
+        .map(|r| r.get::<'_, i64, usize>(0))
+        .collect())
 }
 
 pub async fn run_migration(conn: &mut PoolConnection<Postgres>) -> sqlx::Result<()> {
@@ -53,20 +150,141 @@ pub async fn run_migration(conn: &mut PoolConnection<Postgres>) -> sqlx::Result<
     Ok(())
 }
 
+/// Run a user query, letting Postgres cancel it once it runs longer than
+/// `timeout`. A zero `timeout` disables the limit.
+///
+/// The timeout is set with `SET LOCAL` so it only applies to this query's
+/// transaction, and the connection goes back to the pool unchanged.
 pub async fn run_query(
     conn: &mut PoolConnection<Postgres>,
     query: String,
+    params: Vec<QueryParam>,
+    timeout: Duration,
 ) -> sqlx::Result<JsonValue> {
     #[cfg(feature = "metrics")]
     METRICS.db.postgres.run_query_calls.inc();
 
+    let mut tx = conn.begin().await?;
+
+    sqlx::query(&format!(
+        "SET LOCAL statement_timeout = {}",
+        timeout.as_millis()
+    ))
+    .execute(&mut tx)
+    .await?;
+
     let mut builder = sqlx::QueryBuilder::new(query);
 
-    let query = builder.build();
+    let mut query = builder.build();
+    for param in params {
+        query = bind_param(query, param);
+    }
 
-    Ok(query
-        .fetch_all(conn)
-        .await?
+    let rows = query.fetch_all(&mut tx).await?;
+
+    tx.commit().await?;
+
+    Ok(rows
+        .iter()
+        .map(|r| r.get::<'_, JsonValue, usize>(0))
+        .collect())
+}
+
+/// Run a user query through a server-side cursor, passing each row to `on_row`
+/// as it is fetched rather than collecting them all, and stopping early once
+/// `on_row` returns `false`. `timeout` limits each fetch of `batch_size` rows.
+pub async fn stream_query<F, Fut>(
+    conn: &mut PoolConnection<Postgres>,
+    query: String,
+    params: Vec<QueryParam>,
+    timeout: Duration,
+    batch_size: usize,
+    mut on_row: F,
+) -> sqlx::Result<()>
+where
+    F: FnMut(JsonValue) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.run_query_calls.inc();
+
+    let mut tx = conn.begin().await?;
+
+    sqlx::query(&format!(
+        "SET LOCAL statement_timeout = {}",
+        timeout.as_millis()
+    ))
+    .execute(&mut tx)
+    .await?;
+
+    let mut builder = sqlx::QueryBuilder::new(format!(
+        "DECLARE query_rows NO SCROLL CURSOR FOR {query}"
+    ));
+
+    let mut declare = builder.build();
+    for param in params {
+        declare = bind_param(declare, param);
+    }
+    declare.execute(&mut tx).await?;
+
+    let fetch = format!("FETCH {batch_size} FROM query_rows");
+    loop {
+        let rows = sqlx::query(&fetch).fetch_all(&mut tx).await?;
+        if rows.is_empty() {
+            break;
+        }
+
+        for row in rows {
+            if !on_row(row.get::<'_, JsonValue, usize>(0)).await {
+                // Nothing was written, so there's nothing to commit.
+                return tx.rollback().await;
+            }
+        }
+    }
+
+    tx.commit().await
+}
+
+/// Run a raw `SELECT` in a read-only transaction scoped to `schema`, returning
+/// each row as a JSON object of its columns. A zero `timeout` disables the
+/// limit.
+///
+/// Postgres rejects any write the query attempts, e.g. through a function with
+/// side effects, and the transaction is rolled back either way.
+pub async fn run_read_only_query(
+    conn: &mut PoolConnection<Postgres>,
+    schema: &str,
+    query: String,
+    timeout: Duration,
+) -> sqlx::Result<JsonValue> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.run_read_only_query_calls.inc();
+
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("SET TRANSACTION READ ONLY")
+        .execute(&mut tx)
+        .await?;
+
+    sqlx::query(&format!(
+        "SET LOCAL statement_timeout = {}",
+        timeout.as_millis()
+    ))
+    .execute(&mut tx)
+    .await?;
+
+    // Unqualified table names refer to the indexer's own tables.
+    sqlx::query(&format!("SET LOCAL search_path = {schema}"))
+        .execute(&mut tx)
+        .await?;
+
+    let rows = sqlx::query(&format!("SELECT row_to_json(q) FROM ({query}) q"))
+        .fetch_all(&mut tx)
+        .await?;
+
+    tx.rollback().await?;
+
+    Ok(rows
         .iter()
         .map(|r| r.get::<'_, JsonValue, usize>(0))
         .collect())
@@ -393,7 +611,8 @@ pub async fn columns_get_schema(
             t.table_name as table_name,
             c.column_position as column_position,
             c.column_name as column_name,
-            c.column_type as column_type
+            c.column_type as column_type,
+            c.graphql_type as graphql_type
             FROM graph_registry_type_ids as t
             INNER JOIN graph_registry_columns as c ON t.id = c.type_id
             WHERE t.schema_name = $1 
@@ -413,6 +632,7 @@ pub async fn columns_get_schema(
         let column_position: i32 = row.get(2);
         let column_name: String = row.get(3);
         let column_type: String = row.get(4);
+        let graphql_type: String = row.get(5);
 
         ColumnInfo {
             type_id,
@@ -420,11 +640,46 @@ pub async fn columns_get_schema(
             column_position,
             column_name,
             column_type,
+            graphql_type,
         }
     })
     .collect::<Vec<ColumnInfo>>())
 }
 
+pub async fn indexed_columns(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<IndexedColumn>> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.indexed_columns_calls.inc();
+
+    // Only the leading column of each index is returned, since that is the
+    // only column an index can be used to look up on its own.
+    Ok(sqlx::query(
+        "SELECT t.relname AS table_name, a.attname AS column_name
+        FROM pg_index AS ix
+        INNER JOIN pg_class AS t ON t.oid = ix.indrelid
+        INNER JOIN pg_namespace AS n ON n.oid = t.relnamespace
+        INNER JOIN pg_attribute AS a ON a.attrelid = t.oid AND a.attnum = ix.indkey[0]
+        WHERE n.nspname = $1",
+    )
+    .bind(format!("{namespace}_{identifier}"))
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row: PgRow| {
+        let table_name: String = row.get(0);
+        let column_name: String = row.get(1);
+
+        IndexedColumn {
+            table_name,
+            column_name,
+        }
+    })
+    .collect::<Vec<IndexedColumn>>())
+}
+
 pub async fn index_is_registered(
     conn: &mut PoolConnection<Postgres>,
     namespace: &str,
@@ -474,7 +729,7 @@ pub async fn register_index(
     .bind(namespace)
     .bind(identifier)
     .bind(pubkey)
-    .fetch_one(conn)
+    .fetch_one(&mut *conn)
     .await?;
 
     let id: i64 = row.get(0);
@@ -482,6 +737,22 @@ pub async fn register_index(
     let identifier: String = row.get(2);
     let pubkey = row.get(3);
 
+    let registered_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    sqlx::query(
+        "INSERT INTO index_stats (namespace, identifier, registered_at)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (namespace, identifier) DO NOTHING",
+    )
+    .bind(&namespace)
+    .bind(&identifier)
+    .bind(registered_at as i64)
+    .execute(conn)
+    .await?;
+
     Ok(RegisteredIndex {
         id,
         namespace,
@@ -546,7 +817,7 @@ pub async fn register_index_asset(
     bytes: Vec<u8>,
     asset_type: IndexAssetType,
     pubkey: Option<&str>,
-) -> sqlx::Result<IndexAsset> {
+) -> sqlx::Result<(IndexAsset, bool)> {
     #[cfg(feature = "metrics")]
     METRICS.db.postgres.register_index_asset_calls.inc();
 
@@ -557,22 +828,31 @@ pub async fn register_index_asset(
 
     let digest = sha256_digest(&bytes);
 
-    if let Some(asset) =
-        asset_already_exists(conn, &asset_type, &bytes, &index.id).await?
-    {
-        info!(
-            "Asset({asset_type:?}) for Index({}) already registered.",
-            index.uid()
-        );
-        return Ok(asset);
+    // Re-uploading the latest version is a no-op, so that redeploys neither
+    // bump the version nor reload the indexer.
+    match latest_asset_for_index(conn, &index.id, asset_type.clone()).await {
+        Ok(latest) if latest.digest == digest => {
+            info!(
+                "Asset({asset_type:?}) for Index({}) is unchanged.",
+                index.uid()
+            );
+            return Ok((latest, true));
+        }
+        Ok(_) | Err(sqlx::Error::RowNotFound) => {}
+        Err(e) => return Err(e),
     }
 
     let current_version = index_asset_version(conn, &index.id, &asset_type)
         .await
         .expect("Failed to get asset version.");
 
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
     let query = format!(
-        "INSERT INTO index_asset_registry_{} (index_id, bytes, version, digest) VALUES ({}, $1, {}, '{digest}') RETURNING *",
+        "INSERT INTO index_asset_registry_{} (index_id, bytes, version, digest, created_at) VALUES ({}, $1, {}, '{digest}', {created_at}) RETURNING *",
         asset_type.as_ref(),
         index.id,
         current_version + 1,
@@ -595,14 +875,19 @@ pub async fn register_index_asset(
     let version = row.get(2);
     let digest = row.get(3);
     let bytes = row.get(4);
+    let created_at = row.get(5);
 
-    Ok(IndexAsset {
-        id,
-        index_id,
-        version,
-        digest,
-        bytes,
-    })
+    Ok((
+        IndexAsset {
+            id,
+            index_id,
+            version,
+            digest,
+            bytes,
+            created_at,
+        },
+        false,
+    ))
 }
 
 pub async fn latest_asset_for_index(
@@ -626,6 +911,7 @@ pub async fn latest_asset_for_index(
     let version = row.get(2);
     let digest = row.get(3);
     let bytes = row.get(4);
+    let created_at = row.get(5);
 
     Ok(IndexAsset {
         id,
@@ -633,6 +919,7 @@ pub async fn latest_asset_for_index(
         version,
         digest,
         bytes,
+        created_at,
     })
 }
 
@@ -664,7 +951,7 @@ pub async fn last_block_height_for_indexer(
     conn: &mut PoolConnection<Postgres>,
     namespace: &str,
     identifier: &str,
-) -> sqlx::Result<u64> {
+) -> sqlx::Result<Option<u64>> {
     #[cfg(feature = "metrics")]
     METRICS
         .db
@@ -672,17 +959,150 @@ pub async fn last_block_height_for_indexer(
         .last_block_height_for_indexer_calls
         .inc();
 
+    let row = sqlx::query(
+        "SELECT block_height FROM index_block_heights
+        WHERE namespace = $1 AND identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    if let Some(row) = row {
+        let block_height: i64 = row.get(0);
+        return Ok(Some(block_height as u64));
+    }
+
+    // Indexers that committed blocks before heights were recorded only have
+    // their metadata entities to go by.
     let query = format!(
         "SELECT MAX(id) FROM {namespace}_{identifier}.indexmetadataentity LIMIT 1"
     );
 
     let row = sqlx::query(&query).fetch_one(conn).await?;
-    let id: i64 = match row.try_get(0) {
-        Ok(id) => id,
-        Err(_e) => return Ok(1),
-    };
+    let id: Option<i64> = row.try_get(0).unwrap_or(None);
+
+    Ok(id.map(|id| id as u64))
+}
+
+pub async fn set_last_block_height(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+) -> sqlx::Result<usize> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.set_last_block_height_calls.inc();
+
+    let result = sqlx::query(
+        "INSERT INTO index_block_heights (namespace, identifier, block_height)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (namespace, identifier)
+        DO UPDATE SET block_height = excluded.block_height",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(block_height as i64)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+pub async fn record_indexer_stats(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    blocks: u64,
+    objects: u64,
+) -> sqlx::Result<usize> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.record_indexer_stats_calls.inc();
+
+    let registered_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let result = sqlx::query(
+        "INSERT INTO index_stats
+        (namespace, identifier, registered_at, blocks_processed, objects_written)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (namespace, identifier)
+        DO UPDATE SET
+            blocks_processed = index_stats.blocks_processed + excluded.blocks_processed,
+            objects_written = index_stats.objects_written + excluded.objects_written",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(registered_at as i64)
+    .bind(blocks as i64)
+    .bind(objects as i64)
+    .execute(conn)
+    .await?;
 
-    Ok(id as u64)
+    Ok(result.rows_affected() as usize)
+}
+
+pub async fn indexer_stats(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<IndexerStats>> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.indexer_stats_calls.inc();
+
+    let row = sqlx::query(
+        "SELECT namespace, identifier, registered_at, blocks_processed, objects_written
+        FROM index_stats
+        WHERE namespace = $1 AND identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row: PgRow| {
+        let blocks_processed: i64 = row.get(3);
+        let objects_written: i64 = row.get(4);
+
+        IndexerStats {
+            namespace: row.get(0),
+            identifier: row.get(1),
+            registered_at: row.get(2),
+            blocks_processed: blocks_processed as u64,
+            objects_written: objects_written as u64,
+        }
+    }))
+}
+
+pub async fn assets_for_index(
+    conn: &mut PoolConnection<Postgres>,
+    index_id: &i64,
+    asset_type: IndexAssetType,
+) -> sqlx::Result<Vec<IndexAsset>> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.assets_for_index_calls.inc();
+
+    let query = format!(
+        "SELECT id, index_id, version, digest, created_at FROM index_asset_registry_{} WHERE index_id = {} ORDER BY id ASC",
+        asset_type.as_ref(),
+        index_id
+    );
+
+    let rows = sqlx::query(&query).fetch_all(conn).await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| IndexAsset {
+            id: row.get(0),
+            index_id: row.get(1),
+            version: row.get(2),
+            digest: row.get(3),
+            bytes: Vec::new(),
+            created_at: row.get(4),
+        })
+        .collect())
 }
 
 // TODO: https://github.com/FuelLabs/fuel-indexer/issues/251
@@ -711,6 +1131,7 @@ pub async fn asset_already_exists(
             let version = row.get(2);
             let digest = row.get(3);
             let bytes = row.get(4);
+            let created_at = row.get(5);
 
             Ok(Some(IndexAsset {
                 id,
@@ -718,6 +1139,7 @@ pub async fn asset_already_exists(
                 version,
                 digest,
                 bytes,
+                created_at,
             }))
         }
         Err(_e) => Ok(None),
@@ -770,6 +1192,7 @@ pub async fn penultimate_asset_for_index(
     let version = row.get(2);
     let digest = row.get(3);
     let bytes = row.get(4);
+    let created_at = row.get(5);
 
     Ok(IndexAsset {
         id,
@@ -777,6 +1200,7 @@ pub async fn penultimate_asset_for_index(
         version,
         digest,
         bytes,
+        created_at,
     })
 }
 
@@ -844,6 +1268,134 @@ pub async fn remove_indexer(
     Ok(())
 }
 
+/// Tables created for an indexer, across every version of its schema.
+pub async fn indexer_tables(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<String>> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.indexer_tables_calls.inc();
+
+    let rows = sqlx::query(
+        "SELECT DISTINCT table_name FROM graph_registry_type_ids
+        WHERE schema_name = $1
+        AND schema_identifier = $2
+        ORDER BY table_name",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_all(conn)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| format!("{namespace}_{identifier}.{}", row.get::<String, usize>(0)))
+        .collect())
+}
+
+/// Drop an indexer's tables, along with the metadata of its schema and the
+/// record of its progress, so that it starts afresh if registered again.
+pub async fn purge_indexer(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.purge_indexer_calls.inc();
+
+    // Every table of the indexer lives in its own schema.
+    execute_query(
+        conn,
+        format!("DROP SCHEMA IF EXISTS {namespace}_{identifier} CASCADE"),
+    )
+    .await?;
+
+    let statements = [
+        "DELETE FROM graph_registry_columns WHERE type_id IN
+            (SELECT id FROM graph_registry_type_ids
+            WHERE schema_name = $1 AND schema_identifier = $2)",
+        "DELETE FROM graph_registry_type_ids
+            WHERE schema_name = $1 AND schema_identifier = $2",
+        "DELETE FROM graph_registry_root_columns WHERE root_id IN
+            (SELECT id FROM graph_registry_graph_root
+            WHERE schema_name = $1 AND schema_identifier = $2)",
+        "DELETE FROM graph_registry_graph_root
+            WHERE schema_name = $1 AND schema_identifier = $2",
+        "DELETE FROM index_block_heights WHERE namespace = $1 AND identifier = $2",
+        "DELETE FROM index_stats WHERE namespace = $1 AND identifier = $2",
+        "DELETE FROM index_failed_blocks WHERE namespace = $1 AND identifier = $2",
+        "DELETE FROM index_object_writes WHERE namespace = $1 AND identifier = $2",
+    ];
+    for statement in statements {
+        sqlx::query(statement)
+            .bind(namespace)
+            .bind(identifier)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// A page of the rows of `table` with IDs above `after`, ordered by ID, each
+/// with its ID and as the text of a JSON object.
+///
+/// Rows are kept as text so that numeric columns wider than 64 bits survive the
+/// round trip through `insert_json_rows`.
+pub async fn table_rows_as_json(
+    conn: &mut PoolConnection<Postgres>,
+    table: &str,
+    after: Option<i64>,
+    limit: i64,
+) -> sqlx::Result<Vec<(i64, String)>> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.table_rows_as_json_calls.inc();
+
+    // Pages continue from the last ID of the previous one, rather than from an
+    // offset that every page would have to scan past again.
+    let query = match after {
+        Some(_) => format!(
+            "SELECT t.id, row_to_json(t)::text FROM {table} t \
+            WHERE t.id > $2 ORDER BY t.id LIMIT $1"
+        ),
+        None => format!(
+            "SELECT t.id, row_to_json(t)::text FROM {table} t ORDER BY t.id LIMIT $1"
+        ),
+    };
+
+    let mut query = sqlx::query(&query).bind(limit);
+    if let Some(after) = after {
+        query = query.bind(after);
+    }
+    let rows = query.fetch_all(conn).await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| (row.get::<i64, usize>(0), row.get::<String, usize>(1)))
+        .collect())
+}
+
+/// Insert rows read with `table_rows_as_json` into `table`, in a single
+/// statement.
+pub async fn insert_json_rows(
+    conn: &mut PoolConnection<Postgres>,
+    table: &str,
+    rows: &[String],
+) -> sqlx::Result<usize> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.insert_json_rows_calls.inc();
+
+    let result = sqlx::query(&format!(
+        "INSERT INTO {table} SELECT * FROM json_populate_recordset(NULL::{table}, $1::json)"
+    ))
+    .bind(format!("[{}]", rows.join(",")))
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
 pub async fn remove_asset_by_version(
     conn: &mut PoolConnection<Postgres>,
     index_id: &i64,
@@ -867,6 +1419,219 @@ pub async fn remove_asset_by_version(
     Ok(())
 }
 
+pub async fn put_failed_block(
+    conn: &mut PoolConnection<Postgres>,
+    block: &FailedBlock,
+) -> sqlx::Result<usize> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.put_failed_block_calls.inc();
+
+    let result = sqlx::query(
+        "INSERT INTO index_failed_blocks
+        (namespace, identifier, block_height, strategy, error, block_data)
+        VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(&block.namespace)
+    .bind(&block.identifier)
+    .bind(block.block_height as i64)
+    .bind(&block.strategy)
+    .bind(&block.error)
+    .bind(&block.block_data)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+pub async fn failed_blocks(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<FailedBlock>> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.failed_blocks_calls.inc();
+
+    Ok(sqlx::query(
+        "SELECT namespace, identifier, block_height, strategy, error, block_data
+        FROM index_failed_blocks
+        WHERE namespace = $1 AND identifier = $2
+        ORDER BY block_height ASC",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row: PgRow| {
+        let namespace: String = row.get(0);
+        let identifier: String = row.get(1);
+        let block_height: i64 = row.get(2);
+        let strategy: String = row.get(3);
+        let error: String = row.get(4);
+        let block_data: Option<Vec<u8>> = row.get(5);
+
+        FailedBlock {
+            namespace,
+            identifier,
+            block_height: block_height as u64,
+            strategy,
+            error,
+            block_data,
+        }
+    })
+    .collect::<Vec<FailedBlock>>())
+}
+
+/// Record the objects about to be written to `table_name` by the batch of
+/// blocks starting at `block_height`, along with their current rows, so that
+/// they can be reverted after a chain reorganization.
+pub async fn put_object_writes(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+    table_name: &str,
+    object_ids: Vec<i64>,
+) -> sqlx::Result<usize> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.put_object_writes_calls.inc();
+
+    let result = sqlx::query(&format!(
+        "INSERT INTO index_object_writes (namespace, identifier, block_height, table_name, object_id, previous)
+        SELECT $1, $2, $3, $4, ids.id, (SELECT to_jsonb(t) FROM {table_name} t WHERE t.id = ids.id)
+        FROM UNNEST($5::bigint[]) AS ids(id)"
+    ))
+    .bind(namespace)
+    .bind(identifier)
+    .bind(block_height as i64)
+    .bind(table_name)
+    .bind(object_ids)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+/// Put the objects written at or above `block_height` back as they were
+/// before, deleting those that didn't exist yet, and delete their write
+/// records. Returns the number of objects reverted.
+pub async fn revert_to_block(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+) -> sqlx::Result<usize> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.revert_to_block_calls.inc();
+
+    let tables: Vec<String> = sqlx::query(
+        "SELECT DISTINCT table_name FROM index_object_writes
+        WHERE namespace = $1 AND identifier = $2 AND block_height >= $3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(block_height as i64)
+    .fetch_all(&mut *conn)
+    .await?
+    .iter()
+    .map(|row| row.get(0))
+    .collect();
+
+    let writes = "FROM index_object_writes
+        WHERE namespace = $1 AND identifier = $2 AND block_height >= $3 AND table_name = $4";
+
+    let mut count = 0;
+    for table_name in tables {
+        let result = sqlx::query(&format!(
+            "DELETE FROM {table_name} WHERE id IN (SELECT object_id {writes})"
+        ))
+        .bind(namespace)
+        .bind(identifier)
+        .bind(block_height as i64)
+        .bind(&table_name)
+        .execute(&mut *conn)
+        .await?;
+
+        count += result.rows_affected() as usize;
+
+        // An object is restored to its row before its first write in the range.
+        sqlx::query(&format!(
+            "INSERT INTO {table_name}
+            SELECT (jsonb_populate_record(NULL::{table_name}, previous)).*
+            FROM (SELECT DISTINCT ON (object_id) previous {writes} ORDER BY object_id, id) AS first_writes
+            WHERE previous IS NOT NULL"
+        ))
+        .bind(namespace)
+        .bind(identifier)
+        .bind(block_height as i64)
+        .bind(&table_name)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    sqlx::query(
+        "DELETE FROM index_object_writes
+        WHERE namespace = $1 AND identifier = $2 AND block_height >= $3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(block_height as i64)
+    .execute(conn)
+    .await?;
+
+    Ok(count)
+}
+
+/// Delete the write records below `block_height`, whose objects can no longer
+/// be reverted.
+pub async fn prune_object_writes(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+) -> sqlx::Result<usize> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.prune_object_writes_calls.inc();
+
+    let result = sqlx::query(
+        "DELETE FROM index_object_writes
+        WHERE namespace = $1 AND identifier = $2 AND block_height < $3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(block_height as i64)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+/// The height at which the writes of the batch spanning `block_height` are
+/// recorded, i.e. the highest recorded write height at or below it.
+pub async fn object_writes_batch_start(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+) -> sqlx::Result<Option<u64>> {
+    #[cfg(feature = "metrics")]
+    METRICS.db.postgres.object_writes_batch_start_calls.inc();
+
+    let row = sqlx::query(
+        "SELECT MAX(block_height) FROM index_object_writes
+        WHERE namespace = $1 AND identifier = $2 AND block_height <= $3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(block_height as i64)
+    .fetch_one(conn)
+    .await?;
+
+    let start: Option<i64> = row.get(0);
+
+    Ok(start.map(|start| start as u64))
+}
+
 pub async fn create_nonce(conn: &mut PoolConnection<Postgres>) -> sqlx::Result<Nonce> {
     let uid = uuid::Uuid::new_v4().as_simple().to_string();
     let now = SystemTime::now()
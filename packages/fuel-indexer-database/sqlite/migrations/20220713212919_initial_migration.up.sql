@@ -0,0 +1,105 @@
+-- Add up migration script here
+create table if not exists graph_registry_type_ids (
+    id integer primary key autoincrement,
+    schema_version varchar(512) not null,
+    schema_name varchar(32) not null,
+    graphql_name varchar(32) not null,
+    table_name varchar(32) not null,
+    schema_identifier varchar(255) default 'unnamed'
+);
+
+create table if not exists graph_registry_columns (
+    id integer primary key autoincrement,
+    type_id integer not null,
+    column_position integer not null,
+    column_name varchar(32) not null,
+    column_type varchar(32) not null,
+    nullable boolean not null,
+    graphql_type varchar not null,
+    constraint fk_table_name
+        foreign key(type_id)
+            references graph_registry_type_ids(id)
+);
+
+create table if not exists graph_registry_graph_root (
+    id integer primary key autoincrement,
+    version varchar not null,
+    schema_name varchar not null,
+    query varchar not null,
+    schema varchar not null,
+    schema_identifier varchar(255) default 'unnamed',
+    unique(version, schema_name, schema_identifier)
+);
+
+create table if not exists graph_registry_root_columns (
+    id integer primary key autoincrement,
+    root_id integer not null,
+    column_name varchar(32) not null,
+    graphql_type varchar(32) not null,
+    constraint fk_root_id
+        foreign key(root_id)
+            references graph_registry_graph_root(id)
+);
+
+create table if not exists index_registry (
+   id integer primary key autoincrement,
+   namespace varchar(32) not null,
+   identifier varchar(32) not null,
+   pubkey varchar(255),
+   unique(namespace, identifier)
+);
+
+create table if not exists index_asset_registry_wasm (
+   id integer primary key autoincrement,
+   index_id integer,
+   version integer not null,
+   digest varchar(64) not null,
+   bytes blob not null,
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+            deferrable initially deferred
+);
+
+create table if not exists index_asset_registry_schema (
+   id integer primary key autoincrement,
+   index_id integer,
+   version integer not null,
+   digest varchar(64) not null,
+   bytes blob not null,
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+            deferrable initially deferred
+);
+
+create table if not exists index_asset_registry_manifest (
+   id integer primary key autoincrement,
+   index_id integer,
+   version integer not null,
+   digest varchar(64) not null,
+   bytes blob not null,
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+            deferrable initially deferred
+);
+
+create table if not exists nonce (
+    id integer primary key autoincrement,
+    uid varchar(64) unique not null,
+    expiry integer not null
+);
+
+create table if not exists index_failed_blocks (
+   id integer primary key autoincrement,
+   namespace varchar(32) not null,
+   identifier varchar(32) not null,
+   block_height integer not null,
+   strategy varchar(32) not null,
+   error text not null,
+   block_data blob
+);
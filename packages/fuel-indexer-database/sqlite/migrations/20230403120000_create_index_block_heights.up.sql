@@ -0,0 +1,6 @@
+create table if not exists index_block_heights (
+   namespace varchar(32) not null,
+   identifier varchar(32) not null,
+   block_height integer not null,
+   primary key (namespace, identifier)
+);
@@ -0,0 +1,3 @@
+alter table index_asset_registry_wasm drop column created_at;
+alter table index_asset_registry_schema drop column created_at;
+alter table index_asset_registry_manifest drop column created_at;
@@ -0,0 +1,3 @@
+alter table index_asset_registry_wasm add column created_at integer not null default 0;
+alter table index_asset_registry_schema add column created_at integer not null default 0;
+alter table index_asset_registry_manifest add column created_at integer not null default 0;
@@ -0,0 +1,8 @@
+create table if not exists index_stats (
+   namespace varchar(32) not null,
+   identifier varchar(32) not null,
+   registered_at integer not null,
+   blocks_processed integer not null default 0,
+   objects_written integer not null default 0,
+   primary key (namespace, identifier)
+);
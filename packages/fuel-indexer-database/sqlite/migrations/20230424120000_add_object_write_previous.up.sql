@@ -0,0 +1 @@
+alter table index_object_writes add column previous text;
@@ -0,0 +1,1436 @@
+#![deny(unused_crate_dependencies)]
+
+use fuel_indexer_database_types::*;
+use fuel_indexer_lib::utils::sha256_digest;
+use sqlx::{
+    pool::PoolConnection,
+    query::Query,
+    sqlite::{SqliteArguments, SqliteRow},
+    types::{Json, JsonValue},
+    Column, Row, Sqlite, TypeInfo, ValueRef,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+const NONCE_EXPIRY: u64 = 3600; // 1 hour
+
+/// Bind `param` to the next parameter of `query`.
+fn bind_param<'q>(
+    query: Query<'q, Sqlite, SqliteArguments<'q>>,
+    param: QueryParam,
+) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+    match param {
+        QueryParam::Int(v) => query.bind(v),
+        QueryParam::Float(v) => query.bind(v),
+        QueryParam::String(v) => query.bind(v),
+        QueryParam::Boolean(v) => query.bind(v),
+        QueryParam::Bytes(v) => query.bind(v),
+        QueryParam::Null => query.bind(None::<String>),
+    }
+}
+
+pub async fn put_object(
+    conn: &mut PoolConnection<Sqlite>,
+    query: String,
+    bytes: Vec<u8>,
+) -> sqlx::Result<usize> {
+    let mut builder = sqlx::QueryBuilder::new(query);
+
+    let query = builder.build();
+    let query = query.bind(bytes);
+    let result = query.execute(conn).await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+/// Run a multi-row upsert, binding `params` to its parameters in order.
+pub async fn put_objects(
+    conn: &mut PoolConnection<Sqlite>,
+    query: String,
+    params: Vec<QueryParam>,
+) -> sqlx::Result<usize> {
+    let mut query = sqlx::query(&query);
+    for param in params {
+        query = bind_param(query, param);
+    }
+    let result = query.execute(conn).await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+pub async fn get_object(
+    conn: &mut PoolConnection<Sqlite>,
+    query: String,
+    object_id: i64,
+) -> sqlx::Result<Vec<u8>> {
+    let row = sqlx::query(&query).bind(object_id).fetch_one(conn).await?;
+
+    Ok(row.get(0))
+}
+
+pub async fn get_object_by(
+    conn: &mut PoolConnection<Sqlite>,
+    query: String,
+    params: Vec<QueryParam>,
+) -> sqlx::Result<Option<Vec<u8>>> {
+    let mut query = sqlx::query(&query);
+    for param in params {
+        query = bind_param(query, param);
+    }
+
+    Ok(query.fetch_optional(conn).await?.map(|row| row.get(0)))
+}
+
+pub async fn soft_delete_object(
+    conn: &mut PoolConnection<Sqlite>,
+    table_name: &str,
+    object_id: i64,
+    block_height: Option<i64>,
+) -> sqlx::Result<usize> {
+    let written_at_block = block_height
+        .map(|height| format!(", {WRITTEN_AT_BLOCK_COLUMN} = {height}"))
+        .unwrap_or_default();
+    let result = sqlx::query(&format!(
+        "UPDATE {table_name}
+        SET {DELETED_AT_COLUMN} = CURRENT_TIMESTAMP, {UPDATED_AT_COLUMN} = CURRENT_TIMESTAMP{written_at_block}
+        WHERE id = $1 AND {DELETED_AT_COLUMN} IS NULL"
+    ))
+    .bind(object_id)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+pub async fn get_object_ids(
+    conn: &mut PoolConnection<Sqlite>,
+    query: String,
+    params: Vec<QueryParam>,
+) -> sqlx::Result<Vec<i64>> {
+    let mut query = sqlx::query(&query);
+    for param in params {
+        query = bind_param(query, param);
+    }
+
+    Ok(query
+        .fetch_all(conn)
+        .await?
+        .iter()
+        .map(|r| r.get::<'_, i64, usize>(0))
+        .collect())
+}
+
+pub async fn run_migration(conn: &mut PoolConnection<Sqlite>) -> sqlx::Result<()> {
+    sqlx::migrate!().run(conn).await?;
+
+    Ok(())
+}
+
+pub async fn run_query(
+    conn: &mut PoolConnection<Sqlite>,
+    query: String,
+    params: Vec<QueryParam>,
+) -> sqlx::Result<JsonValue> {
+    let mut builder = sqlx::QueryBuilder::new(query);
+
+    let mut query = builder.build();
+    for param in params {
+        query = bind_param(query, param);
+    }
+
+    Ok(query
+        .fetch_all(conn)
+        .await?
+        .iter()
+        // SQLite has no JSON column type, so JSON results come back as text.
+        .map(|r| r.get::<'_, Json<JsonValue>, usize>(0).0)
+        .collect())
+}
+
+/// Run a raw `SELECT`, returning each row as a JSON object of its columns.
+///
+/// SQLite has no read-only transactions, so the query must already have been
+/// checked to be a single `SELECT`.
+pub async fn run_read_only_query(
+    conn: &mut PoolConnection<Sqlite>,
+    query: String,
+) -> sqlx::Result<JsonValue> {
+    let rows = sqlx::query(&query).fetch_all(conn).await?;
+
+    rows.iter()
+        .map(|row| {
+            let mut object = JsonValue::Object(Default::default());
+            for column in row.columns() {
+                let i = column.ordinal();
+                let raw = row.try_get_raw(i)?;
+                // Columns are decoded by the storage class of each value, since
+                // expressions in a raw query have no declared type.
+                let value = if raw.is_null() {
+                    JsonValue::Null
+                } else {
+                    match raw.type_info().name() {
+                        "INTEGER" => JsonValue::from(row.try_get::<i64, _>(i)?),
+                        "REAL" => JsonValue::from(row.try_get::<f64, _>(i)?),
+                        "BLOB" => JsonValue::from(
+                            row.try_get::<Vec<u8>, _>(i)?
+                                .iter()
+                                .map(|b| format!("{b:02x}"))
+                                .collect::<String>(),
+                        ),
+                        _ => JsonValue::from(row.try_get::<String, _>(i)?),
+                    }
+                };
+                object[column.name()] = value;
+            }
+            Ok(object)
+        })
+        .collect()
+}
+
+pub async fn execute_query(
+    conn: &mut PoolConnection<Sqlite>,
+    query: String,
+) -> sqlx::Result<usize> {
+    let mut builder = sqlx::QueryBuilder::new(query);
+
+    let query = builder.build();
+
+    let result = query.execute(conn).await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+pub async fn root_columns_list_by_id(
+    conn: &mut PoolConnection<Sqlite>,
+    root_id: i64,
+) -> sqlx::Result<Vec<RootColumns>> {
+    Ok(
+        sqlx::query("SELECT * FROM graph_registry_root_columns WHERE root_id = $1")
+            .bind(root_id)
+            .fetch_all(conn)
+            .await?
+            .into_iter()
+            .map(|row| {
+                let id: i64 = row.get(0);
+                let root_id: i64 = row.get(1);
+                let column_name: String = row.get(2);
+                let graphql_type: String = row.get(3);
+                RootColumns {
+                    id,
+                    root_id,
+                    column_name,
+                    graphql_type,
+                }
+            })
+            .collect::<Vec<RootColumns>>(),
+    )
+}
+
+pub async fn new_root_columns(
+    conn: &mut PoolConnection<Sqlite>,
+    cols: Vec<NewRootColumns>,
+) -> sqlx::Result<usize> {
+    let mut builder = sqlx::QueryBuilder::new(
+        "INSERT INTO graph_registry_root_columns (root_id, column_name, graphql_type)",
+    );
+
+    builder.push_values(cols, |mut b, new_col| {
+        b.push_bind(new_col.root_id)
+            .push_bind(new_col.column_name)
+            .push_bind(new_col.graphql_type);
+    });
+
+    let query = builder.build();
+
+    let result = query.execute(conn).await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+pub async fn new_graph_root(
+    conn: &mut PoolConnection<Sqlite>,
+    root: NewGraphRoot,
+) -> sqlx::Result<usize> {
+    let mut builder = sqlx::QueryBuilder::new(
+        "INSERT INTO graph_registry_graph_root (version, schema_name, schema_identifier, query, schema)",
+    );
+
+    builder.push_values(std::iter::once(root), |mut b, root| {
+        b.push_bind(root.version)
+            .push_bind(root.schema_name)
+            .push_bind(root.schema_identifier)
+            .push_bind(root.query)
+            .push_bind(root.schema);
+    });
+
+    let query = builder.build();
+
+    let result = query.execute(conn).await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+pub async fn graph_root_latest(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<GraphRoot> {
+    let row = sqlx::query(
+        "SELECT * FROM graph_registry_graph_root
+        WHERE schema_name = $1 AND schema_identifier = $2
+        ORDER BY id DESC LIMIT 1",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_one(conn)
+    .await?;
+
+    let id: i64 = row.get(0);
+    let version: String = row.get(1);
+    let schema_name: String = row.get(2);
+    let query: String = row.get(3);
+    let schema: String = row.get(4);
+
+    Ok(GraphRoot {
+        id,
+        version,
+        schema_name,
+        query,
+        schema,
+        schema_identifier: identifier.to_string(),
+    })
+}
+
+pub async fn type_id_list_by_name(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    version: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<TypeId>> {
+    Ok(sqlx::query(
+        "SELECT * FROM graph_registry_type_ids
+        WHERE schema_name = $1 
+        AND schema_version = $2 
+        AND schema_identifier = $3",
+    )
+    .bind(namespace)
+    .bind(version)
+    .bind(identifier)
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        let id: i64 = row.get(0);
+        let schema_version: String = row.get(1);
+        let schema_name: String = row.get(2);
+        let graphql_name: String = row.get(3);
+        let table_name: String = row.get(4);
+
+        TypeId {
+            id,
+            schema_version,
+            schema_name,
+            table_name,
+            graphql_name,
+            schema_identifier: identifier.to_string(),
+        }
+    })
+    .collect::<Vec<TypeId>>())
+}
+
+pub async fn type_id_latest(
+    conn: &mut PoolConnection<Sqlite>,
+    schema_name: &str,
+    identifier: &str,
+) -> sqlx::Result<String> {
+    let latest = sqlx::query(
+        "SELECT schema_version FROM graph_registry_type_ids 
+        WHERE schema_name = $1 
+        AND schema_identifier = $2 
+        ORDER BY id",
+    )
+    .bind(schema_name)
+    .bind(identifier)
+    .fetch_one(conn)
+    .await?;
+
+    let schema_version: String = latest.get(0);
+
+    Ok(schema_version)
+}
+
+pub async fn type_id_insert(
+    conn: &mut PoolConnection<Sqlite>,
+    type_ids: Vec<TypeId>,
+) -> sqlx::Result<usize> {
+    let mut builder = sqlx::QueryBuilder::new("INSERT INTO graph_registry_type_ids (id, schema_version, schema_name, schema_identifier, graphql_name, table_name)");
+
+    builder.push_values(type_ids, |mut b, tid| {
+        b.push_bind(tid.id)
+            .push_bind(tid.schema_version)
+            .push_bind(tid.schema_name)
+            .push_bind(tid.schema_identifier)
+            .push_bind(tid.graphql_name)
+            .push_bind(tid.table_name);
+    });
+
+    let query = builder.build();
+
+    let result = query.execute(conn).await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+pub async fn schema_exists(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    version: &str,
+) -> sqlx::Result<bool> {
+    let count = sqlx::query(
+        "SELECT COUNT(*) AS count FROM graph_registry_type_ids 
+        WHERE schema_name = $1 
+        AND schema_identifier = $2 
+        AND schema_version = $3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(version)
+    .fetch_one(conn)
+    .await?;
+
+    let count: i64 = count.get(0);
+
+    Ok(count > 0)
+}
+
+pub async fn new_column_insert(
+    conn: &mut PoolConnection<Sqlite>,
+    cols: Vec<NewColumn>,
+) -> sqlx::Result<usize> {
+    let mut builder = sqlx::QueryBuilder::new("INSERT INTO graph_registry_columns (type_id, column_position, column_name, column_type, nullable, graphql_type)");
+
+    builder.push_values(cols, |mut b, new_col| {
+        b.push_bind(new_col.type_id)
+            .push_bind(new_col.column_position)
+            .push_bind(new_col.column_name)
+            .push_bind(new_col.column_type)
+            .push_bind(new_col.nullable)
+            .push_bind(new_col.graphql_type);
+    });
+
+    let query = builder.build();
+
+    let result = query.execute(conn).await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+pub async fn list_column_by_id(
+    conn: &mut PoolConnection<Sqlite>,
+    col_id: i64,
+) -> sqlx::Result<Vec<Columns>> {
+    Ok(
+        sqlx::query("SELECT * FROM graph_registry_columns WHERE type_id = $1")
+            .bind(col_id)
+            .fetch_all(conn)
+            .await?
+            .into_iter()
+            .map(|row| {
+                let id: i64 = row.get(0);
+                let type_id: i64 = row.get(1);
+                let column_position: i32 = row.get(2);
+                let column_name: String = row.get(3);
+                let column_type: String = row.get(4);
+                let nullable: bool = row.get(5);
+                let graphql_type: String = row.get(6);
+
+                Columns {
+                    id,
+                    type_id,
+                    column_position,
+                    column_name,
+                    column_type,
+                    nullable,
+                    graphql_type,
+                }
+            })
+            .collect::<Vec<Columns>>(),
+    )
+}
+
+pub async fn columns_get_schema(
+    conn: &mut PoolConnection<Sqlite>,
+    name: &str,
+    identifier: &str,
+    version: &str,
+) -> sqlx::Result<Vec<ColumnInfo>> {
+    Ok(sqlx::query(
+        "
+            SELECT
+            c.type_id as type_id,
+            t.table_name as table_name,
+            c.column_position as column_position,
+            c.column_name as column_name,
+            c.column_type as column_type,
+            c.graphql_type as graphql_type
+            FROM graph_registry_type_ids as t
+            INNER JOIN graph_registry_columns as c ON t.id = c.type_id
+            WHERE t.schema_name = $1 
+            AND t.schema_identifier = $2 
+            AND t.schema_version = $3
+            ORDER BY c.type_id, c.column_position",
+    )
+    .bind(name)
+    .bind(identifier)
+    .bind(version)
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row: SqliteRow| {
+        let type_id: i64 = row.get(0);
+        let table_name: String = row.get(1);
+        let column_position: i32 = row.get(2);
+        let column_name: String = row.get(3);
+        let column_type: String = row.get(4);
+        let graphql_type: String = row.get(5);
+
+        ColumnInfo {
+            type_id,
+            table_name,
+            column_position,
+            column_name,
+            column_type,
+            graphql_type,
+        }
+    })
+    .collect::<Vec<ColumnInfo>>())
+}
+
+pub async fn indexed_columns(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<IndexedColumn>> {
+    // SQLite has no schemas, so an indexer's tables are the ones prefixed
+    // with its namespace. Only the leading column of each index is returned,
+    // since that is the only column an index can be used to look up on its own.
+    Ok(sqlx::query(
+        "SELECT substr(m.name, length($1) + 1) AS table_name, ii.name AS column_name
+        FROM sqlite_master AS m
+        INNER JOIN pragma_index_list(m.name) AS il
+        INNER JOIN pragma_index_info(il.name) AS ii
+        WHERE m.type = 'table'
+        AND substr(m.name, 1, length($1)) = $1
+        AND ii.seqno = 0",
+    )
+    .bind(format!("{namespace}_{identifier}_"))
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row: SqliteRow| {
+        let table_name: String = row.get(0);
+        let column_name: String = row.get(1);
+
+        IndexedColumn {
+            table_name,
+            column_name,
+        }
+    })
+    .collect::<Vec<IndexedColumn>>())
+}
+
+pub async fn index_is_registered(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<RegisteredIndex>> {
+    match sqlx::query(
+        "SELECT * FROM index_registry 
+        WHERE namespace = $1 
+        AND identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_optional(conn)
+    .await?
+    {
+        Some(row) => Ok(Some(RegisteredIndex {
+            id: row.get(0),
+            namespace: row.get(1),
+            identifier: row.get(2),
+            pubkey: row.get(3),
+        })),
+        None => Ok(None),
+    }
+}
+
+pub async fn register_index(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    pubkey: Option<&str>,
+) -> sqlx::Result<RegisteredIndex> {
+    if let Some(index) = index_is_registered(conn, namespace, identifier).await? {
+        return Ok(index);
+    }
+
+    let row = sqlx::query(
+        "INSERT INTO index_registry (namespace, identifier, pubkey)
+         VALUES ($1, $2, $3) 
+         RETURNING *",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(pubkey)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let id: i64 = row.get(0);
+    let namespace: String = row.get(1);
+    let identifier: String = row.get(2);
+    let pubkey = row.get(3);
+
+    let registered_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    sqlx::query(
+        "INSERT INTO index_stats (namespace, identifier, registered_at)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (namespace, identifier) DO NOTHING",
+    )
+    .bind(&namespace)
+    .bind(&identifier)
+    .bind(registered_at as i64)
+    .execute(conn)
+    .await?;
+
+    Ok(RegisteredIndex {
+        id,
+        namespace,
+        identifier,
+        pubkey,
+    })
+}
+
+pub async fn registered_indices(
+    conn: &mut PoolConnection<Sqlite>,
+) -> sqlx::Result<Vec<RegisteredIndex>> {
+    Ok(sqlx::query("SELECT * FROM index_registry")
+        .fetch_all(conn)
+        .await?
+        .into_iter()
+        .map(|row| {
+            let id: i64 = row.get(0);
+            let namespace: String = row.get(1);
+            let identifier: String = row.get(2);
+            let pubkey = row.get(3);
+
+            RegisteredIndex {
+                id,
+                namespace,
+                identifier,
+                pubkey,
+            }
+        })
+        .collect::<Vec<RegisteredIndex>>())
+}
+
+pub async fn index_asset_version(
+    conn: &mut PoolConnection<Sqlite>,
+    index_id: &i64,
+    asset_type: &IndexAssetType,
+) -> sqlx::Result<i64> {
+    match sqlx::query(&format!(
+        "SELECT COUNT(*) 
+        FROM index_asset_registry_{} 
+        WHERE index_id = {}",
+        asset_type.as_ref(),
+        index_id,
+    ))
+    .fetch_one(conn)
+    .await
+    {
+        Ok(row) => Ok(row.try_get::<'_, i64, usize>(0).unwrap_or(0)),
+        Err(_e) => Ok(0),
+    }
+}
+
+pub async fn register_index_asset(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    bytes: Vec<u8>,
+    asset_type: IndexAssetType,
+    pubkey: Option<&str>,
+) -> sqlx::Result<(IndexAsset, bool)> {
+    let index = match index_is_registered(conn, namespace, identifier).await? {
+        Some(index) => index,
+        None => register_index(conn, namespace, identifier, pubkey).await?,
+    };
+
+    let digest = sha256_digest(&bytes);
+
+    // Re-uploading the latest version is a no-op, so that redeploys neither
+    // bump the version nor reload the indexer.
+    match latest_asset_for_index(conn, &index.id, asset_type.clone()).await {
+        Ok(latest) if latest.digest == digest => {
+            info!(
+                "Asset({asset_type:?}) for Index({}) is unchanged.",
+                index.uid()
+            );
+            return Ok((latest, true));
+        }
+        Ok(_) | Err(sqlx::Error::RowNotFound) => {}
+        Err(e) => return Err(e),
+    }
+
+    let current_version = index_asset_version(conn, &index.id, &asset_type)
+        .await
+        .expect("Failed to get asset version.");
+
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let query = format!(
+        "INSERT INTO index_asset_registry_{} (index_id, bytes, version, digest, created_at) VALUES ({}, $1, {}, '{digest}', {created_at}) RETURNING *",
+        asset_type.as_ref(),
+        index.id,
+        current_version + 1,
+    );
+
+    let row = sqlx::QueryBuilder::new(query)
+        .build()
+        .bind(bytes)
+        .fetch_one(conn)
+        .await?;
+
+    info!(
+        "Registered Asset({:?}) to Index({}).",
+        asset_type,
+        index.uid()
+    );
+
+    let id = row.get(0);
+    let index_id = row.get(1);
+    let version = row.get(2);
+    let digest = row.get(3);
+    let bytes = row.get(4);
+    let created_at = row.get(5);
+
+    Ok((
+        IndexAsset {
+            id,
+            index_id,
+            version,
+            digest,
+            bytes,
+            created_at,
+        },
+        false,
+    ))
+}
+
+pub async fn latest_asset_for_index(
+    conn: &mut PoolConnection<Sqlite>,
+    index_id: &i64,
+    asset_type: IndexAssetType,
+) -> sqlx::Result<IndexAsset> {
+    let query = format!(
+        "SELECT * FROM index_asset_registry_{} WHERE index_id = {} ORDER BY id DESC LIMIT 1",
+        asset_type.as_ref(),
+        index_id
+    );
+
+    let row = sqlx::query(&query).fetch_one(conn).await?;
+
+    let id = row.get(0);
+    let index_id = row.get(1);
+    let version = row.get(2);
+    let digest = row.get(3);
+    let bytes = row.get(4);
+    let created_at = row.get(5);
+
+    Ok(IndexAsset {
+        id,
+        index_id,
+        version,
+        digest,
+        bytes,
+        created_at,
+    })
+}
+
+pub async fn latest_assets_for_index(
+    conn: &mut PoolConnection<Sqlite>,
+    index_id: &i64,
+) -> sqlx::Result<IndexAssetBundle> {
+    let wasm = latest_asset_for_index(conn, index_id, IndexAssetType::Wasm)
+        .await
+        .expect("Failed to retrieve wasm asset.");
+    let schema = latest_asset_for_index(conn, index_id, IndexAssetType::Schema)
+        .await
+        .expect("Failed to retrieve schema asset.");
+    let manifest = latest_asset_for_index(conn, index_id, IndexAssetType::Manifest)
+        .await
+        .expect("Failed to retrieve manifest asset.");
+
+    Ok(IndexAssetBundle {
+        wasm,
+        schema,
+        manifest,
+    })
+}
+
+pub async fn last_block_height_for_indexer(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<u64>> {
+    let row = sqlx::query(
+        "SELECT block_height FROM index_block_heights
+        WHERE namespace = $1 AND identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    if let Some(row) = row {
+        let block_height: i64 = row.get(0);
+        return Ok(Some(block_height as u64));
+    }
+
+    // Indexers that committed blocks before heights were recorded only have
+    // their metadata entities to go by.
+    let query = format!(
+        "SELECT MAX(id) FROM {namespace}_{identifier}_indexmetadataentity LIMIT 1"
+    );
+
+    let row = sqlx::query(&query).fetch_one(conn).await?;
+    let id: Option<i64> = row.try_get(0).unwrap_or(None);
+
+    Ok(id.map(|id| id as u64))
+}
+
+pub async fn set_last_block_height(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+) -> sqlx::Result<usize> {
+    let result = sqlx::query(
+        "INSERT INTO index_block_heights (namespace, identifier, block_height)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (namespace, identifier)
+        DO UPDATE SET block_height = excluded.block_height",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(block_height as i64)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+pub async fn record_indexer_stats(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    blocks: u64,
+    objects: u64,
+) -> sqlx::Result<usize> {
+    let registered_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let result = sqlx::query(
+        "INSERT INTO index_stats
+        (namespace, identifier, registered_at, blocks_processed, objects_written)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (namespace, identifier)
+        DO UPDATE SET
+            blocks_processed = index_stats.blocks_processed + excluded.blocks_processed,
+            objects_written = index_stats.objects_written + excluded.objects_written",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(registered_at as i64)
+    .bind(blocks as i64)
+    .bind(objects as i64)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+pub async fn indexer_stats(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<IndexerStats>> {
+    let row = sqlx::query(
+        "SELECT namespace, identifier, registered_at, blocks_processed, objects_written
+        FROM index_stats
+        WHERE namespace = $1 AND identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row: SqliteRow| {
+        let blocks_processed: i64 = row.get(3);
+        let objects_written: i64 = row.get(4);
+
+        IndexerStats {
+            namespace: row.get(0),
+            identifier: row.get(1),
+            registered_at: row.get(2),
+            blocks_processed: blocks_processed as u64,
+            objects_written: objects_written as u64,
+        }
+    }))
+}
+
+pub async fn assets_for_index(
+    conn: &mut PoolConnection<Sqlite>,
+    index_id: &i64,
+    asset_type: IndexAssetType,
+) -> sqlx::Result<Vec<IndexAsset>> {
+    let query = format!(
+        "SELECT id, index_id, version, digest, created_at FROM index_asset_registry_{} WHERE index_id = {} ORDER BY id ASC",
+        asset_type.as_ref(),
+        index_id
+    );
+
+    let rows = sqlx::query(&query).fetch_all(conn).await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| IndexAsset {
+            id: row.get(0),
+            index_id: row.get(1),
+            version: row.get(2),
+            digest: row.get(3),
+            bytes: Vec::new(),
+            created_at: row.get(4),
+        })
+        .collect())
+}
+
+// TODO: https://github.com/FuelLabs/fuel-indexer/issues/251
+pub async fn asset_already_exists(
+    conn: &mut PoolConnection<Sqlite>,
+    asset_type: &IndexAssetType,
+    bytes: &Vec<u8>,
+    index_id: &i64,
+) -> sqlx::Result<Option<IndexAsset>> {
+    let digest = sha256_digest(bytes);
+
+    let query = format!(
+        "SELECT * FROM index_asset_registry_{} WHERE index_id = {} AND digest = '{}'",
+        asset_type.as_ref(),
+        index_id,
+        digest
+    );
+
+    match sqlx::QueryBuilder::new(query).build().fetch_one(conn).await {
+        Ok(row) => {
+            let id = row.get(0);
+            let index_id = row.get(1);
+            let version = row.get(2);
+            let digest = row.get(3);
+            let bytes = row.get(4);
+            let created_at = row.get(5);
+
+            Ok(Some(IndexAsset {
+                id,
+                index_id,
+                version,
+                digest,
+                bytes,
+                created_at,
+            }))
+        }
+        Err(_e) => Ok(None),
+    }
+}
+
+pub async fn index_id_for(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<i64> {
+    let row = sqlx::query(
+        "SELECT id FROM index_registry 
+        WHERE namespace = $1 
+        AND identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_one(conn)
+    .await?;
+
+    let id: i64 = row.get(0);
+
+    Ok(id)
+}
+
+pub async fn penultimate_asset_for_index(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    asset_type: IndexAssetType,
+) -> sqlx::Result<IndexAsset> {
+    let index_id = index_id_for(conn, namespace, identifier).await?;
+    let query = format!(
+        "SELECT * FROM index_asset_registry_{} 
+        WHERE index_id = {} ORDER BY id DESC LIMIT 1 OFFSET 1",
+        asset_type.as_ref(),
+        index_id
+    );
+    let row = sqlx::query(&query).fetch_one(conn).await?;
+
+    let id = row.get(0);
+    let index_id = row.get(1);
+    let version = row.get(2);
+    let digest = row.get(3);
+    let bytes = row.get(4);
+    let created_at = row.get(5);
+
+    Ok(IndexAsset {
+        id,
+        index_id,
+        version,
+        digest,
+        bytes,
+        created_at,
+    })
+}
+
+pub async fn start_transaction(conn: &mut PoolConnection<Sqlite>) -> sqlx::Result<usize> {
+    execute_query(conn, "BEGIN".into()).await
+}
+
+pub async fn commit_transaction(
+    conn: &mut PoolConnection<Sqlite>,
+) -> sqlx::Result<usize> {
+    execute_query(conn, "COMMIT".into()).await
+}
+
+pub async fn revert_transaction(
+    conn: &mut PoolConnection<Sqlite>,
+) -> sqlx::Result<usize> {
+    execute_query(conn, "ROLLBACK".into()).await
+}
+
+pub async fn remove_indexer(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    let index_id = index_id_for(conn, namespace, identifier).await?;
+
+    execute_query(
+        conn,
+        format!("DELETE FROM index_asset_registry_wasm WHERE index_id = {index_id}",),
+    )
+    .await?;
+
+    execute_query(
+        conn,
+        format!("DELETE FROM index_asset_registry_manifest WHERE index_id = {index_id}",),
+    )
+    .await?;
+
+    execute_query(
+        conn,
+        format!("DELETE FROM index_asset_registry_schema WHERE index_id = {index_id}",),
+    )
+    .await?;
+
+    execute_query(
+        conn,
+        format!("DELETE FROM index_registry WHERE id = {index_id}",),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Tables created for an indexer, across every version of its schema.
+pub async fn indexer_tables(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<String>> {
+    let rows = sqlx::query(
+        "SELECT DISTINCT table_name FROM graph_registry_type_ids
+        WHERE schema_name = $1
+        AND schema_identifier = $2
+        ORDER BY table_name",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_all(conn)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            DbType::Sqlite.table_name(
+                &format!("{namespace}_{identifier}"),
+                &row.get::<String, usize>(0),
+            )
+        })
+        .collect())
+}
+
+/// Drop an indexer's tables, along with the metadata of its schema and the
+/// record of its progress, so that it starts afresh if registered again.
+pub async fn purge_indexer(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    // SQLite has no schemas, so each of the indexer's tables is dropped.
+    for table in indexer_tables(conn, namespace, identifier).await? {
+        execute_query(conn, format!("DROP TABLE IF EXISTS {table}")).await?;
+    }
+
+    let statements = [
+        "DELETE FROM graph_registry_columns WHERE type_id IN
+            (SELECT id FROM graph_registry_type_ids
+            WHERE schema_name = $1 AND schema_identifier = $2)",
+        "DELETE FROM graph_registry_type_ids
+            WHERE schema_name = $1 AND schema_identifier = $2",
+        "DELETE FROM graph_registry_root_columns WHERE root_id IN
+            (SELECT id FROM graph_registry_graph_root
+            WHERE schema_name = $1 AND schema_identifier = $2)",
+        "DELETE FROM graph_registry_graph_root
+            WHERE schema_name = $1 AND schema_identifier = $2",
+        "DELETE FROM index_block_heights WHERE namespace = $1 AND identifier = $2",
+        "DELETE FROM index_stats WHERE namespace = $1 AND identifier = $2",
+        "DELETE FROM index_failed_blocks WHERE namespace = $1 AND identifier = $2",
+        "DELETE FROM index_object_writes WHERE namespace = $1 AND identifier = $2",
+    ];
+    for statement in statements {
+        sqlx::query(statement)
+            .bind(namespace)
+            .bind(identifier)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn remove_asset_by_version(
+    conn: &mut PoolConnection<Sqlite>,
+    index_id: &i64,
+    version: &i32,
+    asset_type: IndexAssetType,
+) -> sqlx::Result<()> {
+    execute_query(
+        conn,
+        format!(
+            "DELETE FROM index_asset_registry_{0} WHERE index_id = {1} AND version = '{2}'",
+            asset_type.as_ref(),
+            index_id,
+            version
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn put_failed_block(
+    conn: &mut PoolConnection<Sqlite>,
+    block: &FailedBlock,
+) -> sqlx::Result<usize> {
+    let result = sqlx::query(
+        "INSERT INTO index_failed_blocks
+        (namespace, identifier, block_height, strategy, error, block_data)
+        VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(&block.namespace)
+    .bind(&block.identifier)
+    .bind(block.block_height as i64)
+    .bind(&block.strategy)
+    .bind(&block.error)
+    .bind(&block.block_data)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+pub async fn failed_blocks(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<FailedBlock>> {
+    Ok(sqlx::query(
+        "SELECT namespace, identifier, block_height, strategy, error, block_data
+        FROM index_failed_blocks
+        WHERE namespace = $1 AND identifier = $2
+        ORDER BY block_height ASC",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row: SqliteRow| {
+        let namespace: String = row.get(0);
+        let identifier: String = row.get(1);
+        let block_height: i64 = row.get(2);
+        let strategy: String = row.get(3);
+        let error: String = row.get(4);
+        let block_data: Option<Vec<u8>> = row.get(5);
+
+        FailedBlock {
+            namespace,
+            identifier,
+            block_height: block_height as u64,
+            strategy,
+            error,
+            block_data,
+        }
+    })
+    .collect::<Vec<FailedBlock>>())
+}
+
+/// Record the objects about to be written to `table_name` by the batch of
+/// blocks starting at `block_height`, along with their current rows, so that
+/// they can be reverted after a chain reorganization.
+///
+/// SQLite has no row-to-JSON conversion, so each row is recorded as the SQL
+/// tuple of its quoted values, which keeps the bytes of `BLOB` columns intact.
+pub async fn put_object_writes(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+    table_name: &str,
+    object_ids: Vec<i64>,
+) -> sqlx::Result<usize> {
+    let row = table_columns(conn, table_name)
+        .await?
+        .iter()
+        .map(|column| format!("quote(t.\"{column}\")"))
+        .collect::<Vec<String>>()
+        .join(" || ', ' || ");
+
+    let ids = object_ids
+        .iter()
+        .map(|id| format!("({id})"))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let result = sqlx::query(&format!(
+        "INSERT INTO index_object_writes (namespace, identifier, block_height, table_name, object_id, previous)
+        SELECT $1, $2, $3, $4, ids.column1, (SELECT '(' || {row} || ')' FROM {table_name} t WHERE t.id = ids.column1)
+        FROM (VALUES {ids}) AS ids"
+    ))
+    .bind(namespace)
+    .bind(identifier)
+    .bind(block_height as i64)
+    .bind(table_name)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+/// Columns of `table_name`, in the order its rows list them.
+async fn table_columns(
+    conn: &mut PoolConnection<Sqlite>,
+    table_name: &str,
+) -> sqlx::Result<Vec<String>> {
+    Ok(
+        sqlx::query("SELECT name FROM pragma_table_info($1) ORDER BY cid")
+            .bind(table_name.trim_matches('"'))
+            .fetch_all(conn)
+            .await?
+            .iter()
+            .map(|row| row.get(0))
+            .collect(),
+    )
+}
+
+/// Put the objects written at or above `block_height` back as they were
+/// before, deleting those that didn't exist yet, and delete their write
+/// records. Returns the number of objects reverted.
+pub async fn revert_to_block(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+) -> sqlx::Result<usize> {
+    let tables: Vec<String> = sqlx::query(
+        "SELECT DISTINCT table_name FROM index_object_writes
+        WHERE namespace = $1 AND identifier = $2 AND block_height >= $3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(block_height as i64)
+    .fetch_all(&mut *conn)
+    .await?
+    .iter()
+    .map(|row| row.get(0))
+    .collect();
+
+    let writes = "FROM index_object_writes
+        WHERE namespace = $1 AND identifier = $2 AND block_height >= $3 AND table_name = $4";
+
+    let mut count = 0;
+    for table_name in tables {
+        // An object is restored to its row before its first write in the range.
+        // SQLite takes the bare `previous` from the row that `MIN(id)` picks.
+        let previous: Vec<String> = sqlx::query(&format!(
+            "SELECT previous, MIN(id) {writes} GROUP BY object_id"
+        ))
+        .bind(namespace)
+        .bind(identifier)
+        .bind(block_height as i64)
+        .bind(&table_name)
+        .fetch_all(&mut *conn)
+        .await?
+        .iter()
+        .filter_map(|row| row.get::<Option<String>, _>(0))
+        .collect();
+
+        let result = sqlx::query(&format!(
+            "DELETE FROM {table_name} WHERE id IN (SELECT object_id {writes})"
+        ))
+        .bind(namespace)
+        .bind(identifier)
+        .bind(block_height as i64)
+        .bind(&table_name)
+        .execute(&mut *conn)
+        .await?;
+
+        count += result.rows_affected() as usize;
+
+        if !previous.is_empty() {
+            sqlx::query(&format!(
+                "INSERT INTO {table_name} VALUES {}",
+                previous.join(", ")
+            ))
+            .execute(&mut *conn)
+            .await?;
+        }
+    }
+
+    sqlx::query(
+        "DELETE FROM index_object_writes
+        WHERE namespace = $1 AND identifier = $2 AND block_height >= $3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(block_height as i64)
+    .execute(conn)
+    .await?;
+
+    Ok(count)
+}
+
+/// Delete the write records below `block_height`, whose objects can no longer
+/// be reverted.
+pub async fn prune_object_writes(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+) -> sqlx::Result<usize> {
+    let result = sqlx::query(
+        "DELETE FROM index_object_writes
+        WHERE namespace = $1 AND identifier = $2 AND block_height < $3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(block_height as i64)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+/// The height at which the writes of the batch spanning `block_height` are
+/// recorded, i.e. the highest recorded write height at or below it.
+pub async fn object_writes_batch_start(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+) -> sqlx::Result<Option<u64>> {
+    let row = sqlx::query(
+        "SELECT MAX(block_height) FROM index_object_writes
+        WHERE namespace = $1 AND identifier = $2 AND block_height <= $3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(block_height as i64)
+    .fetch_one(conn)
+    .await?;
+
+    let start: Option<i64> = row.get(0);
+
+    Ok(start.map(|start| start as u64))
+}
+
+pub async fn create_nonce(conn: &mut PoolConnection<Sqlite>) -> sqlx::Result<Nonce> {
+    let uid = uuid::Uuid::new_v4().as_simple().to_string();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let expiry = now + NONCE_EXPIRY;
+
+    let row = sqlx::QueryBuilder::new(&format!(
+        "INSERT INTO nonce (uid, expiry) VALUES ('{uid}', {expiry}) RETURNING *"
+    ))
+    .build()
+    .fetch_one(conn)
+    .await?;
+
+    let uid: String = row.get(1);
+    let expiry: i64 = row.get(2);
+
+    Ok(Nonce { uid, expiry })
+}
+
+pub async fn delete_nonce(
+    conn: &mut PoolConnection<Sqlite>,
+    nonce: &Nonce,
+) -> sqlx::Result<()> {
+    let _ = sqlx::query(&format!("DELETE FROM nonce WHERE uid = '{}'", nonce.uid))
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn get_nonce(
+    conn: &mut PoolConnection<Sqlite>,
+    uid: &str,
+) -> sqlx::Result<Nonce> {
+    let row = sqlx::query(&format!("SELECT * FROM nonce WHERE uid = '{uid}'"))
+        .fetch_one(conn)
+        .await?;
+
+    let uid: String = row.get(1);
+    let expiry: i64 = row.get(2);
+
+    Ok(Nonce { uid, expiry })
+}
@@ -1,10 +1,18 @@
 #![deny(unused_crate_dependencies)]
 
 pub use fuel_indexer_database_types::DbType;
-use fuel_indexer_lib::utils::{attempt_database_connection, ServiceStatus};
+use fuel_indexer_lib::{
+    config::IndexerConfig,
+    defaults,
+    utils::{attempt_database_connection, ServiceStatus},
+};
 use fuel_indexer_postgres as postgres;
-use sqlx::{pool::PoolConnection, postgres::PgConnectOptions, Error as SqlxError};
-use std::{cmp::Ordering, str::FromStr};
+use fuel_indexer_sqlite as sqlite;
+use sqlx::{
+    pool::PoolConnection, postgres::PgConnectOptions, sqlite::SqliteConnectOptions,
+    Error as SqlxError,
+};
+use std::{cmp::Ordering, str::FromStr, time::Duration};
 use thiserror::Error;
 
 pub mod queries;
@@ -20,32 +28,79 @@ pub enum IndexerDatabaseError {
     BackendNotSupported(String),
     #[error("No transaction is open.")]
     NoTransactionError,
+    #[error("Query exceeded the timeout of {0:?}.")]
+    QueryTimeout(std::time::Duration),
+    #[error("Timed out waiting for a database connection.")]
+    AcquireTimeout,
     #[error("Error from sqlx: {0:#?}")]
     SqlxError(#[from] SqlxError),
     #[error("Unknown error")]
     Unknown,
 }
 
+/// Sizing of a connection pool, and how long to wait for one of its connections.
+#[derive(Clone, Copy, Debug)]
+pub struct ConnectionPoolConfig {
+    pub max_connections: u32,
+    pub min_idle: u32,
+    pub acquire_timeout: Duration,
+}
+
+impl Default for ConnectionPoolConfig {
+    fn default() -> Self {
+        ConnectionPoolConfig {
+            max_connections: defaults::MAX_DB_CONNECTIONS,
+            min_idle: defaults::MIN_IDLE_DB_CONNECTIONS,
+            acquire_timeout: Duration::from_secs(defaults::DB_ACQUIRE_TIMEOUT_SECS),
+        }
+    }
+}
+
+impl From<&IndexerConfig> for ConnectionPoolConfig {
+    fn from(config: &IndexerConfig) -> Self {
+        ConnectionPoolConfig {
+            max_connections: config.max_db_connections,
+            min_idle: config.min_idle_db_connections,
+            acquire_timeout: Duration::from_secs(config.db_acquire_timeout_secs),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum IndexerConnection {
     Postgres(Box<PoolConnection<sqlx::Postgres>>),
+    Sqlite(Box<PoolConnection<sqlx::Sqlite>>),
 }
 
 #[derive(Clone, Debug)]
 pub enum IndexerConnectionPool {
     Postgres(sqlx::Pool<sqlx::Postgres>),
+    Sqlite(sqlx::Pool<sqlx::Sqlite>),
 }
 
 impl IndexerConnectionPool {
     pub fn database_type(&self) -> DbType {
         match self {
             IndexerConnectionPool::Postgres(_) => DbType::Postgres,
+            IndexerConnectionPool::Sqlite(_) => DbType::Sqlite,
         }
     }
 
     pub async fn connect(
         database_url: &str,
     ) -> Result<IndexerConnectionPool, IndexerDatabaseError> {
+        Self::connect_with_config(database_url, ConnectionPoolConfig::default()).await
+    }
+
+    /// Connect a pool sized by `config`.
+    pub async fn connect_with_config(
+        database_url: &str,
+        config: ConnectionPoolConfig,
+    ) -> Result<IndexerConnectionPool, IndexerDatabaseError> {
+        // A pool without connections would time out every acquire.
+        let max_connections = config.max_connections.max(1);
+        let min_idle = config.min_idle.min(max_connections);
+
         let url = url::Url::parse(database_url);
         if url.is_err() {
             return Err(IndexerDatabaseError::InvalidConnectionString(
@@ -56,16 +111,38 @@ impl IndexerConnectionPool {
         match url.scheme() {
             "postgres" => {
                 let pool = attempt_database_connection(|| {
-                    sqlx::postgres::PgPoolOptions::new().connect_with(
-                        PgConnectOptions::from_str(database_url).unwrap_or_else(|e| {
-                            panic!("Could not derive PgConnectOptions: {e}",)
-                        }),
-                    )
+                    sqlx::postgres::PgPoolOptions::new()
+                        .max_connections(max_connections)
+                        .min_connections(min_idle)
+                        .acquire_timeout(config.acquire_timeout)
+                        .connect_with(
+                            PgConnectOptions::from_str(database_url).unwrap_or_else(
+                                |e| panic!("Could not derive PgConnectOptions: {e}",),
+                            ),
+                        )
                 })
                 .await;
 
                 Ok(IndexerConnectionPool::Postgres(pool))
             }
+            "sqlite" => {
+                let pool = attempt_database_connection(|| {
+                    sqlx::sqlite::SqlitePoolOptions::new()
+                        .max_connections(max_connections)
+                        .min_connections(min_idle)
+                        .acquire_timeout(config.acquire_timeout)
+                        .connect_with(
+                            SqliteConnectOptions::from_str(database_url)
+                                .unwrap_or_else(|e| {
+                                    panic!("Could not derive SqliteConnectOptions: {e}",)
+                                })
+                                .create_if_missing(true),
+                        )
+                })
+                .await;
+
+                Ok(IndexerConnectionPool::Sqlite(pool))
+            }
             err => Err(IndexerDatabaseError::BackendNotSupported(err.into())),
         }
     }
@@ -83,14 +160,35 @@ impl IndexerConnectionPool {
                     _ => Ok(ServiceStatus::NotOk),
                 }
             }
-        }
-    }
+            IndexerConnectionPool::Sqlite(p) => {
+                let mut conn = p.acquire().await?;
 
-    pub async fn acquire(&self) -> sqlx::Result<IndexerConnection> {
-        match self {
-            IndexerConnectionPool::Postgres(p) => {
-                Ok(IndexerConnection::Postgres(Box::new(p.acquire().await?)))
+                // SQLite reports no affected rows for a SELECT, so a successful
+                // round trip is the only signal available.
+                sqlite::execute_query(&mut conn, "SELECT true;".to_string()).await?;
+
+                Ok(ServiceStatus::OK)
             }
         }
     }
+
+    /// Acquire a connection, failing with `AcquireTimeout` if none frees up
+    /// within the pool's acquire timeout.
+    pub async fn acquire(&self) -> Result<IndexerConnection, IndexerDatabaseError> {
+        let conn = match self {
+            IndexerConnectionPool::Postgres(p) => p
+                .acquire()
+                .await
+                .map(|c| IndexerConnection::Postgres(Box::new(c))),
+            IndexerConnectionPool::Sqlite(p) => p
+                .acquire()
+                .await
+                .map(|c| IndexerConnection::Sqlite(Box::new(c))),
+        };
+
+        conn.map_err(|e| match e {
+            SqlxError::PoolTimedOut => IndexerDatabaseError::AcquireTimeout,
+            e => IndexerDatabaseError::SqlxError(e),
+        })
+    }
 }
@@ -1,6 +1,8 @@
-use crate::{types::*, IndexerConnection};
+use crate::{types::*, IndexerConnection, IndexerDatabaseError};
 use fuel_indexer_postgres as postgres;
+use fuel_indexer_sqlite as sqlite;
 use sqlx::types::JsonValue;
+use std::{future::Future, time::Duration};
 
 pub async fn graph_root_latest(
     conn: &mut IndexerConnection,
@@ -11,6 +13,9 @@ pub async fn graph_root_latest(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::graph_root_latest(c, namespace, identifier).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::graph_root_latest(c, namespace, identifier).await
+        }
     }
 }
 
@@ -20,6 +25,7 @@ pub async fn new_graph_root(
 ) -> sqlx::Result<usize> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::new_graph_root(c, root).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::new_graph_root(c, root).await,
     }
 }
 
@@ -33,6 +39,9 @@ pub async fn type_id_list_by_name(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::type_id_list_by_name(c, name, version, identifier).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::type_id_list_by_name(c, name, version, identifier).await
+        }
     }
 }
 
@@ -45,6 +54,9 @@ pub async fn type_id_latest(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::type_id_latest(c, schema_name, identifier).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::type_id_latest(c, schema_name, identifier).await
+        }
     }
 }
 
@@ -56,6 +68,7 @@ pub async fn type_id_insert(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::type_id_insert(c, type_ids).await
         }
+        IndexerConnection::Sqlite(ref mut c) => sqlite::type_id_insert(c, type_ids).await,
     }
 }
 
@@ -69,6 +82,9 @@ pub async fn schema_exists(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::schema_exists(c, namespace, identifier, version).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::schema_exists(c, namespace, identifier, version).await
+        }
     }
 }
 
@@ -80,6 +96,7 @@ pub async fn new_column_insert(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::new_column_insert(c, cols).await
         }
+        IndexerConnection::Sqlite(ref mut c) => sqlite::new_column_insert(c, cols).await,
     }
 }
 
@@ -91,6 +108,9 @@ pub async fn list_column_by_id(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::list_column_by_id(c, col_id).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::list_column_by_id(c, col_id).await
+        }
     }
 }
 
@@ -104,6 +124,9 @@ pub async fn columns_get_schema(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::columns_get_schema(c, name, identifier, version).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::columns_get_schema(c, name, identifier, version).await
+        }
     }
 }
 
@@ -116,24 +139,205 @@ pub async fn put_object(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::put_object(c, query, bytes).await
         }
+        IndexerConnection::Sqlite(ref mut c) => sqlite::put_object(c, query, bytes).await,
+    }
+}
+
+/// Run a multi-row upsert, binding `params` to its parameters in order.
+pub async fn put_objects(
+    conn: &mut IndexerConnection,
+    query: String,
+    params: Vec<QueryParam>,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_objects(c, query, params).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::put_objects(c, query, params).await
+        }
     }
 }
 
+/// Fetch the object selected by `query`, which refers to its ID as `$1`.
 pub async fn get_object(
     conn: &mut IndexerConnection,
     query: String,
+    object_id: i64,
 ) -> sqlx::Result<Vec<u8>> {
     match conn {
-        IndexerConnection::Postgres(ref mut c) => postgres::get_object(c, query).await,
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::get_object(c, query, object_id).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::get_object(c, query, object_id).await
+        }
     }
 }
 
-pub async fn run_query(
+/// Mark an object as deleted, recording when it was removed rather than
+/// deleting its row.
+///
+/// If `block_height` is given, it is recorded as the block that the object
+/// was last written at.
+pub async fn soft_delete_object(
+    conn: &mut IndexerConnection,
+    table_name: &str,
+    object_id: i64,
+    block_height: Option<i64>,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::soft_delete_object(c, table_name, object_id, block_height).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::soft_delete_object(c, table_name, object_id, block_height).await
+        }
+    }
+}
+
+/// Fetch the first object matched by `query`, binding `params` to its
+/// parameters in order.
+pub async fn get_object_by(
     conn: &mut IndexerConnection,
     query: String,
-) -> sqlx::Result<JsonValue> {
+    params: Vec<QueryParam>,
+) -> sqlx::Result<Option<Vec<u8>>> {
     match conn {
-        IndexerConnection::Postgres(ref mut c) => postgres::run_query(c, query).await,
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::get_object_by(c, query, params).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::get_object_by(c, query, params).await
+        }
+    }
+}
+
+pub async fn get_object_ids(
+    conn: &mut IndexerConnection,
+    query: String,
+    params: Vec<QueryParam>,
+) -> sqlx::Result<Vec<i64>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::get_object_ids(c, query, params).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::get_object_ids(c, query, params).await
+        }
+    }
+}
+
+/// Run a user query, cancelling it once it runs longer than `timeout`. A zero
+/// `timeout` disables the limit.
+pub async fn run_query(
+    conn: &mut IndexerConnection,
+    query: String,
+    params: Vec<QueryParam>,
+    timeout: Duration,
+) -> Result<JsonValue, IndexerDatabaseError> {
+    let result = match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::run_query(c, query, params, timeout).await
+        }
+        // SQLite has no statement timeout, so the query is dropped instead,
+        // which stops the worker from stepping it any further.
+        IndexerConnection::Sqlite(ref mut c) if !timeout.is_zero() => {
+            match tokio::time::timeout(timeout, sqlite::run_query(c, query, params)).await
+            {
+                Ok(result) => result,
+                Err(_) => return Err(IndexerDatabaseError::QueryTimeout(timeout)),
+            }
+        }
+        IndexerConnection::Sqlite(ref mut c) => sqlite::run_query(c, query, params).await,
+    };
+
+    result.map_err(|e| query_error(e, timeout))
+}
+
+/// Run a user query like `run_query`, but pass each row to `on_row` as it is
+/// read, stopping early once `on_row` returns `false`.
+///
+/// Postgres reads the rows through a cursor, `batch_size` at a time, so they are
+/// never all held in memory. SQLite has no cursors that outlive a statement, so
+/// its rows are read in full first.
+pub async fn stream_query<F, Fut>(
+    conn: &mut IndexerConnection,
+    query: String,
+    params: Vec<QueryParam>,
+    timeout: Duration,
+    batch_size: usize,
+    mut on_row: F,
+) -> Result<(), IndexerDatabaseError>
+where
+    F: FnMut(JsonValue) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    if let IndexerConnection::Postgres(ref mut c) = conn {
+        return postgres::stream_query(c, query, params, timeout, batch_size, on_row)
+            .await
+            .map_err(|e| query_error(e, timeout));
+    }
+
+    let rows = match run_query(conn, query, params, timeout).await? {
+        JsonValue::Array(rows) => rows,
+        JsonValue::Null => Vec::new(),
+        row => vec![row],
+    };
+    for row in rows {
+        if !on_row(row).await {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Run a raw `SELECT` against the tables of the indexer `namespace.identifier`,
+/// returning each row as a JSON object of its columns, and cancelling it once
+/// it runs longer than `timeout`. A zero `timeout` disables the limit.
+///
+/// On Postgres the query runs in a read-only transaction, so it can't write
+/// even through a function with side effects.
+pub async fn run_read_only_query(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    query: String,
+    timeout: Duration,
+) -> Result<JsonValue, IndexerDatabaseError> {
+    let result = match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            let schema = format!("{namespace}_{identifier}");
+            postgres::run_read_only_query(c, &schema, query, timeout).await
+        }
+        IndexerConnection::Sqlite(ref mut c) if !timeout.is_zero() => {
+            match tokio::time::timeout(timeout, sqlite::run_read_only_query(c, query))
+                .await
+            {
+                Ok(result) => result,
+                Err(_) => return Err(IndexerDatabaseError::QueryTimeout(timeout)),
+            }
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::run_read_only_query(c, query).await
+        }
+    };
+
+    result.map_err(|e| query_error(e, timeout))
+}
+
+/// The error of a user query, telling a query cancelled by the timeout apart.
+fn query_error(e: sqlx::Error, timeout: Duration) -> IndexerDatabaseError {
+    // 57014 is Postgres' query_canceled, raised when statement_timeout hits.
+    let canceled = e
+        .as_database_error()
+        .and_then(|e| e.code())
+        .map_or(false, |code| code == "57014");
+    if canceled {
+        IndexerDatabaseError::QueryTimeout(timeout)
+    } else {
+        e.into()
     }
 }
 
@@ -143,6 +347,7 @@ pub async fn execute_query(
 ) -> sqlx::Result<usize> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::execute_query(c, query).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::execute_query(c, query).await,
     }
 }
 
@@ -154,6 +359,9 @@ pub async fn root_columns_list_by_id(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::root_columns_list_by_id(c, root_id).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::root_columns_list_by_id(c, root_id).await
+        }
     }
 }
 
@@ -165,6 +373,22 @@ pub async fn new_root_columns(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::new_root_columns(c, cols).await
         }
+        IndexerConnection::Sqlite(ref mut c) => sqlite::new_root_columns(c, cols).await,
+    }
+}
+
+pub async fn indexed_columns(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<IndexedColumn>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::indexed_columns(c, namespace, identifier).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::indexed_columns(c, namespace, identifier).await
+        }
     }
 }
 
@@ -177,6 +401,9 @@ pub async fn index_is_registered(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::index_is_registered(c, namespace, identifier).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::index_is_registered(c, namespace, identifier).await
+        }
     }
 }
 
@@ -190,6 +417,9 @@ pub async fn register_index(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::register_index(c, namespace, identifier, pubkey).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::register_index(c, namespace, identifier, pubkey).await
+        }
     }
 }
 
@@ -198,6 +428,7 @@ pub async fn registered_indices(
 ) -> sqlx::Result<Vec<RegisteredIndex>> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::registered_indices(c).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::registered_indices(c).await,
     }
 }
 
@@ -210,9 +441,17 @@ pub async fn index_asset_version(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::index_asset_version(c, index_id, asset_type).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::index_asset_version(c, index_id, asset_type).await
+        }
     }
 }
 
+/// Register a new version of an indexer's asset, registering the indexer
+/// itself if need be.
+///
+/// Returns the registered asset, and whether it was unchanged, in which case
+/// `bytes` matched the latest version and nothing was written.
 pub async fn register_index_asset(
     conn: &mut IndexerConnection,
     namespace: &str,
@@ -220,7 +459,7 @@ pub async fn register_index_asset(
     bytes: Vec<u8>,
     asset_type: IndexAssetType,
     pubkey: Option<&str>,
-) -> sqlx::Result<IndexAsset> {
+) -> sqlx::Result<(IndexAsset, bool)> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => {
             postgres::register_index_asset(
@@ -228,6 +467,12 @@ pub async fn register_index_asset(
             )
             .await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::register_index_asset(
+                c, namespace, identifier, bytes, asset_type, pubkey,
+            )
+            .await
+        }
     }
 }
 
@@ -240,6 +485,9 @@ pub async fn latest_asset_for_index(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::latest_asset_for_index(c, index_id, asset_type).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::latest_asset_for_index(c, index_id, asset_type).await
+        }
     }
 }
 
@@ -251,18 +499,98 @@ pub async fn latest_assets_for_index(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::latest_assets_for_index(c, index_id).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::latest_assets_for_index(c, index_id).await
+        }
+    }
+}
+
+/// All versions of an asset registered to an indexer, oldest first.
+///
+/// Asset bytes are not loaded.
+pub async fn assets_for_index(
+    conn: &mut IndexerConnection,
+    index_id: &i64,
+    asset_type: IndexAssetType,
+) -> sqlx::Result<Vec<IndexAsset>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::assets_for_index(c, index_id, asset_type).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::assets_for_index(c, index_id, asset_type).await
+        }
     }
 }
 
+/// The height of the last block committed by an indexer, if any.
 pub async fn last_block_height_for_indexer(
     conn: &mut IndexerConnection,
     namespace: &str,
     identifier: &str,
-) -> sqlx::Result<u64> {
+) -> sqlx::Result<Option<u64>> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => {
             postgres::last_block_height_for_indexer(c, namespace, identifier).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::last_block_height_for_indexer(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Record the height of the last block committed by an indexer. Call this in
+/// the transaction that commits the block's objects.
+pub async fn set_last_block_height(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::set_last_block_height(c, namespace, identifier, block_height).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::set_last_block_height(c, namespace, identifier, block_height).await
+        }
+    }
+}
+
+/// Add to the number of blocks that an indexer processed and objects that it
+/// wrote. Call this in the transaction that commits the blocks' objects.
+pub async fn record_indexer_stats(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    blocks: u64,
+    objects: u64,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::record_indexer_stats(c, namespace, identifier, blocks, objects)
+                .await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::record_indexer_stats(c, namespace, identifier, blocks, objects).await
+        }
+    }
+}
+
+/// The stats of an indexer, if it was registered or committed a block since
+/// stats were kept.
+pub async fn indexer_stats(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<IndexerStats>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::indexer_stats(c, namespace, identifier).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::indexer_stats(c, namespace, identifier).await
+        }
     }
 }
 
@@ -276,6 +604,9 @@ pub async fn asset_already_exists(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::asset_already_exists(c, asset_type, bytes, index_id).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::asset_already_exists(c, asset_type, bytes, index_id).await
+        }
     }
 }
 
@@ -288,6 +619,9 @@ pub async fn index_id_for(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::index_id_for(c, namespace, identifier).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::index_id_for(c, namespace, identifier).await
+        }
     }
 }
 
@@ -302,30 +636,38 @@ pub async fn penultimate_asset_for_index(
             postgres::penultimate_asset_for_index(c, namespace, identifier, asset_type)
                 .await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::penultimate_asset_for_index(c, namespace, identifier, asset_type)
+                .await
+        }
     }
 }
 
 pub async fn start_transaction(conn: &mut IndexerConnection) -> sqlx::Result<usize> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::start_transaction(c).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::start_transaction(c).await,
     }
 }
 
 pub async fn commit_transaction(conn: &mut IndexerConnection) -> sqlx::Result<usize> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::commit_transaction(c).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::commit_transaction(c).await,
     }
 }
 
 pub async fn revert_transaction(conn: &mut IndexerConnection) -> sqlx::Result<usize> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::revert_transaction(c).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::revert_transaction(c).await,
     }
 }
 
 pub async fn run_migration(conn: &mut IndexerConnection) -> sqlx::Result<()> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::run_migration(c).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::run_migration(c).await,
     }
 }
 
@@ -338,9 +680,81 @@ pub async fn remove_indexer(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::remove_indexer(c, namespace, identifier).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::remove_indexer(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Tables created for an indexer, across every version of its schema.
+pub async fn indexer_tables(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<String>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::indexer_tables(c, namespace, identifier).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::indexer_tables(c, namespace, identifier).await
+        }
     }
 }
 
+/// Drop an indexer's tables, along with the metadata of its schema and the
+/// record of its progress.
+pub async fn purge_indexer(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::purge_indexer(c, namespace, identifier).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::purge_indexer(c, namespace, identifier).await
+        }
+    }
+}
+
+/// A page of the rows of `table` with IDs above `after`, ordered by ID, each
+/// with its ID and as the text of a JSON object. Only supported on Postgres.
+pub async fn table_rows_as_json(
+    conn: &mut IndexerConnection,
+    table: &str,
+    after: Option<i64>,
+    limit: i64,
+) -> sqlx::Result<Vec<(i64, String)>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::table_rows_as_json(c, table, after, limit).await
+        }
+        IndexerConnection::Sqlite(_) => Err(json_rows_not_supported()),
+    }
+}
+
+/// Insert rows read with `table_rows_as_json` into `table`. Only supported on
+/// Postgres.
+pub async fn insert_json_rows(
+    conn: &mut IndexerConnection,
+    table: &str,
+    rows: &[String],
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::insert_json_rows(c, table, rows).await
+        }
+        IndexerConnection::Sqlite(_) => Err(json_rows_not_supported()),
+    }
+}
+
+// SQLite columns are loosely typed, so a row can't be rebuilt from its JSON.
+fn json_rows_not_supported() -> sqlx::Error {
+    sqlx::Error::Protocol("Rows as JSON are not supported on SQLite.".to_string())
+}
+
 pub async fn remove_asset_by_version(
     conn: &mut IndexerConnection,
     index_id: &i64,
@@ -351,18 +765,140 @@ pub async fn remove_asset_by_version(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::remove_asset_by_version(c, index_id, version, asset_type).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::remove_asset_by_version(c, index_id, version, asset_type).await
+        }
+    }
+}
+
+pub async fn put_failed_block(
+    conn: &mut IndexerConnection,
+    block: &FailedBlock,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_failed_block(c, block).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => sqlite::put_failed_block(c, block).await,
+    }
+}
+
+pub async fn failed_blocks(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<FailedBlock>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::failed_blocks(c, namespace, identifier).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::failed_blocks(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Record the objects about to be written to `table_name` by the batch of
+/// blocks starting at `block_height`.
+pub async fn put_object_writes(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+    table_name: &str,
+    object_ids: Vec<i64>,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_object_writes(
+                c,
+                namespace,
+                identifier,
+                block_height,
+                table_name,
+                object_ids,
+            )
+            .await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::put_object_writes(
+                c,
+                namespace,
+                identifier,
+                block_height,
+                table_name,
+                object_ids,
+            )
+            .await
+        }
+    }
+}
+
+/// Revert the objects an indexer wrote at or above `block_height`.
+pub async fn revert_to_block(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::revert_to_block(c, namespace, identifier, block_height).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::revert_to_block(c, namespace, identifier, block_height).await
+        }
+    }
+}
+
+/// Delete an indexer's write records below `block_height`.
+pub async fn prune_object_writes(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::prune_object_writes(c, namespace, identifier, block_height).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::prune_object_writes(c, namespace, identifier, block_height).await
+        }
+    }
+}
+
+/// The start of the batch of blocks that wrote an indexer's objects at
+/// `block_height`, if any writes are recorded at or below it.
+pub async fn object_writes_batch_start(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+) -> sqlx::Result<Option<u64>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::object_writes_batch_start(c, namespace, identifier, block_height)
+                .await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::object_writes_batch_start(c, namespace, identifier, block_height)
+                .await
+        }
     }
 }
 
 pub async fn create_nonce(conn: &mut IndexerConnection) -> sqlx::Result<Nonce> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::create_nonce(c).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::create_nonce(c).await,
     }
 }
 
 pub async fn get_nonce(conn: &mut IndexerConnection, uid: &str) -> sqlx::Result<Nonce> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::get_nonce(c, uid).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::get_nonce(c, uid).await,
     }
 }
 
@@ -372,5 +908,6 @@ pub async fn delete_nonce(
 ) -> sqlx::Result<()> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::delete_nonce(c, nonce).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::delete_nonce(c, nonce).await,
     }
 }
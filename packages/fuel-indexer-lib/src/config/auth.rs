@@ -4,6 +4,7 @@ use crate::{
     utils::{is_opt_env_var, trim_opt_env_key},
 };
 pub use clap::Parser;
+use fuel_indexer_types::Address;
 use serde::{Deserialize, Serialize};
 use strum::{AsRefStr, EnumString};
 
@@ -21,6 +22,12 @@ pub struct AuthenticationConfig {
     pub jwt_secret: Option<String>,
     pub jwt_issuer: Option<String>,
     pub jwt_expiry: Option<usize>,
+    #[serde(default)]
+    pub api_key_hash: Option<String>,
+    #[serde(default)]
+    pub admin_pubkeys: Vec<String>,
+    #[serde(default)]
+    pub signature_encoding: MessageEncoding,
 }
 
 impl Default for AuthenticationConfig {
@@ -31,6 +38,9 @@ impl Default for AuthenticationConfig {
             jwt_secret: None,
             jwt_issuer: None,
             jwt_expiry: None,
+            api_key_hash: None,
+            admin_pubkeys: Vec::new(),
+            signature_encoding: MessageEncoding::default(),
         }
     }
 }
@@ -83,6 +93,63 @@ impl Env for AuthenticationConfig {
 #[serde(rename_all = "snake_case")]
 pub enum AuthenticationStrategy {
     JWT,
+    ApiKey,
+}
+
+/// How a nonce is encoded into the message whose signature is checked.
+#[derive(
+    Serialize,
+    Deserialize,
+    EnumString,
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum MessageEncoding {
+    /// The nonce is signed as it is, as `forc wallet sign` does.
+    #[default]
+    Raw,
+    /// The nonce is prefixed with `PERSONAL_MESSAGE_PREFIX` and its length in
+    /// bytes, as wallets do when signing a personal message, in the manner of
+    /// EIP-191.
+    Personal,
+}
+
+/// Prefix of a personal message, which keeps a signed message from being
+/// mistaken for a signed transaction.
+pub const PERSONAL_MESSAGE_PREFIX: &str = "\x19Fuel Signed Message:\n";
+
+impl MessageEncoding {
+    /// The bytes that are hashed and signed to sign `message`.
+    pub fn encode(&self, message: &str) -> Vec<u8> {
+        match self {
+            Self::Raw => message.as_bytes().to_vec(),
+            Self::Personal => {
+                format!("{PERSONAL_MESSAGE_PREFIX}{}{message}", message.len())
+                    .into_bytes()
+            }
+        }
+    }
+}
+
+/// What the holder of a token may do, in increasing order of privilege.
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Ord,
+)]
+pub enum Role {
+    /// May query indexers.
+    #[default]
+    ReadOnly,
+    /// May also upload and revert indexers.
+    Deployer,
+    /// May also stop indexers.
+    Admin,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -98,6 +165,15 @@ pub struct Claims {
 
     /// Expiration time (as UTC timestamp).
     pub exp: usize,
+
+    /// Role granted to the subject. Tokens issued without one are read-only.
+    #[serde(default)]
+    pub role: Role,
+
+    /// Address of the subject's public key. Only tokens issued for a signed
+    /// nonce carry one.
+    #[serde(default)]
+    pub address: Option<Address>,
 }
 
 impl Default for Claims {
@@ -107,6 +183,9 @@ impl Default for Claims {
             iss: "".to_string(),
             iat: 0,
             exp: 0,
+            // Only used when authentication is disabled, where anything goes.
+            role: Role::Admin,
+            address: None,
         }
     }
 }
@@ -118,10 +197,67 @@ impl Claims {
             iss: "".to_string(),
             iat: 1,
             exp: 1,
+            role: Role::ReadOnly,
+            address: None,
         }
     }
 
     pub fn is_unauthenticated(&self) -> bool {
         self.exp == 1 && self.iat == 1
     }
+
+    /// Whether the subject holds `role` or a more privileged one.
+    pub fn has_role(&self, role: Role) -> bool {
+        !self.is_unauthenticated() && self.role >= role
+    }
+
+    /// Whether the subject may modify an indexer that was deployed by
+    /// `deployer`. Admins may modify any indexer, and deployers only their
+    /// own. An indexer whose deployer is not known, e.g. one started from a
+    /// manifest or uploaded with an API key, may only be modified by admins.
+    pub fn may_modify(&self, deployer: Option<&Address>) -> bool {
+        self.has_role(Role::Admin)
+            || (self.has_role(Role::Deployer)
+                && deployer
+                    .map_or(false, |deployer| self.address.as_ref() == Some(deployer)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_only_admins_may_modify_indexers_of_unknown_deployers() {
+        let deployer = Address::from([1u8; 32]);
+        let claims = |role, address| Claims {
+            sub: "signer".to_string(),
+            iat: 2,
+            exp: 3,
+            role,
+            address,
+            ..Claims::default()
+        };
+
+        assert!(claims(Role::Deployer, Some(deployer)).may_modify(Some(&deployer)));
+        assert!(!claims(Role::Deployer, Some(Address::from([2u8; 32])))
+            .may_modify(Some(&deployer)));
+        assert!(!claims(Role::Deployer, Some(deployer)).may_modify(None));
+        assert!(claims(Role::Admin, None).may_modify(None));
+    }
+
+    #[test]
+    fn test_message_encoding_prefixes_personal_messages() {
+        assert_eq!(MessageEncoding::Raw.encode("nonce"), b"nonce".to_vec());
+        assert_eq!(
+            MessageEncoding::Personal.encode("nonce"),
+            b"\x19Fuel Signed Message:\n5nonce".to_vec()
+        );
+
+        assert_eq!(
+            MessageEncoding::from_str("personal").unwrap(),
+            MessageEncoding::Personal
+        );
+    }
 }
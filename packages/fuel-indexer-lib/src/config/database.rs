@@ -1,11 +1,11 @@
 use std::str::FromStr;
 
 use crate::{
-    config::{Env, IndexerConfigResult},
+    config::{env_or_default, Env, EnvVar, IndexerConfigResult},
     defaults,
     utils::{is_opt_env_var, trim_opt_env_key},
 };
-pub use clap::Parser;
+pub use clap::{Args, Parser};
 use http::Uri;
 use serde::Deserialize;
 use url::{ParseError, Url};
@@ -97,6 +97,58 @@ impl std::fmt::Debug for DatabaseConfig {
     }
 }
 
+/// Postgres connection options, for commands that only need the database.
+#[derive(Debug, Args, Clone)]
+pub struct DatabaseArgs {
+    /// Postgres username.
+    #[clap(long, help = "Postgres username.")]
+    pub postgres_user: Option<String>,
+
+    /// Postgres database.
+    #[clap(long, help = "Postgres database.")]
+    pub postgres_database: Option<String>,
+
+    /// Postgres password.
+    #[clap(long, help = "Postgres password.")]
+    pub postgres_password: Option<String>,
+
+    /// Postgres host.
+    #[clap(long, help = "Postgres host.")]
+    pub postgres_host: Option<String>,
+
+    /// Postgres port.
+    #[clap(long, help = "Postgres port.")]
+    pub postgres_port: Option<String>,
+}
+
+impl From<DatabaseArgs> for DatabaseConfig {
+    fn from(args: DatabaseArgs) -> Self {
+        DatabaseConfig::Postgres {
+            user: args.postgres_user.unwrap_or_else(|| {
+                env_or_default(EnvVar::PostgresUser, defaults::POSTGRES_USER.to_string())
+            }),
+            password: args.postgres_password.unwrap_or_else(|| {
+                env_or_default(
+                    EnvVar::PostgresPassword,
+                    defaults::POSTGRES_PASSWORD.to_string(),
+                )
+            }),
+            host: args.postgres_host.unwrap_or_else(|| {
+                env_or_default(EnvVar::PostgresHost, defaults::POSTGRES_HOST.to_string())
+            }),
+            port: args.postgres_port.unwrap_or_else(|| {
+                env_or_default(EnvVar::PostgresPort, defaults::POSTGRES_PORT.to_string())
+            }),
+            database: args.postgres_database.unwrap_or_else(|| {
+                env_or_default(
+                    EnvVar::PostgresDatabase,
+                    defaults::POSTGRES_DATABASE.to_string(),
+                )
+            }),
+        }
+    }
+}
+
 impl Default for DatabaseConfig {
     fn default() -> Self {
         DatabaseConfig::Postgres {
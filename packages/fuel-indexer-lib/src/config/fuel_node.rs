@@ -14,15 +14,47 @@ pub struct FuelNodeConfig {
     pub host: String,
     #[serde(default)]
     pub port: String,
+    /// `host:port` of nodes to fail over to, in order, when this one can't be
+    /// reached.
+    #[serde(default)]
+    pub fallbacks: Vec<String>,
 }
 
 impl FuelNodeConfig {
+    /// This node, followed by its fallbacks.
+    pub fn nodes(&self) -> Vec<FuelNodeConfig> {
+        let fallbacks = self.fallbacks.iter().map(|addr| {
+            let (host, port) = addr.rsplit_once(':').unwrap_or_else(|| {
+                panic!("Fallback Fuel node '{addr}' is not of the form host:port.")
+            });
+            FuelNodeConfig {
+                host: host.to_string(),
+                port: port.to_string(),
+                fallbacks: Vec::new(),
+            }
+        });
+
+        std::iter::once(FuelNodeConfig {
+            fallbacks: Vec::new(),
+            ..self.clone()
+        })
+        .chain(fallbacks)
+        .collect()
+    }
+
     pub fn health_check_uri(self) -> Uri {
         let base = Uri::from(self);
         format!("{}{}", base, "health")
             .parse()
             .unwrap_or_else(|e| panic!("Failed to build health Uri: {e}"))
     }
+
+    pub fn graphql_uri(self) -> Uri {
+        let base = Uri::from(self);
+        format!("{}{}", base, "graphql")
+            .parse()
+            .unwrap_or_else(|e| panic!("Failed to build GraphQL Uri: {e}"))
+    }
 }
 
 impl Env for FuelNodeConfig {
@@ -53,6 +85,7 @@ impl Default for FuelNodeConfig {
         Self {
             host: defaults::FUEL_NODE_HOST.into(),
             port: defaults::FUEL_NODE_PORT.into(),
+            fallbacks: Vec::new(),
         }
     }
 }
@@ -62,6 +95,10 @@ impl From<SocketAddr> for FuelNodeConfig {
         let parts: Vec<String> = s.to_string().split(':').map(|x| x.to_owned()).collect();
         let host = parts[0].to_owned();
         let port = parts[1].to_owned();
-        FuelNodeConfig { host, port }
+        FuelNodeConfig {
+            host,
+            port,
+            fallbacks: Vec::new(),
+        }
     }
 }
@@ -6,7 +6,7 @@ use crate::{
 pub use clap::Parser;
 use http::Uri;
 use serde::Deserialize;
-use std::net::SocketAddr;
+use std::{net::SocketAddr, path::PathBuf};
 
 use super::derive_http_url;
 
@@ -18,6 +18,40 @@ pub struct GraphQLConfig {
     pub port: String,
     #[serde(default)]
     pub max_body_size: usize,
+    #[serde(default)]
+    pub max_wasm_size: usize,
+    #[serde(default)]
+    pub max_manifest_size: usize,
+    #[serde(default)]
+    pub max_schema_size: usize,
+    #[serde(default)]
+    pub max_query_rows: usize,
+    #[serde(default)]
+    pub max_query_depth: usize,
+    #[serde(default)]
+    pub max_query_complexity: usize,
+    #[serde(default)]
+    pub default_page_size: usize,
+    #[serde(default)]
+    pub query_rate_limit: u32,
+    #[serde(default)]
+    pub query_rate_limit_burst: u32,
+    #[serde(default)]
+    pub query_timeout_secs: u64,
+    #[serde(default)]
+    pub stream_timeout_secs: u64,
+    #[serde(default)]
+    pub max_concurrent_streams: usize,
+    #[serde(default)]
+    pub cors_allowed_origins: Vec<String>,
+    #[serde(default)]
+    pub cors_allowed_methods: Vec<String>,
+    #[serde(default)]
+    pub cors_allowed_headers: Vec<String>,
+    #[serde(default)]
+    pub tls_cert: Option<PathBuf>,
+    #[serde(default)]
+    pub tls_key: Option<PathBuf>,
 }
 
 impl std::string::ToString for GraphQLConfig {
@@ -41,10 +75,39 @@ impl Default for GraphQLConfig {
             host: defaults::GRAPHQL_API_HOST.into(),
             port: defaults::GRAPHQL_API_PORT.into(),
             max_body_size: defaults::MAX_BODY_SIZE,
+            max_wasm_size: defaults::MAX_WASM_SIZE,
+            max_manifest_size: defaults::MAX_MANIFEST_SIZE,
+            max_schema_size: defaults::MAX_SCHEMA_SIZE,
+            max_query_rows: defaults::MAX_QUERY_ROWS,
+            max_query_depth: defaults::MAX_QUERY_DEPTH,
+            max_query_complexity: defaults::MAX_QUERY_COMPLEXITY,
+            default_page_size: defaults::DEFAULT_PAGE_SIZE,
+            query_rate_limit: defaults::QUERY_RATE_LIMIT,
+            query_rate_limit_burst: defaults::QUERY_RATE_LIMIT_BURST,
+            query_timeout_secs: defaults::QUERY_TIMEOUT_SECS,
+            stream_timeout_secs: defaults::STREAM_TIMEOUT_SECS,
+            max_concurrent_streams: defaults::MAX_CONCURRENT_STREAMS,
+            cors_allowed_origins: Vec::new(),
+            cors_allowed_methods: split_list(defaults::CORS_ALLOWED_METHODS),
+            cors_allowed_headers: split_list(defaults::CORS_ALLOWED_HEADERS),
+            tls_cert: None,
+            tls_key: None,
         }
     }
 }
 
+/// Split a comma-separated default into its items.
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',').map(str::to_string).collect()
+}
+
+impl GraphQLConfig {
+    /// The certificate and key paths, if the API is served over HTTPS.
+    pub fn tls(&self) -> Option<(&PathBuf, &PathBuf)> {
+        self.tls_cert.as_ref().zip(self.tls_key.as_ref())
+    }
+}
+
 impl From<GraphQLConfig> for SocketAddr {
     fn from(cfg: GraphQLConfig) -> SocketAddr {
         derive_socket_addr(&cfg.host, &cfg.port)
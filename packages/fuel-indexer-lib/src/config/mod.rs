@@ -5,8 +5,8 @@ pub mod graphql;
 
 pub use crate::{
     config::{
-        auth::{AuthenticationConfig, AuthenticationStrategy},
-        database::DatabaseConfig,
+        auth::{AuthenticationConfig, AuthenticationStrategy, MessageEncoding},
+        database::{DatabaseArgs, DatabaseConfig},
         fuel_node::FuelNodeConfig,
         graphql::GraphQLConfig,
     },
@@ -82,6 +82,14 @@ pub struct IndexerArgs {
     #[clap(short, long, value_name = "FILE", help = "Index config file.")]
     pub manifest: Option<PathBuf>,
 
+    /// Replace a registered indexer of the same namespace and identifier as the manifest.
+    #[clap(
+        long,
+        requires = "manifest",
+        help = "Replace a registered indexer of the same namespace and identifier as the manifest."
+    )]
+    pub replace_indexer: bool,
+
     /// Host of the running Fuel node.
     #[clap(
         long,
@@ -98,6 +106,14 @@ pub struct IndexerArgs {
     )]
     pub fuel_node_port: String,
 
+    /// Comma-separated `host:port` of Fuel nodes to fail over to, in order, when the Fuel node can't be reached.
+    #[clap(
+        long,
+        help = "Comma-separated `host:port` of Fuel nodes to fail over to, in order, when the Fuel node can't be reached.",
+        value_delimiter = ','
+    )]
+    pub fuel_node_fallbacks: Vec<String>,
+
     /// GraphQL API host.
     #[clap(long, help = "GraphQL API host.", default_value = defaults::GRAPHQL_API_HOST)]
     pub graphql_api_host: String,
@@ -114,6 +130,96 @@ pub struct IndexerArgs {
     #[clap(long, help = "Max body size for GraphQL API requests.", default_value_t = defaults::MAX_BODY_SIZE )]
     pub max_body_size: usize,
 
+    /// Max size in bytes of an uploaded WASM module.
+    #[clap(long, help = "Max size in bytes of an uploaded WASM module.", default_value_t = defaults::MAX_WASM_SIZE )]
+    pub max_wasm_size: usize,
+
+    /// Max size in bytes of an uploaded manifest.
+    #[clap(long, help = "Max size in bytes of an uploaded manifest.", default_value_t = defaults::MAX_MANIFEST_SIZE )]
+    pub max_manifest_size: usize,
+
+    /// Max size in bytes of an uploaded GraphQL schema.
+    #[clap(long, help = "Max size in bytes of an uploaded GraphQL schema.", default_value_t = defaults::MAX_SCHEMA_SIZE )]
+    pub max_schema_size: usize,
+
+    /// Max number of rows a single GraphQL query may return.
+    #[clap(long, help = "Max number of rows a single GraphQL query may return.", default_value_t = defaults::MAX_QUERY_ROWS )]
+    pub max_query_rows: usize,
+
+    /// Max nesting depth of a GraphQL query. Use 0 for no limit.
+    #[clap(long, help = "Max nesting depth of a GraphQL query. Use 0 for no limit.", default_value_t = defaults::MAX_QUERY_DEPTH )]
+    pub max_query_depth: usize,
+
+    /// Max complexity of a GraphQL query, weighing fields by the rows they may be read for. Use 0 for no limit.
+    #[clap(long, help = "Max complexity of a GraphQL query, weighing fields by the rows they may be read for. Use 0 for no limit.", default_value_t = defaults::MAX_QUERY_COMPLEXITY )]
+    pub max_query_complexity: usize,
+
+    /// Page size for paginated GraphQL queries that do not specify `first`.
+    #[clap(long, help = "Page size for paginated GraphQL queries that do not specify `first`.", default_value_t = defaults::DEFAULT_PAGE_SIZE )]
+    pub default_page_size: usize,
+
+    /// GraphQL queries per second allowed for each client. Use 0 to disable rate limiting.
+    #[clap(long, help = "GraphQL queries per second allowed for each client. Use 0 to disable rate limiting.", default_value_t = defaults::QUERY_RATE_LIMIT )]
+    pub query_rate_limit: u32,
+
+    /// GraphQL queries a client may burst above the rate limit.
+    #[clap(long, help = "GraphQL queries a client may burst above the rate limit.", default_value_t = defaults::QUERY_RATE_LIMIT_BURST )]
+    pub query_rate_limit_burst: u32,
+
+    /// Seconds a GraphQL query may run before it is cancelled. Use 0 to disable the timeout.
+    #[clap(long, help = "Seconds a GraphQL query may run before it is cancelled. Use 0 to disable the timeout.", default_value_t = defaults::QUERY_TIMEOUT_SECS )]
+    pub query_timeout_secs: u64,
+
+    /// Seconds a streamed GraphQL query may take to be read by its client before it is cut off. Use 0 to disable the timeout.
+    #[clap(long, help = "Seconds a streamed GraphQL query may take to be read by its client before it is cut off. Use 0 to disable the timeout.", default_value_t = defaults::STREAM_TIMEOUT_SECS )]
+    pub stream_timeout_secs: u64,
+
+    /// Max number of GraphQL queries streamed at once, each holding a database connection. Use 0 for no limit.
+    #[clap(long, help = "Max number of GraphQL queries streamed at once, each holding a database connection. Use 0 for no limit.", default_value_t = defaults::MAX_CONCURRENT_STREAMS )]
+    pub max_concurrent_streams: usize,
+
+    /// Comma-separated origins that may call the GraphQL API from a browser, or `*` for any. Only same-origin requests are allowed by default.
+    #[clap(
+        long,
+        help = "Comma-separated origins that may call the GraphQL API from a browser, or `*` for any. Only same-origin requests are allowed by default.",
+        value_delimiter = ','
+    )]
+    pub cors_allowed_origins: Vec<String>,
+
+    /// Comma-separated methods that cross-origin requests may use.
+    #[clap(
+        long,
+        help = "Comma-separated methods that cross-origin requests may use.",
+        value_delimiter = ',',
+        default_value = defaults::CORS_ALLOWED_METHODS
+    )]
+    pub cors_allowed_methods: Vec<String>,
+
+    /// Comma-separated headers that cross-origin requests may send.
+    #[clap(
+        long,
+        help = "Comma-separated headers that cross-origin requests may send.",
+        value_delimiter = ',',
+        default_value = defaults::CORS_ALLOWED_HEADERS
+    )]
+    pub cors_allowed_headers: Vec<String>,
+
+    /// Path to a PEM certificate chain. Serves the GraphQL API over HTTPS, along with `--tls-key`.
+    #[clap(
+        long,
+        help = "Path to a PEM certificate chain. Serves the GraphQL API over HTTPS, along with `--tls-key`.",
+        requires = "tls-key"
+    )]
+    pub tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM private key of `--tls-cert`.
+    #[clap(
+        long,
+        help = "Path to the PEM private key of `--tls-cert`.",
+        requires = "tls-cert"
+    )]
+    pub tls_key: Option<PathBuf>,
+
     /// Postgres username.
     #[clap(long, help = "Postgres username.")]
     pub postgres_user: Option<String>,
@@ -134,6 +240,18 @@ pub struct IndexerArgs {
     #[clap(long, help = "Postgres port.")]
     pub postgres_port: Option<String>,
 
+    /// Max number of connections in each database connection pool.
+    #[clap(long, help = "Max number of connections in each database connection pool.", default_value_t = defaults::MAX_DB_CONNECTIONS)]
+    pub max_db_connections: u32,
+
+    /// Number of idle connections each database connection pool keeps open.
+    #[clap(long, help = "Number of idle connections each database connection pool keeps open.", default_value_t = defaults::MIN_IDLE_DB_CONNECTIONS)]
+    pub min_idle_db_connections: u32,
+
+    /// Seconds to wait for a database connection before giving up.
+    #[clap(long, help = "Seconds to wait for a database connection before giving up.", default_value_t = defaults::DB_ACQUIRE_TIMEOUT_SECS)]
+    pub db_acquire_timeout_secs: u64,
+
     /// Run database migrations before starting service.
     #[clap(long, help = "Run database migrations before starting service.")]
     pub run_migrations: bool,
@@ -149,6 +267,59 @@ pub struct IndexerArgs {
     )]
     pub stop_idle_indexers: bool,
 
+    /// Max number of blocks an indexer can roll back after a chain reorganization.
+    #[clap(long, help = "Max number of blocks an indexer can roll back after a chain reorganization. Set to 0 to disable reorg detection.", default_value_t = defaults::MAX_REORG_DEPTH)]
+    pub max_reorg_depth: u64,
+
+    /// Seconds a stopped indexer has to finish its current block before it is killed.
+    #[clap(long, help = "Seconds a stopped indexer has to finish its current block before it is killed.", default_value_t = defaults::STOP_DRAIN_TIMEOUT_SECS)]
+    pub stop_drain_timeout_secs: u64,
+
+    /// Attempts to reach the Fuel node before an indexer gives up.
+    #[clap(long, help = "Attempts to reach the Fuel node before an indexer gives up.", default_value_t = defaults::NODE_RETRY_ATTEMPTS)]
+    pub node_retry_attempts: usize,
+
+    /// Delay before the first retry of a failed Fuel node request, doubled on each further retry.
+    #[clap(long, help = "Milliseconds before the first retry of a failed Fuel node request, doubled on each further retry.", default_value_t = defaults::NODE_RETRY_BASE_DELAY_MS)]
+    pub node_retry_base_delay_ms: u64,
+
+    /// Max number of blocks committed in one transaction while an indexer catches up to the chain head.
+    #[clap(
+        long,
+        help = "Max number of blocks committed in one transaction while an indexer catches up to the chain head."
+    )]
+    pub commit_window_blocks: Option<usize>,
+
+    /// Max milliseconds that handled blocks are held before being committed while an indexer catches up to the chain head.
+    #[clap(
+        long,
+        help = "Max milliseconds that handled blocks are held before being committed while an indexer catches up to the chain head."
+    )]
+    pub commit_window_ms: Option<u64>,
+
+    /// Max megabytes of memory each WASM indexer can use.
+    #[clap(long, help = "Max megabytes of memory each WASM indexer can use.")]
+    pub wasm_memory_limit_mb: Option<u64>,
+
+    /// Max instructions each WASM indexer can run while handling a batch of blocks.
+    #[clap(
+        long,
+        help = "Max instructions each WASM indexer can run while handling a batch of blocks."
+    )]
+    pub wasm_fuel_limit: Option<u64>,
+
+    /// Max number of blocks an indexer fetches ahead of the blocks it is handling.
+    #[clap(long, help = "Max number of blocks an indexer fetches ahead of the blocks it is handling.", default_value_t = defaults::READ_AHEAD_BLOCKS)]
+    pub read_ahead_blocks: usize,
+
+    /// Max number of registered indexers started at once.
+    #[clap(long, help = "Max number of registered indexers started at once.", default_value_t = defaults::REGISTRY_STARTUP_CONCURRENCY)]
+    pub registry_startup_concurrency: usize,
+
+    /// Max number of batches of blocks handled at once across all indexers, which take turns when they're all busy. 0 for no limit.
+    #[clap(long, help = "Max number of batches of blocks handled at once across all indexers, which take turns when they're all busy. 0 for no limit.", default_value_t = defaults::MAX_CONCURRENT_BATCHES)]
+    pub max_concurrent_batches: usize,
+
     /// Automatically create and start database using provided options or defaults.
     #[clap(
         long,
@@ -182,6 +353,36 @@ pub struct IndexerArgs {
     )]
     pub jwt_expiry: Option<usize>,
 
+    /// SHA-256 hex digest of the API key (if API key scheme is specified).
+    #[clap(
+        long,
+        help = "SHA-256 hex digest of the API key (if API key scheme is specified)."
+    )]
+    pub api_key_hash: Option<String>,
+
+    /// Comma-separated public keys that are issued admin tokens.
+    #[clap(
+        long,
+        help = "Comma-separated public keys that are issued admin tokens.",
+        value_delimiter = ','
+    )]
+    pub admin_pubkeys: Vec<String>,
+
+    /// How signed nonces are encoded: raw, or personal for wallets that prefix the messages they sign.
+    #[clap(
+        long,
+        default_value = "raw",
+        help = "How signed nonces are encoded: raw, or personal for wallets that prefix the messages they sign."
+    )]
+    pub signature_encoding: MessageEncoding,
+
+    /// Emit logs as JSON objects instead of human-readable lines.
+    #[clap(
+        long,
+        help = "Emit logs as JSON objects instead of human-readable lines."
+    )]
+    pub json_logging: bool,
+
     /// Enable verbose logging.
     #[clap(long, help = "Enable verbose logging.")]
     pub verbose: bool,
@@ -230,6 +431,108 @@ pub struct ApiServerArgs {
     #[clap(long, help = "Max body size for GraphQL API requests.", default_value_t = defaults::MAX_BODY_SIZE )]
     pub max_body_size: usize,
 
+    /// Max size in bytes of an uploaded WASM module.
+    #[clap(long, help = "Max size in bytes of an uploaded WASM module.", default_value_t = defaults::MAX_WASM_SIZE )]
+    pub max_wasm_size: usize,
+
+    /// Max size in bytes of an uploaded manifest.
+    #[clap(long, help = "Max size in bytes of an uploaded manifest.", default_value_t = defaults::MAX_MANIFEST_SIZE )]
+    pub max_manifest_size: usize,
+
+    /// Max size in bytes of an uploaded GraphQL schema.
+    #[clap(long, help = "Max size in bytes of an uploaded GraphQL schema.", default_value_t = defaults::MAX_SCHEMA_SIZE )]
+    pub max_schema_size: usize,
+
+    /// Max number of rows a single GraphQL query may return.
+    #[clap(long, help = "Max number of rows a single GraphQL query may return.", default_value_t = defaults::MAX_QUERY_ROWS )]
+    pub max_query_rows: usize,
+
+    /// Max nesting depth of a GraphQL query. Use 0 for no limit.
+    #[clap(long, help = "Max nesting depth of a GraphQL query. Use 0 for no limit.", default_value_t = defaults::MAX_QUERY_DEPTH )]
+    pub max_query_depth: usize,
+
+    /// Max complexity of a GraphQL query, weighing fields by the rows they may be read for. Use 0 for no limit.
+    #[clap(long, help = "Max complexity of a GraphQL query, weighing fields by the rows they may be read for. Use 0 for no limit.", default_value_t = defaults::MAX_QUERY_COMPLEXITY )]
+    pub max_query_complexity: usize,
+
+    /// Page size for paginated GraphQL queries that do not specify `first`.
+    #[clap(long, help = "Page size for paginated GraphQL queries that do not specify `first`.", default_value_t = defaults::DEFAULT_PAGE_SIZE )]
+    pub default_page_size: usize,
+
+    /// GraphQL queries per second allowed for each client. Use 0 to disable rate limiting.
+    #[clap(long, help = "GraphQL queries per second allowed for each client. Use 0 to disable rate limiting.", default_value_t = defaults::QUERY_RATE_LIMIT )]
+    pub query_rate_limit: u32,
+
+    /// GraphQL queries a client may burst above the rate limit.
+    #[clap(long, help = "GraphQL queries a client may burst above the rate limit.", default_value_t = defaults::QUERY_RATE_LIMIT_BURST )]
+    pub query_rate_limit_burst: u32,
+
+    /// Seconds a GraphQL query may run before it is cancelled. Use 0 to disable the timeout.
+    #[clap(long, help = "Seconds a GraphQL query may run before it is cancelled. Use 0 to disable the timeout.", default_value_t = defaults::QUERY_TIMEOUT_SECS )]
+    pub query_timeout_secs: u64,
+
+    /// Seconds a streamed GraphQL query may take to be read by its client before it is cut off. Use 0 to disable the timeout.
+    #[clap(long, help = "Seconds a streamed GraphQL query may take to be read by its client before it is cut off. Use 0 to disable the timeout.", default_value_t = defaults::STREAM_TIMEOUT_SECS )]
+    pub stream_timeout_secs: u64,
+
+    /// Max number of GraphQL queries streamed at once, each holding a database connection. Use 0 for no limit.
+    #[clap(long, help = "Max number of GraphQL queries streamed at once, each holding a database connection. Use 0 for no limit.", default_value_t = defaults::MAX_CONCURRENT_STREAMS )]
+    pub max_concurrent_streams: usize,
+
+    /// Comma-separated origins that may call the GraphQL API from a browser, or `*` for any. Only same-origin requests are allowed by default.
+    #[clap(
+        long,
+        help = "Comma-separated origins that may call the GraphQL API from a browser, or `*` for any. Only same-origin requests are allowed by default.",
+        value_delimiter = ','
+    )]
+    pub cors_allowed_origins: Vec<String>,
+
+    /// Comma-separated methods that cross-origin requests may use.
+    #[clap(
+        long,
+        help = "Comma-separated methods that cross-origin requests may use.",
+        value_delimiter = ',',
+        default_value = defaults::CORS_ALLOWED_METHODS
+    )]
+    pub cors_allowed_methods: Vec<String>,
+
+    /// Comma-separated headers that cross-origin requests may send.
+    #[clap(
+        long,
+        help = "Comma-separated headers that cross-origin requests may send.",
+        value_delimiter = ',',
+        default_value = defaults::CORS_ALLOWED_HEADERS
+    )]
+    pub cors_allowed_headers: Vec<String>,
+
+    /// Path to a PEM certificate chain. Serves the GraphQL API over HTTPS, along with `--tls-key`.
+    #[clap(
+        long,
+        help = "Path to a PEM certificate chain. Serves the GraphQL API over HTTPS, along with `--tls-key`.",
+        requires = "tls-key"
+    )]
+    pub tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM private key of `--tls-cert`.
+    #[clap(
+        long,
+        help = "Path to the PEM private key of `--tls-cert`.",
+        requires = "tls-cert"
+    )]
+    pub tls_key: Option<PathBuf>,
+
+    /// Max number of connections in each database connection pool.
+    #[clap(long, help = "Max number of connections in each database connection pool.", default_value_t = defaults::MAX_DB_CONNECTIONS)]
+    pub max_db_connections: u32,
+
+    /// Number of idle connections each database connection pool keeps open.
+    #[clap(long, help = "Number of idle connections each database connection pool keeps open.", default_value_t = defaults::MIN_IDLE_DB_CONNECTIONS)]
+    pub min_idle_db_connections: u32,
+
+    /// Seconds to wait for a database connection before giving up.
+    #[clap(long, help = "Seconds to wait for a database connection before giving up.", default_value_t = defaults::DB_ACQUIRE_TIMEOUT_SECS)]
+    pub db_acquire_timeout_secs: u64,
+
     /// Run database migrations before starting service.
     #[clap(long, help = "Run database migrations before starting service.")]
     pub run_migrations: bool,
@@ -283,6 +586,36 @@ pub struct ApiServerArgs {
         help = "Amount of time (seconds) before expiring token (if JWT scheme is specified)."
     )]
     pub jwt_expiry: Option<usize>,
+
+    /// SHA-256 hex digest of the API key (if API key scheme is specified).
+    #[clap(
+        long,
+        help = "SHA-256 hex digest of the API key (if API key scheme is specified)."
+    )]
+    pub api_key_hash: Option<String>,
+
+    /// Comma-separated public keys that are issued admin tokens.
+    #[clap(
+        long,
+        help = "Comma-separated public keys that are issued admin tokens.",
+        value_delimiter = ','
+    )]
+    pub admin_pubkeys: Vec<String>,
+
+    /// How signed nonces are encoded: raw, or personal for wallets that prefix the messages they sign.
+    #[clap(
+        long,
+        default_value = "raw",
+        help = "How signed nonces are encoded: raw, or personal for wallets that prefix the messages they sign."
+    )]
+    pub signature_encoding: MessageEncoding,
+
+    /// Emit logs as JSON objects instead of human-readable lines.
+    #[clap(
+        long,
+        help = "Emit logs as JSON objects instead of human-readable lines."
+    )]
+    pub json_logging: bool,
 }
 
 fn derive_http_url(host: &String, port: &String) -> String {
@@ -312,8 +645,23 @@ pub struct IndexerConfig {
     pub graphql_api: GraphQLConfig,
     #[serde(default)]
     pub database: DatabaseConfig,
+    pub max_db_connections: u32,
+    pub min_idle_db_connections: u32,
+    pub db_acquire_timeout_secs: u64,
     pub metrics: bool,
     pub stop_idle_indexers: bool,
+    pub max_reorg_depth: u64,
+    pub stop_drain_timeout_secs: u64,
+    pub node_retry_attempts: usize,
+    pub node_retry_base_delay_ms: u64,
+    pub commit_window_blocks: Option<usize>,
+    pub commit_window_ms: Option<u64>,
+    pub wasm_memory_limit_mb: Option<u64>,
+    pub wasm_fuel_limit: Option<u64>,
+    pub read_ahead_blocks: usize,
+    pub registry_startup_concurrency: usize,
+    pub max_concurrent_batches: usize,
+    pub json_logging: bool,
     pub run_migrations: bool,
     pub authentication: AuthenticationConfig,
 }
@@ -363,14 +711,47 @@ impl From<IndexerArgs> for IndexerConfig {
             fuel_node: FuelNodeConfig {
                 host: args.fuel_node_host,
                 port: args.fuel_node_port,
+                fallbacks: args.fuel_node_fallbacks,
             },
             graphql_api: GraphQLConfig {
                 host: args.graphql_api_host,
                 port: args.graphql_api_port,
                 max_body_size: args.max_body_size,
+                max_wasm_size: args.max_wasm_size,
+                max_manifest_size: args.max_manifest_size,
+                max_schema_size: args.max_schema_size,
+                max_query_rows: args.max_query_rows,
+                max_query_depth: args.max_query_depth,
+                max_query_complexity: args.max_query_complexity,
+                default_page_size: args.default_page_size,
+                query_rate_limit: args.query_rate_limit,
+                query_rate_limit_burst: args.query_rate_limit_burst,
+                query_timeout_secs: args.query_timeout_secs,
+                stream_timeout_secs: args.stream_timeout_secs,
+                max_concurrent_streams: args.max_concurrent_streams,
+                cors_allowed_origins: args.cors_allowed_origins,
+                cors_allowed_methods: args.cors_allowed_methods,
+                cors_allowed_headers: args.cors_allowed_headers,
+                tls_cert: args.tls_cert,
+                tls_key: args.tls_key,
             },
+            max_db_connections: args.max_db_connections,
+            min_idle_db_connections: args.min_idle_db_connections,
+            db_acquire_timeout_secs: args.db_acquire_timeout_secs,
             metrics: args.metrics,
             stop_idle_indexers: args.stop_idle_indexers,
+            max_reorg_depth: args.max_reorg_depth,
+            stop_drain_timeout_secs: args.stop_drain_timeout_secs,
+            node_retry_attempts: args.node_retry_attempts,
+            node_retry_base_delay_ms: args.node_retry_base_delay_ms,
+            commit_window_blocks: args.commit_window_blocks,
+            commit_window_ms: args.commit_window_ms,
+            wasm_memory_limit_mb: args.wasm_memory_limit_mb,
+            wasm_fuel_limit: args.wasm_fuel_limit,
+            read_ahead_blocks: args.read_ahead_blocks,
+            registry_startup_concurrency: args.registry_startup_concurrency,
+            max_concurrent_batches: args.max_concurrent_batches,
+            json_logging: args.json_logging,
             run_migrations: args.run_migrations,
             authentication: AuthenticationConfig {
                 enabled: args.auth_enabled,
@@ -380,6 +761,9 @@ impl From<IndexerArgs> for IndexerConfig {
                 jwt_secret: args.jwt_secret,
                 jwt_issuer: args.jwt_issuer,
                 jwt_expiry: args.jwt_expiry,
+                api_key_hash: args.api_key_hash,
+                admin_pubkeys: args.admin_pubkeys,
+                signature_encoding: args.signature_encoding,
             },
         };
 
@@ -434,14 +818,47 @@ impl From<ApiServerArgs> for IndexerConfig {
             fuel_node: FuelNodeConfig {
                 host: args.fuel_node_host,
                 port: args.fuel_node_port,
+                fallbacks: Vec::new(),
             },
             graphql_api: GraphQLConfig {
                 host: args.graphql_api_host,
                 port: args.graphql_api_port,
                 max_body_size: args.max_body_size,
+                max_wasm_size: args.max_wasm_size,
+                max_manifest_size: args.max_manifest_size,
+                max_schema_size: args.max_schema_size,
+                max_query_rows: args.max_query_rows,
+                max_query_depth: args.max_query_depth,
+                max_query_complexity: args.max_query_complexity,
+                default_page_size: args.default_page_size,
+                query_rate_limit: args.query_rate_limit,
+                query_rate_limit_burst: args.query_rate_limit_burst,
+                query_timeout_secs: args.query_timeout_secs,
+                stream_timeout_secs: args.stream_timeout_secs,
+                max_concurrent_streams: args.max_concurrent_streams,
+                cors_allowed_origins: args.cors_allowed_origins,
+                cors_allowed_methods: args.cors_allowed_methods,
+                cors_allowed_headers: args.cors_allowed_headers,
+                tls_cert: args.tls_cert,
+                tls_key: args.tls_key,
             },
+            max_db_connections: args.max_db_connections,
+            min_idle_db_connections: args.min_idle_db_connections,
+            db_acquire_timeout_secs: args.db_acquire_timeout_secs,
             metrics: args.metrics,
             stop_idle_indexers: defaults::STOP_IDLE_INDEXERS,
+            max_reorg_depth: defaults::MAX_REORG_DEPTH,
+            stop_drain_timeout_secs: defaults::STOP_DRAIN_TIMEOUT_SECS,
+            node_retry_attempts: defaults::NODE_RETRY_ATTEMPTS,
+            node_retry_base_delay_ms: defaults::NODE_RETRY_BASE_DELAY_MS,
+            commit_window_blocks: None,
+            commit_window_ms: None,
+            wasm_memory_limit_mb: None,
+            wasm_fuel_limit: None,
+            read_ahead_blocks: defaults::READ_AHEAD_BLOCKS,
+            registry_startup_concurrency: defaults::REGISTRY_STARTUP_CONCURRENCY,
+            max_concurrent_batches: defaults::MAX_CONCURRENT_BATCHES,
+            json_logging: args.json_logging,
             run_migrations: args.run_migrations,
             authentication: AuthenticationConfig {
                 enabled: args.auth_enabled,
@@ -451,6 +868,9 @@ impl From<ApiServerArgs> for IndexerConfig {
                 jwt_secret: args.jwt_secret,
                 jwt_issuer: args.jwt_issuer,
                 jwt_expiry: args.jwt_expiry,
+                api_key_hash: args.api_key_hash,
+                admin_pubkeys: args.admin_pubkeys,
+                signature_encoding: args.signature_encoding,
             },
         };
 
@@ -507,14 +927,47 @@ impl IndexerConfig {
             fuel_node: FuelNodeConfig {
                 host: args.fuel_node_host,
                 port: args.fuel_node_port,
+                fallbacks: args.fuel_node_fallbacks,
             },
             graphql_api: GraphQLConfig {
                 host: args.graphql_api_host,
                 port: args.graphql_api_port,
                 max_body_size: args.max_body_size,
+                max_wasm_size: args.max_wasm_size,
+                max_manifest_size: args.max_manifest_size,
+                max_schema_size: args.max_schema_size,
+                max_query_rows: args.max_query_rows,
+                max_query_depth: args.max_query_depth,
+                max_query_complexity: args.max_query_complexity,
+                default_page_size: args.default_page_size,
+                query_rate_limit: args.query_rate_limit,
+                query_rate_limit_burst: args.query_rate_limit_burst,
+                query_timeout_secs: args.query_timeout_secs,
+                stream_timeout_secs: args.stream_timeout_secs,
+                max_concurrent_streams: args.max_concurrent_streams,
+                cors_allowed_origins: args.cors_allowed_origins,
+                cors_allowed_methods: args.cors_allowed_methods,
+                cors_allowed_headers: args.cors_allowed_headers,
+                tls_cert: args.tls_cert,
+                tls_key: args.tls_key,
             },
+            max_db_connections: args.max_db_connections,
+            min_idle_db_connections: args.min_idle_db_connections,
+            db_acquire_timeout_secs: args.db_acquire_timeout_secs,
             metrics: args.metrics,
             stop_idle_indexers: args.stop_idle_indexers,
+            max_reorg_depth: args.max_reorg_depth,
+            stop_drain_timeout_secs: args.stop_drain_timeout_secs,
+            node_retry_attempts: args.node_retry_attempts,
+            node_retry_base_delay_ms: args.node_retry_base_delay_ms,
+            commit_window_blocks: args.commit_window_blocks,
+            commit_window_ms: args.commit_window_ms,
+            wasm_memory_limit_mb: args.wasm_memory_limit_mb,
+            wasm_fuel_limit: args.wasm_fuel_limit,
+            read_ahead_blocks: args.read_ahead_blocks,
+            registry_startup_concurrency: args.registry_startup_concurrency,
+            max_concurrent_batches: args.max_concurrent_batches,
+            json_logging: args.json_logging,
             run_migrations: args.run_migrations,
             authentication: AuthenticationConfig {
                 enabled: args.auth_enabled,
@@ -524,6 +977,9 @@ impl IndexerConfig {
                 jwt_secret: args.jwt_secret,
                 jwt_issuer: args.jwt_issuer,
                 jwt_expiry: args.jwt_expiry,
+                api_key_hash: args.api_key_hash,
+                admin_pubkeys: args.admin_pubkeys,
+                signature_encoding: args.signature_encoding,
             },
         };
 
@@ -546,6 +1002,108 @@ impl IndexerConfig {
         let graphql_config_key = serde_yaml::Value::String("graphql".into());
         let database_config_key = serde_yaml::Value::String("database".into());
         let auth_config_key = serde_yaml::Value::String("authentication".into());
+        let max_reorg_depth_key = serde_yaml::Value::String("max_reorg_depth".into());
+        let stop_drain_timeout_secs_key =
+            serde_yaml::Value::String("stop_drain_timeout_secs".into());
+        let node_retry_attempts_key =
+            serde_yaml::Value::String("node_retry_attempts".into());
+        let node_retry_base_delay_ms_key =
+            serde_yaml::Value::String("node_retry_base_delay_ms".into());
+        let commit_window_blocks_key =
+            serde_yaml::Value::String("commit_window_blocks".into());
+        let commit_window_ms_key = serde_yaml::Value::String("commit_window_ms".into());
+        let wasm_memory_limit_mb_key =
+            serde_yaml::Value::String("wasm_memory_limit_mb".into());
+        let wasm_fuel_limit_key = serde_yaml::Value::String("wasm_fuel_limit".into());
+        let read_ahead_blocks_key = serde_yaml::Value::String("read_ahead_blocks".into());
+        let registry_startup_concurrency_key =
+            serde_yaml::Value::String("registry_startup_concurrency".into());
+        let max_concurrent_batches_key =
+            serde_yaml::Value::String("max_concurrent_batches".into());
+        let json_logging_key = serde_yaml::Value::String("json_logging".into());
+        let max_db_connections_key =
+            serde_yaml::Value::String("max_db_connections".into());
+        let min_idle_db_connections_key =
+            serde_yaml::Value::String("min_idle_db_connections".into());
+        let db_acquire_timeout_secs_key =
+            serde_yaml::Value::String("db_acquire_timeout_secs".into());
+
+        config.max_reorg_depth = defaults::MAX_REORG_DEPTH;
+        if let Some(max_reorg_depth) = content.get(max_reorg_depth_key) {
+            config.max_reorg_depth = max_reorg_depth.as_u64().unwrap();
+        }
+
+        config.stop_drain_timeout_secs = defaults::STOP_DRAIN_TIMEOUT_SECS;
+        if let Some(stop_drain_timeout_secs) = content.get(stop_drain_timeout_secs_key) {
+            config.stop_drain_timeout_secs = stop_drain_timeout_secs.as_u64().unwrap();
+        }
+
+        config.node_retry_attempts = defaults::NODE_RETRY_ATTEMPTS;
+        if let Some(node_retry_attempts) = content.get(node_retry_attempts_key) {
+            config.node_retry_attempts = node_retry_attempts.as_u64().unwrap() as usize;
+        }
+
+        config.node_retry_base_delay_ms = defaults::NODE_RETRY_BASE_DELAY_MS;
+        if let Some(node_retry_base_delay_ms) = content.get(node_retry_base_delay_ms_key)
+        {
+            config.node_retry_base_delay_ms = node_retry_base_delay_ms.as_u64().unwrap();
+        }
+
+        if let Some(commit_window_blocks) = content.get(commit_window_blocks_key) {
+            config.commit_window_blocks =
+                Some(commit_window_blocks.as_u64().unwrap() as usize);
+        }
+
+        if let Some(commit_window_ms) = content.get(commit_window_ms_key) {
+            config.commit_window_ms = Some(commit_window_ms.as_u64().unwrap());
+        }
+
+        if let Some(wasm_memory_limit_mb) = content.get(wasm_memory_limit_mb_key) {
+            config.wasm_memory_limit_mb = Some(wasm_memory_limit_mb.as_u64().unwrap());
+        }
+
+        if let Some(wasm_fuel_limit) = content.get(wasm_fuel_limit_key) {
+            config.wasm_fuel_limit = Some(wasm_fuel_limit.as_u64().unwrap());
+        }
+
+        config.read_ahead_blocks = defaults::READ_AHEAD_BLOCKS;
+        if let Some(read_ahead_blocks) = content.get(read_ahead_blocks_key) {
+            config.read_ahead_blocks = read_ahead_blocks.as_u64().unwrap() as usize;
+        }
+
+        config.registry_startup_concurrency = defaults::REGISTRY_STARTUP_CONCURRENCY;
+        if let Some(registry_startup_concurrency) =
+            content.get(registry_startup_concurrency_key)
+        {
+            config.registry_startup_concurrency =
+                registry_startup_concurrency.as_u64().unwrap() as usize;
+        }
+
+        config.max_concurrent_batches = defaults::MAX_CONCURRENT_BATCHES;
+        if let Some(max_concurrent_batches) = content.get(max_concurrent_batches_key) {
+            config.max_concurrent_batches =
+                max_concurrent_batches.as_u64().unwrap() as usize;
+        }
+
+        config.max_db_connections = defaults::MAX_DB_CONNECTIONS;
+        if let Some(max_db_connections) = content.get(max_db_connections_key) {
+            config.max_db_connections = max_db_connections.as_u64().unwrap() as u32;
+        }
+
+        config.min_idle_db_connections = defaults::MIN_IDLE_DB_CONNECTIONS;
+        if let Some(min_idle_db_connections) = content.get(min_idle_db_connections_key) {
+            config.min_idle_db_connections =
+                min_idle_db_connections.as_u64().unwrap() as u32;
+        }
+
+        config.db_acquire_timeout_secs = defaults::DB_ACQUIRE_TIMEOUT_SECS;
+        if let Some(db_acquire_timeout_secs) = content.get(db_acquire_timeout_secs_key) {
+            config.db_acquire_timeout_secs = db_acquire_timeout_secs.as_u64().unwrap();
+        }
+
+        if let Some(json_logging) = content.get(json_logging_key) {
+            config.json_logging = json_logging.as_bool().unwrap();
+        }
 
         if let Some(section) = content.get(fuel_config_key) {
             let fuel_node_host = section.get(&serde_yaml::Value::String("host".into()));
@@ -558,6 +1116,18 @@ impl IndexerConfig {
             if let Some(fuel_node_port) = fuel_node_port {
                 config.fuel_node.port = fuel_node_port.as_u64().unwrap().to_string();
             }
+
+            let fuel_node_fallbacks =
+                section.get(&serde_yaml::Value::String("fallbacks".into()));
+
+            if let Some(fuel_node_fallbacks) = fuel_node_fallbacks {
+                config.fuel_node.fallbacks = fuel_node_fallbacks
+                    .as_sequence()
+                    .unwrap()
+                    .iter()
+                    .map(|addr| addr.as_str().unwrap().to_string())
+                    .collect();
+            }
         }
 
         if let Some(section) = content.get(graphql_config_key) {
@@ -578,6 +1148,152 @@ impl IndexerConfig {
                 config.graphql_api.max_body_size =
                     max_body_size.as_u64().unwrap() as usize;
             }
+
+            let max_wasm_size =
+                section.get(&serde_yaml::Value::String("max_wasm_size".into()));
+
+            if let Some(max_wasm_size) = max_wasm_size {
+                config.graphql_api.max_wasm_size =
+                    max_wasm_size.as_u64().unwrap() as usize;
+            }
+
+            let max_manifest_size =
+                section.get(&serde_yaml::Value::String("max_manifest_size".into()));
+
+            if let Some(max_manifest_size) = max_manifest_size {
+                config.graphql_api.max_manifest_size =
+                    max_manifest_size.as_u64().unwrap() as usize;
+            }
+
+            let max_schema_size =
+                section.get(&serde_yaml::Value::String("max_schema_size".into()));
+
+            if let Some(max_schema_size) = max_schema_size {
+                config.graphql_api.max_schema_size =
+                    max_schema_size.as_u64().unwrap() as usize;
+            }
+
+            let max_query_rows =
+                section.get(&serde_yaml::Value::String("max_query_rows".into()));
+
+            if let Some(max_query_rows) = max_query_rows {
+                config.graphql_api.max_query_rows =
+                    max_query_rows.as_u64().unwrap() as usize;
+            }
+
+            let max_query_depth =
+                section.get(&serde_yaml::Value::String("max_query_depth".into()));
+
+            if let Some(max_query_depth) = max_query_depth {
+                config.graphql_api.max_query_depth =
+                    max_query_depth.as_u64().unwrap() as usize;
+            }
+
+            let max_query_complexity =
+                section.get(&serde_yaml::Value::String("max_query_complexity".into()));
+
+            if let Some(max_query_complexity) = max_query_complexity {
+                config.graphql_api.max_query_complexity =
+                    max_query_complexity.as_u64().unwrap() as usize;
+            }
+
+            let default_page_size =
+                section.get(&serde_yaml::Value::String("default_page_size".into()));
+
+            if let Some(default_page_size) = default_page_size {
+                config.graphql_api.default_page_size =
+                    default_page_size.as_u64().unwrap() as usize;
+            }
+
+            let query_rate_limit =
+                section.get(&serde_yaml::Value::String("query_rate_limit".into()));
+
+            if let Some(query_rate_limit) = query_rate_limit {
+                config.graphql_api.query_rate_limit =
+                    query_rate_limit.as_u64().unwrap() as u32;
+            }
+
+            let query_rate_limit_burst =
+                section.get(&serde_yaml::Value::String("query_rate_limit_burst".into()));
+
+            if let Some(query_rate_limit_burst) = query_rate_limit_burst {
+                config.graphql_api.query_rate_limit_burst =
+                    query_rate_limit_burst.as_u64().unwrap() as u32;
+            }
+
+            let query_timeout_secs =
+                section.get(&serde_yaml::Value::String("query_timeout_secs".into()));
+
+            if let Some(query_timeout_secs) = query_timeout_secs {
+                config.graphql_api.query_timeout_secs =
+                    query_timeout_secs.as_u64().unwrap();
+            }
+
+            let stream_timeout_secs =
+                section.get(&serde_yaml::Value::String("stream_timeout_secs".into()));
+
+            if let Some(stream_timeout_secs) = stream_timeout_secs {
+                config.graphql_api.stream_timeout_secs =
+                    stream_timeout_secs.as_u64().unwrap();
+            }
+
+            let max_concurrent_streams =
+                section.get(&serde_yaml::Value::String("max_concurrent_streams".into()));
+
+            if let Some(max_concurrent_streams) = max_concurrent_streams {
+                config.graphql_api.max_concurrent_streams =
+                    max_concurrent_streams.as_u64().unwrap() as usize;
+            }
+
+            let cors_allowed_origins =
+                section.get(&serde_yaml::Value::String("cors_allowed_origins".into()));
+
+            if let Some(cors_allowed_origins) = cors_allowed_origins {
+                config.graphql_api.cors_allowed_origins = cors_allowed_origins
+                    .as_sequence()
+                    .unwrap()
+                    .iter()
+                    .map(|origin| origin.as_str().unwrap().to_string())
+                    .collect();
+            }
+
+            let cors_allowed_methods =
+                section.get(&serde_yaml::Value::String("cors_allowed_methods".into()));
+
+            if let Some(cors_allowed_methods) = cors_allowed_methods {
+                config.graphql_api.cors_allowed_methods = cors_allowed_methods
+                    .as_sequence()
+                    .unwrap()
+                    .iter()
+                    .map(|method| method.as_str().unwrap().to_string())
+                    .collect();
+            }
+
+            let cors_allowed_headers =
+                section.get(&serde_yaml::Value::String("cors_allowed_headers".into()));
+
+            if let Some(cors_allowed_headers) = cors_allowed_headers {
+                config.graphql_api.cors_allowed_headers = cors_allowed_headers
+                    .as_sequence()
+                    .unwrap()
+                    .iter()
+                    .map(|header| header.as_str().unwrap().to_string())
+                    .collect();
+            }
+
+            let tls_cert = section.get(&serde_yaml::Value::String("tls_cert".into()));
+
+            if let Some(tls_cert) = tls_cert {
+                config.graphql_api.tls_cert =
+                    Some(PathBuf::from(tls_cert.as_str().unwrap()));
+            }
+
+            let tls_key = section.get(&serde_yaml::Value::String("tls_key".into()));
+
+            if let Some(tls_key) = tls_key {
+                config.graphql_api.tls_key =
+                    Some(PathBuf::from(tls_key.as_str().unwrap()));
+            }
         }
 
         if let Some(section) = content.get(database_config_key) {
@@ -656,6 +1372,32 @@ impl IndexerConfig {
                 config.authentication.jwt_issuer =
                     Some(jwt_issuer.as_str().unwrap().to_string());
             }
+
+            let api_key_hash =
+                section.get(&serde_yaml::Value::String("api_key_hash".into()));
+            if let Some(api_key_hash) = api_key_hash {
+                config.authentication.api_key_hash =
+                    Some(api_key_hash.as_str().unwrap().to_string());
+            }
+
+            let admin_pubkeys =
+                section.get(&serde_yaml::Value::String("admin_pubkeys".into()));
+            if let Some(admin_pubkeys) = admin_pubkeys {
+                config.authentication.admin_pubkeys = admin_pubkeys
+                    .as_sequence()
+                    .unwrap()
+                    .iter()
+                    .map(|pubkey| pubkey.as_str().unwrap().to_string())
+                    .collect();
+            }
+
+            let signature_encoding =
+                section.get(&serde_yaml::Value::String("signature_encoding".into()));
+            if let Some(signature_encoding) = signature_encoding {
+                config.authentication.signature_encoding =
+                    MessageEncoding::from_str(signature_encoding.as_str().unwrap())
+                        .unwrap();
+            }
         }
 
         config.inject_opt_env_vars();
@@ -700,6 +1442,58 @@ mod tests {
         fs::remove_file(tmp_file_path).unwrap();
     }
 
+    #[test]
+    fn test_indexer_config_reads_connection_pool_settings() {
+        let config_str = r#"
+    max_db_connections: 25
+    db_acquire_timeout_secs: 5
+    "#;
+
+        let tmp_file_path = "./pool.yaml";
+
+        fs::write(tmp_file_path, config_str).expect("Unable to write file");
+        let config = IndexerConfig::from_file(Path::new(tmp_file_path)).unwrap();
+
+        assert_eq!(config.max_db_connections, 25);
+        assert_eq!(
+            config.min_idle_db_connections,
+            defaults::MIN_IDLE_DB_CONNECTIONS
+        );
+        assert_eq!(config.db_acquire_timeout_secs, 5);
+
+        fs::remove_file(tmp_file_path).unwrap();
+    }
+
+    #[test]
+    fn test_indexer_config_reads_fuel_node_fallbacks() {
+        let config_str = r#"
+    fuel_node:
+      host: 1.1.1.1
+      port: 4000
+      fallbacks:
+        - 2.2.2.2:4000
+        - node.example.com:4001
+    "#;
+
+        let tmp_file_path = "./fallbacks.yaml";
+
+        fs::write(tmp_file_path, config_str).expect("Unable to write file");
+        let config = IndexerConfig::from_file(Path::new(tmp_file_path)).unwrap();
+
+        let nodes: Vec<String> = config
+            .fuel_node
+            .nodes()
+            .iter()
+            .map(|node| node.to_string())
+            .collect();
+        assert_eq!(
+            nodes,
+            vec!["1.1.1.1:4000", "2.2.2.2:4000", "node.example.com:4001"]
+        );
+
+        fs::remove_file(tmp_file_path).unwrap();
+    }
+
     #[test]
     fn test_indexer_config_will_supplement_individual_config_vars_in_sections() {
         let config_str = r#"
@@ -11,20 +11,57 @@ pub const POSTGRES_HOST: &str = "localhost";
 pub const POSTGRES_PORT: &str = "5432";
 pub const POSTGRES_PASSWORD: &str = "postgres";
 
+pub const MAX_DB_CONNECTIONS: u32 = 10;
+pub const MIN_IDLE_DB_CONNECTIONS: u32 = 0;
+pub const DB_ACQUIRE_TIMEOUT_SECS: u64 = 30;
+
 pub const INDEX_FAILED_CALLS: usize = 10;
 pub const STOP_IDLE_INDEXERS: bool = false;
+pub const MAX_REORG_DEPTH: u64 = 10;
+pub const STOP_DRAIN_TIMEOUT_SECS: u64 = 30;
+pub const NODE_RETRY_ATTEMPTS: usize = 10;
+pub const NODE_RETRY_BASE_DELAY_MS: u64 = 500;
+pub const MAX_NODE_RETRY_DELAY_SECS: u64 = 60;
+pub const NODE_HEALTH_CHECK_TIMEOUT_SECS: u64 = 5;
+pub const READ_AHEAD_BLOCKS: usize = 100;
+pub const REGISTRY_STARTUP_CONCURRENCY: usize = 8;
+pub const MAX_CONCURRENT_BATCHES: usize = 0;
 
 pub const MAX_BODY_SIZE: usize = 5242880; // 5MB
+pub const MAX_WASM_SIZE: usize = 5242880; // 5MB
+pub const MAX_MANIFEST_SIZE: usize = 65536; // 64KB
+pub const MAX_SCHEMA_SIZE: usize = 1048576; // 1MB
+pub const MAX_QUERY_ROWS: usize = 10000;
+pub const MAX_QUERY_DEPTH: usize = 10;
+pub const MAX_QUERY_COMPLEXITY: usize = 100000;
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+pub const QUERY_RATE_LIMIT: u32 = 0;
+pub const QUERY_RATE_LIMIT_BURST: u32 = 10;
+pub const QUERY_TIMEOUT_SECS: u64 = 30;
+pub const STREAM_TIMEOUT_SECS: u64 = 300;
+pub const MAX_CONCURRENT_STREAMS: usize = 16;
+pub const CORS_ALLOWED_METHODS: &str = "GET,POST";
+pub const CORS_ALLOWED_HEADERS: &str = "authorization,content-type";
+pub const MAX_PAGE_SIZE: usize = 1000;
+pub const MAX_FIND_IDS_RESULTS: usize = 1000;
 
 pub const SERVICE_REQUEST_CHANNEL_SIZE: usize = 100;
-pub const IDLE_SERVICE_WAIT_SECS: u64 = 3;
+pub const ENTITY_NOTIFICATION_CHANNEL_SIZE: usize = 1024;
+pub const WEBHOOK_QUEUE_SIZE: usize = 1024;
+pub const WEBHOOK_RETRY_ATTEMPTS: usize = 5;
+pub const WEBHOOK_RETRY_BASE_DELAY_MS: u64 = 500;
+pub const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+pub const SERVICE_REPLY_TIMEOUT_SECS: u64 = 6;
 
 pub const MAX_DATABASE_CONNECTION_ATTEMPTS: usize = 5;
 pub const INITIAL_RETRY_DELAY_SECS: u64 = 2;
 pub const MAX_EMPTY_BLOCK_REQUESTS: usize = 10;
+pub const BLOCK_WINDOW: usize = 10;
 
 pub const DELAY_FOR_SERVICE_ERR: u64 = 5;
-pub const DELAY_FOR_EMPTY_PAGE: u64 = 1;
+pub const POLL_INTERVAL_MS: u64 = 1000;
+pub const PAUSE_POLL_INTERVAL_MS: u64 = 500;
+pub const RELOAD_POLL_INTERVAL_MS: u64 = 100;
 
 pub const RUN_MIGRATIONS: bool = true;
 pub const USE_METRICS: bool = true;
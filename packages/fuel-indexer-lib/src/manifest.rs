@@ -1,24 +1,55 @@
+use crate::defaults;
 use anyhow::Result;
+use fuel_abi_types::program_abi::{ProgramABI, TypeApplication};
+use fuel_indexer_types::{Bech32ContractId, ContractId};
+use graphql_parser::schema::{parse_schema, Definition, TypeDefinition};
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::{HashMap, HashSet},
     fs::File,
     io::{Read, Write},
     path::{Path, PathBuf},
+    str::FromStr,
 };
+use strum::AsRefStr;
 use thiserror::Error;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Manifest {
     pub namespace: String,
     pub abi: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub abis: Vec<ContractAbi>,
     pub identifier: String,
     pub graphql_schema: String,
+    /// Further schema files, whose definitions are combined with those of
+    /// `graphql_schema` into a single schema.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub graphql_schemas: Vec<String>,
     pub module: Module,
     pub metrics: Option<bool>,
-    pub contract_id: Option<String>,
+    pub contract_id: Option<ContractIds>,
     pub start_block: Option<u64>,
     #[serde(default)]
+    pub end_block: Option<u64>,
+    #[serde(default)]
     pub resumable: Option<bool>,
+    #[serde(default, alias = "on_error")]
+    pub error_strategy: ErrorStrategy,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipts: Option<Vec<ReceiptKind>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_window: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub poll_interval_ms: Option<u64>,
+    #[serde(default)]
+    pub commit_mode: CommitMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled_handlers: Option<Vec<String>>,
+    /// URL that each committed object of an entity is posted to, keyed by the
+    /// entity's GraphQL type name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhooks: Option<HashMap<String, String>>,
 }
 
 type ManifestResult<T> = Result<T, ManifestError>;
@@ -31,6 +62,161 @@ pub enum ManifestError {
     NativeModuleError,
     #[error("File IO error: {0:?}.")]
     FileError(#[from] std::io::Error),
+    #[error("Invalid contract ID '{0}': {1}")]
+    InvalidContractId(String, String),
+    #[error("Environment variable '{0}' referenced by the manifest is not set.")]
+    UnsetEnvVar(String),
+    #[error("'{0}' is defined in both GraphQL schema '{1}' and '{2}'.")]
+    DuplicateSchemaDefinition(String, String, String),
+}
+
+/// Combine the schema files `files`, pairs of a path and its text, into a
+/// single schema, checking that no type is defined in more than one file.
+///
+/// Files that can't be parsed are combined as they are, and left for the
+/// parser of the combined schema to report.
+pub fn combine_schemas(files: &[(String, String)]) -> ManifestResult<String> {
+    let mut defined_in: HashMap<String, &str> = HashMap::new();
+
+    for (path, text) in files {
+        let ast = match parse_schema::<String>(text) {
+            Ok(ast) => ast,
+            Err(_) => continue,
+        };
+
+        for def in ast.definitions.iter() {
+            let name = match def {
+                Definition::SchemaDefinition(_) => "schema".to_string(),
+                Definition::TypeDefinition(typ) => type_definition_name(typ),
+                Definition::DirectiveDefinition(directive) => {
+                    format!("@{}", directive.name)
+                }
+                Definition::TypeExtension(_) => continue,
+            };
+
+            match defined_in.get(&name) {
+                Some(other) if other != path => {
+                    return Err(ManifestError::DuplicateSchemaDefinition(
+                        name,
+                        other.to_string(),
+                        path.clone(),
+                    ))
+                }
+                Some(_) => {}
+                None => {
+                    defined_in.insert(name, path);
+                }
+            }
+        }
+    }
+
+    Ok(files
+        .iter()
+        .map(|(_, text)| text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn type_definition_name(typ: &TypeDefinition<String>) -> String {
+    match typ {
+        TypeDefinition::Scalar(t) => t.name.clone(),
+        TypeDefinition::Object(t) => t.name.clone(),
+        TypeDefinition::Interface(t) => t.name.clone(),
+        TypeDefinition::Union(t) => t.name.clone(),
+        TypeDefinition::Enum(t) => t.name.clone(),
+        TypeDefinition::InputObject(t) => t.name.clone(),
+    }
+}
+
+/// Replace each `${VAR}` reference in `s` with the value of the environment
+/// variable `VAR`. Text outside of `${...}` is left as it is.
+fn interpolate_env_vars(s: &str) -> ManifestResult<String> {
+    let mut interpolated = String::new();
+    let mut rest = s;
+
+    while let Some(start) = rest.find("${") {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+
+        let name = &rest[start + 2..end];
+        let value = std::env::var(name)
+            .map_err(|_| ManifestError::UnsetEnvVar(name.to_string()))?;
+
+        interpolated.push_str(&rest[..start]);
+        interpolated.push_str(&value);
+        rest = &rest[end + 1..];
+    }
+
+    interpolated.push_str(rest);
+    Ok(interpolated)
+}
+
+/// Interpolate environment variables into every string of a parsed manifest.
+///
+/// A string that is nothing but a single reference takes the type of the
+/// variable's value if that is a number or a boolean, so that fields such as
+/// `start_block: ${START_BLOCK}` can be templated too.
+fn interpolate_yaml_env_vars(value: &mut serde_yaml::Value) -> ManifestResult<()> {
+    match value {
+        serde_yaml::Value::String(s) if s.contains("${") => {
+            let interpolated = interpolate_env_vars(s)?;
+            let single_reference =
+                s.starts_with("${") && s.find('}') == Some(s.len() - 1);
+
+            *value = match serde_yaml::from_str(&interpolated) {
+                Ok(
+                    typed @ (serde_yaml::Value::Number(_) | serde_yaml::Value::Bool(_)),
+                ) if single_reference => typed,
+                _ => serde_yaml::Value::String(interpolated),
+            };
+        }
+        serde_yaml::Value::Sequence(values) => {
+            for value in values.iter_mut() {
+                interpolate_yaml_env_vars(value)?;
+            }
+        }
+        serde_yaml::Value::Mapping(mapping) => {
+            for (_, value) in mapping.iter_mut() {
+                interpolate_yaml_env_vars(value)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// The `contract_id` of a manifest: one contract ID, or a list of them.
+#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum ContractIds {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl ContractIds {
+    pub fn ids(&self) -> &[String] {
+        match self {
+            Self::One(id) => std::slice::from_ref(id),
+            Self::Many(ids) => ids,
+        }
+    }
+}
+
+/// One of the contract ABIs of an indexer that indexes several contracts.
+#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq)]
+pub struct ContractAbi {
+    /// Name of the module that the ABI's types are generated in.
+    pub name: String,
+
+    /// Path to the JSON ABI of the contract.
+    pub abi: String,
+
+    /// ID of the contract whose receipts are decoded with this ABI. If not set,
+    /// receipts of contracts not matched by any other ABI are decoded with it.
+    pub contract_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -40,6 +226,70 @@ pub enum Module {
     Native,
 }
 
+/// How an executor recovers when a handler returns an error for a batch of blocks.
+///
+/// The failed batch's transaction is always rolled back first; the strategy only
+/// decides what happens next.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, Eq, PartialEq, AsRefStr)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ErrorStrategy {
+    /// Retry the batch a few times, then stop the indexer.
+    #[default]
+    #[serde(alias = "stop")]
+    Abort,
+
+    /// Skip each failing block and continue with the next one.
+    #[serde(alias = "skip")]
+    SkipBlock,
+
+    /// Retry the batch up to the given number of times, then stop the indexer.
+    Retry(usize),
+
+    /// Persist each failing block's data for later inspection and continue.
+    DeadLetter,
+}
+
+/// How often an executor commits the blocks it has handled.
+#[derive(
+    Debug, Deserialize, Serialize, Clone, Copy, Default, Eq, PartialEq, AsRefStr,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CommitMode {
+    /// Handle each fetched window of blocks in one transaction.
+    #[default]
+    Batch,
+
+    /// Handle and commit each block in its own transaction.
+    Block,
+}
+
+/// A kind of transaction receipt whose data can be dispatched to an indexer's handlers.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Eq, PartialEq, Hash, AsRefStr)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ReceiptKind {
+    Call,
+    Log,
+    LogData,
+    MessageOut,
+    Panic,
+    Return,
+    ReturnData,
+    Revert,
+    ScriptResult,
+    Transfer,
+    TransferOut,
+}
+
+impl ErrorStrategy {
+    /// Whether failing blocks are recorded and skipped rather than stopping the indexer.
+    pub fn skips_blocks(&self) -> bool {
+        matches!(self, Self::SkipBlock | Self::DeadLetter)
+    }
+}
+
 impl Module {
     pub fn path(&self) -> String {
         match self {
@@ -50,10 +300,22 @@ impl Module {
 }
 
 impl Manifest {
+    /// Max number of blocks requested from the Fuel node at once.
+    pub fn block_window(&self) -> usize {
+        self.block_window.unwrap_or(defaults::BLOCK_WINDOW).max(1)
+    }
+
+    /// Milliseconds to wait before polling the Fuel node again, once the
+    /// indexer has caught up to the chain head.
+    pub fn poll_interval_ms(&self) -> u64 {
+        self.poll_interval_ms.unwrap_or(defaults::POLL_INTERVAL_MS)
+    }
+
+    /// Parse a manifest, expanding any `${VAR}` references in its values
+    /// with the values of those environment variables.
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(content: &str) -> ManifestResult<Self> {
-        let manifest: Manifest = serde_yaml::from_str(content)?;
-        Ok(manifest)
+        Self::from_yaml(serde_yaml::from_str(content)?)
     }
 
     pub fn from_file(path: &Path) -> ManifestResult<Self> {
@@ -63,26 +325,100 @@ impl Manifest {
         Self::from_str(&content)
     }
 
+    /// Parse a manifest, expanding any `${VAR}` references in its values
+    /// with the values of those environment variables.
     pub fn from_slice(s: &[u8]) -> ManifestResult<Self> {
-        Ok(serde_yaml::from_slice(s)?)
+        Self::from_yaml(serde_yaml::from_slice(s)?)
+    }
+
+    fn from_yaml(mut value: serde_yaml::Value) -> ManifestResult<Self> {
+        interpolate_yaml_env_vars(&mut value)?;
+        Ok(serde_yaml::from_value(value)?)
     }
 
     pub fn to_bytes(&self) -> ManifestResult<Vec<u8>> {
         Ok(serde_yaml::to_string(&self)?.as_bytes().to_vec())
     }
 
+    /// Paths of the files that the GraphQL schema is split across.
+    pub fn graphql_schema_paths(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.graphql_schema).chain(self.graphql_schemas.iter())
+    }
+
+    /// The GraphQL schema, combined from each of its files.
     pub fn graphql_schema(&self) -> ManifestResult<String> {
-        let mut file = File::open(&self.graphql_schema)?;
-        let mut schema = String::new();
-        file.read_to_string(&mut schema)?;
+        let mut files = Vec::new();
+        for path in self.graphql_schema_paths() {
+            let mut file = File::open(path)?;
+            let mut schema = String::new();
+            file.read_to_string(&mut schema)?;
+            files.push((path.clone(), schema));
+        }
 
-        Ok(schema)
+        combine_schemas(&files)
     }
 
     pub fn uid(&self) -> String {
         format!("{}.{}", &self.namespace, &self.identifier)
     }
 
+    /// Whether receipts of the given kind are dispatched to the indexer's handlers.
+    ///
+    /// Every kind is dispatched if the manifest does not list any `receipts`.
+    pub fn subscribes_to(&self, kind: ReceiptKind) -> bool {
+        self.receipts
+            .as_ref()
+            .map_or(true, |kinds| kinds.contains(&kind))
+    }
+
+    /// The contracts whose receipts are dispatched to the indexer's handlers.
+    ///
+    /// Receipts of every contract are dispatched if the manifest does not set
+    /// `contract_id`, or sets it to an empty string.
+    pub fn contract_ids(&self) -> ManifestResult<Option<HashSet<ContractId>>> {
+        self.contract_id
+            .as_ref()
+            .filter(|contract_ids| contract_ids.ids().iter().any(|id| !id.is_empty()))
+            .map(|contract_ids| {
+                contract_ids
+                    .ids()
+                    .iter()
+                    .filter(|id| !id.is_empty())
+                    .map(|id| {
+                        Bech32ContractId::from_str(id)
+                            .map(|id| ContractId::from(&id))
+                            .map_err(|e| {
+                                ManifestError::InvalidContractId(
+                                    id.clone(),
+                                    e.to_string(),
+                                )
+                            })
+                    })
+                    .collect()
+            })
+            .transpose()
+    }
+
+    /// Whether the handler with the given name is run.
+    ///
+    /// Every handler is run if the manifest does not list any `enabled_handlers`.
+    pub fn handler_enabled(&self, name: &str) -> bool {
+        self.enabled_handlers
+            .as_ref()
+            .map_or(true, |names| names.iter().any(|n| n == name))
+    }
+
+    /// The `enabled_handlers` that are not among the given handlers of the
+    /// indexer's module.
+    pub fn unknown_handlers(&self, handlers: &[String]) -> Vec<String> {
+        self.enabled_handlers
+            .iter()
+            .flatten()
+            .filter(|name| !handlers.contains(name))
+            .cloned()
+            .collect()
+    }
+
     pub fn is_native(&self) -> bool {
         match &self.module {
             Module::Native => true,
@@ -108,6 +444,237 @@ impl Manifest {
         file.write_all(&self.to_bytes()?)?;
         Ok(())
     }
+
+    /// Check the manifest, and the schema and ABIs it references, without
+    /// registering anything, describing every problem found rather than only
+    /// the first.
+    ///
+    /// Paths are resolved against the working directory, like when the
+    /// manifest's assets are read.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = self.field_errors();
+
+        let mut paths = vec![("graphql_schema", &self.graphql_schema)];
+        paths.extend(
+            self.graphql_schemas
+                .iter()
+                .map(|path| ("graphql_schemas", path)),
+        );
+        if let Module::Wasm(path) = &self.module {
+            paths.push(("module", path));
+        }
+        for (field, path) in paths {
+            if !path.is_empty() && !Path::new(path).is_file() {
+                errors.push(format!("`{field}` file '{path}' does not exist."));
+            }
+        }
+
+        errors.extend(self.abi_type_errors());
+
+        match self.graphql_schema() {
+            Ok(schema) => match parse_schema::<String>(&schema) {
+                Ok(_) => errors.extend(self.schema_errors(&schema)),
+                Err(e) => errors.push(format!(
+                    "GraphQL schema '{}' could not be parsed: {e}",
+                    self.graphql_schema_paths()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join("', '")
+                )),
+            },
+            Err(e @ ManifestError::DuplicateSchemaDefinition(..)) => {
+                errors.push(e.to_string())
+            }
+            Err(_) => {}
+        }
+
+        errors
+    }
+
+    /// Describe each field of the manifest that is missing or invalid.
+    pub fn field_errors(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        for (field, value) in [
+            ("namespace", &self.namespace),
+            ("identifier", &self.identifier),
+            ("graphql_schema", &self.graphql_schema),
+        ] {
+            if value.is_empty() {
+                errors.push(format!("`{field}` is required."));
+            }
+        }
+        if matches!(&self.module, Module::Wasm(path) if path.is_empty()) {
+            errors.push("`module` is required.".to_string());
+        }
+
+        if let (Some(start_block), Some(end_block)) = (self.start_block, self.end_block) {
+            if end_block < start_block {
+                errors.push(format!(
+                    "`end_block` {end_block} is before `start_block` {start_block}."
+                ));
+            }
+        }
+
+        if let Err(e) = self.contract_ids() {
+            errors.push(format!("{e}."));
+        }
+
+        errors
+    }
+
+    /// Describe each way in which `schema` is inconsistent with the manifest
+    /// and its ABI: webhooks of types that the schema doesn't define, and types
+    /// that share a name with a type of the `abi`. Both are generated in the
+    /// root of the indexer module, where the entity would shadow the ABI type
+    /// that handlers take. Types of `abis` are generated in modules of their own.
+    ///
+    /// A schema that can't be parsed has no such errors.
+    pub fn schema_errors(&self, schema: &str) -> Vec<String> {
+        let ast = match parse_schema::<String>(schema) {
+            Ok(ast) => ast,
+            Err(_) => return Vec::new(),
+        };
+        let objects: HashSet<&str> = ast
+            .definitions
+            .iter()
+            .filter_map(|def| match def {
+                Definition::TypeDefinition(TypeDefinition::Object(o)) => {
+                    Some(o.name.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut errors = Vec::new();
+
+        let mut webhooks: Vec<_> = self.webhooks.iter().flatten().collect();
+        webhooks.sort();
+        for (entity, _) in webhooks {
+            if !objects.contains(entity.as_str()) {
+                errors.push(format!(
+                    "Webhook of type '{entity}', which is not in the GraphQL schema."
+                ));
+            }
+        }
+
+        if let Some(path) = &self.abi {
+            // ABIs that can't be loaded are reported by `abi_type_errors`.
+            let abi = match std::fs::read_to_string(path)
+                .ok()
+                .and_then(|json| serde_json::from_str::<ProgramABI>(&json).ok())
+            {
+                Some(abi) => abi,
+                None => return errors,
+            };
+
+            let mut names: Vec<&str> = abi
+                .types
+                .iter()
+                .filter_map(|typ| {
+                    typ.type_field
+                        .strip_prefix("struct ")
+                        .or_else(|| typ.type_field.strip_prefix("enum "))
+                })
+                .filter(|name| objects.contains(name))
+                .collect();
+            names.sort_unstable();
+            names.dedup();
+
+            for name in names {
+                errors.push(format!(
+                    "Type '{name}' of the GraphQL schema has the same name as a type of ABI '{path}'."
+                ));
+            }
+        }
+
+        errors
+    }
+
+    /// Describe each ABI of the manifest that can't be loaded, and each type
+    /// that an ABI references without declaring it.
+    pub fn abi_type_errors(&self) -> Vec<String> {
+        self.abi
+            .iter()
+            .chain(self.abis.iter().map(|contract| &contract.abi))
+            .flat_map(|path| abi_type_errors(path))
+            .collect()
+    }
+}
+
+fn abi_type_errors(path: &str) -> Vec<String> {
+    let abi = std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| {
+            serde_json::from_str::<ProgramABI>(&json).map_err(|e| e.to_string())
+        });
+    let abi = match abi {
+        Ok(abi) => abi,
+        Err(e) => return vec![format!("ABI '{path}' could not be loaded: {e}.")],
+    };
+
+    let declared: HashSet<usize> = abi.types.iter().map(|typ| typ.type_id).collect();
+
+    let mut applications: Vec<(String, &TypeApplication)> = Vec::new();
+    for typ in &abi.types {
+        for component in typ.components.iter().flatten() {
+            let context =
+                format!("Component '{}' of '{}'", component.name, typ.type_field);
+            applications.push((context, component));
+        }
+    }
+    for function in &abi.functions {
+        for input in &function.inputs {
+            let context = format!("Input '{}' of '{}'", input.name, function.name);
+            applications.push((context, input));
+        }
+        applications.push((format!("Output of '{}'", function.name), &function.output));
+    }
+    for logged in abi.logged_types.iter().flatten() {
+        let context = format!("Logged type {}", logged.log_id);
+        applications.push((context, &logged.application));
+    }
+    for message in abi.messages_types.iter().flatten() {
+        let context = format!("Message type {}", message.message_id);
+        applications.push((context, &message.application));
+    }
+
+    let mut errors = Vec::new();
+    for (context, application) in applications {
+        let mut undeclared = Vec::new();
+        undeclared_type_ids(application, &declared, &mut undeclared);
+        for type_id in undeclared {
+            errors.push(format!(
+                "ABI '{path}': {context} references undeclared type {type_id}."
+            ));
+        }
+    }
+    for typ in &abi.types {
+        for type_id in typ.type_parameters.iter().flatten() {
+            if !declared.contains(type_id) {
+                errors.push(format!(
+                    "ABI '{path}': Type parameter of '{}' references undeclared type {type_id}.",
+                    typ.type_field
+                ));
+            }
+        }
+    }
+
+    errors
+}
+
+/// Collect the IDs used by `application`, or its type arguments, that aren't declared.
+fn undeclared_type_ids(
+    application: &TypeApplication,
+    declared: &HashSet<usize>,
+    undeclared: &mut Vec<usize>,
+) {
+    if !declared.contains(&application.type_id) {
+        undeclared.push(application.type_id);
+    }
+    for argument in application.type_arguments.iter().flatten() {
+        undeclared_type_ids(argument, declared, undeclared);
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -115,3 +682,133 @@ pub struct Event {
     pub trigger: String,
     pub payload: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_interpolates_env_vars() {
+        std::env::set_var("MANIFEST_TEST_NAMESPACE", "staging");
+        std::env::set_var("MANIFEST_TEST_START_BLOCK", "1564");
+
+        let manifest = Manifest::from_str(
+            r#"
+namespace: fuel_${MANIFEST_TEST_NAMESPACE}
+identifier: index1
+graphql_schema: schemas/${MANIFEST_TEST_NAMESPACE}.graphql
+start_block: ${MANIFEST_TEST_START_BLOCK}
+module:
+  wasm: $MANIFEST_TEST_NAMESPACE/index1.wasm
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.namespace, "fuel_staging");
+        assert_eq!(manifest.graphql_schema, "schemas/staging.graphql");
+        assert_eq!(manifest.start_block, Some(1564));
+        // Only `${...}` references are expanded.
+        assert_eq!(
+            manifest.module.path(),
+            "$MANIFEST_TEST_NAMESPACE/index1.wasm"
+        );
+
+        let e = Manifest::from_slice(
+            b"namespace: ${MANIFEST_TEST_UNSET}\nidentifier: index1\ngraphql_schema: s.graphql\nmodule: native\n",
+        )
+        .unwrap_err();
+        assert!(
+            matches!(e, ManifestError::UnsetEnvVar(name) if name == "MANIFEST_TEST_UNSET")
+        );
+    }
+
+    #[test]
+    fn test_manifest_validate_reports_every_problem() {
+        let manifest = Manifest::from_str(
+            r#"
+namespace: ""
+identifier: index1
+graphql_schema: does/not/exist.graphql
+abi: does/not/exist.json
+start_block: 10
+end_block: 5
+module:
+  wasm: does/not/exist.wasm
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.validate(),
+            vec![
+                "`namespace` is required.".to_string(),
+                "`end_block` 5 is before `start_block` 10.".to_string(),
+                "`graphql_schema` file 'does/not/exist.graphql' does not exist."
+                    .to_string(),
+                "`module` file 'does/not/exist.wasm' does not exist.".to_string(),
+                "ABI 'does/not/exist.json' could not be loaded: No such file or directory (os error 2).".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_combine_schemas_rejects_types_defined_in_two_files() {
+        let files = vec![
+            (
+                "schema.graphql".to_string(),
+                "schema { query: QueryRoot }\ntype QueryRoot { thing: Thing }\n"
+                    .to_string(),
+            ),
+            (
+                "things.graphql".to_string(),
+                "type Thing { id: ID! }\nenum Kind { A, B }\n".to_string(),
+            ),
+        ];
+        let schema = combine_schemas(&files).unwrap();
+        assert!(schema.contains("type QueryRoot") && schema.contains("enum Kind"));
+        assert!(parse_schema::<String>(&schema).is_ok());
+
+        let mut files = files;
+        files.push((
+            "more.graphql".to_string(),
+            "type Other { id: ID! }\ntype Thing { id: ID! }\n".to_string(),
+        ));
+        let e = combine_schemas(&files).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "'Thing' is defined in both GraphQL schema 'things.graphql' and 'more.graphql'."
+        );
+    }
+
+    #[test]
+    fn test_manifest_schema_errors_checks_schema_against_manifest() {
+        let mut manifest = Manifest::from_str(
+            r#"
+namespace: test_namespace
+identifier: index1
+graphql_schema: schema.graphql
+abi: ../fuel-indexer-tests/contracts/fuel-indexer-test/out/debug/fuel-indexer-test-abi.json
+module:
+  wasm: index1.wasm
+"#,
+        )
+        .unwrap();
+        manifest.webhooks = Some(HashMap::from([
+            (
+                "PingEntity".to_string(),
+                "http://localhost:8080".to_string(),
+            ),
+            ("Missing".to_string(), "http://localhost:8080".to_string()),
+        ]));
+
+        let schema = "type PingEntity { id: ID! }\ntype Pong { id: ID! }\n";
+        assert_eq!(
+            manifest.schema_errors(schema),
+            vec![
+                "Webhook of type 'Missing', which is not in the GraphQL schema.".to_string(),
+                "Type 'Pong' of the GraphQL schema has the same name as a type of ABI '../fuel-indexer-tests/contracts/fuel-indexer-test/out/debug/fuel-indexer-test-abi.json'.".to_string(),
+            ]
+        );
+        assert!(manifest.schema_errors("type {").is_empty());
+    }
+}
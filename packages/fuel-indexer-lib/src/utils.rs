@@ -5,13 +5,17 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::str::FromStr;
 use std::{
+    collections::HashMap,
     fs::canonicalize,
     future::Future,
     net::{SocketAddr, ToSocketAddrs},
     path::Path,
     process::Command,
 };
-use tokio::time::{sleep, Duration};
+use tokio::{
+    sync::{broadcast, oneshot},
+    time::{sleep, Duration},
+};
 use tracing::{info, warn};
 
 // Testing assets use relative paths, while production assets will use absolute paths
@@ -54,12 +58,64 @@ pub fn local_repository_root() -> Option<String> {
 pub struct AssetReloadRequest {
     pub namespace: String,
     pub identifier: String,
+    /// Correlation ID of the API request that triggered the reload, if any.
+    pub request_id: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct AssetValidateRequest {
+    pub namespace: String,
+    pub identifier: String,
+    /// WASM module to be registered for the indexer, or the registered one if
+    /// only a manifest is uploaded.
+    pub wasm: Vec<u8>,
+    /// Schema uploaded along with the module, if any.
+    pub schema: Option<String>,
+    /// Manifest uploaded along with the module, if any.
+    pub manifest: Option<Vec<u8>>,
+    /// Receives why the module cannot be run as the indexer, if it cannot.
+    pub reply: oneshot::Sender<Result<(), String>>,
+    /// Correlation ID of the API request that uploaded the module, if any.
+    pub request_id: Option<String>,
 }
 
 #[derive(Debug)]
 pub struct IndexStopRequest {
     pub namespace: String,
     pub identifier: String,
+    /// Correlation ID of the API request that stopped the indexer, if any.
+    pub request_id: Option<String>,
+}
+
+/// Halt an indexer's block processing after its current batch, keeping its
+/// executor and assets, until it is resumed.
+#[derive(Debug)]
+pub struct IndexPauseRequest {
+    pub namespace: String,
+    pub identifier: String,
+    /// Correlation ID of the API request that paused the indexer, if any.
+    pub request_id: Option<String>,
+}
+
+/// Resume a paused indexer from the last block it handled.
+#[derive(Debug)]
+pub struct IndexResumeRequest {
+    pub namespace: String,
+    pub identifier: String,
+    /// Correlation ID of the API request that resumed the indexer, if any.
+    pub request_id: Option<String>,
+}
+
+/// Handle blocks `[from, to]` of a running indexer again, with its current
+/// module.
+#[derive(Debug)]
+pub struct IndexReplayRequest {
+    pub namespace: String,
+    pub identifier: String,
+    pub from: u64,
+    pub to: u64,
+    /// Correlation ID of the API request that asked for the replay, if any.
+    pub request_id: Option<String>,
 }
 
 #[derive(Debug)]
@@ -68,13 +124,60 @@ pub struct IndexRevertRequest {
     pub penultimate_asset_bytes: Vec<u8>,
     pub namespace: String,
     pub identifier: String,
+    /// Correlation ID of the API request that reverted the indexer, if any.
+    pub request_id: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct IndexStatusRequest {
+    /// Receives whether each tracked indexer's future is still running, keyed by uid.
+    pub reply: oneshot::Sender<HashMap<String, bool>>,
+}
+
+/// Objects of one entity committed by an indexer in a single transaction.
+#[derive(Clone, Debug)]
+pub struct EntityNotification {
+    pub namespace: String,
+    pub identifier: String,
+    /// Lowercase name of the entity, as used for its table.
+    pub entity: String,
+    pub object_ids: Vec<u64>,
+}
+
+#[derive(Debug)]
+pub struct EntitySubscribeRequest {
+    /// Receives a subscription to every indexer's committed objects.
+    pub reply: oneshot::Sender<broadcast::Receiver<EntityNotification>>,
 }
 
 #[derive(Debug)]
 pub enum ServiceRequest {
     AssetReload(AssetReloadRequest),
+    AssetValidate(AssetValidateRequest),
     IndexStop(IndexStopRequest),
+    IndexPause(IndexPauseRequest),
+    IndexResume(IndexResumeRequest),
+    IndexReplay(IndexReplayRequest),
     IndexRevert(IndexRevertRequest),
+    IndexStatus(IndexStatusRequest),
+    EntitySubscribe(EntitySubscribeRequest),
+}
+
+impl ServiceRequest {
+    /// Correlation ID of the API request that triggered this request, which
+    /// is attached to the span the service handles it in.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            ServiceRequest::AssetReload(request) => request.request_id.as_deref(),
+            ServiceRequest::AssetValidate(request) => request.request_id.as_deref(),
+            ServiceRequest::IndexStop(request) => request.request_id.as_deref(),
+            ServiceRequest::IndexPause(request) => request.request_id.as_deref(),
+            ServiceRequest::IndexResume(request) => request.request_id.as_deref(),
+            ServiceRequest::IndexReplay(request) => request.request_id.as_deref(),
+            ServiceRequest::IndexRevert(request) => request.request_id.as_deref(),
+            ServiceRequest::IndexStatus(_) | ServiceRequest::EntitySubscribe(_) => None,
+        }
+    }
 }
 
 pub fn sha256_digest<T: AsRef<[u8]>>(blob: &T) -> String {
@@ -179,7 +282,12 @@ pub mod bin_utils {
     const LOG_FILTER: &str = "RUST_LOG";
     const HUMAN_LOGGING: &str = "HUMAN_LOGGING";
 
-    pub async fn init_logging() -> anyhow::Result<()> {
+    /// Install the global `tracing` subscriber.
+    ///
+    /// Logs are emitted as JSON objects, including the fields of any enclosing
+    /// spans (e.g., `uid`, `block_height`, `request_id`), when `json_logging` is
+    /// set or `HUMAN_LOGGING=false`.
+    pub async fn init_logging(json_logging: bool) -> anyhow::Result<()> {
         let filter = match env::var_os(LOG_FILTER) {
             Some(_) => {
                 EnvFilter::try_from_default_env().expect("Invalid `RUST_LOG` provided")
@@ -193,7 +301,8 @@ pub mod bin_utils {
                     "Expected `true` or `false` to be provided for `HUMAN_LOGGING`",
                 )
             })
-            .unwrap_or(true);
+            .unwrap_or(true)
+            && !json_logging;
 
         let sub = tracing_subscriber::fmt::Subscriber::builder()
             .with_writer(std::io::stderr)
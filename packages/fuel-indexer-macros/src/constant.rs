@@ -4,23 +4,31 @@ use std::collections::HashSet;
 lazy_static! {
     pub static ref FUEL_PRIMITIVES: HashSet<&'static str> = HashSet::from([
         "BlockData",
+        "BlockHeader",
         "Call",
         "Log",
         "LogData",
         "MessageOut",
+        "Panic",
         "Return",
+        "Revert",
         "ScriptResult",
+        "TransactionData",
         "Transfer",
         "TransferOut",
     ]);
     pub static ref FUEL_PRIMITIVES_NAMESPACED: HashSet<&'static str> = HashSet::from([
         "abi :: BlockData",
+        "abi :: BlockHeader",
         "abi :: Call",
         "abi :: Log",
         "abi :: LogData",
         "abi :: MessageOut",
+        "abi :: Panic",
         "abi :: Return",
+        "abi :: Revert",
         "abi :: ScriptResult",
+        "abi :: TransactionData",
         "abi :: Transfer",
         "abi :: TransferOut",
     ]);
@@ -38,11 +46,25 @@ lazy_static! {
         "Log",
         "LogData",
         "MessageOut",
+        "Panic",
         "Return",
+        "Revert",
         "ScriptResult",
         "Transfer",
         "TransferOut",
     ]);
+    pub static ref SDK_PROVIDED_TYPES: HashSet<&'static str> = HashSet::from([
+        "Address",
+        "AssetId",
+        "B512",
+        "ContractId",
+        "EvmAddress",
+        "Identity",
+        "Option",
+        "RawSlice",
+        "Result",
+        "Vec",
+    ]);
     pub static ref RUST_PRIMITIVES: HashSet<&'static str> =
         HashSet::from(["u8", "u16", "u32", "u64", "bool", "String"]);
 }
@@ -1,7 +1,8 @@
 use crate::constant::*;
 use fuel_abi_types::program_abi::{ProgramABI, TypeDeclaration};
+use fuel_indexer_lib::manifest::ReceiptKind;
 use fuels_code_gen::utils::Source;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, ToTokens};
 use syn::Ident;
 
 /// If TypeDeclaration is tuple type
@@ -123,13 +124,17 @@ pub fn rust_type_token(ty: &TypeDeclaration) -> proc_macro2::TokenStream {
             "u64" => quote! { u64 },
             "u8" => quote! { u8 },
             "BlockData" => quote! { BlockData },
+            "BlockHeader" => quote! { BlockHeader },
             "Call" => quote! { abi::Call },
             "Identity" => quote! { abi::Identity },
             "Log" => quote! { abi::Log },
             "LogData" => quote! { abi::LogData },
             "MessageOut" => quote! { abi::MessageOut },
+            "Panic" => quote! { abi::Panic },
             "Return" => quote! { abi::Return },
+            "Revert" => quote! { abi::Revert },
             "ScriptResult" => quote! { abi::ScriptResult },
+            "TransactionData" => quote! { TransactionData },
             "Transfer" => quote! { abi::Transfer },
             "TransferOut" => quote! { abi::TransferOut },
             o if o.starts_with("str[") => quote! { String },
@@ -150,16 +155,33 @@ pub fn is_fuel_primitive(ty: &proc_macro2::TokenStream) -> bool {
         || FUEL_PRIMITIVES_NAMESPACED.contains(ident_str.as_str())
 }
 
+/// The kind of receipt that a Fuel receipt type is decoded from, if any
+pub fn receipt_kind(type_name: &str) -> Option<ReceiptKind> {
+    match type_name {
+        "Call" => Some(ReceiptKind::Call),
+        "Log" => Some(ReceiptKind::Log),
+        "LogData" => Some(ReceiptKind::LogData),
+        "MessageOut" => Some(ReceiptKind::MessageOut),
+        "Panic" => Some(ReceiptKind::Panic),
+        "Return" => Some(ReceiptKind::Return),
+        "Revert" => Some(ReceiptKind::Revert),
+        "ScriptResult" => Some(ReceiptKind::ScriptResult),
+        "Transfer" => Some(ReceiptKind::Transfer),
+        "TransferOut" => Some(ReceiptKind::TransferOut),
+        _ => None,
+    }
+}
+
 /// Whether or not the given token is a Rust primitive
 pub fn is_rust_primitive(ty: &proc_macro2::TokenStream) -> bool {
     let ident_str = ty.to_string();
     RUST_PRIMITIVES.contains(ident_str.as_str())
 }
 
-/// Given a type ID, a type token, and a type Ident, return a decoder snippet
-/// as a set of tokens
+/// Given a type ID (or any other pattern matching it), a type token, and a
+/// type Ident, return a decoder snippet as a set of tokens
 pub fn decode_snippet(
-    ty_id: usize,
+    ty_id: impl ToTokens,
     ty: &proc_macro2::TokenStream,
     name: &Ident,
 ) -> proc_macro2::TokenStream {
@@ -1,22 +1,40 @@
 use crate::{
-    constant::*, helpers::*, native::handler_block_native, parse::IndexerConfig,
-    schema::process_graphql_schema, wasm::handler_block_wasm,
+    constant::*,
+    helpers::*,
+    native::handler_block_native,
+    parse::IndexerConfig,
+    schema::{const_item, process_graphql_schema},
+    wasm::handler_block_wasm,
+};
+use fuel_abi_types::program_abi::{ProgramABI, TypeDeclaration};
+use fuel_indexer_lib::{
+    manifest::{ContractIds, Manifest, ReceiptKind},
+    utils::local_repository_root,
 };
-use fuel_abi_types::program_abi::TypeDeclaration;
-use fuel_indexer_lib::{manifest::Manifest, utils::local_repository_root};
 use fuel_indexer_types::{abi, type_id};
 use fuels_code_gen::{Abigen, AbigenTarget, ProgramType};
 use fuels_core::function_selector::resolve_fn_selector;
 use fuels_types::param_types::ParamType;
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use syn::{parse_macro_input, FnArg, Item, ItemMod, PatType, Type};
 
+/// A contract ABI that the receipts of an indexer are decoded with.
+struct IndexerAbi {
+    /// Module that the types of the ABI are namespaced in, if the indexer has
+    /// several ABIs.
+    name: Option<String>,
+    abi: ProgramABI,
+    /// Contract whose receipts are decoded with this ABI. If not set, receipts
+    /// of contracts not matched by any other ABI are decoded with it.
+    contract_id: Option<String>,
+}
+
 fn process_fn_items(
     manifest: &Manifest,
-    abi_path: Option<String>,
+    abis: Vec<IndexerAbi>,
     indexer_module: ItemMod,
 ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
     let is_native = manifest.is_native();
@@ -33,15 +51,9 @@ fn process_fn_items(
         )
     }
 
-    let abi = get_json_abi(abi_path);
-
-    let mut decoded_abi_types = HashSet::new();
     let mut abi_dispatchers = Vec::new();
+    let mut handler_names = Vec::new();
 
-    let funcs = abi.clone().unwrap_or_default().functions;
-    let abi_types = abi.clone().unwrap_or_default().types;
-    let abi_log_types = abi.clone().unwrap_or_default().logged_types;
-    let abi_msg_types = abi.unwrap_or_default().messages_types;
     let fuel_types = FUEL_PRIMITIVES
         .iter()
         .map(|x| {
@@ -56,77 +68,9 @@ fn process_fn_items(
         })
         .collect::<HashMap<usize, TypeDeclaration>>();
 
-    let mut type_ids = FUEL_PRIMITIVES
-        .iter()
-        .map(|x| {
-            (
-                x.to_string(),
-                type_id(abi::FUEL_TYPES_NAMESPACE, x) as usize,
-            )
-        })
-        .collect::<HashMap<String, usize>>();
-
-    let abi_types_tyid = abi_types
-        .iter()
-        .filter(|typ| {
-            if is_ignored_type(typ) {
-                return false;
-            }
-            true
-        })
-        .map(|typ| (typ.type_id, typ.clone()))
-        .collect::<HashMap<usize, TypeDeclaration>>();
-
-    let log_type_decoders = abi_log_types
-        .iter()
-        .flatten()
-        .map(|typ| {
-            let ty_id = typ.application.type_id;
-            let log_id = typ.log_id as usize;
-
-            quote! {
-                #log_id => {
-                    self.decode_type(#ty_id, data);
-                }
-            }
-        })
-        .collect::<Vec<proc_macro2::TokenStream>>();
-
-    let message_types_decoders = abi_msg_types
-        .iter()
-        .flatten()
-        .map(|typ| {
-            let message_id = typ.message_id;
-            let ty_id = typ.application.type_id;
-
-            quote! {
-                #message_id => {
-                    self.decode_type(#ty_id, data.data.clone());
-                }
-            }
-        })
-        .collect::<Vec<proc_macro2::TokenStream>>();
-
-    let abi_type_decoders = abi_types
-        .iter()
-        .filter_map(|typ| {
-            if is_non_decodable_type(typ) {
-                return None;
-            }
-
-            let name = typ.rust_type_ident();
-            let ty = typ.rust_type_token();
-
-            if is_fuel_primitive(&ty) {
-                proc_macro_error::abort_call_site!("'{}' is a reserved Fuel type.", ty)
-            }
-
-            type_ids.insert(ty.to_string(), typ.type_id);
-            decoded_abi_types.insert(typ.type_id);
-
-            Some(decode_snippet(typ.type_id, &ty, &name))
-        })
-        .collect::<Vec<proc_macro2::TokenStream>>();
+    // Decoder fields that a handler argument of a given type can be read from.
+    // A type name maps to several fields if more than one ABI defines it.
+    let mut decoder_fields: HashMap<String, HashSet<syn::Ident>> = HashMap::new();
 
     let fuel_type_decoders = fuel_types
         .values()
@@ -134,17 +78,17 @@ fn process_fn_items(
             let name = typ.rust_type_ident();
             let ty = typ.rust_type_token();
 
-            type_ids.insert(ty.to_string(), typ.type_id);
-            decoded_abi_types.insert(typ.type_id);
+            decoder_fields
+                .entry(typ.type_field.clone())
+                .or_default()
+                .insert(name.clone());
 
             decode_snippet(typ.type_id, &ty, &name)
         })
         .collect::<Vec<proc_macro2::TokenStream>>();
 
-    let decoders = [fuel_type_decoders, abi_type_decoders].concat();
-
-    let abi_struct_fields = abi_types
-        .iter()
+    let fuel_struct_fields = fuel_types
+        .values()
         .filter_map(|typ| {
             if is_non_decodable_type(typ) {
                 return None;
@@ -153,43 +97,106 @@ fn process_fn_items(
             let name = typ.rust_type_ident();
             let ty = typ.rust_type_token();
 
-            if is_fuel_primitive(&ty) {
-                proc_macro_error::abort_call_site!("'{}' is a reserved Fuel type.", ty)
-            }
-
-            type_ids.insert(ty.to_string(), typ.type_id);
-            decoded_abi_types.insert(typ.type_id);
-
             Some(quote! {
                 #name: Vec<#ty>
             })
         })
         .collect::<Vec<proc_macro2::TokenStream>>();
 
-    let fuel_struct_fields = fuel_types
-        .iter()
-        .filter_map(|(_ty_id, typ)| {
+    let mut abi_type_decoders = Vec::new();
+    let mut abi_struct_fields = Vec::new();
+    let mut abi_selectors = Vec::new();
+    let mut abi_selectors_to_fn_names = Vec::new();
+    let mut log_type_decoders = Vec::new();
+    let mut message_types_decoders = Vec::new();
+    let mut message_ids = HashSet::new();
+    let mut contract_matchers = Vec::new();
+    let mut fallback_abi = quote! { None };
+
+    for (
+        index,
+        IndexerAbi {
+            name,
+            abi,
+            contract_id,
+        },
+    ) in abis.iter().enumerate()
+    {
+        match contract_id {
+            Some(contract_id) => contract_matchers.push(quote! {
+                if contract_id == Bech32ContractId::from_str(#contract_id).expect("Failed to parse manifest 'contract_id' as Bech32ContractId") {
+                    return Some(#index);
+                }
+            }),
+            None => fallback_abi = quote! { Some(#index) },
+        }
+
+        let abi_types_tyid = abi
+            .types
+            .iter()
+            .filter(|typ| !is_ignored_type(typ))
+            .map(|typ| (typ.type_id, typ.clone()))
+            .collect::<HashMap<usize, TypeDeclaration>>();
+
+        let mut decoded_types = HashSet::new();
+
+        for typ in &abi.types {
             if is_non_decodable_type(typ) {
-                return None;
+                continue;
             }
 
-            let name = typ.rust_type_ident();
             let ty = typ.rust_type_token();
 
-            type_ids.insert(ty.to_string(), typ.type_id);
-            decoded_abi_types.insert(typ.type_id);
+            if is_fuel_primitive(&ty) {
+                proc_macro_error::abort_call_site!("'{}' is a reserved Fuel type.", ty)
+            }
 
-            Some(quote! {
-                #name: Vec<#ty>
-            })
-        })
-        .collect::<Vec<proc_macro2::TokenStream>>();
+            let ty_name = ty.to_string();
+
+            // With several ABIs, types generated from an ABI are referred to
+            // through its module, so that ABIs can define types with the same name.
+            let (ty, field) = match name {
+                Some(name) => {
+                    let module = format_ident!("{}", name);
+                    let field = format_ident!("{}_{}", name, typ.rust_type_ident());
+                    let ty = if typ.components.is_some()
+                        && !SDK_PROVIDED_TYPES.contains(ty_name.as_str())
+                    {
+                        quote! { #module::#ty }
+                    } else {
+                        ty
+                    };
+
+                    decoder_fields
+                        .entry(format!("{name}::{ty_name}"))
+                        .or_default()
+                        .insert(field.clone());
+
+                    (ty, field)
+                }
+                None => (ty, typ.rust_type_ident()),
+            };
 
-    let decoder_struct_fields = [abi_struct_fields, fuel_struct_fields].concat();
+            decoder_fields
+                .entry(ty_name)
+                .or_default()
+                .insert(field.clone());
+
+            let ty_id = typ.type_id;
+            abi_type_decoders.push(decode_snippet(
+                quote! { (#index, #ty_id) },
+                &ty,
+                &field,
+            ));
+
+            if decoded_types.insert(field.to_string()) {
+                abi_struct_fields.push(quote! {
+                    #field: Vec<#ty>
+                });
+            }
+        }
 
-    let abi_selectors = funcs
-        .iter()
-        .map(|function| {
+        for function in &abi.functions {
             let params: Vec<ParamType> = function
                 .inputs
                 .iter()
@@ -201,33 +208,45 @@ fn process_fn_items(
             let sig = resolve_fn_selector(&function.name, &params[..]);
             let selector = u64::from_be_bytes(sig);
             let ty_id = function.output.type_id;
+            let fn_name = function.name.clone();
 
-            quote! {
-                #selector => #ty_id,
-            }
-        })
-        .collect::<Vec<proc_macro2::TokenStream>>();
+            abi_selectors.push(quote! {
+                (#index, #selector) => #ty_id,
+            });
 
-    let abi_selectors_to_fn_names = funcs
-        .iter()
-        .map(|function| {
-            let params: Vec<ParamType> = function
-                .inputs
-                .iter()
-                .map(|x| {
-                    ParamType::try_from_type_application(x, &abi_types_tyid)
-                        .expect("Could not derive TypeApplication param types.")
-                })
-                .collect();
-            let sig = resolve_fn_selector(&function.name, &params[..]);
-            let fn_name = function.name.clone();
-            let selector = u64::from_be_bytes(sig);
+            abi_selectors_to_fn_names.push(quote! {
+               (#index, #selector) => #fn_name.to_string(),
+            });
+        }
 
-            quote! {
-               #selector => #fn_name.to_string(),
+        for typ in abi.logged_types.iter().flatten() {
+            let ty_id = typ.application.type_id;
+            let log_id = typ.log_id as usize;
+
+            log_type_decoders.push(quote! {
+                (#index, #log_id) => {
+                    self.decode_abi_type(#index, #ty_id, data);
+                }
+            });
+        }
+
+        // Message receipts carry no contract ID, so a message type ID is decoded
+        // with the first ABI that defines it.
+        for typ in abi.messages_types.iter().flatten() {
+            let message_id = typ.message_id;
+            let ty_id = typ.application.type_id;
+
+            if message_ids.insert(message_id) {
+                message_types_decoders.push(quote! {
+                    #message_id => {
+                        self.decode_abi_type(#index, #ty_id, data.data.clone());
+                    }
+                });
             }
-        })
-        .collect::<Vec<proc_macro2::TokenStream>>();
+        }
+    }
+
+    let decoder_struct_fields = [abi_struct_fields, fuel_struct_fields].concat();
 
     let contents = indexer_module
         .content
@@ -247,20 +266,157 @@ fn process_fn_items(
         None => quote! {},
     };
 
-    let contract = match &manifest.contract_id {
-        Some(contract_id) => {
-            quote! {
-                let manifest_contract_id = Bech32ContractId::from_str(#contract_id).expect("Failed to parse manifest 'contract_id' as Bech32ContractId");
-                let receipt_contract_id = Bech32ContractId::from(id);
-                if receipt_contract_id != manifest_contract_id {
-                    Logger::info("Not subscribed to this contract. Will skip this receipt event. <('-'<)");
-                    continue;
-                }
-            }
+    let contract = quote! {
+        if decoder.contract_abi(id).is_none() {
+            Logger::info("Not subscribed to this contract. Will skip this receipt event. <('-'<)");
+            continue;
         }
-        None => quote! {},
     };
 
+    let contract_abi = quote! {
+        let abi = match decoder.contract_abi(id) {
+            Some(abi) => abi,
+            None => {
+                Logger::info("Not subscribed to this contract. Will skip this receipt event. <('-'<)");
+                continue;
+            }
+        };
+    };
+
+    // Calls are always tracked, since return data is decoded with the
+    // selector of the call that it is returned from.
+    let call_arm = if manifest.subscribes_to(ReceiptKind::Call) {
+        quote! { Receipt::Call { id: contract_id, amount, asset_id, gas, param1, to: id, .. } => {
+            #contract_abi
+
+            let fn_name = decoder.selector_to_fn_name(abi, param1);
+            return_types.push(param1);
+            callees.insert(id);
+
+            let data = bincode::serialize(&abi::Call { contract_id, to: id, amount, asset_id, gas, fn_name }).expect("Bad encoding");
+            let ty_id = abi::Call::type_id();
+            decoder.decode_type(ty_id, data);
+        } }
+    } else {
+        quote! { Receipt::Call { param1, to: id, .. } => {
+            #contract
+            return_types.push(param1);
+            callees.insert(id);
+        } }
+    };
+
+    // Receipts of kinds that the manifest does not subscribe to are skipped.
+    let receipt_arms = [
+        (
+            ReceiptKind::Log,
+            quote! { Receipt::Log { id, ra, rb, .. } => {
+                #contract
+                let ty_id = abi::Log::type_id();
+                let data = bincode::serialize(&abi::Log{ contract_id: id, ra, rb }).expect("Bad encoding,");
+                decoder.decode_type(ty_id, data);
+            } },
+            quote! { Receipt::Log { .. } => {} },
+        ),
+        (
+            ReceiptKind::LogData,
+            quote! { Receipt::LogData { rb, data, ptr, len, id, .. } => {
+                #contract_abi
+                decoder.decode_logdata(abi, rb as usize, data);
+            } },
+            quote! { Receipt::LogData { .. } => {} },
+        ),
+        (
+            ReceiptKind::Return,
+            quote! { Receipt::Return { id, val, pc, is } => {
+                #contract
+                if callees.contains(&id) {
+                    let ty_id = abi::Return::type_id();
+                    let data = bincode::serialize(&abi::Return{ contract_id: id, val, pc, is }).expect("Bad encoding,");
+                    decoder.decode_type(ty_id, data);
+                }
+            } },
+            quote! { Receipt::Return { .. } => {} },
+        ),
+        (
+            ReceiptKind::ReturnData,
+            quote! { Receipt::ReturnData { data, id, .. } => {
+                #contract_abi
+                if callees.contains(&id) {
+                    let selector = return_types.pop().expect("No return type available. <('-'<)");
+                    decoder.decode_return_type(abi, selector, data);
+                }
+            } },
+            quote! { Receipt::ReturnData { .. } => {} },
+        ),
+        (
+            ReceiptKind::MessageOut,
+            quote! { Receipt::MessageOut { message_id, sender, recipient, amount, nonce, len, digest, data } => {
+                let mut buf = [0u8; 8];
+                buf.copy_from_slice(&data[0..8]);
+                let type_id = u64::from_be_bytes(buf);
+                let receipt = abi::MessageOut{ message_id, sender, recipient, amount, nonce, len, digest, data: data[8..].to_vec() };
+                decoder.decode_messageout(type_id, receipt);
+            } },
+            quote! { Receipt::MessageOut { .. } => {} },
+        ),
+        (
+            ReceiptKind::ScriptResult,
+            quote! { Receipt::ScriptResult { result, gas_used } => {
+                let ty_id = abi::ScriptResult::type_id();
+                let data = bincode::serialize(&abi::ScriptResult{ result: u64::from(result), gas_used }).expect("Bad encoding,");
+                decoder.decode_type(ty_id, data);
+            } },
+            quote! { Receipt::ScriptResult { .. } => {} },
+        ),
+        (
+            ReceiptKind::Panic,
+            quote! { Receipt::Panic { id, reason, pc, is, .. } => {
+                let ty_id = abi::Panic::type_id();
+                let data = bincode::serialize(&abi::Panic{ contract_id: id, reason: u64::from(reason), pc, is }).expect("Bad encoding,");
+                decoder.decode_type(ty_id, data);
+            } },
+            quote! { Receipt::Panic { .. } => {} },
+        ),
+        (
+            ReceiptKind::Revert,
+            quote! { Receipt::Revert { id, ra, pc, is } => {
+                let ty_id = abi::Revert::type_id();
+                let data = bincode::serialize(&abi::Revert{ contract_id: id, error_val: ra, pc, is }).expect("Bad encoding,");
+                decoder.decode_type(ty_id, data);
+            } },
+            quote! { Receipt::Revert { .. } => {} },
+        ),
+        (
+            ReceiptKind::Transfer,
+            quote! { Receipt::Transfer { id, to, asset_id, amount, pc, is, .. } => {
+                #contract
+                let ty_id = abi::Transfer::type_id();
+                let data = bincode::serialize(&abi::Transfer{ contract_id: id, to, asset_id, amount, pc, is }).expect("Bad encoding,");
+                decoder.decode_type(ty_id, data);
+            } },
+            quote! { Receipt::Transfer { .. } => {} },
+        ),
+        (
+            ReceiptKind::TransferOut,
+            quote! { Receipt::TransferOut { id, to, asset_id, amount, pc, is, .. } => {
+                #contract
+                let ty_id = abi::TransferOut::type_id();
+                let data = bincode::serialize(&abi::TransferOut{ contract_id: id, to, asset_id, amount, pc, is }).expect("Bad encoding,");
+                decoder.decode_type(ty_id, data);
+            } },
+            quote! { Receipt::TransferOut { .. } => {} },
+        ),
+    ]
+    .into_iter()
+    .map(|(kind, arm, skipped)| {
+        if manifest.subscribes_to(kind) {
+            arm
+        } else {
+            skipped
+        }
+    })
+    .collect::<Vec<proc_macro2::TokenStream>>();
+
     let asyncness = if is_native {
         quote! {async}
     } else {
@@ -287,22 +443,42 @@ fn process_fn_items(
                         }
                         FnArg::Typed(PatType { ty, .. }) => {
                             if let Type::Path(path) = &**ty {
-                                let path = path
-                                    .path
-                                    .segments
+                                let segments = &path.path.segments;
+                                let path = segments
                                     .last()
                                     .expect("Could not get last path segment.");
 
                                 let path_ident = path.ident.to_string();
-                                let name = decoded_ident(&path_ident);
 
-                                if !type_ids.contains_key(&path_ident) {
-                                    proc_macro_error::abort_call_site!(
+                                // A type can be qualified with the name of its ABI,
+                                // e.g. `factory::SomeEvent`.
+                                let qualified = segments.len().checked_sub(2).map(|i| {
+                                    format!("{}::{}", segments[i].ident, path_ident)
+                                });
+
+                                let fields = match qualified
+                                    .and_then(|qualified| decoder_fields.get(&qualified))
+                                    .or_else(|| decoder_fields.get(&path_ident))
+                                {
+                                    Some(fields) => fields,
+                                    None => proc_macro_error::abort_call_site!(
                                         "Type with ident '{:?}' not defined in the ABI.",
                                         path.ident
-                                    );
+                                    ),
                                 };
 
+                                if fields.len() > 1 {
+                                    proc_macro_error::abort_call_site!(
+                                        "Type with ident '{:?}' is defined in several ABIs, qualify it with the name of its ABI.",
+                                        path.ident
+                                    )
+                                }
+
+                                let name = fields
+                                    .iter()
+                                    .next()
+                                    .expect("Could not get decoder field.");
+
                                 if DISALLOWED_ABI_JSON_TYPES.contains(path_ident.as_str())
                                 {
                                     proc_macro_error::abort_call_site!(
@@ -311,6 +487,16 @@ fn process_fn_items(
                                     )
                                 }
 
+                                if let Some(kind) = receipt_kind(&path_ident) {
+                                    if !manifest.subscribes_to(kind) {
+                                        proc_macro_error::abort_call_site!(
+                                            "Type with ident '{:?}' is decoded from '{}' receipts, which are not listed in the manifest's `receipts`.",
+                                            path.ident,
+                                            kind.as_ref()
+                                        )
+                                    }
+                                }
+
                                 input_checks.push(quote! { self.#name.len() > 0 });
 
                                 arg_list.push(quote! { self.#name[0].clone() });
@@ -324,13 +510,26 @@ fn process_fn_items(
                 }
 
                 let fn_name = &fn_item.sig.ident;
+                let handler_name = fn_name.to_string();
+
+                // Handlers run one at a time, in the order of the module, so an
+                // async handler completes before the next handler is called.
+                let call = match (fn_item.sig.asyncness.is_some(), is_native) {
+                    (true, true) => quote! { #fn_name(#(#arg_list),*).await },
+                    (true, false) => quote! { block_on(#fn_name(#(#arg_list),*)) },
+                    (false, _) => quote! { #fn_name(#(#arg_list),*) },
+                };
 
                 abi_dispatchers.push(quote! {
-                    if ( #(#input_checks)&&* ) {
-                        #fn_name(#(#arg_list),*)#awaitness;
+                    if handler_enabled(#handler_name)#awaitness {
+                        if ( #(#input_checks)&&* ) {
+                            #call;
+                        }
                     }
                 });
 
+                handler_names.push(handler_name);
+
                 handler_fns.push(fn_item);
             }
             i => {
@@ -342,6 +541,9 @@ fn process_fn_items(
         }
     }
 
+    // Lets the runtime check the handlers that a manifest enables.
+    let handlers_const = const_item("HANDLERS", &handler_names.join(","));
+
     let decoder_struct = quote! {
         #[derive(Default)]
         struct Decoders {
@@ -349,8 +551,17 @@ fn process_fn_items(
         }
 
         impl Decoders {
-            fn selector_to_type_id(&self, sel: u64) -> usize {
-                match sel {
+            #[allow(unused_variables)]
+            fn contract_abi(&self, contract_id: ContractId) -> Option<usize> {
+                let contract_id = Bech32ContractId::from(contract_id);
+
+                #(#contract_matchers)*
+
+                #fallback_abi
+            }
+
+            fn selector_to_type_id(&self, abi: usize, sel: u64) -> usize {
+                match (abi, sel) {
                     #(#abi_selectors)*
                     _ => {
                         Logger::warn("Unknown selector; check ABI to make sure function outputs match to types");
@@ -359,8 +570,8 @@ fn process_fn_items(
                 }
             }
 
-            pub fn selector_to_fn_name(&self, sel: u64) -> String {
-                match sel {
+            pub fn selector_to_fn_name(&self, abi: usize, sel: u64) -> String {
+                match (abi, sel) {
                     #(#abi_selectors_to_fn_names)*
                     _ => {
                         Logger::warn("Unknown selector; check ABI to make sure function outputs match to types");
@@ -371,7 +582,16 @@ fn process_fn_items(
 
             fn decode_type(&mut self, ty_id: usize, data: Vec<u8>) {
                 match ty_id {
-                    #(#decoders),*
+                    #(#fuel_type_decoders),*
+                    _ => {
+                        Logger::warn("Unknown type ID; check ABI to make sure types are well-formed");
+                    },
+                }
+            }
+
+            fn decode_abi_type(&mut self, abi: usize, ty_id: usize, data: Vec<u8>) {
+                match (abi, ty_id) {
+                    #(#abi_type_decoders),*
                     _ => {
                         Logger::warn("Unknown type ID; check ABI to make sure types are well-formed");
                     },
@@ -382,13 +602,23 @@ fn process_fn_items(
                 self.blockdata_decoded.push(data);
             }
 
-            pub fn decode_return_type(&mut self, sel: u64, data: Vec<u8>) {
-                let ty_id = self.selector_to_type_id(sel);
-                self.decode_type(ty_id, data);
+            pub fn decode_block_header(&mut self, data: BlockHeader) {
+                self.blockheader_decoded.push(data);
+            }
+
+            pub fn decode_transaction(&mut self, data: TransactionData) {
+                // Handlers are dispatched once per transaction, with the
+                // transaction being dispatched.
+                self.transactiondata_decoded = vec![data];
+            }
+
+            pub fn decode_return_type(&mut self, abi: usize, sel: u64, data: Vec<u8>) {
+                let ty_id = self.selector_to_type_id(abi, sel);
+                self.decode_abi_type(abi, ty_id, data);
             }
 
-            pub fn decode_logdata(&mut self, rb: usize, data: Vec<u8>) {
-                match rb {
+            pub fn decode_logdata(&mut self, abi: usize, rb: usize, data: Vec<u8>) {
+                match (abi, rb) {
                     #(#log_type_decoders),*
                     _ => Logger::warn("Unknown logged type ID; check ABI to make sure that logged types are well-formed")
                 }
@@ -417,78 +647,19 @@ fn process_fn_items(
                 let ty_id = abi::BlockData::type_id();
                 let data = bincode::serialize(&block).expect("Bad serialization.");
                 decoder.decode_type(ty_id, data);
+                decoder.decode_block_header(BlockHeader::from(&block));
 
                 for tx in block.transactions {
 
                     let mut return_types = Vec::new();
                     let mut callees = HashSet::new();
 
+                    decoder.decode_transaction(tx.clone());
+
                     for receipt in tx.receipts {
                         match receipt {
-                            Receipt::Call { id: contract_id, amount, asset_id, gas, param1, to: id, .. } => {
-                                #contract
-
-                                let fn_name = decoder.selector_to_fn_name(param1);
-                                return_types.push(param1);
-                                callees.insert(id);
-
-                                let data = bincode::serialize(&abi::Call { contract_id, to: id, amount, asset_id, gas, fn_name }).expect("Bad encoding");
-                                let ty_id = abi::Call::type_id();
-                                decoder.decode_type(ty_id, data);
-                            }
-                            Receipt::Log { id, ra, rb, .. } => {
-                                #contract
-                                let ty_id = abi::Log::type_id();
-                                let data = bincode::serialize(&abi::Log{ contract_id: id, ra, rb }).expect("Bad encoding,");
-                                decoder.decode_type(ty_id, data);
-                            }
-                            Receipt::LogData { rb, data, ptr, len, id, .. } => {
-                                #contract
-                                decoder.decode_logdata(rb as usize, data);
-
-                            }
-                            Receipt::Return { id, val, pc, is } => {
-                                #contract
-                                if callees.contains(&id) {
-                                    let ty_id = abi::Return::type_id();
-                                    let data = bincode::serialize(&abi::Return{ contract_id: id, val, pc, is }).expect("Bad encoding,");
-                                    decoder.decode_type(ty_id, data);
-                                }
-                            }
-                            Receipt::ReturnData { data, id, .. } => {
-                                #contract
-                                if callees.contains(&id) {
-                                    let selector = return_types.pop().expect("No return type available. <('-'<)");
-                                    decoder.decode_return_type(selector, data);
-                                }
-                            }
-                            Receipt::MessageOut { message_id, sender, recipient, amount, nonce, len, digest, data } => {
-                                let mut buf = [0u8; 8];
-                                buf.copy_from_slice(&data[0..8]);
-                                let type_id = u64::from_be_bytes(buf);
-                                let receipt = abi::MessageOut{ message_id, sender, recipient, amount, nonce, len, digest, data: data[8..].to_vec() };
-                                decoder.decode_messageout(type_id, receipt);
-                            }
-                            Receipt::ScriptResult { result, gas_used } => {
-                                let ty_id = abi::ScriptResult::type_id();
-                                let data = bincode::serialize(&abi::ScriptResult{ result: u64::from(result), gas_used }).expect("Bad encoding,");
-                                decoder.decode_type(ty_id, data);
-                            }
-                            Receipt::Transfer { id, to, asset_id, amount, pc, is, .. } => {
-                                #contract
-                                let ty_id = abi::Transfer::type_id();
-                                let data = bincode::serialize(&abi::Transfer{ contract_id: id, to, asset_id, amount, pc, is }).expect("Bad encoding,");
-                                decoder.decode_type(ty_id, data);
-                            }
-                            Receipt::TransferOut { id, to, asset_id, amount, pc, is, .. } => {
-                                #contract
-                                let ty_id = abi::TransferOut::type_id();
-                                let data = bincode::serialize(&abi::TransferOut{ contract_id: id, to, asset_id, amount, pc, is }).expect("Bad encoding,");
-                                decoder.decode_type(ty_id, data);
-                            }
-                            _ => {
-                                Logger::info("This type is not handled yet. (>'.')>");
-                            }
+                            #call_arm
+                            #(#receipt_arms)*
                         }
                     }
 
@@ -502,55 +673,44 @@ fn process_fn_items(
         quote! {
             #decoder_struct
 
+            #handlers_const
+
             #(#handler_fns)*
         },
     )
 }
 
+/// Prefix `path` with the directory in `COMPILE_TEST_PREFIX`, if it is set.
+fn prefix_compile_test_path(path: &str) -> String {
+    match std::env::var("COMPILE_TEST_PREFIX") {
+        Ok(prefix) => std::path::Path::new(&prefix)
+            .join(path)
+            .into_os_string()
+            .to_str()
+            .expect("Could not parse prefixed path.")
+            .to_string(),
+        Err(_) => path.to_string(),
+    }
+}
+
 pub fn prefix_abi_and_schema_paths(
     abi: Option<&String>,
     schema_string: String,
 ) -> (Option<String>, String) {
-    if let Some(abi) = abi {
-        match std::env::var("COMPILE_TEST_PREFIX") {
-            Ok(prefix) => {
-                let prefixed = std::path::Path::new(&prefix).join(abi);
-                let abi_string = prefixed
-                    .into_os_string()
-                    .to_str()
-                    .expect("Could not parse prefixed ABI path.")
-                    .to_string();
-                let prefixed = std::path::Path::new(&prefix).join(&schema_string);
-                let schema_string = prefixed
-                    .into_os_string()
-                    .to_str()
-                    .expect("Could not parse prefixed GraphQL schema path.")
-                    .to_string();
-
-                return (Some(abi_string), schema_string);
-            }
-            Err(_) => {
-                return (Some(abi.into()), schema_string);
-            }
-        };
+    match abi {
+        Some(abi) => (
+            Some(prefix_compile_test_path(abi)),
+            prefix_compile_test_path(&schema_string),
+        ),
+        None => (None, schema_string),
     }
-
-    (None, schema_string)
 }
 
 pub fn get_abi_tokens(
-    namespace: &str,
-    abi: &str,
+    targets: Vec<AbigenTarget>,
     is_native: bool,
 ) -> proc_macro2::TokenStream {
-    match Abigen::generate(
-        vec![AbigenTarget {
-            name: namespace.to_string(),
-            abi: abi.to_owned(),
-            program_type: ProgramType::Contract,
-        }],
-        !is_native,
-    ) {
+    match Abigen::generate(targets, !is_native) {
         Ok(tokens) => tokens,
         Err(e) => {
             proc_macro_error::abort_call_site!(
@@ -561,6 +721,146 @@ pub fn get_abi_tokens(
     }
 }
 
+/// Generate the bindings of every ABI of a multi-ABI indexer, along with a
+/// module per ABI that its types can be referred to through.
+fn get_multi_abi_tokens(
+    abis: &[(String, String)],
+    is_native: bool,
+) -> proc_macro2::TokenStream {
+    let targets = abis
+        .iter()
+        .map(|(name, abi)| AbigenTarget {
+            name: name.clone(),
+            abi: abi.clone(),
+            program_type: ProgramType::Contract,
+        })
+        .collect();
+
+    let abi_tokens = get_abi_tokens(targets, is_native);
+
+    // Types defined identically by several ABIs are generated once, in a
+    // separate module.
+    let shared_types = if abi_tokens.to_string().contains("mod shared_types") {
+        quote! { pub use super::abigen_bindings::shared_types::*; }
+    } else {
+        quote! {}
+    };
+
+    let modules = abis.iter().map(|(name, _)| {
+        let module = format_ident!("{}", name);
+        let bindings = format_ident!("{}_mod", name);
+
+        quote! {
+            pub mod #module {
+                pub use super::abigen_bindings::#bindings::*;
+                #shared_types
+            }
+        }
+    });
+
+    quote! {
+        #abi_tokens
+
+        #(#modules)*
+    }
+}
+
+/// Parse the ABIs of an indexer, returning them along with their bindings.
+fn indexer_abis(
+    manifest: &Manifest,
+    abi: Option<String>,
+) -> (Vec<IndexerAbi>, proc_macro2::TokenStream) {
+    let is_native = manifest.is_native();
+
+    if manifest.abis.is_empty() {
+        let abi_tokens = match abi {
+            Some(ref abi_path) => get_abi_tokens(
+                vec![AbigenTarget {
+                    name: manifest.namespace.clone(),
+                    abi: abi_path.to_owned(),
+                    program_type: ProgramType::Contract,
+                }],
+                is_native,
+            ),
+            None => proc_macro2::TokenStream::new(),
+        };
+
+        let abis = vec![IndexerAbi {
+            name: None,
+            abi: get_json_abi(abi).unwrap_or_default(),
+            // A list of contracts is filtered by the executor, so the ABI
+            // decodes the receipts of each of them.
+            contract_id: match &manifest.contract_id {
+                Some(ContractIds::One(contract_id)) => Some(contract_id.clone()),
+                _ => None,
+            },
+        }];
+
+        return (abis, abi_tokens);
+    }
+
+    if manifest.abi.is_some() || manifest.contract_id.is_some() {
+        proc_macro_error::abort_call_site!(
+            "`abi` and `contract_id` cannot be used with `abis`, set them on each ABI instead."
+        )
+    }
+
+    let mut names = HashSet::new();
+    for contract_abi in &manifest.abis {
+        if syn::parse_str::<syn::Ident>(&contract_abi.name).is_err() {
+            proc_macro_error::abort_call_site!(
+                "ABI name '{}' is not a valid identifier.",
+                contract_abi.name
+            )
+        }
+
+        if !names.insert(contract_abi.name.clone()) {
+            proc_macro_error::abort_call_site!(
+                "ABI name '{}' is used more than once.",
+                contract_abi.name
+            )
+        }
+    }
+
+    if manifest
+        .abis
+        .iter()
+        .filter(|contract_abi| contract_abi.contract_id.is_none())
+        .count()
+        > 1
+    {
+        proc_macro_error::abort_call_site!(
+            "At most one ABI can be used without a `contract_id`."
+        )
+    }
+
+    let paths = manifest
+        .abis
+        .iter()
+        .map(|contract_abi| {
+            (
+                contract_abi.name.clone(),
+                prefix_compile_test_path(&contract_abi.abi),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let abi_tokens = get_multi_abi_tokens(&paths, is_native);
+
+    let abis = manifest
+        .abis
+        .iter()
+        .zip(paths)
+        .map(|(contract_abi, (name, path))| IndexerAbi {
+            name: Some(name),
+            abi: get_json_abi(Some(path)).unwrap_or_default(),
+            contract_id: contract_abi.contract_id.clone(),
+        })
+        .collect();
+
+    (abis, abi_tokens)
+}
+
 pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStream {
     let config = parse_macro_input!(attrs as IndexerConfig);
 
@@ -577,29 +877,36 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
         namespace,
         identifier,
         graphql_schema,
+        graphql_schemas,
         ..
     } = manifest.clone();
 
     let indexer_module = parse_macro_input!(item as ItemMod);
     let is_native = manifest.is_native();
 
-    let (abi, schema_string) = prefix_abi_and_schema_paths(abi.as_ref(), graphql_schema);
-
-    let abi_tokens = match abi {
-        Some(ref abi_path) => get_abi_tokens(&namespace, abi_path, is_native),
-        None => proc_macro2::TokenStream::new(),
+    let (abi, schema_string) = match manifest.abis.first() {
+        Some(_) => (None, prefix_compile_test_path(&graphql_schema)),
+        None => prefix_abi_and_schema_paths(abi.as_ref(), graphql_schema),
     };
+    let prefixed = manifest.abi.is_some() || !manifest.abis.is_empty();
+    let schema_paths = std::iter::once(schema_string)
+        .chain(graphql_schemas.iter().map(|path| {
+            if prefixed {
+                prefix_compile_test_path(path)
+            } else {
+                path.clone()
+            }
+        }))
+        .collect();
+
+    let (abis, abi_tokens) = indexer_abis(&manifest, abi);
 
     // NOTE: https://nickb.dev/blog/cargo-workspace-and-the-feature-unification-pitfall/
-    let graphql_tokens = process_graphql_schema(
-        namespace,
-        identifier,
-        schema_string,
-        manifest.is_native(),
-    );
+    let graphql_tokens =
+        process_graphql_schema(namespace, identifier, schema_paths, manifest.is_native());
 
     let output = if is_native {
-        let (handler_block, fn_items) = process_fn_items(&manifest, abi, indexer_module);
+        let (handler_block, fn_items) = process_fn_items(&manifest, abis, indexer_module);
         let handler_block = handler_block_native(handler_block);
 
         quote! {
@@ -643,7 +950,11 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
                     (None, None)
                 };
 
-                let pool = IndexerConnectionPool::connect(&config.database.to_string()).await?;
+                let pool = IndexerConnectionPool::connect_with_config(
+                    &config.database.to_string(),
+                    ConnectionPoolConfig::from(&config),
+                )
+                .await?;
 
                 let mut c = pool.acquire().await?;
                 queries::run_migration(&mut c).await?;
@@ -657,6 +968,13 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
                 let p = opt.manifest.unwrap();
                 info!("Using manifest file located at '{}'.", p.display());
                 let manifest = Manifest::from_file(&p)?;
+
+                let handlers: Vec<String> = HANDLERS.split(',').map(String::from).collect();
+                let unknown = manifest.unknown_handlers(&handlers);
+                if !unknown.is_empty() {
+                    anyhow::bail!("Unknown handlers in `enabled_handlers`: {}.", unknown.join(", "));
+                }
+
                 service.register_native_index(manifest, handle_events).await?;
                 let service_handle = tokio::spawn(service.run());
 
@@ -668,7 +986,7 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
 
         }
     } else {
-        let (handler_block, fn_items) = process_fn_items(&manifest, abi, indexer_module);
+        let (handler_block, fn_items) = process_fn_items(&manifest, abis, indexer_module);
         let handler_block = handler_block_wasm(handler_block);
 
         quote! {
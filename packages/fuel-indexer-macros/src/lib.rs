@@ -35,6 +35,8 @@ mod tests {
         t.compile_fail(macro_data_root.join("fail_if_attribute_args_not_included.rs"));
         t.pass(macro_data_root.join("pass_if_indexer_is_valid_single_type.rs"));
         t.pass(macro_data_root.join("pass_if_indexer_is_valid_multi_type.rs"));
+        t.pass(macro_data_root.join("pass_if_indexer_is_valid_multi_abi.rs"));
+        t.pass(macro_data_root.join("pass_if_indexer_has_async_handlers.rs"));
         t.compile_fail(
             macro_data_root.join("fail_if_attribute_schema_arg_is_invalid.rs"),
         );
@@ -42,5 +44,11 @@ mod tests {
             macro_data_root.join("fail_if_attribute_abi_arg_includes_invalid_type.rs"),
         );
         t.compile_fail(macro_data_root.join("fail_if_indexer_module_is_empty.rs"));
+        t.pass(
+            macro_data_root.join("pass_if_indexer_handles_transactions_and_receipts.rs"),
+        );
+        t.compile_fail(
+            macro_data_root.join("fail_if_handler_takes_unsubscribed_receipt.rs"),
+        );
     }
 }
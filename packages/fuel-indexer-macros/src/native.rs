@@ -38,5 +38,22 @@ fn native_prelude() -> proc_macro2::TokenStream {
 
         static mut db: Option<Arc<Mutex<Database>>> = None;
 
+        async fn handler_enabled(name: &str) -> bool {
+            unsafe {
+                match &db {
+                    Some(d) => d.lock().await.handler_enabled(name),
+                    None => true,
+                }
+            }
+        }
+
+        async fn metric_inc(name: &str, value: u64) {
+            unsafe {
+                if let Some(d) = &db {
+                    d.lock().await.metric_inc(name, value);
+                }
+            }
+        }
+
     }
 }
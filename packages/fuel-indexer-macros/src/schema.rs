@@ -1,13 +1,16 @@
-use fuel_indexer_database_types::directives;
-use fuel_indexer_lib::utils::local_repository_root;
+use fuel_indexer_database_types::{directives, IdCol};
+use fuel_indexer_lib::{manifest::combine_schemas, utils::local_repository_root};
 use fuel_indexer_schema::utils::{
-    build_schema_fields_and_types_map, build_schema_objects_set, get_join_directive_info,
-    inject_native_entities_into_schema, schema_version, BASE_SCHEMA,
+    build_schema_enums_map, build_schema_fields_and_types_map, build_schema_objects_set,
+    get_derived_id_directive, get_join_directive_info,
+    inject_native_entities_into_schema, is_array_type, is_list_type,
+    normalize_field_type_name, schema_version, BASE_SCHEMA,
 };
 use fuel_indexer_types::type_id;
 use graphql_parser::parse_schema;
 use graphql_parser::schema::{
-    Definition, Document, Field, ObjectType, SchemaDefinition, Type, TypeDefinition,
+    Definition, Document, EnumType, Field, ObjectType, SchemaDefinition, Type,
+    TypeDefinition,
 };
 use lazy_static::lazy_static;
 use proc_macro2::{TokenStream, TokenTree};
@@ -19,7 +22,7 @@ use std::path::{Path, PathBuf};
 
 lazy_static! {
     static ref COPY_TYPES: HashSet<&'static str> =
-        HashSet::from(["Json", "Charfield", "Identity", "Blob"]);
+        HashSet::from(["Json", "Charfield", "Identity", "Blob", "Bytes", "Decimal"]);
 }
 
 fn process_type(
@@ -88,6 +91,187 @@ fn process_field(
     (typ, ident, extractor)
 }
 
+/// Process a field holding a list of scalars, which is stored in an array column.
+fn process_array_field(
+    field: &Field<String>,
+) -> (
+    proc_macro2::TokenStream,
+    proc_macro2::Ident,
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+) {
+    let Field {
+        name, field_type, ..
+    } = field;
+    let ident = format_ident! {"{}", name};
+
+    let (is_nullable, list_type) = match field_type {
+        Type::NonNullType(t) => (false, t.as_ref()),
+        t => (true, t),
+    };
+    let item_type = match list_type {
+        Type::ListType(t) => match t.as_ref() {
+            Type::NonNullType(t) => match t.as_ref() {
+                Type::NamedType(t) => format_ident! {"{}", t},
+                _ => panic!("Field '{name}' must be a list of scalars."),
+            },
+            _ => panic!("Items of list field '{name}' must be non-null."),
+        },
+        _ => panic!("Field '{name}' is not a list."),
+    };
+
+    let typ = if is_nullable {
+        quote! { Option<Vec<#item_type>> }
+    } else {
+        quote! { Vec<#item_type> }
+    };
+
+    let items = quote! {
+        items
+            .into_iter()
+            .map(|item| match item {
+                FtColumn::#item_type(Some(t)) => t,
+                _ => panic!("Invalid array item: {:?}.", item),
+            })
+            .collect()
+    };
+
+    let (extractor, decoder) = if is_nullable {
+        (
+            quote! {
+                let item = vec.pop().expect("Missing item in row.");
+                let #ident = match item {
+                    FtColumn::Array(t) => t.map(|items| #items),
+                    _ => panic!("Invalid column type: {:?}.", item),
+                };
+            },
+            quote! {
+                FtColumn::Array(self.#ident.as_ref().map(|items| {
+                    items.iter().map(|t| FtColumn::#item_type(Some(t.clone()))).collect()
+                })),
+            },
+        )
+    } else {
+        (
+            quote! {
+                let item = vec.pop().expect("Missing item in row.");
+                let #ident = match item {
+                    FtColumn::Array(Some(items)) => #items,
+                    FtColumn::Array(None) => {
+                        panic!("Non-nullable type is returning a None value.")
+                    }
+                    _ => panic!("Invalid column type: {:?}.", item),
+                };
+            },
+            quote! {
+                FtColumn::Array(Some(
+                    self.#ident.iter().map(|t| FtColumn::#item_type(Some(t.clone()))).collect()
+                )),
+            },
+        )
+    };
+
+    (typ, ident, extractor, decoder)
+}
+
+/// Process a field holding one of the schema's enums, which is stored by the
+/// name of its value.
+fn process_enum_field(
+    types: &HashSet<String>,
+    field: &Field<String>,
+) -> (
+    proc_macro2::TokenStream,
+    proc_macro2::Ident,
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+) {
+    let Field {
+        name, field_type, ..
+    } = field;
+    let typ = process_type(types, field_type, true);
+    let ident = format_ident! {"{}", name};
+
+    let (is_nullable, enum_type) = get_column_type(typ.clone());
+
+    let (extractor, decoder) = if is_nullable {
+        (
+            quote! {
+                let item = vec.pop().expect("Missing item in row.");
+                let #ident = match item {
+                    FtColumn::Enum(t) => t.map(|v| {
+                        v.parse::<#enum_type>()
+                            .unwrap_or_else(|e: InvalidEnumValue| panic!("{e}"))
+                    }),
+                    _ => panic!("Invalid column type: {:?}.", item),
+                };
+            },
+            quote! { FtColumn::Enum(self.#ident.as_ref().map(ToString::to_string)), },
+        )
+    } else {
+        (
+            quote! {
+                let item = vec.pop().expect("Missing item in row.");
+                let #ident = match item {
+                    FtColumn::Enum(Some(v)) => v
+                        .parse::<#enum_type>()
+                        .unwrap_or_else(|e: InvalidEnumValue| panic!("{e}")),
+                    FtColumn::Enum(None) => {
+                        panic!("Non-nullable type is returning a None value.")
+                    }
+                    _ => panic!("Invalid column type: {:?}.", item),
+                };
+            },
+            quote! { FtColumn::Enum(Some(self.#ident.to_string())), },
+        )
+    };
+
+    (typ, ident, extractor, decoder)
+}
+
+/// Generate a Rust enum for a GraphQL enum, convertible to and from the names
+/// of its values.
+fn process_enum_def(enum_type: &EnumType<String>) -> proc_macro2::TokenStream {
+    let name = &enum_type.name;
+    let ident = format_ident! {"{}", name};
+    let variants = enum_type
+        .values
+        .iter()
+        .map(|v| format_ident! {"{}", v.name})
+        .collect::<Vec<_>>();
+    let values = enum_type.values.iter().map(|v| v.name.as_str());
+
+    quote! {
+        #[allow(non_camel_case_types)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum #ident {
+            #(#variants),*
+        }
+
+        impl std::fmt::Display for #ident {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    #(Self::#variants => write!(f, #values)),*
+                }
+            }
+        }
+
+        impl std::str::FromStr for #ident {
+            type Err = InvalidEnumValue;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                #(if s == stringify!(#variants) {
+                    return Ok(Self::#variants);
+                })*
+
+                Err(InvalidEnumValue {
+                    enum_name: #name.to_string(),
+                    value: s.to_string(),
+                })
+            }
+        }
+    }
+}
+
 fn process_fk_field<'a>(
     types: &HashSet<String>,
     obj: &ObjectType<'a, String>,
@@ -147,9 +331,11 @@ fn process_type_def(
     processed: &mut HashSet<String>,
     primitives: &HashSet<String>,
     types_map: &HashMap<String, String>,
+    enums: &HashMap<String, Vec<String>>,
     is_native: bool,
 ) -> Option<proc_macro2::TokenStream> {
     match typ {
+        TypeDefinition::Enum(e) => Some(process_enum_def(e)),
         TypeDefinition::Object(obj) => {
             if obj.name == *query_root {
                 return None;
@@ -161,8 +347,45 @@ fn process_type_def(
             let mut row_extractors = quote! {};
             let mut construction = quote! {};
             let mut flattened = quote! {};
+            // Columns of the fields that IDs are derived from, by field name.
+            let derived_id_fields = get_derived_id_directive(obj);
+            let mut derived_id_columns = HashMap::new();
+
+            // List fields are resolved from the foreign key on the listed entity,
+            // so they aren't stored on this entity.
+            for field in obj.fields.iter().filter(|f| !is_list_type(f)) {
+                let is_array = is_array_type(field);
+                if is_array
+                    || enums.contains_key(&normalize_field_type_name(
+                        &field.field_type.to_string(),
+                    ))
+                {
+                    let (type_name, field_name, ext, decoder) = if is_array {
+                        process_array_field(field)
+                    } else {
+                        process_enum_field(types, field)
+                    };
+
+                    block = quote! {
+                        #block
+                        #field_name: #type_name,
+                    };
+                    row_extractors = quote! {
+                        #ext
+                        #row_extractors
+                    };
+                    construction = quote! {
+                        #construction
+                        #field_name,
+                    };
+                    derived_id_columns.insert(field.name.as_str(), decoder.clone());
+                    flattened = quote! {
+                        #flattened
+                        #decoder
+                    };
+                    continue;
+                }
 
-            for field in &obj.fields {
                 let (mut type_name, mut field_name, mut ext) =
                     process_field(types, field);
 
@@ -187,6 +410,17 @@ fn process_type_def(
                 } else {
                     quote! { FtColumn::#column_type_name(Some(self.#field_name.clone())), }
                 };
+                derived_id_columns.insert(field.name.as_str(), decoder.clone());
+
+                // Types with derived IDs are saved with the ID of their fields,
+                // whatever ID they hold.
+                let decoder = if derived_id_fields.is_some()
+                    && field.name == IdCol::to_lowercase_string()
+                {
+                    quote! { FtColumn::ID(Some(self.derived_id())), }
+                } else {
+                    decoder
+                };
 
                 block = quote! {
                     #block
@@ -213,6 +447,28 @@ fn process_type_def(
 
             processed.insert(strct.to_string());
 
+            // The derived ID hashes the columns of its fields, which are the
+            // same on every run, so re-indexing derives the same IDs.
+            let derived_id = derived_id_fields.map(|fields| {
+                let columns = fields.iter().map(|field| {
+                    derived_id_columns.get(field.as_str()).unwrap_or_else(|| {
+                        panic!(
+                            "Type '{name}' derives its IDs from unknown field '{field}'."
+                        )
+                    })
+                });
+
+                quote! {
+                    impl #strct {
+                        /// ID of this object, derived from the fields listed
+                        /// by the type's `@derivedId` directive.
+                        pub fn derived_id(&self) -> u64 {
+                            first8_bytes_to_u64(serialize(&vec![#(#columns)*]))
+                        }
+                    }
+                }
+            });
+
             if is_native {
                 Some(quote! {
                     #[derive(Debug, PartialEq, Eq, Hash)]
@@ -220,6 +476,8 @@ fn process_type_def(
                         #block
                     }
 
+                    #derived_id
+
                     #[async_trait::async_trait]
                     impl Entity for #strct {
                         const TYPE_ID: i64 = #type_id;
@@ -255,6 +513,40 @@ fn process_type_def(
                             }
                         }
 
+                        async fn load_by(column: &str, value: FtColumn) -> Option<Self> {
+                            unsafe {
+                                match &db {
+                                    Some(d) => {
+                                        match d.lock().await.get_object_by(Self::TYPE_ID, column, value).await.expect("Failed to load object.") {
+                                            Some(bytes) => {
+                                                let columns: Vec<FtColumn> = bincode::deserialize(&bytes).expect("Serde error.");
+                                                Some(Self::from_row(columns))
+                                            },
+                                            None => None,
+                                        }
+                                    }
+                                    None => None,
+                                }
+                            }
+                        }
+
+                        async fn load_by_key(key: Vec<FtColumn>) -> Option<Self> {
+                            unsafe {
+                                match &db {
+                                    Some(d) => {
+                                        match d.lock().await.get_object_by_key(Self::TYPE_ID, key).await.expect("Failed to load object.") {
+                                            Some(bytes) => {
+                                                let columns: Vec<FtColumn> = bincode::deserialize(&bytes).expect("Serde error.");
+                                                Some(Self::from_row(columns))
+                                            },
+                                            None => None,
+                                        }
+                                    }
+                                    None => None,
+                                }
+                            }
+                        }
+
                         async fn save(&self) {
                             unsafe {
                                 match &db {
@@ -263,12 +555,43 @@ fn process_type_def(
                                             Self::TYPE_ID,
                                             self.to_row(),
                                             serialize(&self.to_row())
+                                        ).await.expect("Failed to save object.");
+                                    }
+                                    None => {},
+                                }
+                            }
+                        }
+
+                        async fn remove(&self) {
+                            unsafe {
+                                match &db {
+                                    Some(d) => {
+                                        d.lock().await.delete_object(
+                                            Self::TYPE_ID,
+                                            self.to_row(),
                                         ).await;
                                     }
                                     None => {},
                                 }
                             }
                         }
+
+                        async fn find_ids(column: &str, value: FtColumn) -> Vec<u64> {
+                            unsafe {
+                                match &db {
+                                    Some(d) => {
+                                        match d.lock().await.find_ids(Self::TYPE_ID, column, value).await {
+                                            Ok(ids) => ids,
+                                            Err(e) => {
+                                                error!("Failed to find IDs by {column}: {e}");
+                                                Vec::new()
+                                            }
+                                        }
+                                    }
+                                    None => Vec::new(),
+                                }
+                            }
+                        }
                     }
                 })
             } else {
@@ -278,6 +601,8 @@ fn process_type_def(
                         #block
                     }
 
+                    #derived_id
+
                     impl Entity for #strct {
                         const TYPE_ID: i64 = #type_id;
 
@@ -311,12 +636,13 @@ fn process_definition(
     processed: &mut HashSet<String>,
     primitives: &HashSet<String>,
     types_map: &HashMap<String, String>,
+    enums: &HashMap<String, Vec<String>>,
     is_native: bool,
 ) -> Option<proc_macro2::TokenStream> {
     match definition {
         Definition::TypeDefinition(def) => process_type_def(
             query_root, namespace, identifier, types, def, processed, primitives,
-            types_map, is_native,
+            types_map, enums, is_native,
         ),
         Definition::SchemaDefinition(_def) => None,
         def => {
@@ -348,7 +674,7 @@ fn get_query_root(types: &HashSet<String>, ast: &Document<String>) -> String {
     name
 }
 
-fn const_item(id: &str, value: &str) -> proc_macro2::TokenStream {
+pub(crate) fn const_item(id: &str, value: &str) -> proc_macro2::TokenStream {
     let ident = format_ident! {"{}", id};
 
     let fn_ptr = format_ident! {"get_{}_ptr", id.to_lowercase()};
@@ -372,27 +698,36 @@ fn const_item(id: &str, value: &str) -> proc_macro2::TokenStream {
 pub(crate) fn process_graphql_schema(
     namespace: String,
     identifier: String,
-    schema_path: String,
+    schema_paths: Vec<String>,
     is_native: bool,
 ) -> proc_macro2::TokenStream {
-    let path = match local_repository_root() {
-        Some(p) => Path::new(&p).join(schema_path),
-        None => PathBuf::from(&schema_path),
-    };
+    let mut files = Vec::new();
+    for schema_path in schema_paths {
+        let path = match local_repository_root() {
+            Some(p) => Path::new(&p).join(&schema_path),
+            None => PathBuf::from(&schema_path),
+        };
+
+        let mut file = match File::open(&path) {
+            Ok(f) => f,
+            Err(e) => {
+                proc_macro_error::abort_call_site!(
+                    "Could not open schema file {:?} {:?}",
+                    path,
+                    e
+                )
+            }
+        };
 
-    let mut file = match File::open(&path) {
-        Ok(f) => f,
-        Err(e) => {
-            proc_macro_error::abort_call_site!(
-                "Could not open schema file {:?} {:?}",
-                path,
-                e
-            )
-        }
-    };
+        let mut text = String::new();
+        file.read_to_string(&mut text).expect("IO error");
+        files.push((schema_path, text));
+    }
 
-    let mut text = String::new();
-    file.read_to_string(&mut text).expect("IO error");
+    let text = match combine_schemas(&files) {
+        Ok(text) => text,
+        Err(e) => proc_macro_error::abort_call_site!("{}", e),
+    };
 
     let text = inject_native_entities_into_schema(&text);
 
@@ -427,6 +762,7 @@ pub(crate) fn process_graphql_schema(
 
     let mut processed: HashSet<String> = HashSet::new();
     let types_map: HashMap<String, String> = build_schema_fields_and_types_map(&ast);
+    let enums = build_schema_enums_map(&ast);
 
     for definition in ast.definitions.iter() {
         if let Some(def) = process_definition(
@@ -438,6 +774,7 @@ pub(crate) fn process_graphql_schema(
             &mut processed,
             &primitives,
             &types_map,
+            &enums,
             is_native,
         ) {
             output = quote! {
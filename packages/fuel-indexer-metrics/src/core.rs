@@ -1,5 +1,14 @@
 use crate::queries::PostgreQueries;
-use prometheus::{self, register_int_counter, IntCounter};
+use prometheus::{
+    self, register_histogram_vec, register_int_counter, register_int_counter_vec,
+    register_int_gauge_vec, HistogramVec, IntCounter, IntCounterVec, IntGaugeVec,
+};
+
+/// Labels of the metrics kept for each indexer.
+const INDEXER_LABELS: &[&str] = &["namespace", "identifier"];
+
+/// Labels of the counters that an indexer's handlers increment by name.
+const HANDLER_METRIC_LABELS: &[&str] = &["namespace", "identifier", "name"];
 
 pub trait Metric {
     fn init() -> Self;
@@ -65,9 +74,57 @@ impl Metric for Web {
     }
 }
 
+/// Indexing progress of each indexer, labeled by `namespace` and `identifier`.
+#[derive(Clone, Debug)]
+pub struct Executor {
+    pub blocks_processed: IntCounterVec,
+    pub objects_written: IntCounterVec,
+    pub handler_duration_seconds: HistogramVec,
+    pub lag_blocks: IntGaugeVec,
+    pub handler_metrics: IntCounterVec,
+}
+
+impl Metric for Executor {
+    fn init() -> Self {
+        Self {
+            blocks_processed: register_int_counter_vec!(
+                "indexer_blocks_processed",
+                "Count of blocks processed by an indexer.",
+                INDEXER_LABELS
+            )
+            .unwrap(),
+            objects_written: register_int_counter_vec!(
+                "indexer_objects_written",
+                "Count of objects written by an indexer.",
+                INDEXER_LABELS
+            )
+            .unwrap(),
+            handler_duration_seconds: register_histogram_vec!(
+                "indexer_handler_duration_seconds",
+                "Time an indexer spent handling each batch of blocks.",
+                INDEXER_LABELS
+            )
+            .unwrap(),
+            lag_blocks: register_int_gauge_vec!(
+                "indexer_lag_blocks",
+                "Number of blocks an indexer is behind the Fuel node.",
+                INDEXER_LABELS
+            )
+            .unwrap(),
+            handler_metrics: register_int_counter_vec!(
+                "indexer_handler_metric",
+                "Counters incremented by name from an indexer's handlers.",
+                HANDLER_METRIC_LABELS
+            )
+            .unwrap(),
+        }
+    }
+}
+
 pub struct Metrics {
     pub web: Web,
     pub db: Database,
+    pub executor: Executor,
 }
 
 impl Metric for Metrics {
@@ -75,6 +132,7 @@ impl Metric for Metrics {
         Self {
             web: Web::init(),
             db: Database::init(),
+            executor: Executor::init(),
         }
     }
 }
@@ -13,9 +13,15 @@ pub struct PostgreQueries {
     pub new_column_insert_calls: IntCounter,
     pub list_column_by_id_calls: IntCounter,
     pub columns_get_schema_calls: IntCounter,
+    pub indexed_columns_calls: IntCounter,
     pub put_object_calls: IntCounter,
+    pub put_objects_calls: IntCounter,
     pub get_object_calls: IntCounter,
+    pub get_object_by_calls: IntCounter,
+    pub soft_delete_object_calls: IntCounter,
+    pub get_object_ids_calls: IntCounter,
     pub run_query_calls: IntCounter,
+    pub run_read_only_query_calls: IntCounter,
     pub execute_query_calls: IntCounter,
     pub root_columns_list_by_id_calls: IntCounter,
     pub new_root_columns_calls: IntCounter,
@@ -26,6 +32,7 @@ pub struct PostgreQueries {
     pub register_index_asset_calls: IntCounter,
     pub latest_asset_for_index_calls: IntCounter,
     pub latest_assets_for_index_calls: IntCounter,
+    pub assets_for_index_calls: IntCounter,
     pub last_block_height_for_indexer_calls: IntCounter,
     pub asset_already_exists_calls: IntCounter,
     pub index_id_for_calls: IntCounter,
@@ -36,6 +43,19 @@ pub struct PostgreQueries {
     pub run_migration_calls: IntCounter,
     pub remove_asset_by_version_calls: IntCounter,
     pub remove_indexer: IntCounter,
+    pub indexer_tables_calls: IntCounter,
+    pub purge_indexer_calls: IntCounter,
+    pub table_rows_as_json_calls: IntCounter,
+    pub insert_json_rows_calls: IntCounter,
+    pub put_failed_block_calls: IntCounter,
+    pub failed_blocks_calls: IntCounter,
+    pub put_object_writes_calls: IntCounter,
+    pub revert_to_block_calls: IntCounter,
+    pub prune_object_writes_calls: IntCounter,
+    pub object_writes_batch_start_calls: IntCounter,
+    pub set_last_block_height_calls: IntCounter,
+    pub record_indexer_stats_calls: IntCounter,
+    pub indexer_stats_calls: IntCounter,
 }
 
 impl Metric for PostgreQueries {
@@ -86,21 +106,51 @@ impl Metric for PostgreQueries {
                 "Count of calls to postgres columns_get_schema_calls."
             )
             .unwrap(),
+            indexed_columns_calls: register_int_counter!(
+                "postgres_indexed_columns_calls",
+                "Count of calls to postgres indexed_columns_calls."
+            )
+            .unwrap(),
             put_object_calls: register_int_counter!(
                 "postgres_put_object_calls",
                 "Count of calls to postgres put_object_calls."
             )
             .unwrap(),
+            put_objects_calls: register_int_counter!(
+                "postgres_put_objects_calls",
+                "Count of calls to postgres put_objects_calls."
+            )
+            .unwrap(),
             get_object_calls: register_int_counter!(
                 "postgres_get_object_calls",
                 "Count of calls to postgres get_object_calls."
             )
             .unwrap(),
+            get_object_by_calls: register_int_counter!(
+                "postgres_get_object_by_calls",
+                "Count of calls to postgres get_object_by_calls."
+            )
+            .unwrap(),
+            soft_delete_object_calls: register_int_counter!(
+                "postgres_soft_delete_object_calls",
+                "Count of calls to postgres soft_delete_object_calls."
+            )
+            .unwrap(),
+            get_object_ids_calls: register_int_counter!(
+                "postgres_get_object_ids_calls",
+                "Count of calls to postgres get_object_ids_calls."
+            )
+            .unwrap(),
             run_query_calls: register_int_counter!(
                 "postgres_run_query_calls",
                 "Count of calls to postgres run_query_calls."
             )
             .unwrap(),
+            run_read_only_query_calls: register_int_counter!(
+                "postgres_run_read_only_query_calls",
+                "Count of calls to postgres run_read_only_query_calls."
+            )
+            .unwrap(),
             execute_query_calls: register_int_counter!(
                 "postgres_execute_query_calls",
                 "Count of calls to postgres execute_query_calls."
@@ -151,6 +201,11 @@ impl Metric for PostgreQueries {
                 "Count of calls to postgres latest_assets_for_index_calls."
             )
             .unwrap(),
+            assets_for_index_calls: register_int_counter!(
+                "postgres_assets_for_index_calls",
+                "Count of calls to postgres assets_for_index_calls."
+            )
+            .unwrap(),
             last_block_height_for_indexer_calls: register_int_counter!(
                 "postgres_last_block_height_for_indexer_calls",
                 "Count of calls to postgres last_block_height_for_indexer_calls."
@@ -196,6 +251,71 @@ impl Metric for PostgreQueries {
                 "Count of calls to postgres remove_indexer."
             )
             .unwrap(),
+            indexer_tables_calls: register_int_counter!(
+                "postgres_indexer_tables_calls",
+                "Count of calls to postgres indexer_tables_calls."
+            )
+            .unwrap(),
+            purge_indexer_calls: register_int_counter!(
+                "postgres_purge_indexer_calls",
+                "Count of calls to postgres purge_indexer_calls."
+            )
+            .unwrap(),
+            table_rows_as_json_calls: register_int_counter!(
+                "postgres_table_rows_as_json_calls",
+                "Count of calls to postgres table_rows_as_json_calls."
+            )
+            .unwrap(),
+            insert_json_rows_calls: register_int_counter!(
+                "postgres_insert_json_rows_calls",
+                "Count of calls to postgres insert_json_rows_calls."
+            )
+            .unwrap(),
+            put_failed_block_calls: register_int_counter!(
+                "postgres_put_failed_block_calls",
+                "Count of calls to postgres put_failed_block_calls."
+            )
+            .unwrap(),
+            failed_blocks_calls: register_int_counter!(
+                "postgres_failed_blocks_calls",
+                "Count of calls to postgres failed_blocks_calls."
+            )
+            .unwrap(),
+            put_object_writes_calls: register_int_counter!(
+                "postgres_put_object_writes_calls",
+                "Count of calls to postgres put_object_writes_calls."
+            )
+            .unwrap(),
+            revert_to_block_calls: register_int_counter!(
+                "postgres_revert_to_block_calls",
+                "Count of calls to postgres revert_to_block_calls."
+            )
+            .unwrap(),
+            prune_object_writes_calls: register_int_counter!(
+                "postgres_prune_object_writes_calls",
+                "Count of calls to postgres prune_object_writes_calls."
+            )
+            .unwrap(),
+            object_writes_batch_start_calls: register_int_counter!(
+                "postgres_object_writes_batch_start_calls",
+                "Count of calls to postgres object_writes_batch_start_calls."
+            )
+            .unwrap(),
+            set_last_block_height_calls: register_int_counter!(
+                "postgres_set_last_block_height_calls",
+                "Count of calls to postgres set_last_block_height_calls."
+            )
+            .unwrap(),
+            record_indexer_stats_calls: register_int_counter!(
+                "postgres_record_indexer_stats_calls",
+                "Count of calls to postgres record_indexer_stats_calls."
+            )
+            .unwrap(),
+            indexer_stats_calls: register_int_counter!(
+                "postgres_indexer_stats_calls",
+                "Count of calls to postgres indexer_stats_calls."
+            )
+            .unwrap(),
             remove_asset_by_version_calls: register_int_counter!(
                 "postgres_remove_asset_by_version",
                 "Count of calls to postgres remove_asset_by_version."
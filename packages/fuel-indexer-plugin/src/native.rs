@@ -9,7 +9,7 @@ pub use fuel_indexer::prelude::{
 use async_trait;
 pub use bincode;
 pub use fuel_indexer_api_server::api::GraphQlApi;
-pub use fuel_indexer_database::{queries, IndexerConnectionPool};
+pub use fuel_indexer_database::{queries, ConnectionPoolConfig, IndexerConnectionPool};
 pub use fuel_indexer_lib::{
     config::{IndexerArgs, IndexerConfig, Parser},
     defaults::SERVICE_REQUEST_CHANNEL_SIZE,
@@ -63,5 +63,21 @@ pub trait Entity: Sized + PartialEq + Eq + std::fmt::Debug {
 
     async fn load(id: u64) -> Option<Self>;
 
+    /// Load the first entity whose `column` equals `value`.
+    async fn load_by(column: &str, value: FtColumn) -> Option<Self>;
+
+    /// Load the entity whose key columns equal `key`, in the order they are
+    /// declared by its `@key` directive.
+    async fn load_by_key(key: Vec<FtColumn>) -> Option<Self>;
+
     async fn save(&self);
+
+    /// Remove this entity. Its row is kept with `deleted_at` set, and it is no
+    /// longer returned by `load`, `find_ids` or queries; saving it restores it.
+    async fn remove(&self);
+
+    /// Return the IDs of entities whose indexed `column` equals `value`. A
+    /// failed lookup, such as on a column that isn't indexed, is logged and
+    /// returns no IDs.
+    async fn find_ids(column: &str, value: FtColumn) -> Vec<u64>;
 }
@@ -1,5 +1,9 @@
 extern crate alloc;
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    future::Future,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
 use fuel_indexer_schema::{
     utils::{deserialize, serialize},
     FtColumn,
@@ -18,8 +22,59 @@ pub use std::collections::HashSet;
 extern "C" {
     // TODO: error codes? or just panic and let the runtime handle it?
     fn ff_get_object(type_id: i64, ptr: *const u8, len: *mut u8) -> *mut u8;
+    fn ff_get_object_by(
+        type_id: i64,
+        ptr: *const u8,
+        len: u32,
+        len_ptr: *mut u8,
+    ) -> *mut u8;
+    fn ff_get_object_by_key(
+        type_id: i64,
+        ptr: *const u8,
+        len: u32,
+        len_ptr: *mut u8,
+    ) -> *mut u8;
     fn ff_put_object(type_id: i64, ptr: *const u8, len: u32);
+    fn ff_delete_object(type_id: i64, ptr: *const u8, len: u32);
+    fn ff_find_ids(type_id: i64, ptr: *const u8, len: u32, len_ptr: *mut u8) -> *mut u8;
     fn ff_log_data(ptr: *const u8, len: u32, log_level: u32);
+    fn ff_metric_inc(ptr: *const u8, len: u32, value: u64);
+}
+
+/// Handlers the indexer's manifest enables, if it lists any.
+static mut ENABLED_HANDLERS: Option<Vec<String>> = None;
+
+/// Whether the handler with the given name is dispatched to.
+pub fn handler_enabled(name: &str) -> bool {
+    unsafe {
+        (*core::ptr::addr_of!(ENABLED_HANDLERS))
+            .as_ref()
+            .map_or(true, |names| names.iter().any(|n| n == name))
+    }
+}
+
+/// Run `future` to completion, e.g. the future of an `async fn` handler.
+///
+/// Calls to the host, such as loading an object, don't yield, so the future is
+/// polled until it is ready rather than being woken.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
 }
 
 // TODO: more to do here, hook up to 'impl log::Log for Logger'
@@ -47,6 +102,13 @@ impl Logger {
     }
 }
 
+/// Increment the counter `name` of this indexer by `value`. It is reported
+/// as `indexer_handler_metric{name="..."}`, next to the indexer's built-in
+/// metrics.
+pub fn metric_inc(name: &str, value: u64) {
+    unsafe { ff_metric_inc(name.as_ptr(), name.len() as u32, value) }
+}
+
 pub trait Entity: Sized + PartialEq + Eq + std::fmt::Debug {
     const TYPE_ID: i64;
 
@@ -77,12 +139,95 @@ pub trait Entity: Sized + PartialEq + Eq + std::fmt::Debug {
         }
     }
 
+    /// Load the first entity whose `column` equals `value`.
+    fn load_by(column: &str, value: FtColumn) -> Option<Self> {
+        unsafe {
+            let buf = serialize(&(column.to_string(), value));
+            let mut buflen = 0u32.to_le_bytes();
+
+            let ptr = ff_get_object_by(
+                Self::TYPE_ID,
+                buf.as_ptr(),
+                buf.len() as u32,
+                buflen.as_mut_ptr(),
+            );
+
+            if !ptr.is_null() {
+                let len = u32::from_le_bytes(buflen) as usize;
+                let bytes = Vec::from_raw_parts(ptr, len, len);
+                let vec = deserialize(&bytes).expect("Bad serialization.");
+
+                Some(Self::from_row(vec))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Load the entity whose key columns equal `key`, in the order they are
+    /// declared by its `@key` directive.
+    fn load_by_key(key: Vec<FtColumn>) -> Option<Self> {
+        unsafe {
+            let buf = serialize(&key);
+            let mut buflen = 0u32.to_le_bytes();
+
+            let ptr = ff_get_object_by_key(
+                Self::TYPE_ID,
+                buf.as_ptr(),
+                buf.len() as u32,
+                buflen.as_mut_ptr(),
+            );
+
+            if !ptr.is_null() {
+                let len = u32::from_le_bytes(buflen) as usize;
+                let bytes = Vec::from_raw_parts(ptr, len, len);
+                let vec = deserialize(&bytes).expect("Bad serialization.");
+
+                Some(Self::from_row(vec))
+            } else {
+                None
+            }
+        }
+    }
+
     fn save(&self) {
         unsafe {
             let buf = serialize(&self.to_row());
             ff_put_object(Self::TYPE_ID, buf.as_ptr(), buf.len() as u32)
         }
     }
+
+    /// Remove this entity. Its row is kept with `deleted_at` set, and it is no
+    /// longer returned by `load`, `find_ids` or queries; saving it restores it.
+    fn remove(&self) {
+        unsafe {
+            let buf = serialize(&self.to_row());
+            ff_delete_object(Self::TYPE_ID, buf.as_ptr(), buf.len() as u32)
+        }
+    }
+
+    /// Return the IDs of entities whose indexed `column` equals `value`.
+    fn find_ids(column: &str, value: FtColumn) -> Vec<u64> {
+        unsafe {
+            let buf = serialize(&(column.to_string(), value));
+            let mut buflen = 0u32.to_le_bytes();
+
+            let ptr = ff_find_ids(
+                Self::TYPE_ID,
+                buf.as_ptr(),
+                buf.len() as u32,
+                buflen.as_mut_ptr(),
+            );
+
+            if !ptr.is_null() {
+                let len = u32::from_le_bytes(buflen) as usize;
+                let bytes = Vec::from_raw_parts(ptr, len, len);
+                deserialize(&bytes).expect("Bad serialization.")
+            } else {
+                Vec::new()
+            }
+        }
+    }
 }
 
 #[no_mangle]
@@ -99,3 +244,15 @@ fn alloc_fn(size: u32) -> *const u8 {
 fn dealloc_fn(ptr: *mut u8, len: usize) {
     let _vec = unsafe { Vec::from_raw_parts(ptr, len, len) };
 }
+
+/// Called by the runtime at instantiation with the handlers to dispatch to.
+#[no_mangle]
+fn set_enabled_handlers(ptr: *const u8, len: usize) {
+    // The runtime deallocates the argument once this returns.
+    let bytes = unsafe { core::slice::from_raw_parts(ptr, len) };
+    let names: Vec<String> = deserialize(bytes).expect("Bad serialization.");
+
+    unsafe {
+        ENABLED_HANDLERS = Some(names);
+    }
+}
@@ -1,7 +1,10 @@
 use crate::db::tables::Schema;
 use crate::sql_types::{
-    DbType, JoinCondition, QueryElement, QueryFilter, QueryJoinNode, UserQuery,
+    AggregateFunction, Comparison, DbType, JoinCondition, QueryElement, QueryFilter,
+    QueryJoinNode, QueryPagination, QueryParam, QuerySort, SortOrder, UserQuery,
+    DELETED_AT_COLUMN, WRITTEN_AT_BLOCK_COLUMN,
 };
+use base64::{engine::general_purpose::STANDARD, Engine};
 use graphql_parser::query as gql;
 use std::collections::HashMap;
 use thiserror::Error;
@@ -28,33 +31,372 @@ pub enum GraphqlError {
     FragmentResolverFailed,
     #[error("Selection not supported.")]
     SelectionNotSupported,
+    #[error("Entities listed in {0:?} can only select their own fields.")]
+    UnsupportedListSelection(String),
+    #[error("Invalid pagination cursor: {0:?}")]
+    InvalidCursor(String),
+    #[error("Invalid page size: {0}")]
+    InvalidPageSize(i64),
+    #[error("Unsupported filter operator: {0:?}")]
+    UnsupportedFilterOperator(String),
+    #[error("Unsupported sort order: {0:?}")]
+    UnsupportedSortOrder(String),
+    #[error("Sorting on several fields requires a list, e.g. `order: [{{ a: ASC }}, {{ b: DESC }}]`.")]
+    AmbiguousSortOrder,
+    #[error("Unsupported aggregate: {0:?}")]
+    UnsupportedAggregate(String),
+    #[error("Cannot take the {0} of {1:?}, which is not numeric ({2}).")]
+    NonNumericAggregate(String, String, String),
+    #[error("Query depth {0} exceeds the maximum of {1}.")]
+    QueryTooDeep(usize, usize),
+    #[error("Query complexity {0} exceeds the maximum of {1}.")]
+    QueryTooComplex(usize, usize),
+}
+
+impl GraphqlError {
+    /// The field, argument or type of the query that the error is about, if
+    /// there is a single one.
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            GraphqlError::UnrecognizedType(name)
+            | GraphqlError::UnrecognizedField(_, name)
+            | GraphqlError::UnrecognizedArgument(_, name)
+            | GraphqlError::NonNumericAggregate(_, name, _) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+/// Types stored as lowercase hex strings without a `0x` prefix.
+const HEX_TYPES: [&str; 7] = [
+    "Address",
+    "AssetId",
+    "Bytes4",
+    "Bytes8",
+    "Bytes32",
+    "ContractId",
+    "Salt",
+];
+
+/// Types stored as numbers, which can be aggregated with `sum`, `avg`, `min`
+/// and `max`.
+const NUMERIC_TYPES: [&str; 7] =
+    ["ID", "Int4", "Int8", "Int16", "UInt4", "UInt8", "UInt16"];
+
+/// How many rows a list field is assumed to return, when weighing the
+/// complexity of a query.
+pub const LIST_COMPLEXITY_FACTOR: usize = 10;
+
+/// Opaque position in a paginated result, encoded as base64 of `"{id}:{offset}"`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cursor {
+    pub id: i64,
+    pub offset: u64,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        STANDARD.encode(format!("{}:{}", self.id, self.offset))
+    }
+
+    pub fn decode(cursor: &str) -> GraphqlResult<Cursor> {
+        let invalid = || GraphqlError::InvalidCursor(cursor.to_string());
+
+        let bytes = STANDARD.decode(cursor).map_err(|_| invalid())?;
+        let decoded = String::from_utf8(bytes).map_err(|_| invalid())?;
+        let (id, offset) = decoded.split_once(':').ok_or_else(invalid)?;
+
+        Ok(Cursor {
+            id: id.parse().map_err(|_| invalid())?,
+            offset: offset.parse().map_err(|_| invalid())?,
+        })
+    }
 }
 
 #[derive(Clone, Debug)]
 pub enum Selection {
-    Field(String, Vec<Filter>, Selections),
+    /// A field, the alias its value is returned under, if any, its filters,
+    /// sorting and pagination, and the selections on its entity.
+    Field(
+        String,
+        Option<String>,
+        Vec<QueryFilter>,
+        Vec<QuerySort>,
+        Option<QueryPagination>,
+        Selections,
+    ),
+    /// An `<entity>_aggregate` root field: the entity, its filters, and the
+    /// requested aggregates, each with the key it is returned under and the
+    /// fields it is taken over, each also paired with its key.
+    Aggregate(String, Vec<QueryFilter>, Vec<Aggregate>),
     Fragment(String),
 }
 
-#[derive(Clone, Debug)]
-pub struct Filter {
-    name: String,
-    value: String,
+/// An aggregate function, the key its result is returned under, and the
+/// fields it is taken over, each paired with the key it is returned under.
+type Aggregate = (String, AggregateFunction, Vec<(String, String)>);
+
+/// Parse the selection set of an `<entity>_aggregate` field, e.g.
+/// `{ count sum { amount } max { amount height } }`. Aliases rename the keys
+/// of the result, e.g. `{ total: count }`.
+fn parse_aggregates<'a>(
+    schema: &Schema,
+    entity: &str,
+    set: &gql::SelectionSet<'a, &'a str>,
+) -> GraphqlResult<Vec<Aggregate>> {
+    let mut aggregates = Vec::with_capacity(set.items.len());
+
+    for item in &set.items {
+        let field = match item {
+            gql::Selection::Field(field) => field,
+            _ => return Err(GraphqlError::SelectionNotSupported),
+        };
+
+        let key = field.alias.unwrap_or(field.name).to_string();
+        let function = match field.name {
+            "count" => {
+                aggregates.push((key, AggregateFunction::Count, Vec::new()));
+                continue;
+            }
+            "sum" => AggregateFunction::Sum,
+            "avg" => AggregateFunction::Avg,
+            "min" => AggregateFunction::Min,
+            "max" => AggregateFunction::Max,
+            o => return Err(GraphqlError::UnsupportedAggregate(o.to_string())),
+        };
+
+        let mut fields = Vec::with_capacity(field.selection_set.items.len());
+        for item in &field.selection_set.items {
+            let (name, alias) = match item {
+                gql::Selection::Field(field) => (field.name, field.alias),
+                _ => return Err(GraphqlError::SelectionNotSupported),
+            };
+
+            let field_type = schema.field_type(entity, name).ok_or_else(|| {
+                GraphqlError::UnrecognizedField(entity.into(), name.to_string())
+            })?;
+            if !NUMERIC_TYPES.contains(&field_type.trim_end_matches('!')) {
+                return Err(GraphqlError::NonNumericAggregate(
+                    function.name().to_string(),
+                    name.to_string(),
+                    field_type.to_string(),
+                ));
+            }
+
+            fields.push((alias.unwrap_or(name).to_string(), name.to_string()));
+        }
+
+        aggregates.push((key, function, fields));
+    }
+
+    Ok(aggregates)
 }
 
-impl Filter {
-    pub fn new(name: String, value: String) -> Filter {
-        Filter { name, value }
+/// Parse the `where` argument of a field of type `entity`, e.g.
+/// `{ account: { eq: "0x.." }, _or: [{ id: { lt: 5 } }, { id: { gt: 10 } }] }`,
+/// into filters that all have to hold.
+fn parse_where<'a>(
+    schema: &Schema,
+    entity: &str,
+    value: &gql::Value<'a, &'a str>,
+) -> GraphqlResult<Vec<QueryFilter>> {
+    let conditions = match value {
+        gql::Value::Object(conditions) => conditions,
+        o => return Err(GraphqlError::UnsupportedValueType(format!("{o:#?}"))),
+    };
+
+    let mut filters = Vec::with_capacity(conditions.len());
+    for (key, value) in conditions {
+        match *key {
+            "_and" | "_or" => {
+                // Each element of the group is a `where` object of its own.
+                let groups = match value {
+                    gql::Value::List(groups) => groups.iter().collect(),
+                    o => vec![o],
+                };
+                let groups = groups
+                    .into_iter()
+                    .map(|group| parse_where(schema, entity, group).map(QueryFilter::And))
+                    .collect::<GraphqlResult<Vec<QueryFilter>>>()?;
+
+                filters.push(if *key == "_and" {
+                    QueryFilter::And(groups)
+                } else {
+                    QueryFilter::Or(groups)
+                });
+            }
+            field => {
+                let field_type = schema.field_type(entity, field).ok_or_else(|| {
+                    GraphqlError::UnrecognizedArgument(entity.into(), field.to_string())
+                })?;
+
+                let operators = match value {
+                    gql::Value::Object(operators) => operators,
+                    o => {
+                        return Err(GraphqlError::UnsupportedValueType(format!("{o:#?}")))
+                    }
+                };
+
+                for (operator, value) in operators {
+                    let relation = match *operator {
+                        "eq" => Comparison::Eq,
+                        "gt" => Comparison::Gt,
+                        "gte" => Comparison::Gte,
+                        "lt" => Comparison::Lt,
+                        "lte" => Comparison::Lte,
+                        "in" => {
+                            let values = match value {
+                                gql::Value::List(values) => values,
+                                o => {
+                                    return Err(GraphqlError::UnsupportedValueType(
+                                        format!("{o:#?}"),
+                                    ))
+                                }
+                            };
+                            let values = values
+                                .iter()
+                                .map(|v| query_param(field_type, v))
+                                .collect::<GraphqlResult<Vec<QueryParam>>>()?;
+
+                            filters.push(QueryFilter::In {
+                                key: schema.column_name(field),
+                                values,
+                            });
+                            continue;
+                        }
+                        o => {
+                            return Err(GraphqlError::UnsupportedFilterOperator(
+                                o.to_string(),
+                            ))
+                        }
+                    };
+
+                    filters.push(comparison(
+                        &schema.column_name(field),
+                        field_type,
+                        relation,
+                        value,
+                    )?);
+                }
+            }
+        }
     }
 
-    pub fn as_sql(&self, _jsonify: bool) -> String {
-        format!("{} = {}", self.name, self.value)
+    Ok(filters)
+}
+
+/// Parse the `order` argument of a root field of type `entity`, e.g.
+/// `{ height: DESC }`, or `[{ height: DESC }, { id: ASC }]` to sort on several
+/// fields in the given order.
+fn parse_order<'a>(
+    schema: &Schema,
+    entity: &str,
+    value: &gql::Value<'a, &'a str>,
+) -> GraphqlResult<Vec<QuerySort>> {
+    let keys = match value {
+        gql::Value::List(keys) => keys.iter().collect(),
+        o => vec![o],
+    };
+
+    let mut sort = Vec::with_capacity(keys.len());
+    for key in keys {
+        let fields = match key {
+            gql::Value::Object(fields) => fields,
+            o => return Err(GraphqlError::UnsupportedValueType(format!("{o:#?}"))),
+        };
+
+        // Object keys are unordered, so each object may only name one field.
+        if fields.len() > 1 {
+            return Err(GraphqlError::AmbiguousSortOrder);
+        }
+
+        for (field, order) in fields {
+            if schema.field_type(entity, field).is_none() {
+                return Err(GraphqlError::UnrecognizedArgument(
+                    entity.into(),
+                    field.to_string(),
+                ));
+            }
+
+            let order = match order {
+                gql::Value::Enum("ASC") => SortOrder::Asc,
+                gql::Value::Enum("DESC") => SortOrder::Desc,
+                o => return Err(GraphqlError::UnsupportedSortOrder(format!("{o}"))),
+            };
+
+            sort.push(QuerySort {
+                key: schema.column_name(field),
+                order,
+            });
+        }
+    }
+
+    Ok(sort)
+}
+
+/// Parse the `ids` argument of a root field, e.g. `[3, 1, 2]`.
+fn parse_ids<'a>(value: &gql::Value<'a, &'a str>) -> GraphqlResult<Vec<i64>> {
+    let values = match value {
+        gql::Value::List(values) => values,
+        o => return Err(GraphqlError::UnsupportedValueType(format!("{o:#?}"))),
+    };
+
+    values
+        .iter()
+        .map(|value| match value {
+            gql::Value::Int(val) => {
+                Ok(val.as_i64().expect("Failed to parse value as i64"))
+            }
+            o => Err(GraphqlError::UnsupportedValueType(format!("{o:#?}"))),
+        })
+        .collect()
+}
+
+fn comparison<'a>(
+    field: &str,
+    field_type: &str,
+    relation: Comparison,
+    value: &gql::Value<'a, &'a str>,
+) -> GraphqlResult<QueryFilter> {
+    match (relation, value) {
+        (Comparison::Eq, gql::Value::Null) => Ok(QueryFilter::IsNull {
+            key: field.to_string(),
+        }),
+        _ => Ok(QueryFilter::Comparison {
+            key: field.to_string(),
+            relation,
+            value: query_param(field_type, value)?,
+        }),
+    }
+}
+
+fn query_param<'a>(
+    field_type: &str,
+    value: &gql::Value<'a, &'a str>,
+) -> GraphqlResult<QueryParam> {
+    match value {
+        gql::Value::Int(val) => Ok(QueryParam::Int(
+            val.as_i64().expect("Failed to parse value as i64"),
+        )),
+        gql::Value::Float(val) => Ok(QueryParam::Float(*val)),
+        // Field types of non-null fields keep their `!`.
+        gql::Value::String(val)
+            if HEX_TYPES.contains(&field_type.trim_end_matches('!')) =>
+        {
+            let val = val.strip_prefix("0x").unwrap_or(val);
+            Ok(QueryParam::String(val.to_lowercase()))
+        }
+        gql::Value::String(val) => Ok(QueryParam::String(val.to_string())),
+        gql::Value::Boolean(val) => Ok(QueryParam::Boolean(*val)),
+        // Enum values are stored by name.
+        gql::Value::Enum(val) => Ok(QueryParam::String(val.to_string())),
+        o => Err(GraphqlError::UnsupportedValueType(format!("{o:#?}",))),
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct Selections {
-    _field_type: String,
+    field_type: String,
     has_fragments: bool,
     selections: Vec<Selection>,
 }
@@ -73,14 +415,28 @@ impl Selections {
                 gql::Selection::Field(field) => {
                     // TODO: directives and sub-selections for nested types...
                     let gql::Field {
+                        alias,
                         name,
                         selection_set,
                         arguments,
                         ..
                     } = field;
 
-                    let subfield_type =
-                        schema.field_type(field_type, name).ok_or_else(|| {
+                    // `<entity>_aggregate` root fields aggregate the entities of the
+                    // `<entity>` root field, unless the schema has a field of that name.
+                    let aggregate_of = if field_type == schema.query
+                        && schema.field_type(field_type, name).is_none()
+                    {
+                        name.strip_suffix("_aggregate").filter(|entity| {
+                            schema.field_type(field_type, entity).is_some()
+                        })
+                    } else {
+                        None
+                    };
+
+                    let subfield_type = schema
+                        .field_type(field_type, aggregate_of.unwrap_or(name))
+                        .ok_or_else(|| {
                             GraphqlError::UnrecognizedField(
                                 field_type.into(),
                                 name.to_string(),
@@ -88,40 +444,191 @@ impl Selections {
                         })?;
 
                     let mut filters = vec![];
+                    let mut sort = vec![];
+                    let mut pagination = None;
+                    let mut include_deleted = false;
+                    let mut ids = None;
                     for (arg, value) in arguments {
-                        if schema.field_type(subfield_type, arg).is_none() {
-                            return Err(GraphqlError::UnrecognizedArgument(
-                                subfield_type.into(),
-                                arg.to_string(),
-                            ));
+                        if field_type == schema.query && *arg == "includeDeleted" {
+                            include_deleted = match value {
+                                gql::Value::Boolean(val) => *val,
+                                o => {
+                                    return Err(GraphqlError::UnsupportedValueType(
+                                        format!("{o:#?}",),
+                                    ))
+                                }
+                            };
+                            continue;
                         }
 
-                        let val = match value {
-                            gql::Value::Int(val) => {
-                                format!(
-                                    "{}",
-                                    val.as_i64().expect("Failed to parse value as i64")
-                                )
+                        // `changedSince` selects the entities last written after a
+                        // block, e.g. to fetch what changed since the last sync.
+                        if field_type == schema.query && *arg == "changedSince" {
+                            if schema
+                                .field_type(subfield_type, WRITTEN_AT_BLOCK_COLUMN)
+                                .is_none()
+                            {
+                                return Err(GraphqlError::UnrecognizedArgument(
+                                    subfield_type.into(),
+                                    arg.to_string(),
+                                ));
                             }
-                            gql::Value::Float(val) => format!("{val}",),
-                            gql::Value::String(val) => val.to_string(),
-                            gql::Value::Boolean(val) => format!("{val}",),
-                            gql::Value::Null => String::from("NULL"),
-                            o => {
+                            if !matches!(value, gql::Value::Int(_)) {
                                 return Err(GraphqlError::UnsupportedValueType(format!(
-                                    "{o:#?}",
-                                )))
+                                    "{value:#?}",
+                                )));
                             }
-                        };
+                            filters.push(comparison(
+                                &schema.column_name(WRITTEN_AT_BLOCK_COLUMN),
+                                "UInt8",
+                                Comparison::Gt,
+                                value,
+                            )?);
+                            continue;
+                        }
 
-                        filters.push(Filter::new(arg.to_string(), val));
+                        // `first` and `after` paginate root fields rather than filter them.
+                        if field_type == schema.query
+                            && aggregate_of.is_none()
+                            && (*arg == "first" || *arg == "after")
+                        {
+                            let pagination = pagination.get_or_insert(QueryPagination {
+                                first: None,
+                                after_id: None,
+                                offset: 0,
+                            });
+
+                            match (*arg, value) {
+                                ("first", gql::Value::Int(val)) => {
+                                    let first = val
+                                        .as_i64()
+                                        .expect("Failed to parse value as i64");
+                                    if first < 1 {
+                                        return Err(GraphqlError::InvalidPageSize(first));
+                                    }
+                                    pagination.first = Some(first as u64);
+                                }
+                                ("after", gql::Value::String(val)) => {
+                                    let cursor = Cursor::decode(val)?;
+                                    pagination.after_id = Some(cursor.id);
+                                    pagination.offset = cursor.offset;
+                                }
+                                (_, o) => {
+                                    return Err(GraphqlError::UnsupportedValueType(
+                                        format!("{o:#?}",),
+                                    ))
+                                }
+                            }
+
+                            continue;
+                        }
+
+                        if field_type == schema.query
+                            && aggregate_of.is_none()
+                            && *arg == "order"
+                        {
+                            sort = parse_order(schema, subfield_type, value)?;
+                            continue;
+                        }
+
+                        if *arg == "where" {
+                            filters.extend(parse_where(schema, subfield_type, value)?);
+                            continue;
+                        }
+
+                        // `ids` selects the entities with any of the listed IDs,
+                        // unless the entity has a field of that name.
+                        if field_type == schema.query
+                            && *arg == "ids"
+                            && schema.field_type(subfield_type, arg).is_none()
+                        {
+                            let values = parse_ids(value)?;
+                            filters.push(QueryFilter::In {
+                                key: schema.column_name("id"),
+                                values: values
+                                    .iter()
+                                    .copied()
+                                    .map(QueryParam::Int)
+                                    .collect(),
+                            });
+                            ids = Some(values);
+                            continue;
+                        }
+
+                        let arg_type =
+                            schema.field_type(subfield_type, arg).ok_or_else(|| {
+                                GraphqlError::UnrecognizedArgument(
+                                    subfield_type.into(),
+                                    arg.to_string(),
+                                )
+                            })?;
+
+                        filters.push(comparison(
+                            &schema.column_name(arg),
+                            arg_type,
+                            Comparison::Eq,
+                            value,
+                        )?);
+                    }
+
+                    // Entities selected by `ids` are returned in the order of
+                    // the IDs, unless the query is sorted otherwise.
+                    if let Some(ids) = ids {
+                        if sort.is_empty() && aggregate_of.is_none() {
+                            sort.push(QuerySort {
+                                key: schema.column_name("id"),
+                                order: SortOrder::Listed(ids),
+                            });
+                        }
+                    }
+
+                    // Removed entities are left out of root fields unless asked for.
+                    if field_type == schema.query
+                        && !include_deleted
+                        && schema
+                            .field_type(subfield_type, DELETED_AT_COLUMN)
+                            .is_some()
+                    {
+                        filters.push(QueryFilter::IsNull {
+                            key: DELETED_AT_COLUMN.to_string(),
+                        });
+                    }
+
+                    if let Some(entity) = aggregate_of {
+                        let aggregates =
+                            parse_aggregates(schema, subfield_type, selection_set)?;
+                        selections.push(Selection::Aggregate(
+                            entity.to_string(),
+                            filters,
+                            aggregates,
+                        ));
+                        continue;
                     }
 
                     let sub_selections =
                         Selections::new(schema, subfield_type, selection_set)?;
+
+                    // Lists of related entities are selected by a subquery, which
+                    // can't join further entities of its own.
+                    let is_list = schema
+                        .one_to_many
+                        .get(&field_type.to_lowercase())
+                        .map_or(false, |relations| relations.contains_key(*name));
+                    if is_list
+                        && sub_selections.selections.iter().any(|s| {
+                            matches!(s, Selection::Field(_, _, _, _, _, subs) if !subs.selections.is_empty())
+                        })
+                    {
+                        return Err(GraphqlError::UnsupportedListSelection(
+                            name.to_string(),
+                        ));
+                    }
                     selections.push(Selection::Field(
                         name.to_string(),
+                        alias.map(str::to_string),
                         filters,
+                        sort,
+                        pagination,
                         sub_selections,
                     ));
                 }
@@ -136,7 +643,7 @@ impl Selections {
         }
 
         Ok(Selections {
-            _field_type: field_type.to_string(),
+            field_type: field_type.to_string(),
             has_fragments,
             selections,
         })
@@ -169,7 +676,14 @@ impl Selections {
                         selections.push(Selection::Fragment(name.to_string()));
                     }
                 }
-                Selection::Field(name, filters, sub_selection) => {
+                Selection::Field(
+                    name,
+                    alias,
+                    filters,
+                    sort,
+                    pagination,
+                    sub_selection,
+                ) => {
                     let field_type = schema
                         .field_type(cond, name)
                         .expect("Unable to retrieve field type");
@@ -178,10 +692,14 @@ impl Selections {
 
                     selections.push(Selection::Field(
                         name.to_string(),
+                        alias.clone(),
                         filters.to_vec(),
+                        sort.to_vec(),
+                        pagination.clone(),
                         sub_selection.clone(),
                     ));
                 }
+                Selection::Aggregate(..) => selections.push(selection.clone()),
             }
         }
 
@@ -193,6 +711,49 @@ impl Selections {
     pub fn get_selections(&self) -> Vec<Selection> {
         self.selections.clone()
     }
+
+    /// How deeply the selections nest, counting each level of fields.
+    pub fn depth(&self) -> usize {
+        self.selections
+            .iter()
+            .map(|selection| match selection {
+                Selection::Field(_, _, _, _, _, subs) => 1 + subs.depth(),
+                Selection::Aggregate(..) => 2,
+                Selection::Fragment(_) => 0,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// An estimate of the cost of the selections, where each field costs the
+    /// number of rows it may be read for: `rows` at this level, and
+    /// `LIST_COMPLEXITY_FACTOR` times as many beneath a root field or a list
+    /// of related entities.
+    pub fn complexity(&self, schema: &Schema, rows: usize) -> usize {
+        self.selections
+            .iter()
+            .map(|selection| match selection {
+                Selection::Field(name, _, _, _, _, subs) => {
+                    let is_list = self.field_type == schema.query
+                        || schema
+                            .one_to_many
+                            .get(&self.field_type.to_lowercase())
+                            .map_or(false, |relations| relations.contains_key(name));
+                    let sub_rows = if is_list {
+                        rows.saturating_mul(LIST_COMPLEXITY_FACTOR)
+                    } else {
+                        rows
+                    };
+
+                    rows.saturating_add(subs.complexity(schema, sub_rows))
+                }
+                Selection::Aggregate(_, _, aggregates) => {
+                    rows.saturating_mul(1 + aggregates.len())
+                }
+                Selection::Fragment(_) => 0,
+            })
+            .fold(0, usize::saturating_add)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -265,17 +826,76 @@ impl Operation {
         let mut queries = Vec::new();
 
         for selection in selections.get_selections() {
+            // Aggregates are selected as a single `aggregate` object holding an
+            // object of fields for each aggregate function, e.g.
+            // `{ "aggregate": { "count": 2, "sum": { "amount": 10 } } }`.
+            if let Selection::Aggregate(entity_name, filters, aggregates) = &selection {
+                let table = format!(
+                    "{namespace}_{identifier}.{}",
+                    schema.table_name(entity_name)
+                );
+                let mut elements = vec![QueryElement::ObjectOpeningBoundary {
+                    key: "aggregate".to_string(),
+                }];
+
+                for (key, function, fields) in aggregates {
+                    if *function == AggregateFunction::Count {
+                        elements.push(QueryElement::Field {
+                            key: key.clone(),
+                            value: "COUNT(*)".to_string(),
+                        });
+                        continue;
+                    }
+
+                    elements
+                        .push(QueryElement::ObjectOpeningBoundary { key: key.clone() });
+                    elements.extend(fields.iter().map(|(key, field)| {
+                        QueryElement::Field {
+                            key: key.clone(),
+                            value: format!(
+                                "{}({table}.{})",
+                                function.as_sql(),
+                                schema.column_name(field)
+                            ),
+                        }
+                    }));
+                    elements.push(QueryElement::ObjectClosingBoundary);
+                }
+                elements.push(QueryElement::ObjectClosingBoundary);
+
+                queries.push(UserQuery {
+                    elements,
+                    joins: HashMap::new(),
+                    namespace_identifier: format!("{namespace}_{identifier}"),
+                    entity_name: schema.table_name(entity_name),
+                    filters: filters.clone(),
+                    sort: Vec::new(),
+                    pagination: None,
+                });
+                continue;
+            }
+
             let mut elements: Vec<QueryElement> = Vec::new();
             let mut entities: Vec<String> = Vec::new();
 
             let mut joins: HashMap<String, QueryJoinNode> = HashMap::new();
 
-            let mut nested_entity_stack: Vec<String> = Vec::new();
+            // Each nesting level keeps the element that closes it, as nested
+            // objects and lists of related entities are closed differently.
+            let mut nested_entity_stack: Vec<(String, QueryElement)> = Vec::new();
 
             // Selections can have their own set of subselections and so on, so a queue
             // is created with the first level of selections. In order to track the containing
             // entity of the selection, an entity list of the same length is created.
-            if let Selection::Field(entity_name, filters, selections) = selection {
+            if let Selection::Field(
+                entity_name,
+                _,
+                filters,
+                sort,
+                pagination,
+                selections,
+            ) = selection
+            {
                 let mut queue: Vec<Selection> = Vec::new();
 
                 // Selections and entities will be popped from their respective vectors
@@ -305,24 +925,53 @@ impl Operation {
                     // to the queue, then check the entity of the selection against the
                     // current nesting level. If they differ, then the operation has moved
                     // out of a child entity into a parent entity.
-                    if let Some(current_nesting_level) = nested_entity_stack.last() {
+                    if let Some((current_nesting_level, _)) = nested_entity_stack.last() {
                         if entities.len() < last_seen_entities_len
                             && current_nesting_level != &entity_name
                         {
-                            let _ = nested_entity_stack.pop();
-                            elements.push(QueryElement::ObjectClosingBoundary);
+                            if let Some((_, closing)) = nested_entity_stack.pop() {
+                                elements.push(closing);
+                            }
                         }
                     }
 
                     last_seen_entities_len = entities.len();
 
-                    if let Selection::Field(field_name, _f, subselections) = current {
+                    if let Selection::Field(
+                        field_name,
+                        alias,
+                        _f,
+                        _s,
+                        _p,
+                        subselections,
+                    ) = current
+                    {
+                        // Aliases only rename the key that the value is returned under.
+                        let key = alias.unwrap_or_else(|| field_name.clone());
                         if subselections.selections.is_empty() {
-                            elements.push(QueryElement::Field {
-                                key: field_name.clone(),
-                                value: format!(
-                                    "{namespace}_{identifier}.{entity_name}.{field_name}"
-                                ),
+                            let column = format!(
+                                "{namespace}_{identifier}.{}.{}",
+                                schema.table_name(&entity_name),
+                                schema.column_name(&field_name)
+                            );
+                            // Entities are named by table here, i.e. in lowercase.
+                            let field_type = schema
+                                .fields
+                                .iter()
+                                .find(|(name, _)| name.to_lowercase() == entity_name)
+                                .and_then(|(_, fields)| fields.get(&field_name))
+                                .map(|t| t.trim_end_matches('!'));
+                            elements.push(match field_type {
+                                // Decimals are returned as strings, as JSON numbers
+                                // would lose precision in most clients.
+                                Some("Decimal") => QueryElement::Field {
+                                    key,
+                                    value: format!("CAST({column} AS TEXT)"),
+                                },
+                                Some("Bytes") => {
+                                    QueryElement::BytesField { key, value: column }
+                                }
+                                _ => QueryElement::Field { key, value: column },
                             });
                         } else {
                             let mut new_entity = field_name.clone();
@@ -337,13 +986,17 @@ impl Operation {
                                 {
                                     let join_condition = JoinCondition {
                                         referencing_key_table: format!(
-                                            "{namespace}_{identifier}.{entity_name}"
+                                            "{namespace}_{identifier}.{}",
+                                            schema.table_name(&entity_name)
                                         ),
-                                        referencing_key_col: field_name.clone(),
+                                        referencing_key_col: schema
+                                            .column_name(&field_name),
                                         primary_key_table: format!(
-                                            "{namespace}_{identifier}.{foreign_key_table}"
+                                            "{namespace}_{identifier}.{}",
+                                            schema.table_name(foreign_key_table)
                                         ),
-                                        primary_key_col: foreign_key_col.clone(),
+                                        primary_key_col: schema
+                                            .column_name(foreign_key_col),
                                     };
 
                                     // Joins are modelled like a directed graph in
@@ -408,6 +1061,38 @@ impl Operation {
                                 }
                             }
 
+                            // If the selection is a list of related entities, select
+                            // them with a subquery on the foreign key that the related
+                            // entity holds on the current entity.
+                            let closing = match schema
+                                .one_to_many
+                                .get(&entity_name.to_lowercase())
+                                .and_then(|relations| relations.get(&field_name))
+                            {
+                                Some((child_table, child_col, parent_col)) => {
+                                    let table = format!(
+                                        "{namespace}_{identifier}.{}",
+                                        schema.table_name(child_table)
+                                    );
+                                    let condition = format!(
+                                        "{table}.{} = {namespace}_{identifier}.{}.{}",
+                                        schema.column_name(child_col),
+                                        schema.table_name(&entity_name),
+                                        schema.column_name(parent_col)
+                                    );
+                                    new_entity = child_table.clone();
+                                    elements
+                                        .push(QueryElement::ListOpeningBoundary { key });
+                                    QueryElement::ListClosingBoundary { table, condition }
+                                }
+                                None => {
+                                    elements.push(QueryElement::ObjectOpeningBoundary {
+                                        key,
+                                    });
+                                    QueryElement::ObjectClosingBoundary
+                                }
+                            };
+
                             // Add the subselections and entities to the ends of
                             // their respective vectors so that they are resolved
                             // immediately after their parent selection.
@@ -415,11 +1100,7 @@ impl Operation {
                                 new_entity.clone();
                                 subselections.selections.len()
                             ]);
-                            nested_entity_stack.push(new_entity.clone());
-
-                            elements.push(QueryElement::ObjectOpeningBoundary {
-                                key: field_name,
-                            });
+                            nested_entity_stack.push((new_entity, closing));
 
                             queue.append(&mut subselections.get_selections());
                         }
@@ -429,29 +1110,18 @@ impl Operation {
                 // If the query document ends without selections from outer entities,
                 // then append the requisite number of object closing boundaries in
                 // order to properly format the JSON structure for the database query.
-                if !nested_entity_stack.is_empty() {
-                    elements.append(&mut vec![
-                        QueryElement::ObjectClosingBoundary;
-                        nested_entity_stack.len()
-                    ]);
+                while let Some((_, closing)) = nested_entity_stack.pop() {
+                    elements.push(closing);
                 }
 
-                // TODO: Support filtering operations, e.g. <, >, set membership, etc.
-                let filters: Vec<QueryFilter> = filters
-                    .into_iter()
-                    .map(|f| QueryFilter {
-                        key: f.name,
-                        relation: "=".to_string(),
-                        value: f.value,
-                    })
-                    .collect();
-
                 let query = UserQuery {
                     elements,
                     joins,
                     namespace_identifier: format!("{namespace}_{identifier}"),
-                    entity_name,
+                    entity_name: schema.table_name(&entity_name),
                     filters,
+                    sort,
+                    pagination,
                 };
 
                 queries.push(query)
@@ -491,6 +1161,8 @@ impl GraphqlQuery {
 pub struct GraphqlQueryBuilder<'a> {
     schema: &'a Schema,
     document: gql::Document<'a, &'a str>,
+    max_depth: usize,
+    max_complexity: usize,
 }
 
 impl<'a> GraphqlQueryBuilder<'a> {
@@ -499,13 +1171,50 @@ impl<'a> GraphqlQueryBuilder<'a> {
         query: &'a str,
     ) -> GraphqlResult<GraphqlQueryBuilder<'a>> {
         let document = gql::parse_query::<&str>(query)?;
-        Ok(GraphqlQueryBuilder { schema, document })
+        Ok(GraphqlQueryBuilder {
+            schema,
+            document,
+            max_depth: 0,
+            max_complexity: 0,
+        })
+    }
+
+    /// Reject queries whose selections nest deeper than `max_depth`. Use 0 for
+    /// no limit.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Reject queries whose complexity, as weighed by
+    /// `Selections::complexity`, is above `max_complexity`. Use 0 for no limit.
+    pub fn max_complexity(mut self, max_complexity: usize) -> Self {
+        self.max_complexity = max_complexity;
+        self
     }
 
     pub fn build(self) -> GraphqlResult<GraphqlQuery> {
         let fragments = self.process_fragments()?;
         let operations = self.process_operations(fragments)?;
 
+        for operation in &operations {
+            let depth = operation.selections.depth();
+            if self.max_depth > 0 && depth > self.max_depth {
+                return Err(GraphqlError::QueryTooDeep(depth, self.max_depth));
+            }
+        }
+
+        let complexity = operations
+            .iter()
+            .map(|operation| operation.selections.complexity(self.schema, 1))
+            .fold(0, usize::saturating_add);
+        if self.max_complexity > 0 && complexity > self.max_complexity {
+            return Err(GraphqlError::QueryTooComplex(
+                complexity,
+                self.max_complexity,
+            ));
+        }
+
         Ok(GraphqlQuery { operations })
     }
 
@@ -633,6 +1342,7 @@ impl<'a> GraphqlQueryBuilder<'a> {
 
 #[cfg(test)]
 mod tests {
+    use crate::sql_types::NamingStrategy;
     use std::collections::HashSet;
 
     use super::*;
@@ -640,23 +1350,29 @@ mod tests {
     #[test]
     fn test_operation_parse_into_user_query() {
         let selections_on_block_field = Selections {
-            _field_type: "Block".to_string(),
+            field_type: "Block".to_string(),
             has_fragments: false,
             selections: vec![
                 Selection::Field(
                     "id".to_string(),
+                    None,
                     Vec::new(),
+                    Vec::new(),
+                    None,
                     Selections {
-                        _field_type: "ID!".to_string(),
+                        field_type: "ID!".to_string(),
                         has_fragments: false,
                         selections: Vec::new(),
                     },
                 ),
                 Selection::Field(
                     "height".to_string(),
+                    None,
+                    Vec::new(),
                     Vec::new(),
+                    None,
                     Selections {
-                        _field_type: "UInt8!".to_string(),
+                        field_type: "UInt8!".to_string(),
                         has_fragments: false,
                         selections: Vec::new(),
                     },
@@ -665,28 +1381,37 @@ mod tests {
         };
 
         let selections_on_tx_field = Selections {
-            _field_type: "Tx".to_string(),
+            field_type: "Tx".to_string(),
             has_fragments: false,
             selections: vec![
                 Selection::Field(
                     "block".to_string(),
+                    None,
                     Vec::new(),
+                    Vec::new(),
+                    None,
                     selections_on_block_field,
                 ),
                 Selection::Field(
                     "id".to_string(),
+                    None,
+                    Vec::new(),
                     Vec::new(),
+                    None,
                     Selections {
-                        _field_type: "ID!".to_string(),
+                        field_type: "ID!".to_string(),
                         has_fragments: false,
                         selections: Vec::new(),
                     },
                 ),
                 Selection::Field(
                     "timestamp".to_string(),
+                    None,
                     Vec::new(),
+                    Vec::new(),
+                    None,
                     Selections {
-                        _field_type: "Int8!".to_string(),
+                        field_type: "Int8!".to_string(),
                         has_fragments: false,
                         selections: Vec::new(),
                     },
@@ -696,7 +1421,10 @@ mod tests {
 
         let query_selections = vec![Selection::Field(
             "tx".to_string(),
+            None,
+            Vec::new(),
             Vec::new(),
+            None,
             selections_on_tx_field,
         )];
 
@@ -705,7 +1433,7 @@ mod tests {
             namespace: "fuel_indexer_test".to_string(),
             identifier: "test_index".to_string(),
             selections: Selections {
-                _field_type: "QueryRoot".to_string(),
+                field_type: "QueryRoot".to_string(),
                 has_fragments: false,
                 selections: query_selections,
             },
@@ -760,6 +1488,10 @@ mod tests {
             ]),
             fields,
             foreign_keys,
+            one_to_many: HashMap::new(),
+            descriptions: HashMap::new(),
+            naming: NamingStrategy::default(),
+            tables: HashMap::new(),
         };
 
         let expected = vec![UserQuery {
@@ -824,6 +1556,127 @@ mod tests {
             namespace_identifier: "fuel_indexer_test_test_index".to_string(),
             entity_name: "tx".to_string(),
             filters: Vec::new(),
+            sort: Vec::new(),
+            pagination: None,
+        }];
+        assert_eq!(expected, operation.parse(&schema));
+    }
+
+    #[test]
+    fn test_operation_parse_list_of_related_entities_into_user_query() {
+        let field = |name: &str, selections: Vec<Selection>| {
+            Selection::Field(
+                name.to_string(),
+                None,
+                Vec::new(),
+                Vec::new(),
+                None,
+                Selections {
+                    field_type: "".to_string(),
+                    has_fragments: false,
+                    selections,
+                },
+            )
+        };
+
+        let operation = Operation {
+            _name: "".to_string(),
+            namespace: "fuel_indexer_test".to_string(),
+            identifier: "test_index".to_string(),
+            selections: Selections {
+                field_type: "QueryRoot".to_string(),
+                has_fragments: false,
+                selections: vec![field(
+                    "block",
+                    vec![
+                        field("id", Vec::new()),
+                        field(
+                            "transactions",
+                            vec![field("id", Vec::new()), field("timestamp", Vec::new())],
+                        ),
+                    ],
+                )],
+            },
+        };
+
+        let schema = Schema {
+            version: "test_version".to_string(),
+            namespace: "fuel_indexer_test".to_string(),
+            identifier: "test_index".to_string(),
+            query: "QueryRoot".to_string(),
+            types: HashSet::from([
+                "Tx".to_string(),
+                "Block".to_string(),
+                "QueryRoot".to_string(),
+            ]),
+            fields: HashMap::from([
+                (
+                    "QueryRoot".to_string(),
+                    HashMap::from([("block".to_string(), "Block".to_string())]),
+                ),
+                (
+                    "Block".to_string(),
+                    HashMap::from([
+                        ("id".to_string(), "ID!".to_string()),
+                        ("transactions".to_string(), "Tx".to_string()),
+                    ]),
+                ),
+                (
+                    "Tx".to_string(),
+                    HashMap::from([
+                        ("id".to_string(), "ID!".to_string()),
+                        ("timestamp".to_string(), "Int8!".to_string()),
+                        ("block".to_string(), "Block".to_string()),
+                    ]),
+                ),
+            ]),
+            foreign_keys: HashMap::from([(
+                "tx".to_string(),
+                HashMap::from([(
+                    "block".to_string(),
+                    ("block".to_string(), "id".to_string()),
+                )]),
+            )]),
+            one_to_many: HashMap::from([(
+                "block".to_string(),
+                HashMap::from([(
+                    "transactions".to_string(),
+                    ("tx".to_string(), "block".to_string(), "id".to_string()),
+                )]),
+            )]),
+            descriptions: HashMap::new(),
+            naming: NamingStrategy::default(),
+            tables: HashMap::new(),
+        };
+
+        let expected = vec![UserQuery {
+            elements: vec![
+                QueryElement::Field {
+                    key: "id".to_string(),
+                    value: "fuel_indexer_test_test_index.block.id".to_string(),
+                },
+                QueryElement::ListOpeningBoundary {
+                    key: "transactions".to_string(),
+                },
+                QueryElement::Field {
+                    key: "timestamp".to_string(),
+                    value: "fuel_indexer_test_test_index.tx.timestamp".to_string(),
+                },
+                QueryElement::Field {
+                    key: "id".to_string(),
+                    value: "fuel_indexer_test_test_index.tx.id".to_string(),
+                },
+                QueryElement::ListClosingBoundary {
+                    table: "fuel_indexer_test_test_index.tx".to_string(),
+                    condition: "fuel_indexer_test_test_index.tx.block = fuel_indexer_test_test_index.block.id".to_string(),
+                },
+            ],
+            joins: HashMap::new(),
+            namespace_identifier: "fuel_indexer_test_test_index".to_string(),
+            entity_name: "block".to_string(),
+            filters: Vec::new(),
+            sort: Vec::new(),
+            pagination: None,
         }];
         assert_eq!(expected, operation.parse(&schema));
     }
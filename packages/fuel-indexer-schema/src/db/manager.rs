@@ -6,6 +6,7 @@ use crate::{
     utils::{inject_native_entities_into_schema, schema_version},
 };
 use fuel_indexer_database::{queries, IndexerConnection, IndexerConnectionPool};
+use tracing::warn;
 
 pub struct SchemaManager {
     pool: IndexerConnectionPool,
@@ -30,15 +31,38 @@ impl SchemaManager {
         let version = schema_version(&schema);
 
         if !queries::schema_exists(conn, namespace, identifier, &version).await? {
-            let _db_schema = SchemaBuilder::new(
+            let mut builder = SchemaBuilder::new(
                 namespace,
                 identifier,
                 &version,
                 self.pool.database_type(),
             )
-            .build(&schema)
-            .commit_metadata(conn)
-            .await?;
+            .build(&schema);
+
+            // Additive changes are applied to the tables of the latest schema,
+            // so that their rows are kept. Any other change is committed as a
+            // new version, as if there were no previous schema.
+            match queries::graph_root_latest(conn, namespace, identifier).await {
+                Ok(root) => {
+                    let previous = SchemaBuilder::new(
+                        namespace,
+                        identifier,
+                        &root.version,
+                        self.pool.database_type(),
+                    )
+                    .build(&root.schema);
+
+                    if let Err(change) = builder.migrate_from(&previous) {
+                        warn!(
+                            "Schema of Indexer({namespace}.{identifier}) changed in a way that can't be migrated, its existing tables are left as they are: {change}"
+                        );
+                    }
+                }
+                Err(sqlx::Error::RowNotFound) => {}
+                Err(e) => return Err(e.into()),
+            }
+
+            let _db_schema = builder.commit_metadata(conn).await?;
         }
         Ok(())
     }
@@ -49,6 +73,6 @@ impl SchemaManager {
         identifier: &str,
     ) -> IndexerSchemaResult<Schema> {
         // TODO: might be nice to cache this data in server?
-        Ok(Schema::load_from_db(&self.pool, namespace, identifier).await?)
+        Schema::load_from_db(&self.pool, namespace, identifier).await
     }
 }
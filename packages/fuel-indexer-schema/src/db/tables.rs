@@ -1,7 +1,9 @@
+use crate::db::IndexerSchemaResult;
 use crate::utils::{
-    build_schema_fields_and_types_map, build_schema_objects_set, field_type_table_name,
-    get_index_directive, get_join_directive_info, get_unique_directive,
-    normalize_field_type_name, BASE_SCHEMA,
+    build_schema_enums_map, build_schema_fields_and_types_map, build_schema_objects_set,
+    field_type_table_name, get_default_directive, get_index_directive,
+    get_join_directive_info, get_key_directive, get_unique_directive, is_list_type,
+    list_field_type_name, normalize_field_type_name, schema_naming_strategy, BASE_SCHEMA,
 };
 use fuel_indexer_database::{
     queries,
@@ -15,6 +17,16 @@ use graphql_parser::schema::{
 use graphql_parser::{parse_schema, schema::Document};
 use std::collections::{HashMap, HashSet};
 
+/// A column of an entity's table, as it is declared when the table is created.
+#[derive(Clone, Debug)]
+struct ColumnDef {
+    name: String,
+    fragment: String,
+    nullable: bool,
+    /// Whether the column has a `DEFAULT`, from a `@default` directive.
+    has_default: bool,
+}
+
 #[derive(Default)]
 pub struct SchemaBuilder {
     db_type: DbType,
@@ -32,6 +44,12 @@ pub struct SchemaBuilder {
     query: String,
     query_fields: HashMap<String, HashMap<String, String>>,
     primitives: HashSet<String>,
+    enums: HashMap<String, Vec<String>>,
+    table_columns: HashMap<String, Vec<ColumnDef>>,
+    /// Columns of the composite primary key of each table whose type declares
+    /// one with `@key`.
+    table_keys: HashMap<String, Vec<String>>,
+    naming: NamingStrategy,
 }
 
 impl SchemaBuilder {
@@ -72,6 +90,7 @@ impl SchemaBuilder {
             Ok(ast) => ast,
             Err(e) => panic!("Error parsing graphql schema {e:?}",),
         };
+        self.naming = schema_naming_strategy(schema);
 
         let query = ast
             .definitions
@@ -90,6 +109,7 @@ impl SchemaBuilder {
         let query = query.cloned().expect("TODO: this needs to be error type");
 
         let types_map = build_schema_fields_and_types_map(&ast);
+        self.enums = build_schema_enums_map(&ast);
 
         for def in ast.definitions.iter() {
             if let Definition::TypeDefinition(typ) = def {
@@ -103,6 +123,97 @@ impl SchemaBuilder {
         self
     }
 
+    /// Turn this schema into a migration of `previous`, the schema that the
+    /// tables were created from, if every change is additive: new entities,
+    /// and new fields of existing entities that are nullable or have a default.
+    /// Existing rows are kept, with the new fields set to their default, or
+    /// left null.
+    ///
+    /// Otherwise the schema is left as it is, and the first change that is not
+    /// additive is returned.
+    pub fn migrate_from(&mut self, previous: &SchemaBuilder) -> Result<(), String> {
+        if self.naming != previous.naming {
+            return Err(format!(
+                "Naming strategy was changed from {} to {}.",
+                previous.naming.as_ref(),
+                self.naming.as_ref()
+            ));
+        }
+
+        if let Some(table) = previous
+            .table_columns
+            .keys()
+            .find(|table| !self.table_columns.contains_key(*table))
+        {
+            return Err(format!("Entity '{table}' was removed."));
+        }
+
+        let mut migrations = Vec::new();
+
+        for (table, columns) in self.table_columns.iter() {
+            let previous_columns = match previous.table_columns.get(table) {
+                Some(previous_columns) => previous_columns,
+                None => continue,
+            };
+
+            if self.table_keys.get(table) != previous.table_keys.get(table) {
+                return Err(format!("Key of entity '{table}' was changed."));
+            }
+
+            for previous_column in previous_columns {
+                match columns.iter().find(|c| c.name == previous_column.name) {
+                    None => {
+                        return Err(format!(
+                            "Field '{table}.{}' was removed.",
+                            previous_column.name
+                        ))
+                    }
+                    Some(column) if column.fragment != previous_column.fragment => {
+                        return Err(format!(
+                            "Field '{table}.{}' was changed.",
+                            column.name
+                        ))
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            let sql_table = self.db_type.table_name(&self.namespace(), table);
+            for column in columns
+                .iter()
+                .filter(|c| !previous_columns.iter().any(|p| p.name == c.name))
+            {
+                if !column.nullable && !column.has_default {
+                    return Err(format!(
+                        "Field '{table}.{}' was added, but is not nullable and has no default.",
+                        column.name
+                    ));
+                }
+
+                migrations.push(format!(
+                    "ALTER TABLE {sql_table} ADD COLUMN {}",
+                    column.fragment
+                ));
+            }
+        }
+
+        // Tables that already exist are left as they are by their CREATE
+        // statements, but their foreign keys would be added twice.
+        self.foreign_keys.retain(|fk| {
+            previous
+                .table_columns
+                .get(&fk.table_name)
+                .map_or(true, |columns| {
+                    !columns
+                        .iter()
+                        .any(|c| self.naming.column_name(&c.name) == fk.column_name)
+                })
+        });
+        self.statements.extend(migrations);
+
+        Ok(())
+    }
+
     pub async fn commit_metadata(
         self,
         conn: &mut IndexerConnection,
@@ -123,6 +234,7 @@ impl SchemaBuilder {
             query_fields,
             schema,
             db_type,
+            naming,
             ..
         } = self;
 
@@ -162,6 +274,11 @@ impl SchemaBuilder {
             queries::execute_query(conn, idx.create_statement()).await?;
         }
 
+        let tables = type_ids
+            .iter()
+            .map(|t| (t.graphql_name.to_lowercase(), t.table_name.clone()))
+            .collect();
+
         queries::type_id_insert(conn, type_ids).await?;
         queries::new_column_insert(conn, columns).await?;
 
@@ -173,18 +290,26 @@ impl SchemaBuilder {
             types,
             fields,
             foreign_keys: HashMap::new(),
+            one_to_many: HashMap::new(),
+            descriptions: HashMap::new(),
+            naming,
+            tables,
         })
     }
 
     fn process_type(&self, field_type: &Type<String>) -> (ColumnType, bool) {
         match field_type {
             Type::NamedType(t) => {
+                if self.enums.contains_key(t) {
+                    return (ColumnType::Enum, true);
+                }
                 if !self.primitives.contains(t.as_str()) {
                     return (ColumnType::ForeignKey, true);
                 }
                 (ColumnType::from(t.as_str()), true)
             }
-            Type::ListType(_) => panic!("List types not supported yet."),
+            // Lists of entities have no column, so any list here is of scalars.
+            Type::ListType(_) => (ColumnType::Array, true),
             Type::NonNullType(t) => {
                 let (typ, _) = self.process_type(t);
                 (typ, false)
@@ -201,8 +326,14 @@ impl SchemaBuilder {
         types_map: &HashMap<String, String>,
     ) -> String {
         let mut fragments = Vec::new();
-
-        for (pos, field) in fields.iter().enumerate() {
+        let mut defaults = HashSet::new();
+        let first_column = self.columns.len();
+        let key = get_key_directive(obj);
+        let naming = self.naming;
+
+        // List fields are the "many" side of a one-to-many relationship and are
+        // resolved from the foreign key on the listed entity, so they get no column.
+        for (pos, field) in fields.iter().filter(|f| !is_list_type(f)).enumerate() {
             let (typ, nullable) = self.process_type(&field.field_type);
 
             let directives::Unique(unique) = get_unique_directive(field);
@@ -219,9 +350,11 @@ impl SchemaBuilder {
                     self.db_type.clone(),
                     self.namespace(),
                     table_name.to_string(),
-                    field.name.clone(),
-                    field_type_table_name(field),
-                    reference_field_name.clone(),
+                    naming.column_name(&field.name),
+                    naming.table_name(&normalize_field_type_name(
+                        &field.field_type.to_string(),
+                    )),
+                    naming.column_name(&reference_field_name),
                     reference_field_type_name.to_owned(),
                 );
 
@@ -235,13 +368,39 @@ impl SchemaBuilder {
                     unique,
                 };
 
-                fragments.push(column.sql_fragment());
+                // Foreign key columns aren't indexed by the database itself, but
+                // are used to join entities. Unique columns already have an index.
+                if !unique {
+                    self.indices.push(ColumnIndex {
+                        db_type: self.db_type.clone(),
+                        table_name: table_name.to_string(),
+                        namespace: self.namespace(),
+                        method: directives::IndexMethod::Btree,
+                        unique,
+                        column_name: naming.column_name(&field.name),
+                    });
+                }
+
+                match self.db_type {
+                    DbType::Postgres => {
+                        fragments.push(column.sql_fragment(&self.db_type, &naming));
+                        self.foreign_keys.push(fk);
+                    }
+                    DbType::Sqlite => fragments.push(format!(
+                        "{} {}",
+                        column.sql_fragment(&self.db_type, &naming),
+                        fk.create_statement()
+                    )),
+                }
                 self.columns.push(column);
-                self.foreign_keys.push(fk);
 
                 continue;
             }
 
+            // The ID of an entity with a composite key is unique, but is not
+            // its primary key.
+            let keyed_id = typ == ColumnType::ID && key.is_some();
+
             let column = NewColumn {
                 type_id,
                 column_position: pos as i32,
@@ -249,9 +408,17 @@ impl SchemaBuilder {
                 column_type: typ.to_string(),
                 graphql_type: field.field_type.to_string(),
                 nullable,
-                unique,
+                unique: unique || keyed_id,
             };
 
+            // The default is also stored in the rows that exist when the column
+            // is added to a table.
+            let mut fragment = column.sql_fragment(&self.db_type, &naming);
+            if let Some(default) = get_default_directive(field, &self.enums) {
+                fragment = format!("{fragment} default {}", default.literal);
+                defaults.insert(column.column_name.clone());
+            }
+
             if let Some(directives::Index {
                 column_name,
                 method,
@@ -263,11 +430,30 @@ impl SchemaBuilder {
                     namespace: self.namespace(),
                     method,
                     unique,
-                    column_name,
+                    column_name: naming.column_name(&column_name),
                 });
             }
 
-            fragments.push(column.sql_fragment());
+            // Enum columns only accept the names of the enum's values.
+            if typ == ColumnType::Enum {
+                let values = self.enums
+                    [&normalize_field_type_name(&field.field_type.to_string())]
+                    .iter()
+                    .map(|v| format!("'{v}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                fragments.push(format!(
+                    "{fragment} CHECK ({} IN ({values}))",
+                    naming.column_name(&field.name)
+                ));
+            } else if keyed_id {
+                fragments.push(format!(
+                    "{} bigint not null unique",
+                    naming.column_name(&field.name)
+                ));
+            } else {
+                fragments.push(fragment);
+            }
             self.columns.push(column);
         }
 
@@ -282,9 +468,80 @@ impl SchemaBuilder {
             unique: false,
         };
 
-        fragments.push(object_column.sql_fragment());
+        fragments.push(object_column.sql_fragment(&self.db_type, &naming));
         self.columns.push(object_column);
 
+        // Write times are set by the database, so that they don't depend on the
+        // clock of the indexer that wrote the object.
+        for column_name in [CREATED_AT_COLUMN, UPDATED_AT_COLUMN] {
+            let column = NewColumn {
+                type_id,
+                column_position: fragments.len() as i32,
+                column_name: column_name.to_string(),
+                column_type: "Timestamp".to_string(),
+                graphql_type: "Timestamp!".to_string(),
+                nullable: false,
+                unique: false,
+            };
+
+            fragments.push(format!(
+                "{} default {}",
+                column.sql_fragment(&self.db_type, &naming),
+                self.db_type.current_timestamp()
+            ));
+            self.columns.push(column);
+        }
+
+        // Removed objects keep their row, with the time they were removed.
+        let deleted_at_column = NewColumn {
+            type_id,
+            column_position: fragments.len() as i32,
+            column_name: DELETED_AT_COLUMN.to_string(),
+            column_type: "Timestamp".to_string(),
+            graphql_type: "Timestamp".to_string(),
+            nullable: true,
+            unique: false,
+        };
+
+        fragments.push(deleted_at_column.sql_fragment(&self.db_type, &naming));
+        self.columns.push(deleted_at_column);
+
+        // Objects keep the block they were last written at, so that clients can
+        // fetch what changed since a block, and objects of `@onlyNewer` types
+        // are only replaced by newer ones. Rows stored before blocks were
+        // recorded have none.
+        let written_at_block_column = NewColumn {
+            type_id,
+            column_position: fragments.len() as i32,
+            column_name: WRITTEN_AT_BLOCK_COLUMN.to_string(),
+            column_type: "UInt8".to_string(),
+            graphql_type: "UInt8".to_string(),
+            nullable: true,
+            unique: false,
+        };
+
+        fragments.push(written_at_block_column.sql_fragment(&self.db_type, &naming));
+        self.columns.push(written_at_block_column);
+
+        // Each fragment declares the column pushed along with it.
+        let defs = self.columns[first_column..]
+            .iter()
+            .zip(&fragments)
+            .map(|(column, fragment)| ColumnDef {
+                name: column.column_name.clone(),
+                fragment: fragment.clone(),
+                nullable: column.nullable,
+                has_default: defaults.contains(&column.column_name),
+            })
+            .collect();
+        self.table_columns.insert(table_name.to_string(), defs);
+
+        if let Some(key) = key {
+            let key_columns: Vec<_> = key.iter().map(|f| naming.column_name(f)).collect();
+            fragments.push(format!("PRIMARY KEY ({})", key_columns.join(", ")));
+            self.table_keys.insert(table_name.to_string(), key);
+        }
+
         fragments.join(",\n")
     }
 
@@ -317,7 +574,7 @@ impl SchemaBuilder {
                     return;
                 }
 
-                let table_name = o.name.to_lowercase();
+                let table_name = self.naming.table_name(&o.name);
                 let type_id = type_id(&self.namespace(), &o.name);
                 let columns =
                     self.generate_columns(o, type_id, &o.fields, &table_name, types_map);
@@ -337,11 +594,20 @@ impl SchemaBuilder {
                     table_name,
                 });
             }
+            // Enums are stored in the columns of the objects that use them.
+            TypeDefinition::Enum(_) => {}
             o => panic!("Got a non-object type: '{o:?}'"),
         }
     }
 }
-#[derive(Debug)]
+/// The descriptions given to a type and its fields in a GraphQL schema.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypeDescription {
+    pub description: Option<String>,
+    pub fields: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug)]
 pub struct Schema {
     pub version: String,
     pub namespace: String,
@@ -350,6 +616,13 @@ pub struct Schema {
     pub types: HashSet<String>,
     pub fields: HashMap<String, HashMap<String, String>>,
     pub foreign_keys: HashMap<String, HashMap<String, (String, String)>>,
+    pub one_to_many: HashMap<String, HashMap<String, (String, String, String)>>,
+    /// Descriptions of the documented types of the schema, keyed by type name.
+    pub descriptions: HashMap<String, TypeDescription>,
+    /// How the tables and columns of the schema's entities are named.
+    pub naming: NamingStrategy,
+    /// Table of each entity, by lowercase entity name.
+    pub tables: HashMap<String, String>,
 }
 
 impl Schema {
@@ -357,7 +630,7 @@ impl Schema {
         pool: &IndexerConnectionPool,
         namespace: &str,
         identifier: &str,
-    ) -> sqlx::Result<Self> {
+    ) -> IndexerSchemaResult<Self> {
         let mut conn = pool.acquire().await?;
         let root = queries::graph_root_latest(&mut conn, namespace, identifier).await?;
         let root_cols = queries::root_columns_list_by_id(&mut conn, root.id).await?;
@@ -370,7 +643,8 @@ impl Schema {
         .await?;
 
         let mut types = HashSet::new();
-        let mut fields = HashMap::new();
+        let mut fields: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut tables = HashMap::new();
 
         types.insert(root.query.clone());
         fields.insert(
@@ -382,6 +656,7 @@ impl Schema {
         );
         for tid in typeids {
             types.insert(tid.graphql_name.clone());
+            tables.insert(tid.graphql_name.to_lowercase(), tid.table_name.clone());
 
             let columns = queries::list_column_by_id(&mut conn, tid.id).await?;
             fields.insert(
@@ -394,6 +669,24 @@ impl Schema {
         }
 
         let foreign_keys = get_foreign_keys(&root.schema);
+        let one_to_many = get_one_to_many(&root.schema);
+        let descriptions = get_descriptions(&root.schema);
+        let naming = schema_naming_strategy(&root.schema);
+
+        // List fields have no column, so add them to the fields of their entity
+        // with the type of the listed entity.
+        for (name, fieldset) in fields.iter_mut() {
+            if let Some(relations) = one_to_many.get(&name.to_lowercase()) {
+                for (field, (child, _, _)) in relations.iter() {
+                    let child_type = types
+                        .iter()
+                        .find(|t| t.to_lowercase() == *child)
+                        .cloned()
+                        .unwrap_or_else(|| child.clone());
+                    fieldset.insert(field.clone(), child_type);
+                }
+            }
+        }
 
         Ok(Schema {
             version: root.version,
@@ -403,9 +696,26 @@ impl Schema {
             types,
             fields,
             foreign_keys,
+            one_to_many,
+            descriptions,
+            naming,
+            tables,
         })
     }
 
+    /// Table of the entity named `entity` in lowercase, as an SQL identifier.
+    pub fn table_name(&self, entity: &str) -> String {
+        self.tables
+            .get(entity)
+            .cloned()
+            .unwrap_or_else(|| entity.to_string())
+    }
+
+    /// Column of the field named `field`, as an SQL identifier.
+    pub fn column_name(&self, field: &str) -> String {
+        self.naming.column_name(field)
+    }
+
     pub fn check_type(&self, type_name: &str) -> bool {
         self.types.contains(type_name)
     }
@@ -426,6 +736,7 @@ impl Schema {
 
 fn get_foreign_keys(schema: &str) -> HashMap<String, HashMap<String, (String, String)>> {
     let (ast, primitives, types_map) = parse_schema_for_ast_data(schema);
+    let enums = build_schema_enums_map(&ast);
     let mut foreign_keys: HashMap<String, HashMap<String, (String, String)>> =
         HashMap::new();
 
@@ -435,7 +746,12 @@ fn get_foreign_keys(schema: &str) -> HashMap<String, HashMap<String, (String, St
                 continue;
             }
 
-            for field in o.fields.iter() {
+            for field in o.fields.iter().filter(|f| {
+                !is_list_type(f)
+                    && !enums.contains_key(&normalize_field_type_name(
+                        &f.field_type.to_string(),
+                    ))
+            }) {
                 if let ColumnType::ForeignKey =
                     get_column_type(&field.field_type, &primitives)
                 {
@@ -474,6 +790,103 @@ fn get_foreign_keys(schema: &str) -> HashMap<String, HashMap<String, (String, St
     foreign_keys
 }
 
+/// Collect the one-to-many relationships of a schema.
+///
+/// A list field `things: [Thing!]!` on `Parent` is resolved from the single field of
+/// `Thing` that references `Parent`. The result maps each parent table to its list
+/// fields, and each list field to the child table, the child's foreign key column,
+/// and the parent column it references.
+fn get_one_to_many(
+    schema: &str,
+) -> HashMap<String, HashMap<String, (String, String, String)>> {
+    let (ast, _, types_map) = parse_schema_for_ast_data(schema);
+    let objects: HashMap<&String, &ObjectType<String>> = ast
+        .definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::TypeDefinition(TypeDefinition::Object(o)) => Some((&o.name, o)),
+            _ => None,
+        })
+        .collect();
+
+    let mut one_to_many: HashMap<String, HashMap<String, (String, String, String)>> =
+        HashMap::new();
+
+    for o in objects.values() {
+        for field in o.fields.iter().filter(|f| is_list_type(f)) {
+            let child_name = list_field_type_name(field);
+            let child = objects.get(&child_name).unwrap_or_else(|| {
+                panic!(
+                    "List field '{}.{}' must hold an entity.",
+                    o.name, field.name
+                )
+            });
+
+            let mut refs = child.fields.iter().filter(|f| {
+                !is_list_type(f)
+                    && normalize_field_type_name(&f.field_type.to_string()) == o.name
+            });
+
+            let child_field = match (refs.next(), refs.next()) {
+                (Some(f), None) => f,
+                _ => panic!(
+                    "List field '{}.{}' requires exactly one field of '{child_name}' referencing '{}'.",
+                    o.name, field.name, o.name
+                ),
+            };
+
+            let directives::Join {
+                reference_field_name,
+                ..
+            } = get_join_directive_info(child_field, child, &types_map);
+
+            one_to_many
+                .entry(o.name.to_lowercase())
+                .or_default()
+                .insert(
+                    field.name.clone(),
+                    (
+                        child_name.to_lowercase(),
+                        child_field.name.clone(),
+                        reference_field_name,
+                    ),
+                );
+        }
+    }
+
+    one_to_many
+}
+
+/// Collect the descriptions of the object types of a schema and their fields,
+/// leaving out types that have none.
+fn get_descriptions(schema: &str) -> HashMap<String, TypeDescription> {
+    let (ast, _, _) = parse_schema_for_ast_data(schema);
+
+    ast.definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::TypeDefinition(TypeDefinition::Object(o)) => Some(o),
+            _ => None,
+        })
+        .filter_map(|o| {
+            let description = TypeDescription {
+                description: o.description.clone(),
+                fields: o
+                    .fields
+                    .iter()
+                    .filter_map(|f| Some((f.name.clone(), f.description.clone()?)))
+                    .collect(),
+            };
+
+            if description == TypeDescription::default() {
+                return None;
+            }
+
+            Some((o.name.clone(), description))
+        })
+        .collect()
+}
+
 fn parse_schema_for_ast_data(
     schema: &str,
 ) -> (Document<String>, HashSet<String>, HashMap<String, String>) {
@@ -505,7 +918,7 @@ fn get_column_type(
             }
             ColumnType::from(t.as_str())
         }
-        Type::ListType(_) => panic!("List types not supported yet."),
+        Type::ListType(_) => ColumnType::Array,
         Type::NonNullType(t) => get_column_type(t, primitives),
     }
 }
@@ -544,7 +957,7 @@ mod tests {
             " test_namespace_index1.thing1 (\n",
             " id bigint primary key not null,\n",
             "account varchar(64) not null,\n",
-            "object bytea not null",
+            "object bytea not null,\ncreated_at timestamp not null default now(),\nupdated_at timestamp not null default now(),\ndeleted_at timestamp,\nwritten_at_block bigint",
             "\n)"
         );
         let create_thing2_schema: &str = concat!(
@@ -553,7 +966,7 @@ mod tests {
             " id bigint primary key not null,\n",
             "account varchar(64) not null,\n",
             "hash varchar(64) not null,\n",
-            "object bytea not null\n",
+            "object bytea not null,\ncreated_at timestamp not null default now(),\nupdated_at timestamp not null default now(),\ndeleted_at timestamp,\nwritten_at_block bigint\n",
             ")"
         );
 
@@ -602,7 +1015,7 @@ mod tests {
             " test_namespace_index1.thing1 (\n",
             " id bigint primary key not null,\n",
             "account varchar(64),\n",
-            "object bytea not null",
+            "object bytea not null,\ncreated_at timestamp not null default now(),\nupdated_at timestamp not null default now(),\ndeleted_at timestamp,\nwritten_at_block bigint",
             "\n)"
         );
         let create_thing2_schema: &str = concat!(
@@ -611,7 +1024,7 @@ mod tests {
             " id bigint primary key not null,\n",
             "account varchar(64),\n",
             "hash varchar(64),\n",
-            "object bytea not null\n",
+            "object bytea not null,\ncreated_at timestamp not null default now(),\nupdated_at timestamp not null default now(),\ndeleted_at timestamp,\nwritten_at_block bigint\n",
             ")"
         );
 
@@ -660,12 +1073,12 @@ mod tests {
         assert_eq!(indices.len(), 2);
         assert_eq!(
             indices[0].create_statement(),
-            "CREATE INDEX payer_account_idx ON namespace_index1.payer USING btree (account);"
+            "CREATE INDEX IF NOT EXISTS payer_account_idx ON namespace_index1.payer USING btree (account);"
                 .to_string()
         );
         assert_eq!(
             indices[1].create_statement(),
-            "CREATE INDEX payee_hash_idx ON namespace_index1.payee USING btree (hash);"
+            "CREATE INDEX IF NOT EXISTS payee_hash_idx ON namespace_index1.payee USING btree (hash);"
                 .to_string()
         );
     }
@@ -778,11 +1191,87 @@ mod tests {
 
         let sb = SchemaBuilder::new("namespace", "index1", "v1", DbType::Postgres);
 
-        let SchemaBuilder { foreign_keys, .. } = sb.build(graphql_schema);
+        let SchemaBuilder {
+            foreign_keys,
+            indices,
+            ..
+        } = sb.build(graphql_schema);
 
         assert_eq!(foreign_keys.len(), 2);
         assert_eq!(foreign_keys[0].create_statement(), "ALTER TABLE namespace_index1.message ADD CONSTRAINT fk_message_sender__account_id FOREIGN KEY (sender) REFERENCES namespace_index1.account(id) ON DELETE NO ACTION ON UPDATE NO ACTION INITIALLY DEFERRED;".to_string());
         assert_eq!(foreign_keys[1].create_statement(), "ALTER TABLE namespace_index1.message ADD CONSTRAINT fk_message_receiver__account_id FOREIGN KEY (receiver) REFERENCES namespace_index1.account(id) ON DELETE NO ACTION ON UPDATE NO ACTION INITIALLY DEFERRED;".to_string());
+
+        let indices = indices
+            .iter()
+            .map(|idx| idx.create_statement())
+            .collect::<Vec<String>>();
+        assert!(indices.contains(&"CREATE INDEX IF NOT EXISTS message_sender_idx ON namespace_index1.message USING btree (sender);".to_string()));
+        assert!(indices.contains(&"CREATE INDEX IF NOT EXISTS message_receiver_idx ON namespace_index1.message USING btree (receiver);".to_string()));
+    }
+
+    #[test]
+    fn test_schema_builder_for_sqlite_schema_returns_proper_create_sql() {
+        let graphql_schema: &str = r#"
+        schema {
+            query: QueryRoot
+        }
+
+        type QueryRoot {
+            account: Account
+            message: Message
+        }
+
+        type Account {
+            id: ID!
+            account: Address! @indexed
+        }
+
+        type Message {
+            id: ID!
+            sender: Account!
+        }
+    "#;
+
+        let create_account_schema: &str = concat!(
+            "CREATE TABLE IF NOT EXISTS\n",
+            " namespace_index1_account (\n",
+            " id bigint primary key not null,\n",
+            "account varchar(64) not null,\n",
+            "object bytea not null,\ncreated_at timestamp not null default CURRENT_TIMESTAMP,\nupdated_at timestamp not null default CURRENT_TIMESTAMP,\ndeleted_at timestamp,\nwritten_at_block bigint\n",
+            ")"
+        );
+        let create_message_schema: &str = concat!(
+            "CREATE TABLE IF NOT EXISTS\n",
+            " namespace_index1_message (\n",
+            " id bigint primary key not null,\n",
+            "sender bigint not null REFERENCES namespace_index1_account(id) ON DELETE NO ACTION ON UPDATE NO ACTION DEFERRABLE INITIALLY DEFERRED,\n",
+            "object bytea not null,\ncreated_at timestamp not null default CURRENT_TIMESTAMP,\nupdated_at timestamp not null default CURRENT_TIMESTAMP,\ndeleted_at timestamp,\nwritten_at_block bigint\n",
+            ")"
+        );
+
+        let sb = SchemaBuilder::new("namespace", "index1", "v1", DbType::Sqlite);
+
+        let SchemaBuilder {
+            statements,
+            foreign_keys,
+            indices,
+            ..
+        } = sb.build(graphql_schema);
+
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[0], create_account_schema);
+        assert_eq!(statements[1], create_message_schema);
+        assert!(foreign_keys.is_empty());
+        assert_eq!(
+            indices[0].create_statement(),
+            "CREATE INDEX IF NOT EXISTS namespace_index1_account_account_idx ON namespace_index1_account (account);"
+                .to_string()
+        );
+        assert_eq!(
+            indices[1].create_statement(),
+            "CREATE INDEX IF NOT EXISTS namespace_index1_message_sender_idx ON namespace_index1_message (sender);"
+                .to_string()
+        );
     }
 
     #[test]
@@ -887,4 +1376,513 @@ mod tests {
         let explicit_fk_foreign_keys = get_foreign_keys(explicit_fk_graphql_schema);
         assert_eq!(expected, explicit_fk_foreign_keys);
     }
+    const ONE_TO_MANY_GRAPHQL_SCHEMA: &str = r#"
+        schema {
+            query: QueryRoot
+        }
+
+        type QueryRoot {
+            borrower: Borrower
+            lender: Lender
+            auditor: Auditor
+        }
+
+        type Borrower {
+            id: ID!
+            account: Address! @unique
+            lenders: [Lender!]!
+            auditors: [Auditor!]!
+        }
+
+        type Lender {
+            id: ID!
+            borrower: Borrower!
+        }
+
+        type Auditor {
+            id: ID!
+            borrower: Borrower! @join(on:account)
+        }
+    "#;
+
+    #[test]
+    fn test_schema_builder_does_not_create_columns_for_list_fields() {
+        let sb = SchemaBuilder::new("namespace", "index1", "v1", DbType::Postgres);
+
+        let SchemaBuilder {
+            statements,
+            columns,
+            foreign_keys,
+            ..
+        } = sb.build(ONE_TO_MANY_GRAPHQL_SCHEMA);
+
+        assert!(statements.iter().any(|s| s
+            == "CREATE TABLE IF NOT EXISTS\n namespace_index1.borrower (\n id bigint primary key not null,\naccount varchar(64) not null unique,\nobject bytea not null,\ncreated_at timestamp not null default now(),\nupdated_at timestamp not null default now(),\ndeleted_at timestamp,\nwritten_at_block bigint\n)"));
+        assert!(!columns
+            .iter()
+            .any(|c| c.column_name == "lenders" || c.column_name == "auditors"));
+        assert_eq!(foreign_keys.len(), 2);
+    }
+
+    #[test]
+    fn test_schema_builder_checks_enum_columns_against_enum_values() {
+        let graphql_schema: &str = r#"
+        schema {
+            query: QueryRoot
+        }
+
+        type QueryRoot {
+            loan: Loan
+        }
+
+        enum LoanStatus {
+            ACTIVE
+            REPAID
+        }
+
+        type Loan {
+            id: ID!
+            status: LoanStatus!
+            previous_status: LoanStatus
+        }
+    "#;
+
+        let sb = SchemaBuilder::new("namespace", "index1", "v1", DbType::Postgres);
+
+        let SchemaBuilder {
+            statements,
+            columns,
+            foreign_keys,
+            ..
+        } = sb.build(graphql_schema);
+
+        assert_eq!(
+            statements[1],
+            concat!(
+                "CREATE TABLE IF NOT EXISTS\n",
+                " namespace_index1.loan (\n",
+                " id bigint primary key not null,\n",
+                "status varchar(255) not null CHECK (status IN ('ACTIVE', 'REPAID')),\n",
+                "previous_status varchar(255) CHECK (previous_status IN ('ACTIVE', 'REPAID')),\n",
+                "object bytea not null,\ncreated_at timestamp not null default now(),\nupdated_at timestamp not null default now(),\ndeleted_at timestamp,\nwritten_at_block bigint\n",
+                ")"
+            )
+        );
+        assert!(columns
+            .iter()
+            .any(|c| c.column_name == "status" && c.column_type == "Enum"));
+        assert!(foreign_keys.is_empty());
+        assert!(get_foreign_keys(graphql_schema).is_empty());
+    }
+
+    #[test]
+    fn test_schema_builder_creates_array_columns_for_scalar_lists() {
+        let graphql_schema: &str = r#"
+        schema {
+            query: QueryRoot
+        }
+
+        type QueryRoot {
+            block: Block
+        }
+
+        type Block {
+            id: ID!
+            hashes: [Bytes32!]!
+            heights: [UInt8!]
+        }
+    "#;
+
+        let sb = SchemaBuilder::new("namespace", "index1", "v1", DbType::Postgres);
+        let SchemaBuilder {
+            statements,
+            columns,
+            foreign_keys,
+            ..
+        } = sb.build(graphql_schema);
+
+        assert_eq!(
+            statements[1],
+            concat!(
+                "CREATE TABLE IF NOT EXISTS\n",
+                " namespace_index1.block (\n",
+                " id bigint primary key not null,\n",
+                "hashes varchar(64)[] not null,\n",
+                "heights bigint[],\n",
+                "object bytea not null,\ncreated_at timestamp not null default now(),\nupdated_at timestamp not null default now(),\ndeleted_at timestamp,\nwritten_at_block bigint\n",
+                ")"
+            )
+        );
+        assert!(columns.iter().any(|c| c.column_name == "hashes"
+            && c.column_type == "Array"
+            && c.graphql_type == "[Bytes32!]!"));
+        assert!(foreign_keys.is_empty());
+
+        let sb = SchemaBuilder::new("namespace", "index1", "v1", DbType::Sqlite);
+        let SchemaBuilder { statements, .. } = sb.build(graphql_schema);
+
+        assert_eq!(
+            statements[0],
+            concat!(
+                "CREATE TABLE IF NOT EXISTS\n",
+                " namespace_index1_block (\n",
+                " id bigint primary key not null,\n",
+                "hashes json not null,\n",
+                "heights json,\n",
+                "object bytea not null,\ncreated_at timestamp not null default CURRENT_TIMESTAMP,\nupdated_at timestamp not null default CURRENT_TIMESTAMP,\ndeleted_at timestamp,\nwritten_at_block bigint\n",
+                ")"
+            )
+        );
+    }
+
+    #[test]
+    fn test_schema_builder_creates_composite_primary_keys() {
+        let graphql_schema: &str = r#"
+        schema {
+            query: QueryRoot
+        }
+
+        type QueryRoot {
+            balance: Balance
+        }
+
+        type Balance @key(fields: ["owner", "asset"]) {
+            id: ID!
+            owner: Address!
+            asset: AssetId!
+            amount: UInt8!
+        }
+    "#;
+
+        let sb = SchemaBuilder::new("namespace", "index1", "v1", DbType::Postgres);
+        let SchemaBuilder {
+            statements,
+            columns,
+            ..
+        } = sb.build(graphql_schema);
+
+        assert_eq!(
+            statements[1],
+            concat!(
+                "CREATE TABLE IF NOT EXISTS\n",
+                " namespace_index1.balance (\n",
+                " id bigint not null unique,\n",
+                "owner varchar(64) not null,\n",
+                "asset varchar(64) not null,\n",
+                "amount bigint not null,\n",
+                "object bytea not null,\ncreated_at timestamp not null default now(),\nupdated_at timestamp not null default now(),\ndeleted_at timestamp,\nwritten_at_block bigint,\n",
+                "PRIMARY KEY (owner, asset)\n",
+                ")"
+            )
+        );
+        assert!(columns.iter().any(|c| c.column_name == "id" && c.unique));
+
+        let unkeyed = graphql_schema.replace(r#"@key(fields: ["owner", "asset"])"#, "");
+        let previous = SchemaBuilder::new("namespace", "index1", "v1", DbType::Postgres)
+            .build(&unkeyed);
+        let mut keyed = SchemaBuilder::new("namespace", "index1", "v2", DbType::Postgres)
+            .build(graphql_schema);
+        assert_eq!(
+            keyed.migrate_from(&previous),
+            Err("Key of entity 'balance' was changed.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_schema_builder_records_write_blocks_of_every_type() {
+        let graphql_schema = r#"
+        schema {
+            query: QueryRoot
+        }
+
+        type QueryRoot {
+            balance: Balance
+        }
+
+        type Balance @onlyNewer {
+            id: ID!
+            amount: UInt8!
+        }
+    "#;
+
+        let sb = SchemaBuilder::new("namespace", "index1", "v1", DbType::Postgres);
+        let SchemaBuilder { statements, .. } = sb.build(graphql_schema);
+
+        assert_eq!(
+            statements[1],
+            concat!(
+                "CREATE TABLE IF NOT EXISTS\n",
+                " namespace_index1.balance (\n",
+                " id bigint primary key not null,\n",
+                "amount bigint not null,\n",
+                "object bytea not null,\ncreated_at timestamp not null default now(),\nupdated_at timestamp not null default now(),\ndeleted_at timestamp,\n",
+                "written_at_block bigint\n",
+                ")"
+            )
+        );
+
+        // Marking a type doesn't change its table.
+        let unmarked = graphql_schema.replace("@onlyNewer", "");
+        let previous = SchemaBuilder::new("namespace", "index1", "v1", DbType::Postgres)
+            .build(&unmarked);
+        assert_eq!(previous.statements[1], statements[1]);
+        let mut marked =
+            SchemaBuilder::new("namespace", "index1", "v2", DbType::Postgres)
+                .build(graphql_schema);
+        assert_eq!(marked.migrate_from(&previous), Ok(()));
+        assert!(!marked
+            .statements
+            .iter()
+            .any(|statement| statement.starts_with("ALTER TABLE")));
+    }
+
+    #[test]
+    fn test_schema_builder_names_tables_and_columns_by_naming_strategy() {
+        let graphql_schema = |naming: &str| {
+            format!(
+                r#"
+        schema @naming(strategy: {naming}) {{
+            query: QueryRoot
+        }}
+
+        type QueryRoot {{
+            blockheader: BlockHeader
+        }}
+
+        type TxOutput {{
+            id: ID!
+        }}
+
+        type BlockHeader {{
+            id: ID!
+            blockHeight: UInt8! @indexed
+            firstOutput: TxOutput!
+        }}
+    "#
+            )
+        };
+
+        let sb = SchemaBuilder::new("namespace", "index1", "v1", DbType::Postgres);
+        let SchemaBuilder {
+            statements,
+            foreign_keys,
+            indices,
+            type_ids,
+            ..
+        } = sb.build(&graphql_schema("SnakeCase"));
+
+        assert_eq!(
+            statements[2],
+            concat!(
+                "CREATE TABLE IF NOT EXISTS\n",
+                " namespace_index1.block_header (\n",
+                " id bigint primary key not null,\n",
+                "block_height bigint not null,\n",
+                "first_output bigint not null,\n",
+                "object bytea not null,\ncreated_at timestamp not null default now(),\nupdated_at timestamp not null default now(),\ndeleted_at timestamp,\nwritten_at_block bigint\n",
+                ")"
+            )
+        );
+        assert_eq!(
+            foreign_keys[0].create_statement(),
+            "ALTER TABLE namespace_index1.block_header ADD CONSTRAINT fk_block_header_first_output__tx_output_id FOREIGN KEY (first_output) REFERENCES namespace_index1.tx_output(id) ON DELETE NO ACTION ON UPDATE NO ACTION INITIALLY DEFERRED;"
+        );
+        assert_eq!(
+            indices[0].create_statement(),
+            "CREATE INDEX IF NOT EXISTS block_header_block_height_idx ON namespace_index1.block_header USING btree (block_height);"
+        );
+        assert_eq!(type_ids[1].graphql_name, "BlockHeader");
+        assert_eq!(type_ids[1].table_name, "block_header");
+
+        let sb = SchemaBuilder::new("namespace", "index1", "v1", DbType::Sqlite);
+        let SchemaBuilder {
+            statements,
+            indices,
+            ..
+        } = sb.build(&graphql_schema("Verbatim"));
+
+        assert_eq!(
+            statements[1],
+            concat!(
+                "CREATE TABLE IF NOT EXISTS\n",
+                " \"namespace_index1_BlockHeader\" (\n",
+                " \"id\" bigint primary key not null,\n",
+                "\"blockHeight\" bigint not null,\n",
+                "\"firstOutput\" bigint not null REFERENCES \"namespace_index1_TxOutput\"(\"id\") ON DELETE NO ACTION ON UPDATE NO ACTION DEFERRABLE INITIALLY DEFERRED,\n",
+                "\"object\" bytea not null,\n\"created_at\" timestamp not null default CURRENT_TIMESTAMP,\n\"updated_at\" timestamp not null default CURRENT_TIMESTAMP,\n\"deleted_at\" timestamp,\n\"written_at_block\" bigint\n",
+                ")"
+            )
+        );
+        assert_eq!(
+            indices[0].create_statement(),
+            "CREATE INDEX IF NOT EXISTS namespace_index1_BlockHeader_blockHeight_idx ON \"namespace_index1_BlockHeader\" (\"blockHeight\");"
+        );
+
+        let previous = SchemaBuilder::new("namespace", "index1", "v1", DbType::Postgres)
+            .build(&graphql_schema("Lowercase"));
+        let mut renamed =
+            SchemaBuilder::new("namespace", "index1", "v2", DbType::Postgres)
+                .build(&graphql_schema("SnakeCase"));
+        assert_eq!(
+            renamed.migrate_from(&previous),
+            Err("Naming strategy was changed from Lowercase to SnakeCase.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_one_to_many_for_schema() {
+        let expected = HashMap::from([(
+            "borrower".to_string(),
+            HashMap::from([
+                (
+                    "lenders".to_string(),
+                    (
+                        "lender".to_string(),
+                        "borrower".to_string(),
+                        "id".to_string(),
+                    ),
+                ),
+                (
+                    "auditors".to_string(),
+                    (
+                        "auditor".to_string(),
+                        "borrower".to_string(),
+                        "account".to_string(),
+                    ),
+                ),
+            ]),
+        )]);
+
+        assert_eq!(expected, get_one_to_many(ONE_TO_MANY_GRAPHQL_SCHEMA));
+        assert_eq!(
+            get_foreign_keys(ONE_TO_MANY_GRAPHQL_SCHEMA).get("borrower"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_descriptions_for_schema() {
+        let schema = r#"
+schema {
+    query: QueryRoot
+}
+
+type QueryRoot {
+    thing: Thing
+}
+
+"""
+A thing that was indexed.
+"""
+type Thing {
+    id: ID!
+    "Account that owns the thing."
+    account: Address!
+    amount: UInt8!
+}
+"#;
+
+        assert_eq!(
+            get_descriptions(schema),
+            HashMap::from([(
+                "Thing".to_string(),
+                TypeDescription {
+                    description: Some("A thing that was indexed.".to_string()),
+                    fields: HashMap::from([(
+                        "account".to_string(),
+                        "Account that owns the thing.".to_string()
+                    )]),
+                }
+            )])
+        );
+    }
+
+    #[test]
+    fn test_schema_builder_migrates_additive_changes_only() {
+        let schema = |thing_fields: &str| {
+            format!(
+                r#"
+        schema {{
+            query: QueryRoot
+        }}
+
+        type QueryRoot {{
+            thing: Thing
+        }}
+
+        type Account {{
+            id: ID!
+        }}
+
+        type Thing {{
+            id: ID!
+            account: Address!
+            {thing_fields}
+        }}
+    "#
+            )
+        };
+        let build = |thing_fields: &str| {
+            SchemaBuilder::new("test_namespace", "index1", "v", DbType::Postgres)
+                .build(&schema(thing_fields))
+        };
+
+        let previous = build("owner: Account");
+
+        let mut additive = build("owner: Account\n hash: Bytes32\n creator: Account");
+        assert!(additive.migrate_from(&previous).is_ok());
+        assert!(additive.statements.contains(
+            &"ALTER TABLE test_namespace_index1.thing ADD COLUMN hash varchar(64)"
+                .to_string()
+        ));
+        assert!(additive.statements.contains(
+            &"ALTER TABLE test_namespace_index1.thing ADD COLUMN creator bigint"
+                .to_string()
+        ));
+        // Only the foreign key of the new column is added.
+        assert_eq!(
+            additive
+                .foreign_keys
+                .iter()
+                .map(|fk| fk.column_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["creator"]
+        );
+
+        let mut not_nullable = build("owner: Account\n hash: Bytes32!");
+        assert_eq!(
+            not_nullable.migrate_from(&previous),
+            Err(
+                "Field 'thing.hash' was added, but is not nullable and has no default."
+                    .to_string()
+            )
+        );
+        assert!(!not_nullable
+            .statements
+            .iter()
+            .any(|s| s.starts_with("ALTER TABLE")));
+
+        // Existing rows get the default of a new non-null field.
+        let mut defaulted = build(
+            "owner: Account
+ count: UInt8! @default(value: 0)",
+        );
+        assert!(defaulted.migrate_from(&previous).is_ok());
+        assert!(defaulted.statements.contains(
+            &"ALTER TABLE test_namespace_index1.thing ADD COLUMN count bigint not null default 0"
+                .to_string()
+        ));
+
+        let mut removed = build("");
+        assert_eq!(
+            removed.migrate_from(&previous),
+            Err("Field 'thing.owner' was removed.".to_string())
+        );
+
+        let mut changed = build("owner: Account!");
+        assert_eq!(
+            changed.migrate_from(&previous),
+            Err("Field 'thing.owner' was changed.".to_string())
+        );
+    }
 }
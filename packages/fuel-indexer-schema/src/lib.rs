@@ -1,11 +1,11 @@
 #![deny(unused_crate_dependencies)]
 
 extern crate alloc;
-use crate::sql_types::ColumnType;
+use crate::sql_types::{ColumnType, DbType, QueryParam};
 use core::convert::TryInto;
 use fuel_indexer_types::{
-    try_from_bytes, Address, AssetId, Blob, Bytes32, Bytes4, Bytes8, ContractId,
-    Identity, Int16, Int4, Int8, Json, MessageId, Salt, UInt16, UInt4, UInt8,
+    try_from_bytes, Address, AssetId, Blob, Bytes, Bytes32, Bytes4, Bytes8, ContractId,
+    Decimal, Identity, Int16, Int4, Int8, Json, MessageId, Salt, UInt16, UInt4, UInt8,
 };
 use serde::{Deserialize, Serialize};
 
@@ -35,6 +35,7 @@ pub enum FtColumn {
     UInt4(Option<UInt4>),
     UInt8(Option<UInt8>),
     UInt16(Option<UInt16>),
+    Decimal(Option<Decimal>),
     Timestamp(Option<Int8>),
     Salt(Option<Salt>),
     Json(Option<Json>),
@@ -43,8 +44,30 @@ pub enum FtColumn {
     Identity(Option<Identity>),
     Boolean(Option<bool>),
     Blob(Option<Blob>),
+    Bytes(Option<Bytes>),
+    Enum(Option<String>),
+    Array(Option<Vec<FtColumn>>),
 }
 
+/// A stored value that is not one of the variants of its GraphQL enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidEnumValue {
+    pub enum_name: String,
+    pub value: String,
+}
+
+impl core::fmt::Display for InvalidEnumValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "'{}' is not a variant of enum {}.",
+            self.value, self.enum_name
+        )
+    }
+}
+
+impl std::error::Error for InvalidEnumValue {}
+
 impl FtColumn {
     pub fn new(ty: ColumnType, size: usize, bytes: &[u8]) -> FtColumn {
         match ty {
@@ -124,6 +147,12 @@ impl FtColumn {
                 );
                 FtColumn::UInt16(Some(uint16))
             }
+            ColumnType::Decimal => {
+                let decimal = String::from_utf8_lossy(&bytes[..size])
+                    .parse()
+                    .expect("Invalid decimal");
+                FtColumn::Decimal(Some(decimal))
+            }
             ColumnType::Timestamp => {
                 let int8 = i64::from_le_bytes(
                     bytes[..size].try_into().expect("Invalid slice length"),
@@ -131,6 +160,7 @@ impl FtColumn {
                 FtColumn::Timestamp(Some(int8))
             }
             ColumnType::Blob => FtColumn::Blob(Some(bytes[..size].to_vec())),
+            ColumnType::Bytes => FtColumn::Bytes(Some(bytes[..size].to_vec())),
             ColumnType::ForeignKey => {
                 panic!("ForeignKey not supported for FtColumn.");
             }
@@ -165,6 +195,13 @@ impl FtColumn {
             ColumnType::Object => {
                 panic!("Object not supported for FtColumn.");
             }
+            ColumnType::Enum => {
+                let s = String::from_utf8_lossy(&bytes[..size]).to_string();
+                FtColumn::Enum(Some(s))
+            }
+            ColumnType::Array => {
+                panic!("Array not supported for FtColumn.");
+            }
         }
     }
 
@@ -225,6 +262,10 @@ impl FtColumn {
                 Some(val) => format!("{val}"),
                 None => String::from(NULL_VALUE),
             },
+            FtColumn::Decimal(value) => match value {
+                Some(val) => format!("{val}"),
+                None => String::from(NULL_VALUE),
+            },
             FtColumn::Timestamp(value) => match value {
                 Some(val) => format!("{val}"),
                 None => String::from(NULL_VALUE),
@@ -266,8 +307,156 @@ impl FtColumn {
                 }
                 None => String::from(NULL_VALUE),
             },
+            FtColumn::Bytes(value) => match value {
+                Some(val) => format!("'\\x{}'", hex::encode(val)),
+                None => String::from(NULL_VALUE),
+            },
+            FtColumn::Enum(value) => match value {
+                Some(val) => format!("'{val}'"),
+                None => String::from(NULL_VALUE),
+            },
+            FtColumn::Array(value) => match value {
+                // The type of an empty `ARRAY[]` can't be inferred, unlike `'{}'`.
+                Some(items) if items.is_empty() => String::from("'{}'"),
+                Some(items) => {
+                    let items: Vec<_> =
+                        items.iter().map(|i| i.query_fragment()).collect();
+                    format!("ARRAY[{}]", items.join(", "))
+                }
+                None => String::from(NULL_VALUE),
+            },
         }
     }
+
+    /// The value as a parameter bound to a query, rather than formatted into it.
+    ///
+    /// Null values, and values of columns that can't be compared with a bound
+    /// parameter (e.g. `numeric` or JSON columns), have none.
+    pub fn query_param(&self) -> Option<QueryParam> {
+        match self {
+            FtColumn::ID(value) | FtColumn::UInt8(value) => value.map(uint_param),
+            FtColumn::Int4(value) => value.map(|v| QueryParam::Int(v as i64)),
+            FtColumn::Int8(value) => value.map(QueryParam::Int),
+            FtColumn::UInt4(value) => value.map(|v| QueryParam::Int(v as i64)),
+            FtColumn::Address(value) => {
+                value.map(|v| QueryParam::String(format!("{v:x}")))
+            }
+            FtColumn::AssetId(value) => {
+                value.map(|v| QueryParam::String(format!("{v:x}")))
+            }
+            FtColumn::Bytes4(value) => {
+                value.map(|v| QueryParam::String(format!("{v:x}")))
+            }
+            FtColumn::Bytes8(value) => {
+                value.map(|v| QueryParam::String(format!("{v:x}")))
+            }
+            FtColumn::Bytes32(value) => {
+                value.map(|v| QueryParam::String(format!("{v:x}")))
+            }
+            FtColumn::ContractId(value) => {
+                value.map(|v| QueryParam::String(format!("{v:x}")))
+            }
+            FtColumn::Salt(value) => value.map(|v| QueryParam::String(format!("{v:x}"))),
+            FtColumn::MessageId(value) => {
+                value.map(|v| QueryParam::String(format!("{v:x}")))
+            }
+            FtColumn::Identity(value) => value.as_ref().map(|v| match v {
+                Identity::Address(v) => QueryParam::String(format!("{v:x}")),
+                Identity::ContractId(v) => QueryParam::String(format!("{v:x}")),
+            }),
+            FtColumn::Charfield(value) | FtColumn::Enum(value) => {
+                value.clone().map(QueryParam::String)
+            }
+            FtColumn::Blob(value) => {
+                value.as_ref().map(|v| QueryParam::String(hex::encode(v)))
+            }
+            // Bound as raw bytes, rather than formatted as a hex string.
+            FtColumn::Bytes(value) => value.clone().map(QueryParam::Bytes),
+            FtColumn::Boolean(value) => value.map(QueryParam::Boolean),
+            FtColumn::Int16(_)
+            | FtColumn::UInt16(_)
+            | FtColumn::Decimal(_)
+            | FtColumn::Timestamp(_)
+            | FtColumn::Json(_)
+            | FtColumn::Array(_) => None,
+        }
+    }
+
+    /// The value as a parameter bound to a write of its column.
+    ///
+    /// Unlike `query_param`, every value can be bound, with null values bound as
+    /// `NULL`. Arrays are bound as an array literal on Postgres, and as JSON on
+    /// SQLite, which has no array type.
+    pub fn bind_param(&self, db_type: &DbType) -> QueryParam {
+        match self {
+            FtColumn::ID(None) => panic!("Schema fields of type ID cannot be nullable"),
+            FtColumn::Int16(value) => {
+                value.map_or(QueryParam::Null, |v| QueryParam::String(v.to_string()))
+            }
+            FtColumn::UInt16(value) => {
+                value.map_or(QueryParam::Null, |v| QueryParam::String(v.to_string()))
+            }
+            // Bound as text, so that no precision is lost on the way to `numeric`.
+            FtColumn::Decimal(value) => value
+                .as_ref()
+                .map_or(QueryParam::Null, |v| QueryParam::String(v.to_string())),
+            FtColumn::Timestamp(value) => value.map_or(QueryParam::Null, QueryParam::Int),
+            FtColumn::Json(value) => value
+                .as_ref()
+                .map_or(QueryParam::Null, |v| QueryParam::String(v.0.clone())),
+            FtColumn::Array(None) => QueryParam::Null,
+            FtColumn::Array(Some(items)) => {
+                let items: Vec<_> = items
+                    .iter()
+                    .map(|item| match (item.bind_param(db_type), item) {
+                        (QueryParam::Int(v), _) => v.to_string(),
+                        (QueryParam::Float(v), _) => v.to_string(),
+                        (QueryParam::Boolean(v), _) => v.to_string(),
+                        (
+                            QueryParam::String(v),
+                            FtColumn::Int16(_)
+                            | FtColumn::UInt16(_)
+                            | FtColumn::Decimal(_),
+                        ) => v,
+                        (QueryParam::String(v), _) => {
+                            format!(
+                                "\"{}\"",
+                                v.replace('\\', "\\\\").replace('"', "\\\"")
+                            )
+                        }
+                        // Array literals are text, so bytes are written in the
+                        // hex format of `bytea`.
+                        (QueryParam::Bytes(v), _) => match db_type {
+                            DbType::Postgres => format!("\"\\\\x{}\"", hex::encode(v)),
+                            DbType::Sqlite => format!("\"{}\"", hex::encode(v)),
+                        },
+                        (QueryParam::Null, _) => match db_type {
+                            DbType::Postgres => String::from(NULL_VALUE),
+                            DbType::Sqlite => String::from("null"),
+                        },
+                    })
+                    .collect();
+
+                match db_type {
+                    DbType::Postgres => {
+                        QueryParam::String(format!("{{{}}}", items.join(",")))
+                    }
+                    DbType::Sqlite => {
+                        QueryParam::String(format!("[{}]", items.join(",")))
+                    }
+                }
+            }
+            _ => self.query_param().unwrap_or(QueryParam::Null),
+        }
+    }
+}
+
+/// An unsigned integer as a parameter. Integer columns are signed, so values
+/// beyond `i64::MAX` are bound as text, which the database rejects rather than
+/// wrapping around.
+fn uint_param(value: u64) -> QueryParam {
+    i64::try_from(value)
+        .map_or_else(|_| QueryParam::String(value.to_string()), QueryParam::Int)
 }
 
 mod tests {
@@ -327,6 +516,145 @@ mod tests {
         insta::assert_yaml_snapshot!(identity.query_fragment());
     }
 
+    #[test]
+    fn test_query_params() {
+        use super::*;
+
+        let addr = Address::try_from([0x12; 32]).expect("Bad bytes");
+
+        assert_eq!(
+            FtColumn::ID(Some(7)).query_param(),
+            Some(QueryParam::Int(7))
+        );
+        assert_eq!(
+            FtColumn::Address(Some(addr)).query_param(),
+            Some(QueryParam::String(format!("{addr:x}")))
+        );
+        assert_eq!(
+            FtColumn::Charfield(Some("it's".to_string())).query_param(),
+            Some(QueryParam::String("it's".to_string()))
+        );
+        assert_eq!(
+            FtColumn::Boolean(Some(true)).query_param(),
+            Some(QueryParam::Boolean(true))
+        );
+        assert_eq!(FtColumn::Address(None).query_param(), None);
+        assert_eq!(FtColumn::UInt16(Some(1)).query_param(), None);
+        assert_eq!(
+            FtColumn::UInt8(Some(u64::MAX)).query_param(),
+            Some(QueryParam::String(u64::MAX.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_bind_params() {
+        use super::*;
+
+        let db_type = DbType::Postgres;
+
+        assert_eq!(
+            FtColumn::ID(Some(7)).bind_param(&db_type),
+            QueryParam::Int(7)
+        );
+        assert_eq!(
+            FtColumn::Charfield(Some("it's".to_string())).bind_param(&db_type),
+            QueryParam::String("it's".to_string())
+        );
+        assert_eq!(
+            FtColumn::UInt16(Some(u128::MAX)).bind_param(&db_type),
+            QueryParam::String(u128::MAX.to_string())
+        );
+        assert_eq!(
+            FtColumn::Int16(Some(-1)).bind_param(&db_type),
+            QueryParam::String("-1".to_string())
+        );
+        assert_eq!(
+            FtColumn::Timestamp(Some(5)).bind_param(&db_type),
+            QueryParam::Int(5)
+        );
+        assert_eq!(
+            FtColumn::Json(Some(Json("{\"a\":1}".to_string()))).bind_param(&db_type),
+            QueryParam::String("{\"a\":1}".to_string())
+        );
+        assert_eq!(
+            FtColumn::Address(None).bind_param(&db_type),
+            QueryParam::Null
+        );
+        assert_eq!(
+            FtColumn::UInt16(None).bind_param(&db_type),
+            QueryParam::Null
+        );
+        assert_eq!(FtColumn::Json(None).bind_param(&db_type), QueryParam::Null);
+    }
+
+    #[test]
+    fn test_decimal_types() {
+        use super::*;
+
+        let amount: Decimal = "123456789012345678901234567890.000000000000000001"
+            .parse()
+            .expect("Bad decimal");
+        let column = FtColumn::Decimal(Some(amount.clone()));
+
+        assert_eq!(
+            column.query_fragment(),
+            "123456789012345678901234567890.000000000000000001"
+        );
+        assert_eq!(column.query_param(), None);
+        assert_eq!(
+            column.bind_param(&DbType::Postgres),
+            QueryParam::String(amount.to_string())
+        );
+        assert_eq!(
+            FtColumn::new(ColumnType::Decimal, 4, b"-1.5"),
+            FtColumn::Decimal(Some("-1.5".parse().unwrap()))
+        );
+        assert_eq!(FtColumn::Decimal(None).query_fragment(), NULL_VALUE);
+
+        assert_eq!(Decimal::new(-12345, 2).as_str(), "-123.45");
+        assert_eq!(Decimal::new(5, 3).as_str(), "0.005");
+        assert_eq!(Decimal::new(42, 0).as_str(), "42");
+        for invalid in ["", "-", "1.", ".5", "1e5", "1.2.3", "1; DROP TABLE x"] {
+            assert!(invalid.parse::<Decimal>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_bytes_types() {
+        use super::*;
+
+        let payload = vec![0x00, 0xff, 0x27];
+        let column = FtColumn::Bytes(Some(payload.clone()));
+
+        assert_eq!(column.query_fragment(), "'\\x00ff27'");
+        assert_eq!(
+            column.query_param(),
+            Some(QueryParam::Bytes(payload.clone()))
+        );
+        assert_eq!(
+            column.bind_param(&DbType::Postgres),
+            QueryParam::Bytes(payload.clone())
+        );
+        assert_eq!(
+            FtColumn::new(ColumnType::Bytes, 3, &payload),
+            FtColumn::Bytes(Some(payload))
+        );
+        assert_eq!(
+            FtColumn::Bytes(None).bind_param(&DbType::Postgres),
+            QueryParam::Null
+        );
+
+        let items = FtColumn::Array(Some(vec![FtColumn::Bytes(Some(vec![0x01, 0xab]))]));
+        assert_eq!(
+            items.bind_param(&DbType::Postgres),
+            QueryParam::String("{\"\\\\x01ab\"}".to_string())
+        );
+        assert_eq!(
+            items.bind_param(&DbType::Sqlite),
+            QueryParam::String("[\"01ab\"]".to_string())
+        );
+    }
+
     #[test]
     fn test_fragments_none_types() {
         use super::*;
@@ -370,6 +698,91 @@ mod tests {
         insta::assert_yaml_snapshot!(identity_none.query_fragment());
     }
 
+    #[test]
+    fn test_fragments_enum_types() {
+        use super::*;
+
+        let status = FtColumn::Enum(Some(String::from("ACTIVE")));
+        assert_eq!(status.query_fragment(), "'ACTIVE'");
+        assert_eq!(FtColumn::Enum(None).query_fragment(), NULL_VALUE);
+    }
+
+    #[test]
+    fn test_fragments_array_types() {
+        use super::*;
+
+        let hashes = FtColumn::Array(Some(vec![
+            FtColumn::Bytes32(Some(Bytes32::from([0x01; 32]))),
+            FtColumn::Bytes32(Some(Bytes32::from([0x02; 32]))),
+        ]));
+        let values = FtColumn::Array(Some(vec![
+            FtColumn::UInt8(Some(1)),
+            FtColumn::UInt8(Some(2)),
+        ]));
+
+        assert_eq!(
+            hashes.query_fragment(),
+            format!("ARRAY['{}', '{}']", "01".repeat(32), "02".repeat(32))
+        );
+        assert_eq!(values.query_fragment(), "ARRAY[1, 2]");
+        assert_eq!(FtColumn::Array(Some(vec![])).query_fragment(), "'{}'");
+        assert_eq!(FtColumn::Array(None).query_fragment(), NULL_VALUE);
+    }
+
+    #[test]
+    fn test_bind_params_array_types() {
+        use super::*;
+
+        let hashes = FtColumn::Array(Some(vec![
+            FtColumn::Bytes32(Some(Bytes32::from([0x01; 32]))),
+            FtColumn::Bytes32(Some(Bytes32::from([0x02; 32]))),
+        ]));
+        let values = FtColumn::Array(Some(vec![
+            FtColumn::UInt16(Some(1)),
+            FtColumn::UInt16(Some(2)),
+        ]));
+        let names = FtColumn::Array(Some(vec![FtColumn::Charfield(Some(
+            "a \"quoted\" name".to_string(),
+        ))]));
+
+        assert_eq!(
+            hashes.bind_param(&DbType::Postgres),
+            QueryParam::String(format!(
+                "{{\"{}\",\"{}\"}}",
+                "01".repeat(32),
+                "02".repeat(32)
+            ))
+        );
+        assert_eq!(
+            hashes.bind_param(&DbType::Sqlite),
+            QueryParam::String(format!(
+                "[\"{}\",\"{}\"]",
+                "01".repeat(32),
+                "02".repeat(32)
+            ))
+        );
+        assert_eq!(
+            values.bind_param(&DbType::Postgres),
+            QueryParam::String("{1,2}".to_string())
+        );
+        assert_eq!(
+            values.bind_param(&DbType::Sqlite),
+            QueryParam::String("[1,2]".to_string())
+        );
+        assert_eq!(
+            names.bind_param(&DbType::Postgres),
+            QueryParam::String("{\"a \\\"quoted\\\" name\"}".to_string())
+        );
+        assert_eq!(
+            FtColumn::Array(Some(vec![])).bind_param(&DbType::Postgres),
+            QueryParam::String("{}".to_string())
+        );
+        assert_eq!(
+            FtColumn::Array(None).bind_param(&DbType::Sqlite),
+            QueryParam::Null
+        );
+    }
+
     #[test]
     #[should_panic(expected = "Schema fields of type ID cannot be nullable")]
     fn test_panic_on_none_id_fragment() {
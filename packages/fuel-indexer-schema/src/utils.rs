@@ -4,7 +4,8 @@ use alloc::vec::Vec;
 pub use fuel_indexer_database_types as sql_types;
 use fuel_indexer_types::graphql::{GraphqlObject, IndexMetadata};
 use graphql_parser::schema::{
-    Definition, Directive, Document, Field, ObjectType, TypeDefinition,
+    parse_schema, Definition, Directive, Document, Field, ObjectType, Type,
+    TypeDefinition, Value,
 };
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -13,7 +14,16 @@ use std::collections::{HashMap, HashSet};
 pub const BASE_SCHEMA: &str = include_str!("./base.graphql");
 pub const JOIN_DIRECTIVE_NAME: &str = "join";
 pub const UNIQUE_DIRECTIVE_NAME: &str = "unique";
+pub const UPSERT_ARGUMENT_NAME: &str = "upsert";
 pub const INDEX_DIRECTIVE_NAME: &str = "indexed";
+pub const KEY_DIRECTIVE_NAME: &str = "key";
+pub const KEY_FIELDS_ARGUMENT_NAME: &str = "fields";
+pub const DERIVED_ID_DIRECTIVE_NAME: &str = "derivedId";
+pub const ONLY_NEWER_DIRECTIVE_NAME: &str = "onlyNewer";
+pub const DEFAULT_DIRECTIVE_NAME: &str = "default";
+pub const DEFAULT_VALUE_ARGUMENT_NAME: &str = "value";
+pub const NAMING_DIRECTIVE_NAME: &str = "naming";
+pub const NAMING_STRATEGY_ARGUMENT_NAME: &str = "strategy";
 
 pub fn inject_native_entities_into_schema(schema: &str) -> String {
     format!("{}{}", schema, IndexMetadata::schema_fragment())
@@ -27,6 +37,612 @@ pub fn field_type_table_name(f: &Field<String>) -> String {
     normalize_field_type_name(&f.field_type.to_string()).to_lowercase()
 }
 
+fn is_list(f: &Field<String>) -> bool {
+    match &f.field_type {
+        Type::ListType(_) => true,
+        Type::NonNullType(t) => matches!(**t, Type::ListType(_)),
+        Type::NamedType(_) => false,
+    }
+}
+
+/// Whether `name` is one of the scalar types of the base schema.
+pub fn is_scalar_type(name: &str) -> bool {
+    BASE_SCHEMA
+        .lines()
+        .any(|line| line.strip_prefix("scalar ") == Some(name))
+}
+
+/// Scalar types that can be stored in a column, i.e. that have an `FtColumn` variant.
+pub const COLUMN_SCALAR_TYPES: [&str; 23] = [
+    "ID",
+    "Address",
+    "AssetId",
+    "Bytes4",
+    "Bytes8",
+    "Bytes32",
+    "ContractId",
+    "Int4",
+    "Int8",
+    "Int16",
+    "UInt4",
+    "UInt8",
+    "UInt16",
+    "Decimal",
+    "Timestamp",
+    "Salt",
+    "Json",
+    "MessageId",
+    "Charfield",
+    "Identity",
+    "Boolean",
+    "Blob",
+    "Bytes",
+];
+
+/// Describe each entity field of the schema whose type can't be stored: one that
+/// is neither a column scalar, an enum, nor another type of the schema. The query
+/// root has no table, so its fields are not checked.
+pub fn schema_type_errors(schema: &str) -> Vec<String> {
+    let ast = match parse_schema::<String>(schema) {
+        Ok(ast) => ast,
+        Err(e) => return vec![format!("GraphQL schema could not be parsed: {e}")],
+    };
+
+    let query_root = ast.definitions.iter().find_map(|def| match def {
+        Definition::SchemaDefinition(schema) => schema.query.as_deref(),
+        _ => None,
+    });
+
+    let objects: HashSet<&str> = ast
+        .definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::TypeDefinition(TypeDefinition::Object(o)) => {
+                Some(o.name.as_str())
+            }
+            _ => None,
+        })
+        .collect();
+    let enums = build_schema_enums_map(&ast);
+
+    let mut errors = Vec::new();
+    for def in &ast.definitions {
+        if let Definition::TypeDefinition(TypeDefinition::Object(o)) = def {
+            if query_root == Some(o.name.as_str()) {
+                continue;
+            }
+            for field in &o.fields {
+                if [
+                    sql_types::CREATED_AT_COLUMN,
+                    sql_types::UPDATED_AT_COLUMN,
+                    sql_types::DELETED_AT_COLUMN,
+                    sql_types::WRITTEN_AT_BLOCK_COLUMN,
+                ]
+                .contains(&field.name.as_str())
+                {
+                    errors.push(format!(
+                        "Field '{}.{}' has a reserved name; every type gets an implicit '{}' field.",
+                        o.name, field.name, field.name
+                    ));
+                    continue;
+                }
+                if is_upsert_key(field)
+                    && (is_list(field)
+                        || field.name == sql_types::IdCol::to_lowercase_string())
+                {
+                    errors.push(format!(
+                        "Field '{}.{}' can't be the upsert key of its type; only single-valued fields other than 'id' can.",
+                        o.name, field.name
+                    ));
+                }
+                if has_default_directive(field)
+                    && get_default_directive(field, &enums).is_none()
+                {
+                    errors.push(format!(
+                        "Field '{}.{}' has a default that doesn't suit its type; only single fields of integer, Decimal, Boolean, Charfield, and enum types can have one.",
+                        o.name, field.name
+                    ));
+                }
+                let is_column_scalar = |name: &str| COLUMN_SCALAR_TYPES.contains(&name);
+
+                if is_list(field) {
+                    let name = list_field_type_name(field);
+                    if !is_column_scalar(&name) && !objects.contains(name.as_str()) {
+                        errors.push(format!(
+                            "Field '{}.{}' is a list of {name}, which is neither a column scalar nor a type of the schema.",
+                            o.name, field.name
+                        ));
+                    }
+                } else {
+                    let name = normalize_field_type_name(&field.field_type.to_string());
+                    if !is_column_scalar(&name)
+                        && !enums.contains_key(&name)
+                        && !objects.contains(name.as_str())
+                    {
+                        errors.push(format!(
+                            "Field '{}.{}' has type {name}, which is neither a column scalar, an enum, nor a type of the schema.",
+                            o.name, field.name
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    let naming = match naming_directive(&ast) {
+        Some(strategy) => strategy.parse().unwrap_or_else(|_| {
+            errors.push(format!(
+                "Schema has naming strategy '{strategy}', which is not one of Lowercase, SnakeCase, or Verbatim."
+            ));
+            sql_types::NamingStrategy::default()
+        }),
+        None => sql_types::NamingStrategy::default(),
+    };
+    errors.extend(naming_errors(&ast, query_root, &naming));
+
+    for def in &ast.definitions {
+        if let Definition::TypeDefinition(TypeDefinition::Object(o)) = def {
+            if let Some(key) = get_key_directive(o) {
+                errors.extend(key_errors(o, &key));
+            }
+            if let Some(fields) = get_derived_id_directive(o) {
+                errors.extend(derived_id_errors(o, &fields));
+            }
+
+            let keys: Vec<&str> = o
+                .fields
+                .iter()
+                .filter(|field| is_upsert_key(field))
+                .map(|field| field.name.as_str())
+                .collect();
+            if keys.len() > 1 {
+                errors.push(format!(
+                    "Type '{}' has more than one upsert key: {}.",
+                    o.name,
+                    keys.join(", ")
+                ));
+            }
+        }
+    }
+
+    errors
+}
+
+/// Describe the types whose tables, and the fields whose columns, would have
+/// the same name as another's under `naming`.
+fn naming_errors(
+    ast: &Document<String>,
+    query_root: Option<&str>,
+    naming: &sql_types::NamingStrategy,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut tables: HashMap<String, &str> = HashMap::new();
+
+    for def in &ast.definitions {
+        if let Definition::TypeDefinition(TypeDefinition::Object(o)) = def {
+            if query_root == Some(o.name.as_str()) {
+                continue;
+            }
+            match tables.get(&naming.table_name(&o.name)) {
+                Some(other) => errors.push(format!(
+                    "Types '{other}' and '{}' would both be stored in table {}.",
+                    o.name,
+                    naming.table_name(&o.name)
+                )),
+                None => {
+                    tables.insert(naming.table_name(&o.name), &o.name);
+                }
+            }
+
+            let mut columns: HashMap<String, &str> = HashMap::new();
+            for field in o.fields.iter().filter(|f| !is_list_type(f)) {
+                match columns.get(&naming.stored_column_name(&field.name)) {
+                    Some(other) => errors.push(format!(
+                        "Fields '{}.{other}' and '{}.{}' would both be stored in column {}.",
+                        o.name,
+                        o.name,
+                        field.name,
+                        naming.column_name(&field.name)
+                    )),
+                    None => {
+                        columns.insert(naming.stored_column_name(&field.name), &field.name);
+                    }
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+/// The value of the `@naming(strategy: ...)` directive of the schema
+/// definition, if it has one.
+fn naming_directive(ast: &Document<String>) -> Option<&str> {
+    ast.definitions.iter().find_map(|def| match def {
+        Definition::SchemaDefinition(schema) => schema
+            .directives
+            .iter()
+            .find(|directive| directive.name == NAMING_DIRECTIVE_NAME)?
+            .arguments
+            .iter()
+            .find(|(name, _)| name == NAMING_STRATEGY_ARGUMENT_NAME)
+            .and_then(|(_, value)| match value {
+                Value::Enum(strategy) | Value::String(strategy) => {
+                    Some(strategy.as_str())
+                }
+                _ => None,
+            }),
+        _ => None,
+    })
+}
+
+/// How the tables and columns of a schema's entities are named, as set by
+/// `schema @naming(strategy: ...)`. Schemas without one, or with one that
+/// `schema_type_errors` rejects, use `NamingStrategy::Lowercase`.
+pub fn schema_naming_strategy(schema: &str) -> sql_types::NamingStrategy {
+    parse_schema::<String>(schema)
+        .ok()
+        .and_then(|ast| naming_directive(&ast).and_then(|s| s.parse().ok()))
+        .unwrap_or_default()
+}
+
+/// The lowercase name of each type of a schema, by the name of its table.
+pub fn schema_table_entities(schema: &str) -> HashMap<String, String> {
+    let ast = match parse_schema::<String>(schema) {
+        Ok(ast) => ast,
+        Err(_) => return HashMap::new(),
+    };
+    let naming = schema_naming_strategy(schema);
+
+    ast.definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::TypeDefinition(TypeDefinition::Object(o)) => {
+                Some((naming.table_name(&o.name), o.name.to_lowercase()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Describe what is wrong with the `fields` that the IDs of type `o` are
+/// derived from, if anything.
+fn derived_id_errors(o: &ObjectType<String>, fields: &[String]) -> Vec<String> {
+    if fields.is_empty() {
+        return vec![format!("Type '{}' derives its IDs from no fields.", o.name)];
+    }
+
+    let mut errors = Vec::new();
+    for (i, name) in fields.iter().enumerate() {
+        if fields[..i].contains(name) {
+            errors.push(format!(
+                "Type '{}' derives its IDs from field '{name}' more than once.",
+                o.name
+            ));
+            continue;
+        }
+        match o.fields.iter().find(|field| &field.name == name) {
+            None => errors.push(format!(
+                "Type '{}' derives its IDs from field '{name}', which is not one of its fields.",
+                o.name
+            )),
+            Some(field)
+                if is_list(field)
+                    || !matches!(field.field_type, Type::NonNullType(_))
+                    || field.name == sql_types::IdCol::to_lowercase_string() =>
+            {
+                errors.push(format!(
+                    "Field '{}.{name}' can't be used to derive IDs; only non-null, single-valued fields other than 'id' can.",
+                    o.name
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+
+    errors
+}
+
+/// Describe what is wrong with the composite `key` of type `o`, if anything.
+fn key_errors(o: &ObjectType<String>, key: &[String]) -> Vec<String> {
+    if key.is_empty() {
+        return vec![format!("Type '{}' has an empty key.", o.name)];
+    }
+
+    let mut errors = Vec::new();
+    for (i, name) in key.iter().enumerate() {
+        if key[..i].contains(name) {
+            errors.push(format!(
+                "Type '{}' lists key field '{name}' more than once.",
+                o.name
+            ));
+            continue;
+        }
+        match o.fields.iter().find(|field| &field.name == name) {
+            None => errors.push(format!(
+                "Type '{}' has key field '{name}', which is not one of its fields.",
+                o.name
+            )),
+            Some(field)
+                if is_list(field)
+                    || !matches!(field.field_type, Type::NonNullType(_))
+                    || field.name == sql_types::IdCol::to_lowercase_string() =>
+            {
+                errors.push(format!(
+                    "Field '{}.{name}' can't be part of the key of its type; only non-null, single-valued fields other than 'id' can.",
+                    o.name
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+
+    if o.fields.iter().any(is_upsert_key) {
+        errors.push(format!(
+            "Type '{}' has both a key and an upsert key.",
+            o.name
+        ));
+    }
+
+    errors
+}
+
+/// Fields of the composite key that type `o` declares with `@key(fields: [...])`,
+/// if it declares one.
+pub fn get_key_directive(o: &ObjectType<String>) -> Option<Vec<String>> {
+    get_fields_directive(o, KEY_DIRECTIVE_NAME)
+}
+
+/// Fields that the IDs of type `o` are derived from, if it declares them with
+/// `@derivedId(fields: [...])`.
+pub fn get_derived_id_directive(o: &ObjectType<String>) -> Option<Vec<String>> {
+    get_fields_directive(o, DERIVED_ID_DIRECTIVE_NAME)
+}
+
+/// Whether type `o` is marked `@onlyNewer`, so that a stored object is only
+/// replaced by one written at the same or a later block.
+pub fn has_only_newer_directive(o: &ObjectType<String>) -> bool {
+    o.directives
+        .iter()
+        .any(|directive| directive.name == ONLY_NEWER_DIRECTIVE_NAME)
+}
+
+/// The `fields` argument of the directive `name` of type `o`, if it has the
+/// directive.
+fn get_fields_directive(o: &ObjectType<String>, name: &str) -> Option<Vec<String>> {
+    let directive = o
+        .directives
+        .iter()
+        .find(|directive| directive.name == name)?;
+
+    let fields = directive
+        .arguments
+        .iter()
+        .find(|(name, _)| name == KEY_FIELDS_ARGUMENT_NAME)
+        .map(|(_, value)| match value {
+            Value::List(values) => values
+                .iter()
+                .filter_map(|value| match value {
+                    Value::String(name) => Some(name.clone()),
+                    _ => None,
+                })
+                .collect(),
+            Value::String(name) => vec![name.clone()],
+            _ => Vec::new(),
+        })
+        .unwrap_or_default();
+
+    Some(fields)
+}
+
+/// Whether a field is marked `@unique(upsert: true)`, so that saving an object
+/// whose value of the field is already stored replaces the stored object.
+pub fn is_upsert_key(field: &Field<String>) -> bool {
+    field.directives.iter().any(|directive| {
+        directive.name == UNIQUE_DIRECTIVE_NAME
+            && directive.arguments.iter().any(|(name, value)| {
+                name == UPSERT_ARGUMENT_NAME && *value == Value::Boolean(true)
+            })
+    })
+}
+
+/// The fields that upserts of each type conflict on, by the name of its table,
+/// for the types with a composite key or an upsert key. Other types upsert on
+/// `id`.
+pub fn schema_upsert_keys(schema: &str) -> HashMap<String, Vec<String>> {
+    let ast = match parse_schema::<String>(schema) {
+        Ok(ast) => ast,
+        Err(_) => return HashMap::new(),
+    };
+    let naming = schema_naming_strategy(schema);
+
+    ast.definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::TypeDefinition(TypeDefinition::Object(o)) => get_key_directive(o)
+                .or_else(|| {
+                    o.fields
+                        .iter()
+                        .find(|field| is_upsert_key(field))
+                        .map(|field| vec![field.name.clone()])
+                })
+                .map(|key| (naming.table_name(&o.name), key)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Non-null fields of each type of the schema that are stored in a column,
+/// keyed by the type's table name. Objects can't be saved with these fields
+/// null.
+pub fn schema_required_fields(schema: &str) -> HashMap<String, HashSet<String>> {
+    let ast = match parse_schema::<String>(schema) {
+        Ok(ast) => ast,
+        Err(_) => return HashMap::new(),
+    };
+    let naming = schema_naming_strategy(schema);
+
+    ast.definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::TypeDefinition(TypeDefinition::Object(o)) => Some((
+                naming.table_name(&o.name),
+                o.fields
+                    .iter()
+                    .filter(|field| {
+                        matches!(field.field_type, Type::NonNullType(_))
+                            && !is_list_type(field)
+                    })
+                    .map(|field| field.name.clone())
+                    .collect(),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Tables of the types marked `@onlyNewer`.
+pub fn schema_only_newer_tables(schema: &str) -> HashSet<String> {
+    let ast = match parse_schema::<String>(schema) {
+        Ok(ast) => ast,
+        Err(_) => return HashSet::new(),
+    };
+    let naming = schema_naming_strategy(schema);
+
+    ast.definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::TypeDefinition(TypeDefinition::Object(o))
+                if has_only_newer_directive(o) =>
+            {
+                Some(naming.table_name(&o.name))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether a field is marked `@default`.
+pub fn has_default_directive(field: &Field<String>) -> bool {
+    field
+        .directives
+        .iter()
+        .any(|directive| directive.name == DEFAULT_DIRECTIVE_NAME)
+}
+
+/// The value of a field's `@default(value: ...)` directive, if it has one whose
+/// value suits the field's type, e.g. a non-negative integer for a `UInt8!`
+/// field, or one of the values of the enum of an enum field.
+pub fn get_default_directive(
+    field: &Field<String>,
+    enums: &HashMap<String, Vec<String>>,
+) -> Option<sql_types::directives::ColumnDefault> {
+    if is_list(field) {
+        return None;
+    }
+
+    let value = field
+        .directives
+        .iter()
+        .find(|directive| directive.name == DEFAULT_DIRECTIVE_NAME)?
+        .arguments
+        .iter()
+        .find(|(name, _)| name == DEFAULT_VALUE_ARGUMENT_NAME)
+        .map(|(_, value)| value)?;
+
+    let quoted = |s: &str| format!("'{}'", s.replace('\'', "''"));
+    let type_name = normalize_field_type_name(&field.field_type.to_string());
+
+    // Values are bound as their columns' values are, so 128-bit integers and
+    // decimals are bound as text.
+    let (param, literal) = match (type_name.as_str(), value) {
+        ("Int4" | "Int8" | "Timestamp", Value::Int(n)) => {
+            let n = n.as_i64()?;
+            (sql_types::QueryParam::Int(n), n.to_string())
+        }
+        ("UInt4" | "UInt8", Value::Int(n)) => {
+            let n = n.as_i64().filter(|n| *n >= 0)?;
+            (sql_types::QueryParam::Int(n), n.to_string())
+        }
+        ("Int16" | "Decimal", Value::Int(n)) => {
+            let n = n.as_i64()?;
+            (sql_types::QueryParam::String(n.to_string()), n.to_string())
+        }
+        ("UInt16", Value::Int(n)) => {
+            let n = n.as_i64().filter(|n| *n >= 0)?;
+            (sql_types::QueryParam::String(n.to_string()), n.to_string())
+        }
+        ("Decimal", Value::Float(f)) if f.is_finite() => {
+            (sql_types::QueryParam::String(f.to_string()), f.to_string())
+        }
+        ("Boolean", Value::Boolean(b)) => {
+            (sql_types::QueryParam::Boolean(*b), b.to_string())
+        }
+        ("Charfield", Value::String(s)) => {
+            (sql_types::QueryParam::String(s.clone()), quoted(s))
+        }
+        (name, Value::Enum(v) | Value::String(v))
+            if enums.get(name).map_or(false, |values| values.contains(v)) =>
+        {
+            (sql_types::QueryParam::String(v.clone()), quoted(v))
+        }
+        _ => return None,
+    };
+
+    Some(sql_types::directives::ColumnDefault { param, literal })
+}
+
+/// The defaults of the fields of each type of the schema that have one, keyed
+/// by the type's table name and then by field name.
+pub fn schema_default_values(
+    schema: &str,
+) -> HashMap<String, HashMap<String, sql_types::QueryParam>> {
+    let ast = match parse_schema::<String>(schema) {
+        Ok(ast) => ast,
+        Err(_) => return HashMap::new(),
+    };
+    let naming = schema_naming_strategy(schema);
+    let enums = build_schema_enums_map(&ast);
+
+    ast.definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::TypeDefinition(TypeDefinition::Object(o)) => {
+                let defaults: HashMap<_, _> = o
+                    .fields
+                    .iter()
+                    .filter_map(|field| {
+                        get_default_directive(field, &enums)
+                            .map(|default| (field.name.clone(), default.param))
+                    })
+                    .collect();
+                (!defaults.is_empty()).then(|| (naming.table_name(&o.name), defaults))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether a field is a list of entities, i.e. the "many" side of a one-to-many
+/// relationship. List fields have no column of their own; they are resolved from
+/// the foreign key on the listed entity.
+pub fn is_list_type(f: &Field<String>) -> bool {
+    is_list(f) && !is_scalar_type(&list_field_type_name(f))
+}
+
+/// Whether a field is a list of scalars, which is stored in an array column.
+pub fn is_array_type(f: &Field<String>) -> bool {
+    is_list(f) && is_scalar_type(&list_field_type_name(f))
+}
+
+/// Name of the entity held by a list field (e.g. `Thing2` for `[Thing2!]!`).
+pub fn list_field_type_name(f: &Field<String>) -> String {
+    normalize_field_type_name(&f.field_type.to_string())
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string()
+}
+
 // serde_scale for now, can look at other options if necessary.
 pub fn serialize(obj: &impl Serialize) -> Vec<u8> {
     bincode::serialize(obj).expect("Serialize failed")
@@ -171,6 +787,8 @@ pub fn build_schema_fields_and_types_map(
                         types_map.insert(field_id, field_type);
                     }
                 }
+                // Enums have no fields of their own.
+                TypeDefinition::Enum(_) => {}
                 o => panic!("Got a non-object type: '{o:?}'"),
             }
         }
@@ -179,6 +797,20 @@ pub fn build_schema_fields_and_types_map(
     types_map
 }
 
+/// Map the name of each enum in the schema to the names of its values.
+pub fn build_schema_enums_map(ast: &Document<String>) -> HashMap<String, Vec<String>> {
+    ast.definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::TypeDefinition(TypeDefinition::Enum(e)) => Some((
+                e.name.clone(),
+                e.values.iter().map(|v| v.name.clone()).collect(),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
 pub fn build_schema_objects_set(
     ast: &Document<String>,
 ) -> (HashSet<String>, HashSet<String>) {
@@ -213,7 +845,314 @@ pub fn build_schema_objects_set(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use graphql_parser::parse_schema;
+    use sql_types::QueryParam;
+
+    #[test]
+    fn test_schema_type_errors_reports_fields_that_cannot_be_stored() {
+        let schema = r#"
+schema {
+    query: QueryRoot
+}
+
+type QueryRoot {
+    thing: Thing
+    missing: Missing
+}
+
+enum Color {
+    Red,
+    Blue,
+}
+
+type Thing {
+    id: ID!
+    owner: Owner!
+    color: Color!
+    heights: [UInt8!]!
+    signature: Bytes64!
+    weight: UInt9!
+    owners: [Owner!]!
+    colors: [Color!]!
+}
+
+type Owner {
+    id: ID!
+}
+"#;
+
+        assert_eq!(
+            schema_type_errors(schema),
+            vec![
+                "Field 'Thing.signature' has type Bytes64, which is neither a column scalar, an enum, nor a type of the schema.".to_string(),
+                "Field 'Thing.weight' has type UInt9, which is neither a column scalar, an enum, nor a type of the schema.".to_string(),
+                "Field 'Thing.colors' is a list of Color, which is neither a column scalar nor a type of the schema.".to_string(),
+            ]
+        );
+        assert!(schema_type_errors("type Owner { id: ID! }").is_empty());
+        assert_eq!(
+            schema_type_errors("type Owner { id: ID! updated_at: UInt8! }"),
+            vec!["Field 'Owner.updated_at' has a reserved name; every type gets an implicit 'updated_at' field.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_schema_upsert_keys_finds_one_upsert_key_per_type() {
+        let schema = r#"
+type Account {
+    id: ID!
+    address: Address! @unique(upsert: true)
+    label: Charfield! @unique
+}
+
+type Transfer {
+    id: ID!
+    hash: Bytes32! @unique(upsert: false)
+}
+"#;
+
+        assert!(schema_type_errors(schema).is_empty());
+        assert_eq!(
+            schema_upsert_keys(schema),
+            HashMap::from([("account".to_string(), vec!["address".to_string()])])
+        );
+
+        assert_eq!(
+            schema_type_errors(
+                "type Account { id: ID! @unique(upsert: true) address: Address! @unique(upsert: true) label: Charfield! @unique(upsert: true) }"
+            ),
+            vec![
+                "Field 'Account.id' can't be the upsert key of its type; only single-valued fields other than 'id' can.".to_string(),
+                "Type 'Account' has more than one upsert key: id, address, label.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schema_upsert_keys_finds_composite_keys() {
+        let schema = r#"
+type Balance @key(fields: ["owner", "asset"]) {
+    id: ID!
+    owner: Address!
+    asset: AssetId!
+    amount: UInt8!
+}
+"#;
+
+        assert!(schema_type_errors(schema).is_empty());
+        assert_eq!(
+            schema_upsert_keys(schema),
+            HashMap::from([(
+                "balance".to_string(),
+                vec!["owner".to_string(), "asset".to_string()]
+            )])
+        );
+
+        assert_eq!(
+            schema_type_errors(
+                r#"type Balance @key(fields: ["id", "owner", "memo", "owner", "asset"]) { id: ID! owner: Address! @unique(upsert: true) memo: Charfield }"#
+            ),
+            vec![
+                "Field 'Balance.id' can't be part of the key of its type; only non-null, single-valued fields other than 'id' can.".to_string(),
+                "Field 'Balance.memo' can't be part of the key of its type; only non-null, single-valued fields other than 'id' can.".to_string(),
+                "Type 'Balance' lists key field 'owner' more than once.".to_string(),
+                "Type 'Balance' has key field 'asset', which is not one of its fields.".to_string(),
+                "Type 'Balance' has both a key and an upsert key.".to_string(),
+            ]
+        );
+        assert_eq!(
+            schema_type_errors("type Balance @key(fields: []) { id: ID! }"),
+            vec!["Type 'Balance' has an empty key.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_schema_required_fields_finds_non_null_columns() {
+        let schema = r#"
+type Owner {
+    id: ID!
+}
+
+type Thing {
+    id: ID!
+    owner: Owner!
+    label: Charfield
+    tags: [Charfield!]!
+    owners: [Owner!]!
+}
+"#;
+
+        assert_eq!(
+            schema_required_fields(schema),
+            HashMap::from([
+                ("owner".to_string(), HashSet::from(["id".to_string()])),
+                (
+                    "thing".to_string(),
+                    HashSet::from([
+                        "id".to_string(),
+                        "owner".to_string(),
+                        "tags".to_string()
+                    ])
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_schema_default_values_checks_defaults_against_field_types() {
+        let schema = r#"
+enum Status {
+    Open,
+    Closed,
+}
+
+type Thing {
+    id: ID!
+    count: UInt8! @default(value: 0)
+    amount: Decimal @default(value: 1.5)
+    active: Boolean! @default(value: true)
+    label: Charfield @default(value: "it's new")
+    status: Status! @default(value: Open)
+    hash: Bytes32
+}
+"#;
+
+        assert!(schema_type_errors(schema).is_empty());
+        assert_eq!(
+            schema_default_values(schema),
+            HashMap::from([(
+                "thing".to_string(),
+                HashMap::from([
+                    ("count".to_string(), QueryParam::Int(0)),
+                    ("amount".to_string(), QueryParam::String("1.5".to_string())),
+                    ("active".to_string(), QueryParam::Boolean(true)),
+                    (
+                        "label".to_string(),
+                        QueryParam::String("it's new".to_string())
+                    ),
+                    ("status".to_string(), QueryParam::String("Open".to_string())),
+                ])
+            )])
+        );
+
+        let ast = parse_schema::<String>(schema).unwrap();
+        let enums = build_schema_enums_map(&ast);
+        let o = match &ast.definitions[1] {
+            Definition::TypeDefinition(TypeDefinition::Object(o)) => o,
+            _ => panic!("Expected an object type."),
+        };
+        assert_eq!(
+            get_default_directive(&o.fields[4], &enums).unwrap().literal,
+            "'it''s new'"
+        );
+
+        assert_eq!(
+            schema_type_errors(
+                r#"enum Status { Open } type Thing { id: ID! @default(value: 1) count: UInt8! @default(value: -1) status: Status @default(value: Closed) tags: [Charfield!] @default(value: "a") }"#
+            ),
+            ["id", "count", "status", "tags"]
+                .iter()
+                .map(|field| format!("Field 'Thing.{field}' has a default that doesn't suit its type; only single fields of integer, Decimal, Boolean, Charfield, and enum types can have one."))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_schema_type_errors_checks_derived_id_fields() {
+        let schema = r#"
+type Transfer @derivedId(fields: ["account", "hash"]) {
+    id: ID!
+    account: Address!
+    hash: Bytes32!
+}
+"#;
+
+        assert!(schema_type_errors(schema).is_empty());
+        let ast = parse_schema::<String>(schema).unwrap();
+        let o = match &ast.definitions[0] {
+            Definition::TypeDefinition(TypeDefinition::Object(o)) => o,
+            _ => panic!("Expected an object type."),
+        };
+        assert_eq!(
+            get_derived_id_directive(o),
+            Some(vec!["account".to_string(), "hash".to_string()])
+        );
+
+        assert_eq!(
+            schema_type_errors(
+                r#"type Transfer @derivedId(fields: ["id", "hash", "hash", "memo", "amount"]) { id: ID! hash: Bytes32! memo: Charfield }"#
+            ),
+            vec![
+                "Field 'Transfer.id' can't be used to derive IDs; only non-null, single-valued fields other than 'id' can.".to_string(),
+                "Type 'Transfer' derives its IDs from field 'hash' more than once.".to_string(),
+                "Field 'Transfer.memo' can't be used to derive IDs; only non-null, single-valued fields other than 'id' can.".to_string(),
+                "Type 'Transfer' derives its IDs from field 'amount', which is not one of its fields.".to_string(),
+            ]
+        );
+        assert_eq!(
+            schema_type_errors("type Transfer @derivedId(fields: []) { id: ID! }"),
+            vec!["Type 'Transfer' derives its IDs from no fields.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_schema_naming_strategy_names_tables_and_rejects_collisions() {
+        let schema = r#"
+schema @naming(strategy: SnakeCase) {
+    query: QueryRoot
+}
+
+type QueryRoot {
+    blockheader: BlockHeader
+}
+
+type BlockHeader @key(fields: ["blockHeight", "txIndex"]) {
+    id: ID!
+    blockHeight: UInt8!
+    txIndex: UInt4!
+}
+"#;
+
+        assert!(schema_type_errors(schema).is_empty());
+        assert_eq!(
+            schema_naming_strategy(schema),
+            sql_types::NamingStrategy::SnakeCase
+        );
+        assert_eq!(
+            schema_upsert_keys(schema),
+            HashMap::from([(
+                "block_header".to_string(),
+                vec!["blockHeight".to_string(), "txIndex".to_string()]
+            )])
+        );
+        assert_eq!(
+            schema_table_entities(schema).get("block_header"),
+            Some(&"blockheader".to_string())
+        );
+        assert_eq!(
+            schema_naming_strategy("type Thing { id: ID! }"),
+            sql_types::NamingStrategy::Lowercase
+        );
+
+        assert_eq!(
+            schema_type_errors(
+                r#"schema @naming(strategy: SnakeCase) { query: QueryRoot } type QueryRoot { thing: Thing } type Thing { id: ID! txIndex: UInt4! tx_index: UInt4! } type thing { id: ID! }"#
+            ),
+            vec![
+                "Fields 'Thing.txIndex' and 'Thing.tx_index' would both be stored in column tx_index.".to_string(),
+                "Types 'Thing' and 'thing' would both be stored in table thing.".to_string(),
+            ]
+        );
+        assert!(schema_type_errors(
+            r#"schema @naming(strategy: Verbatim) { query: QueryRoot } type QueryRoot { thing: Thing } type Thing { id: ID! txIndex: UInt4! txindex: UInt4! } type thing { id: ID! }"#
+        )
+        .is_empty());
+        assert_eq!(
+            schema_type_errors(
+                r#"schema @naming(strategy: Camel) { query: QueryRoot } type QueryRoot { thing: Thing } type Thing { id: ID! }"#
+            ),
+            vec!["Schema has naming strategy 'Camel', which is not one of Lowercase, SnakeCase, or Verbatim.".to_string()]
+        );
+    }
 
     #[test]
     fn test_build_schema_fields_and_types_map_properly_builds_schema_types_map() {
@@ -545,6 +545,8 @@ mod fuel_indexer_test {
             id: explicit.id,
             name: "The Indexers".to_string(),
             municipality: municipality.name,
+            league: League::NHL,
+            retired_numbers: vec![99],
         };
 
         team.save();
@@ -5,10 +5,11 @@ use fuel_indexer_api_server::api::GraphQlApi;
 use fuel_indexer_database::IndexerConnectionPool;
 use fuel_indexer_lib::{
     config::{
-        auth::AuthenticationStrategy, defaults as config_defaults, AuthenticationConfig,
-        DatabaseConfig, FuelNodeConfig, GraphQLConfig, IndexerConfig,
+        auth::{AuthenticationStrategy, MessageEncoding},
+        defaults as config_defaults, AuthenticationConfig, DatabaseConfig,
+        FuelNodeConfig, GraphQLConfig, IndexerConfig,
     },
-    utils::derive_socket_addr,
+    utils::{derive_socket_addr, ServiceRequest},
 };
 use fuel_indexer_postgres;
 use fuels::{
@@ -27,6 +28,7 @@ use sqlx::{Connection, Executor};
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use tokio::sync::mpsc::channel;
 use tracing_subscriber::filter::EnvFilter;
 
 abigen!(Contract(
@@ -91,6 +93,9 @@ impl TestPostgresDb {
                         fuel_indexer_postgres::run_migration(&mut conn).await?;
                         p
                     }
+                    IndexerConnectionPool::Sqlite(_) => {
+                        unreachable!("Test database URL is always a Postgres URL.")
+                    }
                 },
                 Err(e) => return Err(TestError::PoolCreationError(e)),
             };
@@ -283,6 +288,21 @@ pub async fn api_server_app_postgres(database_url: Option<&str>) -> Router {
         graphql_api: GraphQLConfig::default(),
         metrics: false,
         stop_idle_indexers: true,
+        max_reorg_depth: config_defaults::MAX_REORG_DEPTH,
+        stop_drain_timeout_secs: config_defaults::STOP_DRAIN_TIMEOUT_SECS,
+        node_retry_attempts: config_defaults::NODE_RETRY_ATTEMPTS,
+        read_ahead_blocks: config_defaults::READ_AHEAD_BLOCKS,
+        node_retry_base_delay_ms: config_defaults::NODE_RETRY_BASE_DELAY_MS,
+        commit_window_blocks: None,
+        commit_window_ms: None,
+        wasm_memory_limit_mb: None,
+        wasm_fuel_limit: None,
+        registry_startup_concurrency: config_defaults::REGISTRY_STARTUP_CONCURRENCY,
+        max_concurrent_batches: config_defaults::MAX_CONCURRENT_BATCHES,
+        max_db_connections: config_defaults::MAX_DB_CONNECTIONS,
+        min_idle_db_connections: config_defaults::MIN_IDLE_DB_CONNECTIONS,
+        db_acquire_timeout_secs: config_defaults::DB_ACQUIRE_TIMEOUT_SECS,
+        json_logging: false,
         run_migrations: false,
         authentication: AuthenticationConfig::default(),
     };
@@ -306,13 +326,31 @@ pub async fn authenticated_api_server_app_postgres(database_url: Option<&str>) -
         graphql_api: GraphQLConfig::default(),
         metrics: false,
         stop_idle_indexers: true,
+        max_reorg_depth: config_defaults::MAX_REORG_DEPTH,
+        stop_drain_timeout_secs: config_defaults::STOP_DRAIN_TIMEOUT_SECS,
+        node_retry_attempts: config_defaults::NODE_RETRY_ATTEMPTS,
+        read_ahead_blocks: config_defaults::READ_AHEAD_BLOCKS,
+        node_retry_base_delay_ms: config_defaults::NODE_RETRY_BASE_DELAY_MS,
+        commit_window_blocks: None,
+        commit_window_ms: None,
+        wasm_memory_limit_mb: None,
+        wasm_fuel_limit: None,
+        registry_startup_concurrency: config_defaults::REGISTRY_STARTUP_CONCURRENCY,
+        max_concurrent_batches: config_defaults::MAX_CONCURRENT_BATCHES,
+        max_db_connections: config_defaults::MAX_DB_CONNECTIONS,
+        min_idle_db_connections: config_defaults::MIN_IDLE_DB_CONNECTIONS,
+        db_acquire_timeout_secs: config_defaults::DB_ACQUIRE_TIMEOUT_SECS,
+        json_logging: false,
         run_migrations: false,
         authentication: AuthenticationConfig{
             enabled: true,
             strategy: Some(AuthenticationStrategy::JWT),
             jwt_secret: Some("6906573247652854078288872150120717701634680141358560585446649749925714230966".to_string()),
             jwt_issuer: Some("FuelLabs".to_string()),
-            jwt_expiry: Some(config_defaults::JWT_EXPIRY_SECS)
+            jwt_expiry: Some(config_defaults::JWT_EXPIRY_SECS),
+            api_key_hash: None,
+            admin_pubkeys: Vec::new(),
+            signature_encoding: MessageEncoding::default(),
         },
     };
 
@@ -335,6 +373,21 @@ pub async fn indexer_service_postgres(database_url: Option<&str>) -> IndexerServ
         graphql_api: GraphQLConfig::default(),
         metrics: false,
         stop_idle_indexers: true,
+        max_reorg_depth: config_defaults::MAX_REORG_DEPTH,
+        stop_drain_timeout_secs: config_defaults::STOP_DRAIN_TIMEOUT_SECS,
+        node_retry_attempts: config_defaults::NODE_RETRY_ATTEMPTS,
+        read_ahead_blocks: config_defaults::READ_AHEAD_BLOCKS,
+        node_retry_base_delay_ms: config_defaults::NODE_RETRY_BASE_DELAY_MS,
+        commit_window_blocks: None,
+        commit_window_ms: None,
+        wasm_memory_limit_mb: None,
+        wasm_fuel_limit: None,
+        registry_startup_concurrency: config_defaults::REGISTRY_STARTUP_CONCURRENCY,
+        max_concurrent_batches: config_defaults::MAX_CONCURRENT_BATCHES,
+        max_db_connections: config_defaults::MAX_DB_CONNECTIONS,
+        min_idle_db_connections: config_defaults::MIN_IDLE_DB_CONNECTIONS,
+        db_acquire_timeout_secs: config_defaults::DB_ACQUIRE_TIMEOUT_SECS,
+        json_logging: false,
         run_migrations: false,
         authentication: AuthenticationConfig::default(),
     };
@@ -346,6 +399,56 @@ pub async fn indexer_service_postgres(database_url: Option<&str>) -> IndexerServ
     IndexerService::new(config, pool, None).await.unwrap()
 }
 
+/// An API server that sends its requests to an indexer service, which is
+/// returned along with it so that the test can run it.
+pub async fn api_server_app_with_service_postgres(
+    database_url: Option<&str>,
+) -> (Router, IndexerService) {
+    let database: DatabaseConfig = database_url
+        .map_or(DatabaseConfig::default(), |url| {
+            DatabaseConfig::from_str(url).unwrap()
+        });
+
+    let config = IndexerConfig {
+        fuel_node: FuelNodeConfig::default(),
+        database,
+        graphql_api: GraphQLConfig::default(),
+        metrics: false,
+        stop_idle_indexers: true,
+        max_reorg_depth: config_defaults::MAX_REORG_DEPTH,
+        stop_drain_timeout_secs: config_defaults::STOP_DRAIN_TIMEOUT_SECS,
+        node_retry_attempts: config_defaults::NODE_RETRY_ATTEMPTS,
+        read_ahead_blocks: config_defaults::READ_AHEAD_BLOCKS,
+        node_retry_base_delay_ms: config_defaults::NODE_RETRY_BASE_DELAY_MS,
+        commit_window_blocks: None,
+        commit_window_ms: None,
+        wasm_memory_limit_mb: None,
+        wasm_fuel_limit: None,
+        registry_startup_concurrency: config_defaults::REGISTRY_STARTUP_CONCURRENCY,
+        max_concurrent_batches: config_defaults::MAX_CONCURRENT_BATCHES,
+        max_db_connections: config_defaults::MAX_DB_CONNECTIONS,
+        min_idle_db_connections: config_defaults::MIN_IDLE_DB_CONNECTIONS,
+        db_acquire_timeout_secs: config_defaults::DB_ACQUIRE_TIMEOUT_SECS,
+        json_logging: false,
+        run_migrations: false,
+        authentication: AuthenticationConfig::default(),
+    };
+
+    let pool = IndexerConnectionPool::connect(&config.database.to_string())
+        .await
+        .expect("Failed to create connection pool");
+
+    let (tx, rx) =
+        channel::<ServiceRequest>(config_defaults::SERVICE_REQUEST_CHANNEL_SIZE);
+
+    let app = GraphQlApi::build(config.clone(), pool.clone(), Some(tx))
+        .await
+        .unwrap();
+    let service = IndexerService::new(config, pool, Some(rx)).await.unwrap();
+
+    (app, service)
+}
+
 pub async fn connect_to_deployed_contract(
 ) -> Result<FuelIndexerTest, Box<dyn std::error::Error>> {
     let wallet_path = Path::new(WORKSPACE_ROOT).join("test-chain-config.json");
@@ -67,16 +67,17 @@ pub mod utils {
     // in manifest files relative from 'fuel-indexer')
     pub fn update_test_manifest_asset_paths(manifest: &mut Manifest) {
         let manifest_dir = Path::new(WORKSPACE_ROOT);
-        manifest.graphql_schema = manifest_dir
-            .parent()
-            .unwrap()
-            .parent()
-            .unwrap()
-            .join(&manifest.graphql_schema)
-            .into_os_string()
-            .to_str()
-            .unwrap()
-            .to_string();
+        let root = manifest_dir.parent().unwrap().parent().unwrap();
+        for path in std::iter::once(&mut manifest.graphql_schema)
+            .chain(manifest.graphql_schemas.iter_mut())
+        {
+            *path = root
+                .join(&*path)
+                .into_os_string()
+                .to_str()
+                .unwrap()
+                .to_string();
+        }
         manifest.abi = Some(
             manifest_dir
                 .parent()
@@ -74,6 +74,7 @@ async fn test_can_return_query_response_with_all_fields_required_postgres() {
 
     let body = resp.text().await.unwrap();
     let v: Value = serde_json::from_str(&body).unwrap();
+    let v = &v["data"];
 
     assert!(v[0]["height"].as_u64().unwrap() > 0);
     assert!(v[0]["timestamp"].as_u64().unwrap() > 0);
@@ -118,6 +119,7 @@ async fn test_can_return_query_response_with_nullable_fields_postgres() {
 
     let body = resp.text().await.unwrap();
     let v: Value = serde_json::from_str(&body).unwrap();
+    let v = &v["data"];
 
     assert_eq!(v[0]["int_required"], Value::from(Number::from(100)));
     assert_eq!(v[0]["int_optional_some"], Value::from(Number::from(999)));
@@ -165,6 +167,7 @@ async fn test_can_return_nested_query_response_with_implicit_foreign_keys_postgr
 
     let body = resp.text().await.unwrap();
     let v: Value = serde_json::from_str(&body).unwrap();
+    let v = &v["data"];
 
     assert!(v[0]["id"].as_i64().is_some());
     assert!(v[0]["id"].as_i64().unwrap() > 0);
@@ -278,6 +281,7 @@ async fn test_can_return_query_response_with_deeply_nested_query_postgres() {
 
     let body = resp.text().await.unwrap();
     let v: Value = serde_json::from_str(&body).unwrap();
+    let v = &v["data"];
 
     // Multiple reference to same foreign key table
     assert_eq!(
@@ -369,6 +373,7 @@ async fn test_can_return_nested_query_response_with_explicit_foreign_keys_postgr
 
     let body = resp.text().await.unwrap();
     let v: Value = serde_json::from_str(&body).unwrap();
+    let v = &v["data"];
 
     assert_eq!(v[0]["name"].as_str(), Some("The Indexers"));
     assert!(v[0]["municipality"]["id"].as_i64().is_some());
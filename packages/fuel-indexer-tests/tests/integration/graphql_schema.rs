@@ -1,4 +1,7 @@
-use fuel_indexer_database::types::{QueryElement, QueryFilter, UserQuery};
+use fuel_indexer_database::types::{
+    Comparison, DbType, NamingStrategy, QueryElement, QueryFilter, QueryPagination,
+    QueryParam, QuerySort, SortOrder, UserQuery,
+};
 use fuel_indexer_schema::db::{graphql::*, tables::Schema};
 use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
@@ -20,9 +23,14 @@ fn generate_schema() -> Schema {
             .map(|(k, v)| (k.to_string(), v.to_string())),
     );
     let f3 = HashMap::from_iter(
-        [("id", "ID"), ("account", "Address"), ("hash", "Bytes32")]
-            .iter()
-            .map(|(k, v)| (k.to_string(), v.to_string())),
+        [
+            ("id", "ID"),
+            ("account", "Address"),
+            ("hash", "Bytes32"),
+            ("written_at_block", "UInt8"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string())),
     );
     let fields = HashMap::from_iter([
         ("Query".to_string(), f1),
@@ -38,6 +46,10 @@ fn generate_schema() -> Schema {
         types,
         fields,
         foreign_keys: HashMap::new(),
+        one_to_many: HashMap::new(),
+        descriptions: HashMap::new(),
+        naming: NamingStrategy::default(),
+        tables: HashMap::new(),
     }
 }
 
@@ -95,11 +107,13 @@ fn test_query_builder_parses_correctly() {
             joins: HashMap::new(),
             namespace_identifier: "test_namespace_index1".to_string(),
             entity_name: "thing2".to_string(),
-            filters: vec![QueryFilter {
+            filters: vec![QueryFilter::Comparison {
                 key: "id".to_string(),
-                relation: "=".to_string(),
-                value: "1234".to_string(),
+                relation: Comparison::Eq,
+                value: QueryParam::Int(1234),
             }],
+            sort: Vec::new(),
+            pagination: None,
         },
         UserQuery {
             elements: vec![
@@ -119,11 +133,13 @@ fn test_query_builder_parses_correctly() {
             joins: HashMap::new(),
             namespace_identifier: "test_namespace_index1".to_string(),
             entity_name: "thing2".to_string(),
-            filters: vec![QueryFilter {
+            filters: vec![QueryFilter::Comparison {
                 key: "id".to_string(),
-                relation: "=".to_string(),
-                value: "84848".to_string(),
+                relation: Comparison::Eq,
+                value: QueryParam::Int(84848),
             }],
+            sort: Vec::new(),
+            pagination: None,
         },
         UserQuery {
             elements: vec![QueryElement::Field {
@@ -133,11 +149,13 @@ fn test_query_builder_parses_correctly() {
             joins: HashMap::new(),
             namespace_identifier: "test_namespace_index1".to_string(),
             entity_name: "thing1".to_string(),
-            filters: vec![QueryFilter {
+            filters: vec![QueryFilter::Comparison {
                 key: "id".to_string(),
-                relation: "=".to_string(),
-                value: "4321".to_string(),
+                relation: Comparison::Eq,
+                value: QueryParam::Int(4321),
             }],
+            sort: Vec::new(),
+            pagination: None,
         },
     ];
 
@@ -162,3 +180,392 @@ fn test_query_builder_parses_correctly() {
         o => panic!("Should have gotten Unrecognized type, got {o:?}",),
     }
 }
+
+#[test]
+fn test_query_builder_parses_pagination_arguments() {
+    let schema = generate_schema();
+    let cursor = Cursor { id: 42, offset: 10 };
+    assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+
+    let query = format!(
+        r#"{{ thing1(first: 5, after: "{}") {{ account }} }}"#,
+        cursor.encode()
+    );
+
+    let q = GraphqlQueryBuilder::new(&schema, &query)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        q.parse(&schema)[0].pagination,
+        Some(QueryPagination {
+            first: Some(5),
+            after_id: Some(42),
+            offset: 10,
+        })
+    );
+
+    let bad_cursor = r#"{ thing1(after: "not a cursor") { account } }"#;
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, bad_cursor)
+            .unwrap()
+            .build(),
+        Err(GraphqlError::InvalidCursor(_))
+    ));
+
+    let bad_page_size = r#"{ thing1(first: 0) { account } }"#;
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, bad_page_size)
+            .unwrap()
+            .build(),
+        Err(GraphqlError::InvalidPageSize(0))
+    ));
+}
+
+#[test]
+fn test_query_builder_parses_where_filters() {
+    let schema = generate_schema();
+
+    let query = r#"{
+        thing2(where: {
+            account: { eq: "0xABCD" },
+            hash: { in: ["01", "02"] },
+            _or: [{ id: { gt: 10, lte: 20 } }, { id: { eq: null } }]
+        }) { account }
+    }"#;
+
+    let q = GraphqlQueryBuilder::new(&schema, query)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        q.parse(&schema)[0].filters,
+        vec![
+            QueryFilter::Or(vec![
+                QueryFilter::And(vec![
+                    QueryFilter::Comparison {
+                        key: "id".to_string(),
+                        relation: Comparison::Gt,
+                        value: QueryParam::Int(10),
+                    },
+                    QueryFilter::Comparison {
+                        key: "id".to_string(),
+                        relation: Comparison::Lte,
+                        value: QueryParam::Int(20),
+                    },
+                ]),
+                QueryFilter::And(vec![QueryFilter::IsNull {
+                    key: "id".to_string(),
+                }]),
+            ]),
+            QueryFilter::Comparison {
+                key: "account".to_string(),
+                relation: Comparison::Eq,
+                value: QueryParam::String("abcd".to_string()),
+            },
+            QueryFilter::In {
+                key: "hash".to_string(),
+                values: vec![
+                    QueryParam::String("01".to_string()),
+                    QueryParam::String("02".to_string()),
+                ],
+            },
+        ]
+    );
+
+    let bad_field = r#"{ thing2(where: { nope: { eq: 1 } }) { account } }"#;
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, bad_field)
+            .unwrap()
+            .build(),
+        Err(GraphqlError::UnrecognizedArgument(_, _))
+    ));
+
+    let bad_operator = r#"{ thing2(where: { id: { like: 1 } }) { account } }"#;
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, bad_operator)
+            .unwrap()
+            .build(),
+        Err(GraphqlError::UnsupportedFilterOperator(_))
+    ));
+}
+
+#[test]
+fn test_query_builder_parses_order_argument() {
+    let schema = generate_schema();
+
+    let query = r#"{ thing2(order: [{ hash: DESC }, { id: ASC }]) { account } }"#;
+    let q = GraphqlQueryBuilder::new(&schema, query)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        q.parse(&schema)[0].sort,
+        vec![
+            QuerySort {
+                key: "hash".to_string(),
+                order: SortOrder::Desc,
+            },
+            QuerySort {
+                key: "id".to_string(),
+                order: SortOrder::Asc,
+            },
+        ]
+    );
+
+    let bad_field = r#"{ thing2(order: { nope: ASC }) { account } }"#;
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, bad_field)
+            .unwrap()
+            .build(),
+        Err(GraphqlError::UnrecognizedArgument(_, _))
+    ));
+
+    let bad_order = r#"{ thing2(order: { id: SIDEWAYS }) { account } }"#;
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, bad_order)
+            .unwrap()
+            .build(),
+        Err(GraphqlError::UnsupportedSortOrder(_))
+    ));
+
+    let ambiguous = r#"{ thing2(order: { id: ASC, hash: DESC }) { account } }"#;
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, ambiguous)
+            .unwrap()
+            .build(),
+        Err(GraphqlError::AmbiguousSortOrder)
+    ));
+}
+
+#[test]
+fn test_query_builder_parses_ids_argument() {
+    let schema = generate_schema();
+
+    let query = r#"{ thing2(ids: [3, 1, 2]) { account } }"#;
+    let q = GraphqlQueryBuilder::new(&schema, query)
+        .unwrap()
+        .build()
+        .unwrap();
+    let queries = q.parse(&schema);
+
+    assert_eq!(
+        queries[0].filters,
+        vec![QueryFilter::In {
+            key: "id".to_string(),
+            values: vec![QueryParam::Int(3), QueryParam::Int(1), QueryParam::Int(2)],
+        }]
+    );
+    assert_eq!(
+        queries[0].sort,
+        vec![QuerySort {
+            key: "id".to_string(),
+            order: SortOrder::Listed(vec![3, 1, 2]),
+        }]
+    );
+
+    // Sorting the query otherwise overrides the order of the IDs.
+    let query = r#"{ thing2(ids: [3, 1], order: { hash: DESC }) { account } }"#;
+    let q = GraphqlQueryBuilder::new(&schema, query)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        q.parse(&schema)[0].sort,
+        vec![QuerySort {
+            key: "hash".to_string(),
+            order: SortOrder::Desc,
+        }]
+    );
+
+    let bad_ids = r#"{ thing2(ids: ["one"]) { account } }"#;
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, bad_ids).unwrap().build(),
+        Err(GraphqlError::UnsupportedValueType(_))
+    ));
+}
+
+#[test]
+fn test_query_builder_parses_changed_since_argument() {
+    let schema = generate_schema();
+
+    let query = r#"{ thing2(changedSince: 100, order: { id: ASC }) { account } }"#;
+    let q = GraphqlQueryBuilder::new(&schema, query)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        q.parse(&schema)[0].filters,
+        vec![QueryFilter::Comparison {
+            key: "written_at_block".to_string(),
+            relation: Comparison::Gt,
+            value: QueryParam::Int(100),
+        }]
+    );
+
+    // Entities whose table doesn't record write blocks can't be filtered by them.
+    let unrecorded = r#"{ thing1(changedSince: 100) { account } }"#;
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, unrecorded)
+            .unwrap()
+            .build(),
+        Err(GraphqlError::UnrecognizedArgument(_, _))
+    ));
+
+    let bad_block = r#"{ thing2(changedSince: "100") { account } }"#;
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, bad_block)
+            .unwrap()
+            .build(),
+        Err(GraphqlError::UnsupportedValueType(_))
+    ));
+}
+
+#[test]
+fn test_query_builder_names_tables_and_columns_by_naming_strategy() {
+    let schema = Schema {
+        naming: NamingStrategy::Verbatim,
+        tables: HashMap::from([("thing2".to_string(), "\"Thing2\"".to_string())]),
+        ..generate_schema()
+    };
+
+    let query =
+        r#"{ thing2(where: { hash: { eq: "0xAB" } }, order: { id: DESC }) { account } }"#;
+    let q = GraphqlQueryBuilder::new(&schema, query)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        q.as_sql(&schema, DbType::Postgres),
+        vec![concat!(
+            r#"SELECT json_build_object('account', test_namespace_index1."Thing2"."account") "#,
+            r#"FROM test_namespace_index1."Thing2" "#,
+            r#"WHERE test_namespace_index1."Thing2"."hash" = $1 "#,
+            r#"ORDER BY test_namespace_index1."Thing2"."id" DESC"#
+        )
+        .to_string()]
+    );
+}
+
+#[test]
+fn test_query_builder_parses_aggregates() {
+    let schema = generate_schema();
+
+    let query = r#"{ thing2_aggregate(where: { account: { eq: "0xAB" } }) { count max { id } } }"#;
+    let q = GraphqlQueryBuilder::new(&schema, query)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let queries = q.parse(&schema);
+    assert_eq!(
+        queries[0].elements,
+        vec![
+            QueryElement::ObjectOpeningBoundary {
+                key: "aggregate".to_string(),
+            },
+            QueryElement::Field {
+                key: "count".to_string(),
+                value: "COUNT(*)".to_string(),
+            },
+            QueryElement::ObjectOpeningBoundary {
+                key: "max".to_string(),
+            },
+            QueryElement::Field {
+                key: "id".to_string(),
+                value: "MAX(test_namespace_index1.thing2.id)".to_string(),
+            },
+            QueryElement::ObjectClosingBoundary,
+            QueryElement::ObjectClosingBoundary,
+        ]
+    );
+    assert_eq!(queries[0].entity_name, "thing2");
+    assert_eq!(
+        queries[0].filters,
+        vec![QueryFilter::Comparison {
+            key: "account".to_string(),
+            relation: Comparison::Eq,
+            value: QueryParam::String("ab".to_string()),
+        }]
+    );
+
+    let non_numeric = r#"{ thing2_aggregate { sum { hash } } }"#;
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, non_numeric)
+            .unwrap()
+            .build(),
+        Err(GraphqlError::NonNumericAggregate(..))
+    ));
+
+    let unsupported = r#"{ thing2_aggregate { median { id } } }"#;
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, unsupported)
+            .unwrap()
+            .build(),
+        Err(GraphqlError::UnsupportedAggregate(_))
+    ));
+
+    let paginated = r#"{ thing2_aggregate(first: 1) { count } }"#;
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, paginated)
+            .unwrap()
+            .build(),
+        Err(GraphqlError::UnrecognizedArgument(..))
+    ));
+}
+
+#[test]
+fn test_query_builder_rejects_queries_above_max_depth_and_complexity() {
+    let schema = generate_schema();
+
+    // The root field is a list, so each of its fields is weighed as read for
+    // `LIST_COMPLEXITY_FACTOR` rows: 1 + 3 * 10.
+    let query = r#"{ thing2 { id account hash } }"#;
+
+    assert!(GraphqlQueryBuilder::new(&schema, query)
+        .unwrap()
+        .max_depth(2)
+        .max_complexity(31)
+        .build()
+        .is_ok());
+
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .max_depth(1)
+            .build(),
+        Err(GraphqlError::QueryTooDeep(2, 1))
+    ));
+
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .max_complexity(30)
+            .build(),
+        Err(GraphqlError::QueryTooComplex(31, 30))
+    ));
+
+    // Fields selected through fragments count as well.
+    let with_fragment = r#"
+        fragment frag on Thing2 {
+            account
+            hash
+        }
+
+        query {
+            thing2 { id ...frag }
+            thing1 { id }
+        }
+    "#;
+    assert!(matches!(
+        GraphqlQueryBuilder::new(&schema, with_fragment)
+            .unwrap()
+            .max_complexity(41)
+            .build(),
+        Err(GraphqlError::QueryTooComplex(42, 41))
+    ));
+}
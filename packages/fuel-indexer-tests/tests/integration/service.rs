@@ -1,8 +1,25 @@
 extern crate alloc;
-use fuel_indexer_lib::manifest::Manifest;
+use fuel_indexer::{
+    prelude::{Arc, BlockData, Mutex},
+    Database, IndexerError, IndexerResult, IndexerService, WasmIndexExecutor, WasmLimits,
+};
+use fuel_indexer_database::{
+    queries, types::IndexAssetType, ConnectionPoolConfig, IndexerConnection,
+    IndexerConnectionPool,
+};
+use fuel_indexer_lib::{
+    config::{DatabaseConfig, IndexerConfig},
+    defaults as config_defaults,
+    manifest::Manifest,
+    utils::{
+        AssetReloadRequest, IndexPauseRequest, IndexResumeRequest, IndexStatusRequest,
+        ServiceRequest,
+    },
+};
+use fuel_indexer_schema::db::manager::SchemaManager;
 use fuel_indexer_tests::{
     defaults,
-    fixtures::{indexer_service_postgres, tx_params},
+    fixtures::{indexer_service_postgres, tx_params, TestPostgresDb},
 };
 use fuels::prelude::{
     setup_single_asset_coins, setup_test_client, AssetId, Contract, Provider,
@@ -10,10 +27,18 @@ use fuels::prelude::{
 };
 use fuels::signers::Signer;
 use fuels_macros::abigen;
-use std::path::Path;
+use std::{path::Path, str::FromStr};
+use tokio::{
+    sync::{mpsc::channel, oneshot},
+    time::{sleep, timeout, Duration},
+};
 
 const SIMPLE_WASM_MANIFEST: &str =
     include_str!("./../../components/indices/simple-wasm/simple_wasm.yaml");
+const SIMPLE_WASM_GRAPHQL_SCHEMA: &str =
+    include_str!("./../../components/indices/simple-wasm/schema/simple_wasm.graphql");
+const SIMPLE_WASM_WASM: &[u8] =
+    include_bytes!("./../../components/indices/simple-wasm/simple_wasm.wasm");
 const WORKSPACE_DIR: &str = env!("CARGO_MANIFEST_DIR");
 
 abigen!(Contract(
@@ -78,3 +103,571 @@ async fn test_can_trigger_event_from_contract_and_index_emited_event_in_postgres
 
     srvc.run().await;
 }
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_registry_startup_skips_indexers_that_fail_to_start_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::connect(&test_db.url).await.unwrap();
+    let mut conn = pool.acquire().await.unwrap();
+
+    // An indexer whose manifest can't be parsed is registered first.
+    for (asset_type, bytes) in [
+        (IndexAssetType::Manifest, b"not: [a manifest".to_vec()),
+        (
+            IndexAssetType::Schema,
+            SIMPLE_WASM_GRAPHQL_SCHEMA.as_bytes().to_vec(),
+        ),
+        (IndexAssetType::Wasm, SIMPLE_WASM_WASM.to_vec()),
+    ] {
+        queries::register_index_asset(
+            &mut conn,
+            "test_namespace",
+            "broken_executor",
+            bytes,
+            asset_type,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    register_simple_wasm(&pool, &mut conn).await;
+
+    let mut srvc = indexer_service_postgres(Some(&test_db.url)).await;
+    assert!(srvc.register_indices_from_registry().await.is_ok());
+}
+
+/// Register the simple-wasm indexer's assets, and create its schema.
+#[cfg(feature = "postgres")]
+async fn register_simple_wasm(
+    pool: &IndexerConnectionPool,
+    conn: &mut IndexerConnection,
+) {
+    SchemaManager::new(pool.clone())
+        .new_schema(
+            "test_namespace",
+            "simple_wasm_executor",
+            SIMPLE_WASM_GRAPHQL_SCHEMA,
+            conn,
+        )
+        .await
+        .unwrap();
+    for (asset_type, bytes) in [
+        (
+            IndexAssetType::Manifest,
+            SIMPLE_WASM_MANIFEST.as_bytes().to_vec(),
+        ),
+        (
+            IndexAssetType::Schema,
+            SIMPLE_WASM_GRAPHQL_SCHEMA.as_bytes().to_vec(),
+        ),
+        (IndexAssetType::Wasm, SIMPLE_WASM_WASM.to_vec()),
+    ] {
+        queries::register_index_asset(
+            conn,
+            "test_namespace",
+            "simple_wasm_executor",
+            bytes,
+            asset_type,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_service_refuses_manifest_of_registered_indexer_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::connect(&test_db.url).await.unwrap();
+    let mut conn = pool.acquire().await.unwrap();
+
+    register_simple_wasm(&pool, &mut conn).await;
+    let manifest = Manifest::from_str(SIMPLE_WASM_MANIFEST).unwrap();
+
+    // Registered with other assets, but not running.
+    let mut srvc = indexer_service_postgres(Some(&test_db.url)).await;
+    match srvc.register_index_from_manifest(manifest.clone()).await {
+        Err(IndexerError::IndexerAlreadyRegistered(uid)) => {
+            assert_eq!(uid, "test_namespace.simple_wasm_executor")
+        }
+        other => panic!("Expected a registered indexer, got {other:?}."),
+    }
+
+    // Running in the service.
+    srvc.register_indices_from_registry().await.unwrap();
+    assert!(matches!(
+        srvc.register_index_from_manifest(manifest).await,
+        Err(IndexerError::IndexerAlreadyRegistered(_))
+    ));
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_service_restarts_registered_indexer_of_same_manifest_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+
+    let dir = std::env::temp_dir().join(&test_db.db_name);
+    std::fs::create_dir_all(&dir).unwrap();
+    let schema_path = dir.join("schema.graphql");
+    std::fs::write(&schema_path, SIMPLE_WASM_GRAPHQL_SCHEMA).unwrap();
+    let module_path = dir.join("simple_wasm.wasm");
+    std::fs::write(&module_path, SIMPLE_WASM_WASM).unwrap();
+
+    let manifest = Manifest::from_str(&format!(
+        "namespace: test_namespace
+identifier: restarted_executor
+graphql_schema: {}
+module:
+  wasm: {}
+",
+        schema_path.display(),
+        module_path.display()
+    ))
+    .unwrap();
+
+    let mut srvc = indexer_service_postgres(Some(&test_db.url)).await;
+    srvc.register_index_from_manifest(manifest.clone())
+        .await
+        .unwrap();
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let service = tokio::spawn(srvc.run_until(async {
+        let _ = shutdown_rx.await;
+    }));
+    shutdown_tx.send(()).unwrap();
+    let drain_timeout = Duration::from_secs(config_defaults::STOP_DRAIN_TIMEOUT_SECS + 5);
+    assert!(timeout(drain_timeout, service).await.is_ok());
+
+    // Once stopped, the indexer is started again from the same manifest.
+    let mut srvc = indexer_service_postgres(Some(&test_db.url)).await;
+    srvc.register_index_from_manifest(manifest.clone())
+        .await
+        .unwrap();
+
+    // But not once its module has changed.
+    std::fs::write(&module_path, b"another module").unwrap();
+    let mut srvc = indexer_service_postgres(Some(&test_db.url)).await;
+    assert!(matches!(
+        srvc.register_index_from_manifest(manifest).await,
+        Err(IndexerError::IndexerAlreadyRegistered(_))
+    ));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_service_stops_registry_indexers_on_shutdown_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::connect(&test_db.url).await.unwrap();
+    let mut conn = pool.acquire().await.unwrap();
+
+    register_simple_wasm(&pool, &mut conn).await;
+
+    let mut srvc = indexer_service_postgres(Some(&test_db.url)).await;
+    srvc.register_indices_from_registry().await.unwrap();
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let service = tokio::spawn(srvc.run_until(async {
+        let _ = shutdown_rx.await;
+    }));
+
+    // The indexer can't reach a Fuel node, so it only stops once shut down.
+    sleep(Duration::from_secs(1)).await;
+    assert!(!service.is_finished());
+
+    shutdown_tx.send(()).unwrap();
+    let drain_timeout = Duration::from_secs(config_defaults::STOP_DRAIN_TIMEOUT_SECS + 5);
+    assert!(timeout(drain_timeout, service).await.is_ok());
+}
+
+#[cfg(feature = "postgres")]
+async fn handle_no_events(
+    _blocks: Vec<BlockData>,
+    _db: Arc<Mutex<Database>>,
+) -> IndexerResult<()> {
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_registry_startup_restores_native_indexers_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::connect(&test_db.url).await.unwrap();
+    let mut conn = pool.acquire().await.unwrap();
+
+    // A native indexer has a manifest and schema, but no WASM module.
+    let manifest = SIMPLE_WASM_MANIFEST
+        .replace("simple_wasm_executor", "simple_native_executor")
+        .replace(
+            "module:\n  wasm: target/wasm32-unknown-unknown/release/simple_wasm.wasm",
+            "module: native",
+        );
+    SchemaManager::new(pool.clone())
+        .new_schema(
+            "test_namespace",
+            "simple_native_executor",
+            SIMPLE_WASM_GRAPHQL_SCHEMA,
+            &mut conn,
+        )
+        .await
+        .unwrap();
+    for (asset_type, bytes) in [
+        (IndexAssetType::Manifest, manifest.into_bytes()),
+        (
+            IndexAssetType::Schema,
+            SIMPLE_WASM_GRAPHQL_SCHEMA.as_bytes().to_vec(),
+        ),
+    ] {
+        queries::register_index_asset(
+            &mut conn,
+            "test_namespace",
+            "simple_native_executor",
+            bytes,
+            asset_type,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    let mut srvc = indexer_service_postgres(Some(&test_db.url)).await;
+    srvc.register_native_handler(
+        "test_namespace.simple_native_executor",
+        handle_no_events,
+    );
+    srvc.register_indices_from_registry().await.unwrap();
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let service = tokio::spawn(srvc.run_until(async {
+        let _ = shutdown_rx.await;
+    }));
+
+    // The restored indexer can't reach a Fuel node, so it only stops once shut down.
+    sleep(Duration::from_secs(1)).await;
+    assert!(!service.is_finished());
+
+    shutdown_tx.send(()).unwrap();
+    let drain_timeout = Duration::from_secs(config_defaults::STOP_DRAIN_TIMEOUT_SECS + 5);
+    assert!(timeout(drain_timeout, service).await.is_ok());
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_service_stops_once_its_request_channel_closes_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let config = IndexerConfig {
+        database: DatabaseConfig::from_str(&test_db.url).unwrap(),
+        ..IndexerConfig::default()
+    };
+    let pool = IndexerConnectionPool::connect(&test_db.url).await.unwrap();
+
+    let (tx, rx) =
+        channel::<ServiceRequest>(config_defaults::SERVICE_REQUEST_CHANNEL_SIZE);
+    let srvc = IndexerService::new(config, pool, Some(rx)).await.unwrap();
+    let service = tokio::spawn(srvc.run_until(std::future::pending()));
+
+    // With no indexers running, nothing is left to wait for.
+    drop(tx);
+    assert!(timeout(Duration::from_secs(5), service).await.is_ok());
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_failed_reload_keeps_previous_executor_running_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let config = IndexerConfig {
+        database: DatabaseConfig::from_str(&test_db.url).unwrap(),
+        ..IndexerConfig::default()
+    };
+    let pool = IndexerConnectionPool::connect(&test_db.url).await.unwrap();
+    let mut conn = pool.acquire().await.unwrap();
+
+    register_simple_wasm(&pool, &mut conn).await;
+
+    let (tx, rx) =
+        channel::<ServiceRequest>(config_defaults::SERVICE_REQUEST_CHANNEL_SIZE);
+    let mut srvc = IndexerService::new(config, pool.clone(), Some(rx))
+        .await
+        .unwrap();
+    srvc.register_indices_from_registry().await.unwrap();
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let service = tokio::spawn(srvc.run_until(async {
+        let _ = shutdown_rx.await;
+    }));
+
+    // The new version stops before handling any blocks, as its block range
+    // ends before it starts.
+    queries::register_index_asset(
+        &mut conn,
+        "test_namespace",
+        "simple_wasm_executor",
+        format!("{SIMPLE_WASM_MANIFEST}\nend_block: 0\n").into_bytes(),
+        IndexAssetType::Manifest,
+        None,
+    )
+    .await
+    .unwrap();
+    tx.send(ServiceRequest::AssetReload(AssetReloadRequest {
+        namespace: "test_namespace".to_string(),
+        identifier: "simple_wasm_executor".to_string(),
+        request_id: None,
+    }))
+    .await
+    .unwrap();
+    sleep(Duration::from_secs(1)).await;
+
+    let (reply, statuses) = oneshot::channel();
+    tx.send(ServiceRequest::IndexStatus(IndexStatusRequest { reply }))
+        .await
+        .unwrap();
+    let statuses = statuses.await.unwrap();
+    assert_eq!(
+        statuses.get("test_namespace.simple_wasm_executor"),
+        Some(&true)
+    );
+
+    shutdown_tx.send(()).unwrap();
+    let drain_timeout = Duration::from_secs(config_defaults::STOP_DRAIN_TIMEOUT_SECS + 5);
+    assert!(timeout(drain_timeout, service).await.is_ok());
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_paused_indexer_stays_alive_until_stopped_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let config = IndexerConfig {
+        database: DatabaseConfig::from_str(&test_db.url).unwrap(),
+        ..IndexerConfig::default()
+    };
+    let pool = IndexerConnectionPool::connect(&test_db.url).await.unwrap();
+    let mut conn = pool.acquire().await.unwrap();
+
+    register_simple_wasm(&pool, &mut conn).await;
+
+    let (tx, rx) =
+        channel::<ServiceRequest>(config_defaults::SERVICE_REQUEST_CHANNEL_SIZE);
+    let mut srvc = IndexerService::new(config, pool.clone(), Some(rx))
+        .await
+        .unwrap();
+    srvc.register_indices_from_registry().await.unwrap();
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let service = tokio::spawn(srvc.run_until(async {
+        let _ = shutdown_rx.await;
+    }));
+
+    let is_alive = || async {
+        let (reply, statuses) = oneshot::channel();
+        tx.send(ServiceRequest::IndexStatus(IndexStatusRequest { reply }))
+            .await
+            .unwrap();
+        statuses
+            .await
+            .unwrap()
+            .get("test_namespace.simple_wasm_executor")
+            .copied()
+    };
+
+    tx.send(ServiceRequest::IndexPause(IndexPauseRequest {
+        namespace: "test_namespace".to_string(),
+        identifier: "simple_wasm_executor".to_string(),
+        request_id: None,
+    }))
+    .await
+    .unwrap();
+    sleep(Duration::from_secs(1)).await;
+    assert_eq!(is_alive().await, Some(true));
+
+    tx.send(ServiceRequest::IndexResume(IndexResumeRequest {
+        namespace: "test_namespace".to_string(),
+        identifier: "simple_wasm_executor".to_string(),
+        request_id: None,
+    }))
+    .await
+    .unwrap();
+    sleep(Duration::from_secs(1)).await;
+    assert_eq!(is_alive().await, Some(true));
+
+    // A paused indexer is stopped as promptly as a running one.
+    tx.send(ServiceRequest::IndexPause(IndexPauseRequest {
+        namespace: "test_namespace".to_string(),
+        identifier: "simple_wasm_executor".to_string(),
+        request_id: None,
+    }))
+    .await
+    .unwrap();
+    shutdown_tx.send(()).unwrap();
+    let drain_timeout = Duration::from_secs(config_defaults::STOP_DRAIN_TIMEOUT_SECS + 5);
+    assert!(timeout(drain_timeout, service).await.is_ok());
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_registration_rejects_manifest_inconsistent_with_abi_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::connect(&test_db.url).await.unwrap();
+    let mut conn = pool.acquire().await.unwrap();
+
+    let dir = std::env::temp_dir().join(&test_db.db_name);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let schema_path = dir.join("schema.graphql");
+    std::fs::write(
+        &schema_path,
+        "type Thing {\n  id: ID!\n  weight: UInt9!\n}\n",
+    )
+    .unwrap();
+
+    // The function's output references a type that the ABI doesn't declare.
+    let abi_path = dir.join("abi.json");
+    std::fs::write(
+        &abi_path,
+        r#"{
+  "types": [{ "typeId": 0, "type": "u64", "components": null, "typeParameters": null }],
+  "functions": [
+    {
+      "inputs": [{ "name": "num", "type": 0, "typeArguments": null }],
+      "name": "gimme_thing",
+      "output": { "name": "", "type": 7, "typeArguments": null }
+    }
+  ],
+  "loggedTypes": []
+}"#,
+    )
+    .unwrap();
+
+    let manifest = Manifest::from_str(&format!(
+        "namespace: test_namespace
+identifier: invalid_executor
+abi: {}
+graphql_schema: {}
+module:
+  wasm: invalid_executor.wasm
+",
+        abi_path.display(),
+        schema_path.display()
+    ))
+    .unwrap();
+
+    let mut srvc = indexer_service_postgres(Some(&test_db.url)).await;
+    match srvc.register_index_from_manifest(manifest).await {
+        Err(IndexerError::InvalidManifest(e)) => {
+            assert!(e.contains("Output of 'gimme_thing' references undeclared type 7."));
+            assert!(e.contains("Field 'Thing.weight' has type UInt9"));
+        }
+        other => panic!("Expected an invalid manifest, got {other:?}."),
+    }
+
+    // Nothing is registered for a rejected manifest.
+    assert!(queries::registered_indices(&mut conn)
+        .await
+        .unwrap()
+        .is_empty());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_wasm_validation_checks_module_against_indexer_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::connect(&test_db.url).await.unwrap();
+    let mut conn = pool.acquire().await.unwrap();
+
+    let validate = |identifier: &'static str, wasm: &'static [u8], schema| {
+        WasmIndexExecutor::validate(
+            test_db.url.clone(),
+            ConnectionPoolConfig::default(),
+            "test_namespace",
+            identifier,
+            wasm,
+            schema,
+            None,
+            WasmLimits::default(),
+        )
+    };
+
+    assert!(validate(
+        "simple_wasm_executor",
+        SIMPLE_WASM_WASM,
+        Some(SIMPLE_WASM_GRAPHQL_SCHEMA)
+    )
+    .await
+    .is_ok());
+
+    match validate("other_executor", SIMPLE_WASM_WASM, None).await {
+        Err(IndexerError::InvalidModule(e)) => assert_eq!(
+            e,
+            "Module was built for Indexer(test_namespace.simple_wasm_executor), not Indexer(test_namespace.other_executor)."
+        ),
+        other => panic!("Expected an invalid module, got {other:?}."),
+    }
+
+    match validate(
+        "simple_wasm_executor",
+        SIMPLE_WASM_WASM,
+        Some("type Thing {\n  id: ID!\n}\n"),
+    )
+    .await
+    {
+        Err(IndexerError::InvalidModule(e)) => {
+            assert!(e.contains("but the uploaded schema is version"))
+        }
+        other => panic!("Expected an invalid module, got {other:?}."),
+    }
+
+    // Without a schema, the module's schema must already be registered.
+    match validate("simple_wasm_executor", SIMPLE_WASM_WASM, None).await {
+        Err(IndexerError::InvalidModule(e)) => assert!(e.contains("is not registered")),
+        other => panic!("Expected an invalid module, got {other:?}."),
+    }
+
+    register_simple_wasm(&pool, &mut conn).await;
+    assert!(validate("simple_wasm_executor", SIMPLE_WASM_WASM, None)
+        .await
+        .is_ok());
+
+    assert!(matches!(
+        validate("simple_wasm_executor", b"not a wasm module", None).await,
+        Err(IndexerError::CompileError(_))
+    ));
+
+    // Modules built before they listed their handlers can't have any disabled.
+    let mut manifest = Manifest::from_str(SIMPLE_WASM_MANIFEST).unwrap();
+    assert!(WasmIndexExecutor::validate(
+        test_db.url.clone(),
+        ConnectionPoolConfig::default(),
+        "test_namespace",
+        "simple_wasm_executor",
+        SIMPLE_WASM_WASM,
+        None,
+        Some(&manifest),
+        WasmLimits::default(),
+    )
+    .await
+    .is_ok());
+
+    manifest.enabled_handlers = Some(vec!["function_one".to_string()]);
+    match WasmIndexExecutor::validate(
+        test_db.url.clone(),
+        ConnectionPoolConfig::default(),
+        "test_namespace",
+        "simple_wasm_executor",
+        SIMPLE_WASM_WASM,
+        None,
+        Some(&manifest),
+        WasmLimits::default(),
+    )
+    .await
+    {
+        Err(IndexerError::InvalidManifest(e)) => {
+            assert!(e.contains("built without a list of its handlers"))
+        }
+        other => panic!("Expected an invalid manifest, got {other:?}."),
+    }
+}
@@ -1,23 +1,158 @@
-use fuel_indexer_api_server::api::GraphQlApi;
-use fuel_indexer_database::queries;
-use fuel_indexer_lib::{config::GraphQLConfig, defaults};
+use axum::Router;
+use fuel_indexer_api_server::api::{GraphQlApi, REQUEST_ID_HEADER};
+use fuel_indexer_database::{queries, types::IndexAssetType, IndexerConnectionPool};
+use fuel_indexer_lib::{
+    config::{
+        auth::{AuthenticationConfig, AuthenticationStrategy},
+        DatabaseConfig, GraphQLConfig, IndexerConfig,
+    },
+    defaults,
+    utils::sha256_digest,
+};
 use fuel_indexer_postgres as postgres;
 use fuel_indexer_tests::assets::{
     SIMPLE_WASM_MANIFEST, SIMPLE_WASM_SCHEMA, SIMPLE_WASM_WASM,
 };
 use fuel_indexer_tests::fixtures::{
-    api_server_app_postgres, authenticated_api_server_app_postgres, http_client,
-    indexer_service_postgres, TestPostgresDb,
+    api_server_app_postgres, api_server_app_with_service_postgres,
+    authenticated_api_server_app_postgres, http_client, indexer_service_postgres,
+    TestPostgresDb,
 };
-use hyper::header::{AUTHORIZATION, CONTENT_TYPE};
-use reqwest::{multipart, Body};
+use hyper::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{multipart, Body, RequestBuilder};
 use serde::{Deserialize, Serialize};
-use std::time::{SystemTime, UNIX_EPOCH};
-use tokio::task::spawn;
+use serde_json::Value;
+use std::{
+    ops::RangeInclusive,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::task::{spawn, JoinHandle};
 
 const SIGNATURE: &str = "cb19384361af5dd7fec2a0052ca49d289f997238ea90590baf47f16ff0a33fb20170a43bd20208ce16daf443bad06dd66c1d1bf73f48b5ae53de682a5731d7d9";
 const NONCE: &str = "ea35be0c98764e7ca06d02067982e3b4";
 
+/// Lines of the metrics response, leaving out the per-indexer series, which
+/// depend on the indexers that other tests have run.
+fn global_metrics(metrics: &str) -> Vec<&str> {
+    metrics
+        .split('\n')
+        .filter(|line| {
+            !["indexer_", "# HELP indexer_", "# TYPE indexer_"]
+                .iter()
+                .any(|prefix| line.starts_with(prefix))
+        })
+        .collect()
+}
+
+/// Serves `app` on the default API server address until the handle is aborted.
+fn serve(app: Router) -> JoinHandle<hyper::Result<()>> {
+    let server = axum::Server::bind(&GraphQLConfig::default().into())
+        .serve(app.into_make_service());
+
+    spawn(server)
+}
+
+/// Indexer config using the given test database.
+fn test_config(test_db: &TestPostgresDb) -> IndexerConfig {
+    IndexerConfig {
+        database: DatabaseConfig::from_str(&test_db.url).unwrap(),
+        ..IndexerConfig::default()
+    }
+}
+
+async fn build_app(config: IndexerConfig) -> Router {
+    let pool = IndexerConnectionPool::connect(&config.database.to_string())
+        .await
+        .unwrap();
+
+    GraphQlApi::build(config, pool, None).await.unwrap()
+}
+
+fn manifest_part() -> multipart::Part {
+    multipart::Part::stream(SIMPLE_WASM_MANIFEST).file_name("simple_wasm.yaml")
+}
+
+fn schema_part() -> multipart::Part {
+    multipart::Part::stream(SIMPLE_WASM_SCHEMA).file_name("simple_wasm.graphql")
+}
+
+fn wasm_part() -> multipart::Part {
+    multipart::Part::stream(SIMPLE_WASM_WASM).file_name("simple_wasm.wasm")
+}
+
+/// Posts `form` to the asset upload endpoint of `identifier`.
+fn upload_assets(
+    client: &reqwest::Client,
+    identifier: &str,
+    form: multipart::Form,
+) -> RequestBuilder {
+    client
+        .post(format!(
+            "http://localhost:29987/api/index/test_namespace/{identifier}"
+        ))
+        .multipart(form)
+        .header(CONTENT_TYPE, "multipart/form-data".to_owned())
+        .header(AUTHORIZATION, "foo".to_owned())
+}
+
+/// Uploads the manifest, schema and module of the simple WASM indexer.
+async fn upload_simple_wasm(client: &reqwest::Client) -> reqwest::Response {
+    let form = multipart::Form::new()
+        .part("manifest", manifest_part())
+        .part("schema", schema_part())
+        .part("wasm", wasm_part());
+
+    upload_assets(client, "simple_wasm_executor", form)
+        .send()
+        .await
+        .unwrap()
+}
+
+/// Posts a query to the graph of the simple WASM indexer.
+fn graph_query(client: &reqwest::Client, body: impl Into<Body>) -> RequestBuilder {
+    client
+        .post("http://localhost:29987/api/graph/test_namespace/simple_wasm_executor")
+        .header(CONTENT_TYPE, "application/json".to_owned())
+        .body(body)
+}
+
+/// Inserts a `thing1` row for each of `ids`, with an account made of the id's
+/// digit.
+async fn insert_thing1_rows(test_db: &TestPostgresDb, ids: RangeInclusive<i64>) {
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    for id in ids {
+        let _ = postgres::execute_query(
+            &mut conn,
+            format!(
+                "INSERT INTO test_namespace_simple_wasm_executor.thing1 (id, account, object) \
+                VALUES ({id}, '{}', ''::bytea)",
+                id.to_string().repeat(64)
+            ),
+        )
+        .await
+        .unwrap();
+    }
+}
+
+/// Stores `NONCE` so that it can be signed within the next hour.
+async fn insert_nonce(test_db: &TestPostgresDb) {
+    let expiry = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        + 3600;
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let _ = sqlx::QueryBuilder::new("INSERT INTO nonce (uid, expiry) VALUES ($1, $2)")
+        .build()
+        .bind(NONCE)
+        .bind(expiry as i64)
+        .execute(&mut conn)
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 #[cfg(all(feature = "postgres"))]
 async fn test_metrics_endpoint_returns_proper_count_of_metrics_postgres() {
@@ -25,10 +160,7 @@ async fn test_metrics_endpoint_returns_proper_count_of_metrics_postgres() {
     let _srvc = indexer_service_postgres(Some(&test_db.url)).await;
     let app = api_server_app_postgres(Some(&test_db.url)).await;
 
-    let server = axum::Server::bind(&GraphQLConfig::default().into())
-        .serve(app.into_make_service());
-
-    let server_handle = tokio::spawn(server);
+    let server_handle = serve(app);
 
     let client = http_client();
     let _ = client
@@ -47,7 +179,7 @@ async fn test_metrics_endpoint_returns_proper_count_of_metrics_postgres() {
         .unwrap();
 
     server_handle.abort();
-    assert_eq!(resp.split('\n').count(), 112);
+    assert_eq!(global_metrics(&resp).len(), 163);
 }
 
 #[tokio::test]
@@ -58,10 +190,7 @@ async fn test_database_postgres_metrics_properly_increments_counts_when_queries_
     let _ = indexer_service_postgres(Some(&test_db.url)).await;
     let app = api_server_app_postgres(Some(&test_db.url)).await;
 
-    let server = axum::Server::bind(&GraphQLConfig::default().into())
-        .serve(app.into_make_service());
-
-    let server_handle = tokio::spawn(server);
+    let server_handle = serve(app);
 
     let mut conn = test_db.pool.acquire().await.unwrap();
     let _ = postgres::execute_query(&mut conn, "SELECT 1;".into()).await;
@@ -83,19 +212,19 @@ async fn test_database_postgres_metrics_properly_increments_counts_when_queries_
         .await
         .unwrap();
 
-    let categories = resp.split('\n').collect::<Vec<&str>>();
+    let categories = global_metrics(&resp);
 
     assert_eq!(
-        categories[18],
+        categories[21],
         "# HELP postgres_execute_query_calls Count of calls to postgres execute_query_calls."
     );
     assert_eq!(
-        categories[19],
+        categories[22],
         "# TYPE postgres_execute_query_calls counter"
     );
 
     assert!(
-        categories[20].split(' ').collect::<Vec<&str>>()[1]
+        categories[23].split(' ').collect::<Vec<&str>>()[1]
             .to_string()
             .parse::<i64>()
             .unwrap()
@@ -109,10 +238,7 @@ async fn test_asset_upload_endpoint_properly_adds_assets_to_database_postgres()
     let test_db = TestPostgresDb::new().await.unwrap();
     let app = api_server_app_postgres(Some(&test_db.url)).await;
 
-    let server = axum::Server::bind(&GraphQLConfig::default().into())
-        .serve(app.into_make_service());
-
-    let server_handle = tokio::spawn(server);
+    let server_handle = serve(app);
 
     let mut conn = test_db.pool.acquire().await.unwrap();
     let is_index_registered = postgres::index_is_registered(
@@ -124,27 +250,8 @@ async fn test_asset_upload_endpoint_properly_adds_assets_to_database_postgres()
     .unwrap();
     assert!(is_index_registered.is_none());
 
-    let manifest_file =
-        multipart::Part::stream(SIMPLE_WASM_MANIFEST).file_name("simple_wasm.yaml");
-    let schema_file =
-        multipart::Part::stream(SIMPLE_WASM_SCHEMA).file_name("simple_wasm.graphql");
-    let wasm_file =
-        multipart::Part::stream(SIMPLE_WASM_WASM).file_name("simple_wasm.wasm");
-
-    let form = multipart::Form::new()
-        .part("manifest", manifest_file)
-        .part("schema", schema_file)
-        .part("wasm", wasm_file);
-
     let client = http_client();
-    let resp = client
-        .post("http://localhost:29987/api/index/test_namespace/simple_wasm_executor")
-        .multipart(form)
-        .header(CONTENT_TYPE, "multipart/form-data".to_owned())
-        .header(AUTHORIZATION, "foo".to_owned())
-        .send()
-        .await
-        .unwrap();
+    let resp = upload_simple_wasm(&client).await;
 
     server_handle.abort();
 
@@ -161,6 +268,113 @@ async fn test_asset_upload_endpoint_properly_adds_assets_to_database_postgres()
     assert!(is_index_registered.is_some());
 }
 
+#[tokio::test]
+#[cfg(all(feature = "postgres"))]
+async fn test_asset_upload_endpoint_rejects_module_for_another_indexer_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let (app, service) = api_server_app_with_service_postgres(Some(&test_db.url)).await;
+
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    let service_handle = tokio::spawn(service.run_until(async {
+        let _ = shutdown_rx.await;
+    }));
+
+    let server_handle = serve(app);
+
+    let form = multipart::Form::new()
+        .part("schema", schema_part())
+        .part("wasm", wasm_part());
+
+    let client = http_client();
+    let resp = upload_assets(&client, "other_executor", form)
+        .send()
+        .await
+        .unwrap();
+
+    server_handle.abort();
+    shutdown_tx.send(()).unwrap();
+    service_handle.await.unwrap();
+
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+    let body: Value = resp.json().await.unwrap();
+    assert!(body["details"]
+        .as_str()
+        .unwrap()
+        .contains("Module was built for Indexer(test_namespace.simple_wasm_executor)"));
+
+    // Nothing is registered for a rejected upload.
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let is_index_registered =
+        postgres::index_is_registered(&mut conn, "test_namespace", "other_executor")
+            .await
+            .unwrap();
+    assert!(is_index_registered.is_none());
+}
+
+#[tokio::test]
+#[cfg(all(feature = "postgres"))]
+async fn test_service_keeps_running_after_failed_asset_reload_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let (app, service) = api_server_app_with_service_postgres(Some(&test_db.url)).await;
+
+    // An indexer whose manifest can't be parsed, so reloading it fails.
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    for (asset_type, bytes) in [
+        (IndexAssetType::Manifest, b"not: [a manifest".to_vec()),
+        (IndexAssetType::Schema, b"type Old { id: ID! }".to_vec()),
+        (IndexAssetType::Wasm, SIMPLE_WASM_WASM.to_vec()),
+    ] {
+        postgres::register_index_asset(
+            &mut conn,
+            "test_namespace",
+            "broken_executor",
+            bytes,
+            asset_type,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    let service_handle = tokio::spawn(service.run_until(async {
+        let _ = shutdown_rx.await;
+    }));
+
+    let server_handle = serve(app);
+
+    // Uploading a new schema asks the service to reload the indexer.
+    let form = multipart::Form::new().part("schema", schema_part());
+
+    let client = http_client();
+    let resp = upload_assets(&client, "broken_executor", form)
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.status().is_success());
+
+    // The service still answers requests once the reload has failed.
+    let body: Value = client
+        .get("http://localhost:29987/api/status")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    server_handle.abort();
+    shutdown_tx.send(()).unwrap();
+    service_handle.await.unwrap();
+
+    let indexers = body["indexers"].as_array().unwrap();
+    let broken = indexers
+        .iter()
+        .find(|indexer| indexer["uid"] == "test_namespace.broken_executor")
+        .unwrap();
+    assert_eq!(broken["alive"], Value::Bool(false));
+}
+
 #[derive(Serialize, Debug)]
 struct SignatureRequest {
     signature: String,
@@ -178,25 +392,9 @@ async fn test_signature_route_validates_signature_expires_nonce_and_creates_jwt(
     let test_db = TestPostgresDb::new().await.unwrap();
     let app = authenticated_api_server_app_postgres(Some(&test_db.url)).await;
 
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-    let expiry = now + 3600;
-
-    let mut conn = test_db.pool.acquire().await.unwrap();
-    let _ = sqlx::QueryBuilder::new("INSERT INTO nonce (uid, expiry) VALUES ($1, $2)")
-        .build()
-        .bind(NONCE)
-        .bind(expiry as i64)
-        .execute(&mut conn)
-        .await
-        .unwrap();
-
-    let server = axum::Server::bind(&GraphQLConfig::default().into())
-        .serve(app.into_make_service());
+    insert_nonce(&test_db).await;
 
-    let server_handle = tokio::spawn(server);
+    let server_handle = serve(app);
 
     let resp = http_client()
         .post("http://localhost:29987/api/auth/signature")
@@ -214,6 +412,7 @@ async fn test_signature_route_validates_signature_expires_nonce_and_creates_jwt(
     assert!(res.token.is_some());
     assert!(res.token.unwrap().len() > 300);
 
+    let mut conn = test_db.pool.acquire().await.unwrap();
     let _ = sqlx::QueryBuilder::new("DELETE FROM nonce WHERE uid = $1")
         .build()
         .bind(NONCE)
@@ -221,3 +420,1242 @@ async fn test_signature_route_validates_signature_expires_nonce_and_creates_jwt(
         .await
         .unwrap();
 }
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_query_graph_rejects_queries_exceeding_max_query_rows_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let _ = upload_simple_wasm(&client).await;
+
+    server_handle.abort();
+
+    insert_thing1_rows(&test_db, 1..=3).await;
+
+    let mut config = test_config(&test_db);
+    config.graphql_api.max_query_rows = 2;
+
+    let server_handle = serve(build_app(config).await);
+
+    let resp = graph_query(
+        &client,
+        r#"{"query": "query { thing1 { id account } }", "params": "b"}"#,
+    )
+    .send()
+    .await
+    .unwrap();
+
+    server_handle.abort();
+
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(
+        body["details"],
+        "Query would return more than 2 rows. Narrow the query with filters."
+    );
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_query_graph_streams_rows_as_ndjson_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let _ = upload_simple_wasm(&client).await;
+
+    server_handle.abort();
+
+    insert_thing1_rows(&test_db, 1..=3).await;
+
+    // Streamed rows are never all held in memory, so they aren't limited.
+    let mut config = test_config(&test_db);
+    config.graphql_api.max_query_rows = 2;
+
+    let server_handle = serve(build_app(config).await);
+
+    let resp = graph_query(
+        &client,
+        r#"{"query": "query { thing1 { id } }", "params": "b"}"#,
+    )
+    .header(ACCEPT, "application/x-ndjson".to_owned())
+    .send()
+    .await
+    .unwrap();
+
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    assert_eq!(resp.headers()[CONTENT_TYPE], "application/x-ndjson");
+
+    let body = resp.text().await.unwrap();
+    let mut ids = body
+        .lines()
+        .map(|line| {
+            let row: Value = serde_json::from_str(line).unwrap();
+            row["id"].as_i64().unwrap()
+        })
+        .collect::<Vec<i64>>();
+    ids.sort();
+    assert_eq!(ids, vec![1, 2, 3]);
+
+    // Pages are already bounded, so they aren't streamed.
+    let resp = graph_query(
+        &client,
+        r#"{"query": "query { thing1(first: 2) { id } }", "params": "b"}"#,
+    )
+    .header(ACCEPT, "application/x-ndjson".to_owned())
+    .send()
+    .await
+    .unwrap();
+
+    server_handle.abort();
+
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_query_graph_returns_partial_results_when_a_root_field_fails_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let _ = upload_simple_wasm(&client).await;
+
+    server_handle.abort();
+
+    insert_thing1_rows(&test_db, 1..=1).await;
+
+    // Hold a lock on thing2 so that querying it times out.
+    let mut locker = test_db.pool.acquire().await.unwrap();
+    let _ = postgres::execute_query(&mut locker, "BEGIN".to_string())
+        .await
+        .unwrap();
+    let _ = postgres::execute_query(
+        &mut locker,
+        "LOCK TABLE test_namespace_simple_wasm_executor.thing2 IN ACCESS EXCLUSIVE MODE"
+            .to_string(),
+    )
+    .await
+    .unwrap();
+
+    let mut config = test_config(&test_db);
+    config.graphql_api.query_timeout_secs = 1;
+
+    let server_handle = serve(build_app(config).await);
+
+    let partial = graph_query(
+        &client,
+        r#"{"query": "query { thing1 { id } thing2 { id } }", "params": "b"}"#,
+    )
+    .send()
+    .await
+    .unwrap();
+    let failed = graph_query(
+        &client,
+        r#"{"query": "query { thing2 { id } }", "params": "b"}"#,
+    )
+    .send()
+    .await
+    .unwrap();
+
+    server_handle.abort();
+
+    let _ = postgres::execute_query(&mut locker, "ROLLBACK".to_string())
+        .await
+        .unwrap();
+
+    assert_eq!(partial.status(), reqwest::StatusCode::OK);
+    let body: Value = partial.json().await.unwrap();
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+    assert_eq!(body["data"][0]["id"], 1);
+    assert_eq!(body["errors"].as_array().unwrap().len(), 1);
+    assert_eq!(body["errors"][0]["path"][0], "thing2");
+
+    // A query whose only root field fails still fails as a whole.
+    assert_eq!(failed.status(), reqwest::StatusCode::GATEWAY_TIMEOUT);
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_schema_endpoint_describes_entities_and_fields_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let _ = upload_simple_wasm(&client).await;
+
+    let resp = client
+        .get(
+            "http://localhost:29987/api/graph/test_namespace/simple_wasm_executor/schema",
+        )
+        .send()
+        .await
+        .unwrap();
+    let missing = client
+        .get("http://localhost:29987/api/graph/test_namespace/no_such_indexer/schema")
+        .send()
+        .await
+        .unwrap();
+
+    server_handle.abort();
+
+    assert_eq!(missing.status(), reqwest::StatusCode::NOT_FOUND);
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["query"], "QueryRoot");
+
+    let entities = body["entities"].as_array().unwrap();
+    assert!(entities.iter().all(|entity| entity["name"] != "QueryRoot"));
+
+    let thing2 = entities
+        .iter()
+        .find(|entity| entity["name"] == "Thing2")
+        .unwrap();
+    let fields = thing2["fields"].as_array().unwrap();
+    assert!(fields.contains(&serde_json::json!({ "name": "hash", "type": "Bytes32!" })));
+    assert!(fields.iter().all(|field| field["name"] != "object"));
+
+    let scalars = body["scalars"].as_array().unwrap();
+    for scalar in ["Address", "Bytes32", "ID"] {
+        assert!(scalars.contains(&Value::from(scalar)));
+    }
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_playground_serves_html_for_registered_graphs_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let _ = upload_simple_wasm(&client).await;
+
+    let resp = client
+        .get("http://localhost:29987/api/playground/test_namespace/simple_wasm_executor")
+        .send()
+        .await
+        .unwrap();
+    let missing = client
+        .get("http://localhost:29987/api/playground/test_namespace/no_such_indexer")
+        .send()
+        .await
+        .unwrap();
+
+    server_handle.abort();
+
+    assert_eq!(missing.status(), reqwest::StatusCode::NOT_FOUND);
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    assert!(resp.headers()[CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .starts_with("text/html"));
+    assert!(resp.text().await.unwrap().contains("GraphQL Playground"));
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_playground_is_served_without_a_token_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+
+    let config = IndexerConfig {
+        authentication: AuthenticationConfig {
+            enabled: true,
+            strategy: Some(AuthenticationStrategy::JWT),
+            jwt_secret: Some("6906573247652854078288872150120717701634680141358560585446649749925714230966".to_string()),
+            ..AuthenticationConfig::default()
+        },
+        ..test_config(&test_db)
+    };
+
+    let server_handle = serve(build_app(config).await);
+
+    let client = http_client();
+    let resp = client
+        .get("http://localhost:29987/api/playground/test_namespace/no_such_indexer")
+        .send()
+        .await
+        .unwrap();
+
+    server_handle.abort();
+
+    // The graph is looked up rather than the request turned away.
+    assert_eq!(resp.status(), reqwest::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_query_graph_rejects_invalid_queries_with_bad_request_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let _ = upload_simple_wasm(&client).await;
+
+    let mut responses = Vec::new();
+    for query in [
+        "query { thing1 { id nope } }",
+        r#"query { thing1(where: { nope: { eq: 1 } }) { id } }"#,
+        "query { thing1 { id ",
+    ] {
+        let resp = graph_query(
+            &client,
+            serde_json::json!({ "query": query, "params": "" }).to_string(),
+        )
+        .send()
+        .await
+        .unwrap();
+        assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+        responses.push(resp.json::<Value>().await.unwrap());
+    }
+
+    server_handle.abort();
+
+    // Unknown fields and arguments are named in the response.
+    assert_eq!(responses[0]["field"], "nope");
+    assert!(responses[0]["details"]
+        .as_str()
+        .unwrap()
+        .starts_with("Unrecognized Field"));
+    assert_eq!(responses[1]["field"], "nope");
+    assert!(responses[1]["details"]
+        .as_str()
+        .unwrap()
+        .starts_with("Unrecognized Argument"));
+
+    // A syntax error isn't about any one field.
+    assert_eq!(responses[2]["field"], Value::Null);
+    assert!(responses[2]["details"]
+        .as_str()
+        .unwrap()
+        .starts_with("GraphQl Parser error"));
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_query_graph_paginates_root_fields_with_cursors_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let _ = upload_simple_wasm(&client).await;
+
+    insert_thing1_rows(&test_db, 1..=3).await;
+
+    let query_page = |after: Option<String>| {
+        let after = after
+            .map(|c| format!(r#", after: \"{c}\""#))
+            .unwrap_or_default();
+        graph_query(&client, format!(
+                r#"{{"query": "query {{ thing1(first: 2{after}) {{ id }} }}", "params": "b"}}"#
+            ))
+            .send()
+    };
+
+    let resp = query_page(None).await.unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+
+    // Clients can tell which version of the schema the results conform to.
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let root =
+        postgres::graph_root_latest(&mut conn, "test_namespace", "simple_wasm_executor")
+            .await
+            .unwrap();
+    assert_eq!(resp.headers()["x-schema-version"], root.version.as_str());
+
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["data"], serde_json::json!([{"id": 1}, {"id": 2}]));
+    assert_eq!(body["pageInfo"]["hasNextPage"], true);
+
+    let cursor = body["pageInfo"]["endCursor"].as_str().unwrap().to_string();
+    let body: Value = query_page(Some(cursor))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["data"], serde_json::json!([{"id": 3}]));
+    assert_eq!(body["pageInfo"]["hasNextPage"], false);
+
+    // Pages sorted on other fields continue from the offset of the cursor.
+    let query_sorted_page = |after: Option<String>| {
+        let after = after
+            .map(|c| format!(r#", after: \"{c}\""#))
+            .unwrap_or_default();
+        graph_query(&client, format!(
+                r#"{{"query": "query {{ thing1(first: 2, order: {{ id: DESC }}{after}) {{ id }} }}", "params": "b"}}"#
+            ))
+            .send()
+    };
+
+    let body: Value = query_sorted_page(None).await.unwrap().json().await.unwrap();
+    assert_eq!(body["data"], serde_json::json!([{"id": 3}, {"id": 2}]));
+
+    let cursor = body["pageInfo"]["endCursor"].as_str().unwrap().to_string();
+    let body: Value = query_sorted_page(Some(cursor))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["data"], serde_json::json!([{"id": 1}]));
+    assert_eq!(body["pageInfo"]["hasNextPage"], false);
+
+    let resp = graph_query(
+        &client,
+        format!(
+            r#"{{"query": "query {{ thing1(first: {}) {{ id }} }}", "params": "b"}}"#,
+            defaults::MAX_PAGE_SIZE + 1
+        ),
+    )
+    .send()
+    .await
+    .unwrap();
+
+    server_handle.abort();
+
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_query_graph_filters_root_fields_with_where_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let _ = upload_simple_wasm(&client).await;
+
+    insert_thing1_rows(&test_db, 1..=3).await;
+
+    let query = |filter: &str| {
+        graph_query(&client, format!(
+                r#"{{"query": "query {{ thing1(where: {filter}) {{ id }} }}", "params": "b"}}"#
+            ))
+            .send()
+    };
+
+    let body: Value = query(&format!(
+        r#"{{ _or: [{{ id: {{ lt: 2 }} }}, {{ account: {{ in: [\"0x{}\"] }} }}] }}"#,
+        "3".repeat(64)
+    ))
+    .await
+    .unwrap()
+    .json()
+    .await
+    .unwrap();
+    let mut ids = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| row["id"].as_i64().unwrap())
+        .collect::<Vec<i64>>();
+    ids.sort();
+    assert_eq!(ids, vec![1, 3]);
+
+    // Values are bound as parameters, so they can't change the query.
+    let body: Value = query(r#"{ account: { eq: \"' OR 1 = 1 --\" } }"#)
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body, serde_json::json!({ "data": [] }));
+
+    server_handle.abort();
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_query_graph_aggregates_root_fields_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let _ = upload_simple_wasm(&client).await;
+
+    insert_thing1_rows(&test_db, 1..=3).await;
+
+    let query = |selection: &str| {
+        graph_query(&client, format!(
+                r#"{{"query": "query {{ thing1_aggregate(where: {{ id: {{ gt: 1 }} }}) {{ {selection} }} }}", "params": "b"}}"#
+            ))
+            .send()
+    };
+
+    let body: Value = query("count sum { id } max { id }")
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({ "data": [{ "aggregate": { "count": 2, "sum": { "id": 5 }, "max": { "id": 3 } } }] })
+    );
+
+    // Aliases rename the keys of the aggregates and of their fields.
+    let body: Value = query("total: count highest: max { top: id }")
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({ "data": [{ "aggregate": { "total": 2, "highest": { "top": 3 } } }] })
+    );
+
+    let res = query("sum { account }").await.unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    // Aliased fields are returned under their alias, and the same field may
+    // be selected more than once under different aliases.
+    let body: Value = graph_query(&client, r#"{"query": "query { thing1(id: 2) { key: id other: id account } }", "params": "b"}"#)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({ "data": [{ "key": 2, "other": 2, "account": "2".repeat(64) }] })
+    );
+
+    server_handle.abort();
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_status_endpoint_reports_last_block_height_per_indexer_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let _ = upload_simple_wasm(&client).await;
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    postgres::set_last_block_height(
+        &mut conn,
+        "test_namespace",
+        "simple_wasm_executor",
+        7,
+    )
+    .await
+    .unwrap();
+
+    let body: Value = client
+        .get("http://localhost:29987/api/status")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    server_handle.abort();
+
+    let indexers = body["indexers"].as_array().unwrap();
+    assert_eq!(indexers.len(), 1);
+    assert_eq!(indexers[0]["uid"], "test_namespace.simple_wasm_executor");
+    assert_eq!(indexers[0]["last_block_height"], 7);
+
+    // The API server is running without an indexer service to ask.
+    assert_eq!(indexers[0]["alive"], Value::Null);
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_query_graph_rate_limits_clients_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+
+    let mut config = test_config(&test_db);
+    config.graphql_api.query_rate_limit = 1;
+    config.graphql_api.query_rate_limit_burst = 2;
+
+    let server_handle = serve(build_app(config).await);
+
+    let client = http_client();
+    let mut statuses = Vec::new();
+    let mut retry_after = None;
+    for _ in 0..3 {
+        let resp = graph_query(
+            &client,
+            r#"{"query": "query { thing1 { id } }", "params": "b"}"#,
+        )
+        .send()
+        .await
+        .unwrap();
+        statuses.push(resp.status());
+        retry_after = resp.headers().get(reqwest::header::RETRY_AFTER).cloned();
+    }
+
+    server_handle.abort();
+
+    assert_ne!(statuses[0], reqwest::StatusCode::TOO_MANY_REQUESTS);
+    assert_ne!(statuses[1], reqwest::StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(statuses[2], reqwest::StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(retry_after.unwrap(), "1");
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_api_key_authentication_guards_asset_upload_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+
+    let config = IndexerConfig {
+        authentication: AuthenticationConfig {
+            enabled: true,
+            strategy: Some(AuthenticationStrategy::ApiKey),
+            api_key_hash: Some(sha256_digest(&"foo")),
+            ..AuthenticationConfig::default()
+        },
+        ..test_config(&test_db)
+    };
+
+    let server_handle = serve(build_app(config).await);
+
+    let upload = |api_key: &'static str| {
+        let form = multipart::Form::new()
+            .part("manifest", manifest_part())
+            .part("schema", schema_part())
+            .part("wasm", wasm_part());
+
+        http_client()
+            .post("http://localhost:29987/api/index/test_namespace/simple_wasm_executor")
+            .multipart(form)
+            .header(CONTENT_TYPE, "multipart/form-data".to_owned())
+            .header(AUTHORIZATION, api_key.to_owned())
+            .send()
+    };
+
+    let resp = upload("bar").await.unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let resp = upload("foo").await.unwrap();
+    assert!(resp.status().is_success());
+
+    server_handle.abort();
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let index = postgres::index_is_registered(
+        &mut conn,
+        "test_namespace",
+        "simple_wasm_executor",
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    // The key's hash, not the key itself, identifies who registered the indexer.
+    assert_eq!(index.pubkey, Some(sha256_digest(&"foo")));
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_deployers_are_forbidden_from_reverting_indexers_of_others_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = authenticated_api_server_app_postgres(Some(&test_db.url)).await;
+
+    insert_nonce(&test_db).await;
+
+    // Registered from a manifest, so whoever deployed it isn't known.
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    postgres::register_index(&mut conn, "test_namespace", "simple_wasm_executor", None)
+        .await
+        .unwrap();
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let res: SignatureResponse = client
+        .post("http://localhost:29987/api/auth/signature")
+        .header(CONTENT_TYPE, "application/json".to_owned())
+        .json(&serde_json::json!({
+            "signature": SIGNATURE,
+            "message": NONCE,
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let token = res.token.unwrap();
+
+    let resp = client
+        .put("http://localhost:29987/api/index/test_namespace/simple_wasm_executor")
+        .header(AUTHORIZATION, token)
+        .send()
+        .await
+        .unwrap();
+
+    server_handle.abort();
+
+    assert_eq!(resp.status(), reqwest::StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_read_only_tokens_are_forbidden_from_stopping_indexers_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = authenticated_api_server_app_postgres(Some(&test_db.url)).await;
+
+    insert_nonce(&test_db).await;
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let sign = |role: &str| {
+        client
+            .post("http://localhost:29987/api/auth/signature")
+            .header(CONTENT_TYPE, "application/json".to_owned())
+            .json(&serde_json::json!({
+                "signature": SIGNATURE,
+                "message": NONCE,
+                "role": role,
+            }))
+            .send()
+    };
+
+    // The signer is not configured as an admin.
+    let resp = sign("Admin").await.unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::FORBIDDEN);
+
+    let res: SignatureResponse = sign("ReadOnly").await.unwrap().json().await.unwrap();
+    let token = res.token.unwrap();
+
+    let resp = client
+        .delete("http://localhost:29987/api/index/test_namespace/simple_wasm_executor")
+        .header(AUTHORIZATION, token)
+        .send()
+        .await
+        .unwrap();
+
+    server_handle.abort();
+
+    assert_eq!(resp.status(), reqwest::StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_remove_endpoint_purges_indexer_tables_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let (app, service) = api_server_app_with_service_postgres(Some(&test_db.url)).await;
+
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    let service_handle = tokio::spawn(service.run_until(async {
+        let _ = shutdown_rx.await;
+    }));
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let resp = upload_simple_wasm(&client).await;
+    assert!(resp.status().is_success());
+
+    let remove = |query: &str| {
+        client
+            .delete(format!(
+                "http://localhost:29987/api/index/test_namespace/simple_wasm_executor?{query}"
+            ))
+            .header(AUTHORIZATION, "foo".to_owned())
+            .send()
+    };
+
+    // A dry run only lists the tables that would be dropped.
+    let body: Value = remove("purge=true&dry_run=true")
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let tables = body["dropped_tables"].as_array().unwrap();
+    assert!(tables.contains(&Value::from("test_namespace_simple_wasm_executor.thing1")));
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let schema_count = "SELECT COUNT(*) FROM information_schema.schemata
+        WHERE schema_name = 'test_namespace_simple_wasm_executor'";
+    let schemas: i64 = sqlx::query_scalar(schema_count)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(schemas, 1);
+
+    let resp = remove("purge=true").await.unwrap();
+
+    server_handle.abort();
+    shutdown_tx.send(()).unwrap();
+    service_handle.await.unwrap();
+
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["dropped_tables"].as_array().unwrap(), tables);
+
+    let schemas: i64 = sqlx::query_scalar(schema_count)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(schemas, 0);
+    let type_ids: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM graph_registry_type_ids
+        WHERE schema_name = 'test_namespace'
+        AND schema_identifier = 'simple_wasm_executor'",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap();
+    assert_eq!(type_ids, 0);
+    assert!(postgres::index_is_registered(
+        &mut conn,
+        "test_namespace",
+        "simple_wasm_executor"
+    )
+    .await
+    .unwrap()
+    .is_none());
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_rollback_endpoint_reverts_objects_to_block_height_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let _ = upload_simple_wasm(&client).await;
+
+    insert_thing1_rows(&test_db, 1..=3).await;
+
+    let table = "test_namespace_simple_wasm_executor.thing1";
+    let mut conn = test_db.pool.acquire().await.unwrap();
+
+    // Object 1 was written by the batch starting at block 12, and objects 2
+    // and 3 by the batch starting at block 15.
+    for (block_height, object_ids) in [(12, vec![1]), (15, vec![2, 3])] {
+        postgres::put_object_writes(
+            &mut conn,
+            "test_namespace",
+            "simple_wasm_executor",
+            block_height,
+            table,
+            object_ids,
+        )
+        .await
+        .unwrap();
+    }
+    postgres::set_last_block_height(
+        &mut conn,
+        "test_namespace",
+        "simple_wasm_executor",
+        20,
+    )
+    .await
+    .unwrap();
+
+    let rollback = |height: u64| {
+        client
+            .post(format!(
+                "http://localhost:29987/api/index/test_namespace/simple_wasm_executor/rollback/{height}"
+            ))
+            .header(AUTHORIZATION, "foo".to_owned())
+            .send()
+    };
+
+    // Blocks past the last committed block can't be rolled back to.
+    let resp = rollback(21).await.unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    // Block 17 is within the batch starting at block 15.
+    let resp = rollback(17).await.unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["block_height"], 15);
+    assert_eq!(body["objects_deleted"], 2);
+
+    let ids: Vec<i64> = sqlx::query_scalar(&format!("SELECT id FROM {table}"))
+        .fetch_all(&test_db.pool)
+        .await
+        .unwrap();
+    assert_eq!(ids, vec![1]);
+
+    let last_block_height = postgres::last_block_height_for_indexer(
+        &mut conn,
+        "test_namespace",
+        "simple_wasm_executor",
+    )
+    .await
+    .unwrap();
+    assert_eq!(last_block_height, Some(14));
+
+    // Writes older than the max reorg depth are no longer recorded.
+    let resp = rollback(3).await.unwrap();
+
+    server_handle.abort();
+
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_replay_endpoint_refuses_blocks_past_last_committed_block_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let _ = upload_simple_wasm(&client).await;
+
+    let replay = |from: u64, to: u64| {
+        client
+            .post(format!(
+                "http://localhost:29987/api/index/test_namespace/simple_wasm_executor/replay/{from}/{to}"
+            ))
+            .header(AUTHORIZATION, "foo".to_owned())
+            .send()
+    };
+
+    // Nothing can be replayed before the indexer has committed a block.
+    let resp = replay(1, 1).await.unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    postgres::set_last_block_height(
+        &mut conn,
+        "test_namespace",
+        "simple_wasm_executor",
+        20,
+    )
+    .await
+    .unwrap();
+
+    let past_last_block = replay(15, 21).await.unwrap();
+    let reversed = replay(10, 5).await.unwrap();
+    let from_genesis = replay(0, 5).await.unwrap();
+
+    server_handle.abort();
+
+    assert_eq!(past_last_block.status(), reqwest::StatusCode::BAD_REQUEST);
+    let body: Value = past_last_block.json().await.unwrap();
+    assert!(body["details"]
+        .as_str()
+        .unwrap()
+        .contains("last committed Block(20)"));
+    assert_eq!(reversed.status(), reqwest::StatusCode::BAD_REQUEST);
+    assert_eq!(from_genesis.status(), reqwest::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[cfg(all(feature = "postgres"))]
+async fn test_assets_endpoint_lists_every_asset_version_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let server_handle = serve(app);
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    for (bytes, asset_type) in [
+        (SIMPLE_WASM_WASM.to_vec(), IndexAssetType::Wasm),
+        (
+            SIMPLE_WASM_MANIFEST.as_bytes().to_vec(),
+            IndexAssetType::Manifest,
+        ),
+        (
+            SIMPLE_WASM_SCHEMA.as_bytes().to_vec(),
+            IndexAssetType::Schema,
+        ),
+        (b"type Thing { id: ID! }".to_vec(), IndexAssetType::Schema),
+    ] {
+        postgres::register_index_asset(
+            &mut conn,
+            "test_namespace",
+            "asset_versions",
+            bytes,
+            asset_type,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    let client = http_client();
+    let resp = client
+        .get("http://localhost:29987/api/index/test_namespace/asset_versions/assets")
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.status().is_success());
+    let body: Value = resp.json().await.unwrap();
+
+    let missing = client
+        .get("http://localhost:29987/api/index/test_namespace/not_registered/assets")
+        .send()
+        .await
+        .unwrap();
+
+    server_handle.abort();
+
+    assert_eq!(missing.status(), reqwest::StatusCode::NOT_FOUND);
+
+    assert_eq!(body["wasm"].as_array().unwrap().len(), 1);
+    assert_eq!(body["manifest"].as_array().unwrap().len(), 1);
+
+    let schemas = body["schema"].as_array().unwrap();
+    assert_eq!(schemas.len(), 2);
+    assert_eq!(schemas[0]["version"], 1);
+    assert_eq!(schemas[1]["version"], 2);
+    assert_eq!(
+        schemas[0]["digest"].as_str().unwrap(),
+        sha256_digest(&SIMPLE_WASM_SCHEMA.as_bytes().to_vec())
+    );
+    assert_eq!(
+        schemas[1]["digest"].as_str().unwrap(),
+        sha256_digest(&b"type Thing { id: ID! }".to_vec())
+    );
+    assert!(schemas[1]["created_at"].as_i64().unwrap() > 0);
+    assert!(schemas[1].get("bytes").is_none());
+}
+
+#[tokio::test]
+#[cfg(all(feature = "postgres"))]
+async fn test_asset_upload_endpoint_skips_unchanged_assets_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let mut responses = Vec::new();
+    for manifest in [
+        SIMPLE_WASM_MANIFEST.to_string(),
+        SIMPLE_WASM_MANIFEST.to_string(),
+        format!("{SIMPLE_WASM_MANIFEST}\n# redeployed\n"),
+    ] {
+        let form = multipart::Form::new()
+            .part(
+                "manifest",
+                multipart::Part::stream(manifest).file_name("simple_wasm.yaml"),
+            )
+            .part("schema", schema_part())
+            .part("wasm", wasm_part());
+
+        let resp = upload_assets(&client, "simple_wasm_executor", form)
+            .send()
+            .await
+            .unwrap();
+        assert!(resp.status().is_success());
+        responses.push(resp.json::<Value>().await.unwrap());
+    }
+
+    server_handle.abort();
+
+    let versions = |body: &Value| {
+        body["assets"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|asset| asset["version"].as_i64().unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(responses[0]["unchanged"], false);
+    assert_eq!(versions(&responses[0]), vec![1, 1, 1]);
+
+    // Identical bytes leave every asset at its current version.
+    assert_eq!(responses[1]["unchanged"], true);
+    assert_eq!(versions(&responses[1]), vec![1, 1, 1]);
+
+    // Only the changed manifest gets a new version.
+    assert_eq!(responses[2]["unchanged"], false);
+    assert_eq!(versions(&responses[2]), vec![2, 1, 1]);
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_asset_upload_endpoint_rejects_oversized_assets_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+
+    let mut config = test_config(&test_db);
+    config.graphql_api.max_manifest_size = 16;
+
+    let server_handle = serve(build_app(config).await);
+
+    let form = multipart::Form::new()
+        .part("wasm", wasm_part())
+        .part("manifest", manifest_part());
+
+    let client = http_client();
+    let resp = upload_assets(&client, "simple_wasm_executor", form)
+        .send()
+        .await
+        .unwrap();
+
+    server_handle.abort();
+
+    assert_eq!(resp.status(), reqwest::StatusCode::PAYLOAD_TOO_LARGE);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(
+        body["details"],
+        "The manifest asset exceeds the max size of 16 bytes."
+    );
+
+    // Nothing is registered, including the WASM module within its limit.
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let is_index_registered = postgres::index_is_registered(
+        &mut conn,
+        "test_namespace",
+        "simple_wasm_executor",
+    )
+    .await
+    .unwrap();
+    assert!(is_index_registered.is_none());
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_asset_upload_endpoint_accepts_modules_just_under_max_wasm_size_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+
+    // As by default, the general body limit is no larger than a module may be.
+    let mut config = test_config(&test_db);
+    config.graphql_api.max_wasm_size = SIMPLE_WASM_WASM.len() + 1;
+    config.graphql_api.max_body_size = SIMPLE_WASM_WASM.len() + 1;
+
+    let server_handle = serve(build_app(config).await);
+
+    let client = http_client();
+    let resp = upload_simple_wasm(&client).await;
+
+    server_handle.abort();
+
+    assert!(resp.status().is_success());
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let is_index_registered = postgres::index_is_registered(
+        &mut conn,
+        "test_namespace",
+        "simple_wasm_executor",
+    )
+    .await
+    .unwrap();
+    assert!(is_index_registered.is_some());
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_cors_preflight_skips_authentication_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+
+    let mut config = IndexerConfig {
+        authentication: AuthenticationConfig {
+            enabled: true,
+            strategy: Some(AuthenticationStrategy::JWT),
+            jwt_secret: Some("6906573247652854078288872150120717701634680141358560585446649749925714230966".to_string()),
+            ..AuthenticationConfig::default()
+        },
+        ..test_config(&test_db)
+    };
+    config.graphql_api.cors_allowed_origins = vec!["https://app.example".to_string()];
+
+    let server_handle = serve(build_app(config).await);
+
+    let client = http_client();
+    let preflight = |origin: &'static str| {
+        client
+            .request(
+                reqwest::Method::OPTIONS,
+                "http://localhost:29987/api/index/test_namespace/simple_wasm_executor",
+            )
+            .header(reqwest::header::ORIGIN, origin)
+            .header(reqwest::header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .header(
+                reqwest::header::ACCESS_CONTROL_REQUEST_HEADERS,
+                "authorization",
+            )
+            .send()
+    };
+
+    let allowed = preflight("https://app.example").await.unwrap();
+    let refused = preflight("https://other.example").await.unwrap();
+
+    server_handle.abort();
+
+    assert_eq!(allowed.status(), reqwest::StatusCode::OK);
+    assert_eq!(
+        allowed
+            .headers()
+            .get(reqwest::header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .unwrap(),
+        "https://app.example"
+    );
+    assert!(refused
+        .headers()
+        .get(reqwest::header::ACCESS_CONTROL_ALLOW_ORIGIN)
+        .is_none());
+}
+
+#[tokio::test]
+#[cfg(feature = "postgres")]
+async fn test_responses_carry_the_correlation_id_of_their_request_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let app = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let server_handle = serve(app);
+
+    let client = http_client();
+    let given = client
+        .get("http://localhost:29987/api/health")
+        .header(REQUEST_ID_HEADER, "debug-1234")
+        .send()
+        .await
+        .unwrap();
+    let generated = client
+        .get("http://localhost:29987/api/health")
+        .send()
+        .await
+        .unwrap();
+
+    server_handle.abort();
+
+    assert_eq!(
+        given.headers().get(REQUEST_ID_HEADER).unwrap(),
+        "debug-1234"
+    );
+
+    // Without one, each request is assigned a UUID.
+    let generated = generated
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .unwrap()
+        .to_str()
+        .unwrap();
+    assert_eq!(generated.len(), 36);
+    assert!(generated.chars().all(|c| c == '-' || c.is_ascii_hexdigit()));
+}
@@ -0,0 +1,17 @@
+extern crate alloc;
+use fuel_indexer_macros::indexer;
+
+#[no_mangle]
+fn ff_log_data(_inp: ()) {}
+
+#[no_mangle]
+fn ff_put_object(_inp: ()) {}
+
+#[indexer(manifest = "packages/fuel-indexer-tests/trybuild/simple_wasm_receipts.yaml")]
+mod indexer {
+    fn function_one(transfer: abi::Transfer) {
+        assert_eq!(transfer.amount, 1);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,64 @@
+extern crate alloc;
+use fuel_indexer_macros::indexer;
+
+#[no_mangle]
+fn ff_log_data(_inp: ()) {}
+
+#[no_mangle]
+fn ff_put_object(_inp: ()) {}
+
+#[indexer(manifest = "packages/fuel-indexer-tests/trybuild/simple_wasm_receipts.yaml")]
+mod indexer {
+    fn function_one(tx: TransactionData, result: abi::ScriptResult) {
+        assert_eq!(tx.receipts.len(), 3);
+        assert_eq!(result.gas_used, 100);
+    }
+
+    fn function_two(panic: abi::Panic) {
+        assert_eq!(panic.pc, 8);
+    }
+
+    fn function_three(revert: abi::Revert) {
+        assert_eq!(revert.error_val, 42);
+    }
+}
+
+fn main() {
+    let data: Vec<BlockData> = vec![BlockData {
+        id: [0u8; 32].into(),
+        time: 1,
+        producer: None,
+        height: 0,
+        transactions: vec![TransactionData {
+            id: [0u8; 32].into(),
+            status: TransactionStatus::default(),
+            receipts: vec![
+                Receipt::Panic {
+                    id: [0u8; 32].into(),
+                    reason: InstructionResult::error(PanicReason::Revert, 0),
+                    pc: 8,
+                    is: 0,
+                    contract_id: None,
+                },
+                Receipt::Revert {
+                    id: [0u8; 32].into(),
+                    ra: 42,
+                    pc: 0,
+                    is: 0,
+                },
+                Receipt::ScriptResult {
+                    result: ScriptExecutionResult::Revert,
+                    gas_used: 100,
+                },
+            ],
+            transaction: Transaction::default(),
+        }],
+    }];
+
+    let mut bytes = serialize(&data);
+
+    let ptr = bytes.as_mut_ptr();
+    let len = bytes.len();
+
+    handle_events(ptr, len);
+}
@@ -0,0 +1,102 @@
+extern crate alloc;
+use fuel_indexer_macros::indexer;
+
+#[no_mangle]
+fn ff_log_data(_inp: ()) {}
+
+#[no_mangle]
+fn ff_put_object(_inp: ()) {}
+
+#[indexer(manifest = "packages/fuel-indexer-tests/trybuild/simple_wasm_multi_abi.yaml")]
+mod indexer {
+    fn function_one(event: factory::SomeEvent) {
+        let factory::SomeEvent { id, account } = event;
+
+        assert_eq!(id, 9);
+        assert_eq!(account, Bits256([48u8; 32]));
+    }
+
+    fn function_two(event: child::SomeEvent) {
+        let child::SomeEvent { id } = event;
+
+        assert_eq!(id, 10);
+    }
+}
+
+fn main() {
+    use fuels_core::abi_encoder::ABIEncoder;
+
+    let factory_event = factory::SomeEvent {
+        id: 9,
+        account: Bits256([48u8; 32]),
+    };
+    let child_event = child::SomeEvent { id: 10 };
+
+    let factory_bytes = ABIEncoder::encode(&[factory_event.into_token()])
+        .expect("Failed compile test")
+        .resolve(0);
+    let child_bytes = ABIEncoder::encode(&[child_event.into_token()])
+        .expect("Failed compile test")
+        .resolve(0);
+
+    let data: Vec<BlockData> = vec![BlockData {
+        id: [0u8; 32].into(),
+        time: 1,
+        producer: None,
+        height: 0,
+        transactions: vec![TransactionData {
+            status: TransactionStatus::default(),
+            id: [0u8; 32].into(),
+            receipts: vec![
+                Receipt::Call {
+                    id: [0u8; 32].into(),
+                    to: [1u8; 32].into(),
+                    amount: 400,
+                    asset_id: [0u8; 32].into(),
+                    gas: 4,
+                    param1: 2048508220,
+                    param2: 0,
+                    pc: 0,
+                    is: 0,
+                },
+                Receipt::ReturnData {
+                    id: [1u8; 32].into(),
+                    ptr: 2342143,
+                    len: factory_bytes.len() as u64,
+                    digest: [0u8; 32].into(),
+                    data: factory_bytes,
+                    pc: 0,
+                    is: 0,
+                },
+                Receipt::Call {
+                    id: [0u8; 32].into(),
+                    to: [2u8; 32].into(),
+                    amount: 400,
+                    asset_id: [0u8; 32].into(),
+                    gas: 4,
+                    param1: 2048508220,
+                    param2: 0,
+                    pc: 0,
+                    is: 0,
+                },
+                Receipt::ReturnData {
+                    id: [2u8; 32].into(),
+                    ptr: 2342143,
+                    len: child_bytes.len() as u64,
+                    digest: [0u8; 32].into(),
+                    data: child_bytes,
+                    pc: 0,
+                    is: 0,
+                },
+            ],
+            transaction: Transaction::default(),
+        }],
+    }];
+
+    let mut bytes = serialize(&data);
+
+    let ptr = bytes.as_mut_ptr();
+    let len = bytes.len();
+
+    handle_events(ptr, len);
+}
@@ -20,6 +20,12 @@ pub struct TransactionData {
     pub id: TxId,
 }
 
+impl NativeFuelType for TransactionData {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "TransactionData") as usize
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct BlockData {
     pub height: u64,
@@ -35,6 +41,32 @@ impl NativeFuelType for BlockData {
     }
 }
 
+/// The metadata of a block, without its transactions.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub height: u64,
+    pub id: Bytes32,
+    pub producer: Option<Bytes32>,
+    pub time: i64,
+}
+
+impl From<&BlockData> for BlockHeader {
+    fn from(block: &BlockData) -> Self {
+        Self {
+            height: block.height,
+            id: block.id,
+            producer: block.producer,
+            time: block.time,
+        }
+    }
+}
+
+impl NativeFuelType for BlockHeader {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "BlockHeader") as usize
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Transfer {
     pub contract_id: ContractId,
@@ -155,3 +187,31 @@ impl NativeFuelType for Call {
         type_id(FUEL_TYPES_NAMESPACE, "Call") as usize
     }
 }
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Panic {
+    pub contract_id: ContractId,
+    pub reason: u64,
+    pub pc: u64,
+    pub is: u64,
+}
+
+impl NativeFuelType for Panic {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "Panic") as usize
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Revert {
+    pub contract_id: ContractId,
+    pub error_val: u64,
+    pub pc: u64,
+    pub is: u64,
+}
+
+impl NativeFuelType for Revert {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "Revert") as usize
+    }
+}
@@ -26,10 +26,82 @@ pub type Timestamp = u64;
 pub type Charfield = String;
 pub type Boolean = bool;
 pub type Blob = Vec<u8>;
+/// Variable-length binary data, stored as raw bytes in a `bytea` column.
+pub type Bytes = Vec<u8>;
 
 #[derive(Deserialize, Serialize, Clone, Eq, PartialEq, Debug, Hash)]
 pub struct Json(pub String);
 
+/// An exact decimal number, stored in a `numeric` column so that no precision
+/// is lost to floating point rounding.
+#[derive(Deserialize, Serialize, Clone, Eq, PartialEq, Debug, Hash)]
+#[serde(try_from = "String")]
+pub struct Decimal(String);
+
+impl Decimal {
+    /// The number `value * 10^-scale`, e.g. `Decimal::new(-12345, 2)` is `-123.45`.
+    pub fn new(value: i128, scale: u32) -> Decimal {
+        let scale = scale as usize;
+        let digits = format!("{:0>1$}", value.unsigned_abs(), scale + 1);
+        let (int, frac) = digits.split_at(digits.len() - scale);
+        let sign = if value < 0 { "-" } else { "" };
+
+        if frac.is_empty() {
+            Decimal(format!("{sign}{int}"))
+        } else {
+            Decimal(format!("{sign}{int}.{frac}"))
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A string that is not a decimal number, i.e. digits with an optional sign
+/// and fractional part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidDecimal(pub String);
+
+impl std::fmt::Display for InvalidDecimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' is not a decimal number.", self.0)
+    }
+}
+
+impl std::error::Error for InvalidDecimal {}
+
+impl std::str::FromStr for Decimal {
+    type Err = InvalidDecimal;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unsigned = s.strip_prefix('-').unwrap_or(s);
+        let (int, frac) = unsigned.split_once('.').unwrap_or((unsigned, "0"));
+        let is_digits =
+            |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+
+        if is_digits(int) && is_digits(frac) {
+            Ok(Decimal(s.to_string()))
+        } else {
+            Err(InvalidDecimal(s.to_string()))
+        }
+    }
+}
+
+impl TryFrom<String> for Decimal {
+    type Error = InvalidDecimal;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl std::fmt::Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 // IMPORTANT: https://github.com/launchbadge/sqlx/issues/499
 pub fn type_id(namespace: &str, type_name: &str) -> i64 {
     let mut bytes = [0u8; 8];
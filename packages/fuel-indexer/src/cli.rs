@@ -1,7 +1,10 @@
-pub(crate) use crate::commands::run;
+pub(crate) use crate::commands::{
+    export::{self, ExportArgs},
+    import::{self, ImportArgs},
+    run,
+};
 use clap::{Parser, Subcommand};
 use fuel_indexer_lib::config::IndexerArgs;
-use fuel_indexer_lib::utils::bin_utils::init_logging;
 
 #[derive(Parser, Debug)]
 #[clap(
@@ -20,16 +23,18 @@ pub struct Opt {
 #[derive(Debug, Subcommand)]
 pub enum Indexer {
     Run(IndexerArgs),
+    Export(ExportArgs),
+    Import(ImportArgs),
 }
 
 pub async fn run_cli() -> anyhow::Result<()> {
-    init_logging().await?;
-
     let opt = Opt::try_parse();
 
     match opt {
         Ok(opt) => match opt.command {
             Indexer::Run(args) => run::exec(args).await,
+            Indexer::Export(args) => export::exec(args).await,
+            Indexer::Import(args) => import::exec(args).await,
         },
         Err(e) => {
             // Prints the error and exits.
@@ -0,0 +1,55 @@
+use crate::snapshot::export_indexer;
+use clap::Parser;
+use fuel_indexer_database::IndexerConnectionPool;
+use fuel_indexer_lib::{
+    config::{DatabaseArgs, DatabaseConfig},
+    utils::bin_utils::init_logging,
+};
+use std::{fs::File, io::BufWriter, path::PathBuf};
+use tracing::info;
+
+/// Export an indexer's indexed data to a snapshot file.
+#[derive(Debug, Parser, Clone)]
+pub struct ExportArgs {
+    /// Namespace of the indexer.
+    #[clap(long, help = "Namespace of the indexer.")]
+    pub namespace: String,
+
+    /// Identifier of the indexer.
+    #[clap(long, help = "Identifier of the indexer.")]
+    pub identifier: String,
+
+    /// Path of the snapshot file to write.
+    #[clap(
+        short,
+        long,
+        value_name = "FILE",
+        help = "Path of the snapshot file to write."
+    )]
+    pub output: PathBuf,
+
+    #[clap(flatten)]
+    pub database: DatabaseArgs,
+}
+
+pub async fn exec(args: ExportArgs) -> anyhow::Result<()> {
+    init_logging(false).await?;
+
+    let database = DatabaseConfig::from(args.database);
+    let pool = IndexerConnectionPool::connect(&database.to_string()).await?;
+
+    let mut writer = BufWriter::new(File::create(&args.output)?);
+    let summary =
+        export_indexer(&pool, &args.namespace, &args.identifier, &mut writer).await?;
+
+    info!(
+        "Exported {} rows of Indexer({}.{}) at block height {:?} to '{}'.",
+        summary.rows,
+        args.namespace,
+        args.identifier,
+        summary.header.last_block_height,
+        args.output.display()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::snapshot::import_indexer;
+use clap::Parser;
+use fuel_indexer_database::{queries, IndexerConnectionPool};
+use fuel_indexer_lib::{
+    config::{DatabaseArgs, DatabaseConfig},
+    utils::bin_utils::init_logging,
+};
+use std::{fs::File, io::BufReader, path::PathBuf};
+use tracing::info;
+
+/// Import an indexer's indexed data from a snapshot file.
+#[derive(Debug, Parser, Clone)]
+pub struct ImportArgs {
+    /// Path of the snapshot file to read.
+    #[clap(
+        short,
+        long,
+        value_name = "FILE",
+        help = "Path of the snapshot file to read."
+    )]
+    pub input: PathBuf,
+
+    /// Run database migrations before importing.
+    #[clap(long, help = "Run database migrations before importing.")]
+    pub run_migrations: bool,
+
+    #[clap(flatten)]
+    pub database: DatabaseArgs,
+}
+
+pub async fn exec(args: ImportArgs) -> anyhow::Result<()> {
+    init_logging(false).await?;
+
+    let database = DatabaseConfig::from(args.database);
+    let pool = IndexerConnectionPool::connect(&database.to_string()).await?;
+
+    if args.run_migrations {
+        let mut c = pool.acquire().await?;
+        queries::run_migration(&mut c).await?;
+    }
+
+    let reader = BufReader::new(File::open(&args.input)?);
+    let summary = import_indexer(&pool, reader).await?;
+
+    info!(
+        "Imported {} rows of Indexer({}.{}) at block height {:?} from '{}'.",
+        summary.rows,
+        summary.header.namespace,
+        summary.header.identifier,
+        summary.header.last_block_height,
+        args.input.display()
+    );
+
+    Ok(())
+}
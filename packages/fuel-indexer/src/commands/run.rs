@@ -1,9 +1,9 @@
 use crate::IndexerService;
-use fuel_indexer_database::{queries, IndexerConnectionPool};
+use fuel_indexer_database::{queries, ConnectionPoolConfig, IndexerConnectionPool};
 use fuel_indexer_lib::{
     config::{IndexerArgs, IndexerConfig},
     manifest::Manifest,
-    utils::ServiceRequest,
+    utils::{bin_utils::init_logging, ServiceRequest},
 };
 use tracing::info;
 
@@ -33,13 +33,19 @@ async fn run_fuel_core_node() -> anyhow::Result<FuelService> {
 }
 
 pub async fn exec(args: IndexerArgs) -> anyhow::Result<()> {
-    let IndexerArgs { manifest, .. } = args.clone();
+    let IndexerArgs {
+        manifest,
+        replace_indexer,
+        ..
+    } = args.clone();
 
     let config = match &args.config {
         Some(path) => IndexerConfig::from_file(path)?,
         None => IndexerConfig::from(args.clone()),
     };
 
+    init_logging(config.json_logging).await?;
+
     info!("Configuration: {:?}", config);
 
     let (tx, rx) = if cfg!(feature = "api-server") {
@@ -52,7 +58,11 @@ pub async fn exec(args: IndexerArgs) -> anyhow::Result<()> {
     #[cfg(feature = "fuel-core-lib")]
     let _srvc = run_fuel_core_node().await?;
 
-    let pool = IndexerConnectionPool::connect(&config.database.to_string()).await?;
+    let pool = IndexerConnectionPool::connect_with_config(
+        &config.database.to_string(),
+        ConnectionPoolConfig::from(&config),
+    )
+    .await?;
 
     if config.run_migrations {
         let mut c = pool.acquire().await?;
@@ -65,6 +75,9 @@ pub async fn exec(args: IndexerArgs) -> anyhow::Result<()> {
         info!("Using manifest file located at '{}'", p.display());
         Manifest::from_file(&p).unwrap()
     }) {
+        Some(m) if replace_indexer => {
+            service.replace_index_from_manifest(m).await?;
+        }
         Some(m) => {
             service.register_index_from_manifest(m).await?;
         }
@@ -75,8 +88,12 @@ pub async fn exec(args: IndexerArgs) -> anyhow::Result<()> {
 
     let service_handle = tokio::spawn(service.run());
 
+    // The service returns once its indexers have drained after a shutdown
+    // signal, at which point the API server is no longer needed.
     if cfg!(feature = "api-server") {
-        let _ = tokio::join!(service_handle, GraphQlApi::build_and_run(config, pool, tx));
+        let api_handle = tokio::spawn(GraphQlApi::build_and_run(config, pool, tx));
+        service_handle.await?;
+        api_handle.abort();
     } else {
         service_handle.await?;
     };
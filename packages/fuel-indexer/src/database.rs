@@ -1,41 +1,222 @@
 use crate::ffi;
-use crate::{IndexerError, IndexerResult, Manifest};
+use crate::webhook::{object_json, WebhookEvent, WebhookQueue};
+use crate::{ErrorStrategy, IndexerError, IndexerResult, Manifest};
 use fuel_indexer_database::{
-    queries, types::IdCol, IndexerConnection, IndexerConnectionPool,
+    queries,
+    types::{
+        DbType, FailedBlock, IdCol, NamingStrategy, QueryParam, CREATED_AT_COLUMN,
+        DELETED_AT_COLUMN, UPDATED_AT_COLUMN, WRITTEN_AT_BLOCK_COLUMN,
+    },
+    ConnectionPoolConfig, IndexerConnection, IndexerConnectionPool,
 };
-use fuel_indexer_schema::FtColumn;
-use std::collections::HashMap;
-use tracing::error;
+use fuel_indexer_lib::{defaults, utils::EntityNotification};
+use fuel_indexer_schema::{
+    utils::{
+        schema_default_values, schema_naming_strategy, schema_only_newer_tables,
+        schema_required_fields, schema_table_entities, schema_upsert_keys,
+    },
+    FtColumn,
+};
+use std::collections::{HashMap, HashSet};
+use tokio::sync::broadcast;
+use tracing::{error, warn};
 use wasmer::Instance;
 
+#[cfg(feature = "metrics")]
+use fuel_indexer_metrics::METRICS;
+
+/// Maximum number of rows written by a single multi-row upsert.
+const MAX_PUT_OBJECTS_BATCH: usize = 1000;
+
+/// Maximum number of parameters bound to a single query. SQLite allows 32766,
+/// and Postgres 65535.
+const MAX_QUERY_PARAMS: usize = 32766;
+
+/// Upserts and removals of one table, buffered until the open transaction commits.
+#[derive(Debug, Default)]
+struct PendingObjects {
+    /// Object ID, column values and serialized object of each row, in
+    /// insertion order.
+    rows: Vec<(u64, Vec<QueryParam>, Vec<u8>)>,
+    /// Position in `rows` of each object ID, so that saving an object twice
+    /// overwrites its buffered row rather than conflicting with it.
+    positions: HashMap<u64, usize>,
+    /// Position in `rows` of each value of the table's upsert key, if it has
+    /// one, for the same reason. Values of composite keys are joined.
+    key_positions: HashMap<String, usize>,
+    /// IDs of removed objects, which are soft-deleted after the upserts.
+    deleted: HashSet<u64>,
+    /// Each row as JSON, by position in `rows`, if the table has a webhook.
+    webhook_objects: HashMap<usize, serde_json::Value>,
+}
+
 /// Database for an executor instance, with schema info.
 #[derive(Debug)]
 pub struct Database {
     pub pool: IndexerConnectionPool,
     stashed: Option<IndexerConnection>,
+    pending: HashMap<i64, PendingObjects>,
+    /// Height of the first block of the batch being handled, which the objects
+    /// written by the open transaction are attributed to.
+    block_height: u64,
+    /// Height of the last block of the batch being handled, which is recorded
+    /// as the indexer's progress when the open transaction commits.
+    last_block_height: Option<u64>,
+    /// Number of blocks handled by the open transaction, and of objects it
+    /// wrote, added to the indexer's stats when it commits.
+    processed_blocks: u64,
+    written_objects: u64,
+    /// Height recorded by the last transaction whose `COMMIT` failed, e.g.
+    /// because the connection dropped, which may have committed anyway.
+    unconfirmed_commit: Option<u64>,
+    /// Whether the blocks being handled are replayed, having already been
+    /// committed. Their heights are not recorded as the indexer's progress.
+    pub replaying: bool,
+    /// Number of blocks for which object writes are recorded so that they can
+    /// be reverted after a chain reorganization. Writes are not recorded if 0.
+    pub max_reorg_depth: u64,
+    /// Where to announce the objects written by each committed transaction.
+    pub notifier: Option<broadcast::Sender<EntityNotification>>,
+    /// Notifications for the objects flushed by the open transaction, sent
+    /// once it commits.
+    notifications: Vec<EntityNotification>,
+    /// URL that the objects of each type ID are posted to, for the entities
+    /// with a webhook in the manifest.
+    webhooks: HashMap<i64, String>,
+    webhook_queue: Option<WebhookQueue>,
+    /// Webhook events for the objects flushed by the open transaction, queued
+    /// once it commits.
+    webhook_events: Vec<(String, WebhookEvent)>,
     pub namespace: String,
     pub identifier: String,
     pub version: String,
     pub schema: HashMap<String, Vec<String>>,
+    /// How the tables and columns of the schema's entities are named. Columns
+    /// are listed in `schema` by field name.
+    pub naming: NamingStrategy,
+    /// SQL type of each column in `schema`, which bound values are cast to.
+    value_types: HashMap<String, Vec<String>>,
     pub tables: HashMap<i64, String>,
+    /// Lowercase entity name of each type ID.
+    pub entities: HashMap<i64, String>,
+    pub indexed_columns: HashMap<String, HashSet<String>>,
+    /// Tables with an `updated_at` column. Tables created before write times
+    /// were recorded don't have one.
+    pub timestamped: HashSet<String>,
+    /// Tables with a `deleted_at` column, whose objects can be removed.
+    pub soft_deletable: HashSet<String>,
+    /// Tables with a `written_at_block` column, holding the block that each
+    /// object was last written at. Tables created before write blocks were
+    /// recorded only have one if their schema type is marked `@onlyNewer`.
+    pub block_recorded: HashSet<String>,
+    /// Tables whose schema type is marked `@onlyNewer`, so that stored objects
+    /// are only replaced by objects written at the same or a later block.
+    pub only_newer: HashSet<String>,
+    /// Columns that upserts of each table conflict on, for tables whose schema
+    /// type has a composite `@key` or a `@unique(upsert: true)` field. Other
+    /// tables upsert on `id`.
+    pub upsert_keys: HashMap<String, Vec<String>>,
+    /// Columns of each table whose schema fields are non-null, which objects
+    /// can't be saved without.
+    pub required_columns: HashMap<String, HashSet<String>>,
+    /// Values that null values of each table's columns are saved as, for the
+    /// columns whose schema fields have a `@default`.
+    pub column_defaults: HashMap<String, HashMap<String, QueryParam>>,
+    /// Handlers that native indexers dispatch to, if the manifest restricts them.
+    enabled_handlers: Option<HashSet<String>>,
 }
 
 // TODO: Use mutex
 unsafe impl Sync for Database {}
 unsafe impl Send for Database {}
 
+impl Drop for Database {
+    // An executor that is dropped mid-batch (e.g., when it doesn't stop within
+    // `stop_drain_timeout_secs` of a shutdown) leaves its transaction open. Roll
+    // it back, rather than returning the connection to the pool inside it.
+    fn drop(&mut self) {
+        if let Some(mut conn) = self.stashed.take() {
+            if let Ok(handle) = tokio::runtime::Handle::try_current() {
+                handle.spawn(async move {
+                    if let Err(e) =
+                        queries::execute_query(&mut conn, "ROLLBACK".into()).await
+                    {
+                        error!("Failed to roll back dropped transaction: {e:?}");
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// SQLSTATE of a unique constraint violation on Postgres, and the extended
+/// result codes of one on SQLite.
+const UNIQUE_VIOLATION_CODES: [&str; 3] = ["23505", "2067", "1555"];
+
+/// Error of a failed write to `table`, telling unique constraint violations
+/// apart from other database errors.
+fn write_error(table: &str, e: sqlx::Error) -> IndexerError {
+    match &e {
+        sqlx::Error::Database(db_error)
+            if db_error.code().map_or(false, |code| {
+                UNIQUE_VIOLATION_CODES.contains(&code.as_ref())
+            }) =>
+        {
+            IndexerError::UniqueViolation(
+                table.to_string(),
+                db_error.message().to_string(),
+            )
+        }
+        _ => e.into(),
+    }
+}
+
 impl Database {
     pub async fn new(conn_uri: &str) -> IndexerResult<Database> {
-        let pool = IndexerConnectionPool::connect(conn_uri).await?;
+        Self::with_pool_config(conn_uri, ConnectionPoolConfig::default()).await
+    }
+
+    /// Connect to the database with a connection pool sized by `pool_config`.
+    pub async fn with_pool_config(
+        conn_uri: &str,
+        pool_config: ConnectionPoolConfig,
+    ) -> IndexerResult<Database> {
+        let pool =
+            IndexerConnectionPool::connect_with_config(conn_uri, pool_config).await?;
 
         Ok(Database {
             pool,
             stashed: None,
+            pending: Default::default(),
+            block_height: Default::default(),
+            last_block_height: Default::default(),
+            processed_blocks: 0,
+            written_objects: 0,
+            unconfirmed_commit: None,
+            replaying: false,
+            max_reorg_depth: Default::default(),
+            notifier: None,
+            notifications: Vec::new(),
+            webhooks: Default::default(),
+            webhook_queue: None,
+            webhook_events: Vec::new(),
             namespace: Default::default(),
             identifier: Default::default(),
             version: Default::default(),
             schema: Default::default(),
+            naming: Default::default(),
+            value_types: Default::default(),
             tables: Default::default(),
+            entities: Default::default(),
+            indexed_columns: Default::default(),
+            timestamped: Default::default(),
+            soft_deletable: Default::default(),
+            block_recorded: Default::default(),
+            only_newer: Default::default(),
+            upsert_keys: Default::default(),
+            required_columns: Default::default(),
+            column_defaults: Default::default(),
+            enabled_handlers: None,
         })
     }
 
@@ -49,14 +230,80 @@ impl Database {
     }
 
     pub async fn commit_transaction(&mut self) -> IndexerResult<usize> {
-        let mut conn = self
+        let flushed = self.flush_objects().await;
+        let notifications = std::mem::take(&mut self.notifications);
+        let webhook_events = std::mem::take(&mut self.webhook_events);
+        flushed?;
+
+        // The connection stays stashed until `COMMIT`, so the transaction can
+        // still be rolled back by `revert_transaction` if a write below fails.
+        let conn = self
             .stashed
-            .take()
+            .as_mut()
             .ok_or(IndexerError::NoTransactionError)?;
-        Ok(queries::execute_query(&mut conn, "COMMIT".into()).await?)
+
+        // The height is written in the same transaction as the objects, so it
+        // is never recorded for a block whose writes were rolled back.
+        let block_height = self.last_block_height.take();
+        if let Some(block_height) = block_height {
+            queries::set_last_block_height(
+                conn,
+                &self.namespace,
+                &self.identifier,
+                block_height,
+            )
+            .await?;
+        }
+
+        let blocks = std::mem::take(&mut self.processed_blocks);
+        let objects = std::mem::take(&mut self.written_objects);
+        if blocks > 0 || objects > 0 {
+            queries::record_indexer_stats(
+                conn,
+                &self.namespace,
+                &self.identifier,
+                blocks,
+                objects,
+            )
+            .await?;
+        }
+
+        let mut conn = self.stashed.take().expect("The transaction is open.");
+
+        // A `COMMIT` that fails may still have been applied, so whether the
+        // blocks must be handled again is decided by the recorded height.
+        let result = match queries::execute_query(&mut conn, "COMMIT".into()).await {
+            Ok(result) => result,
+            Err(e) => {
+                self.unconfirmed_commit = block_height;
+                return Err(e.into());
+            }
+        };
+
+        if let Some(notifier) = &self.notifier {
+            for notification in notifications {
+                // Sending only fails when nobody is subscribed.
+                let _ = notifier.send(notification);
+            }
+        }
+
+        if let Some(queue) = &self.webhook_queue {
+            for (url, event) in webhook_events {
+                queue.send(url, event);
+            }
+        }
+
+        Ok(result)
     }
 
     pub async fn revert_transaction(&mut self) -> IndexerResult<usize> {
+        self.pending.clear();
+        self.notifications.clear();
+        self.webhook_events.clear();
+        self.last_block_height = None;
+        self.processed_blocks = 0;
+        self.written_objects = 0;
+
         let mut conn = self
             .stashed
             .take()
@@ -64,41 +311,351 @@ impl Database {
         Ok(queries::execute_query(&mut conn, "ROLLBACK".into()).await?)
     }
 
-    fn upsert_query(
+    /// The height that blocks were committed through by the last transaction
+    /// whose `COMMIT` failed, if it was committed anyway. Handling those blocks
+    /// again would apply their writes twice.
+    ///
+    /// The recorded height is only checked once after such a failure, and
+    /// `None` is returned otherwise.
+    pub async fn committed_through(&mut self) -> IndexerResult<Option<u64>> {
+        let unconfirmed = match self.unconfirmed_commit {
+            Some(unconfirmed) => unconfirmed,
+            None => return Ok(None),
+        };
+
+        let mut conn = self.pool.acquire().await?;
+        let last = queries::last_block_height_for_indexer(
+            &mut conn,
+            &self.namespace,
+            &self.identifier,
+        )
+        .await?;
+        self.unconfirmed_commit = None;
+
+        Ok(last.filter(|last| *last == unconfirmed))
+    }
+
+    /// Attribute the objects written by the next transaction to `block_height`.
+    pub fn set_block_height(&mut self, block_height: u64) {
+        self.block_height = block_height;
+    }
+
+    /// Record `block_height` as the last block committed by this indexer when
+    /// the next transaction commits, unless the blocks are replayed.
+    pub fn set_last_block_height(&mut self, block_height: u64) {
+        if !self.replaying {
+            self.last_block_height = Some(block_height);
+        }
+    }
+
+    /// Count `count` blocks as processed by this indexer when the next
+    /// transaction commits, unless the blocks are replayed.
+    pub fn add_processed_blocks(&mut self, count: u64) {
+        if !self.replaying {
+            self.processed_blocks += count;
+        }
+    }
+
+    /// Revert the objects written at or above `block_height`, returning the
+    /// number of objects reverted.
+    ///
+    /// Objects are put back as they were before `block_height`, and those
+    /// created since are deleted.
+    pub async fn revert_to_block(&self, block_height: u64) -> IndexerResult<usize> {
+        let mut conn = self.pool.acquire().await?;
+        queries::start_transaction(&mut conn).await?;
+
+        match queries::revert_to_block(
+            &mut conn,
+            &self.namespace,
+            &self.identifier,
+            block_height,
+        )
+        .await
+        {
+            Ok(count) => {
+                // Blocks from `block_height` on are indexed again, so they no
+                // longer count as committed.
+                queries::set_last_block_height(
+                    &mut conn,
+                    &self.namespace,
+                    &self.identifier,
+                    block_height.saturating_sub(1),
+                )
+                .await?;
+                queries::commit_transaction(&mut conn).await?;
+                Ok(count)
+            }
+            Err(e) => {
+                queries::revert_transaction(&mut conn).await?;
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Forget the object writes recorded below `block_height`, which can no
+    /// longer be reverted.
+    pub async fn prune_object_writes(&self, block_height: u64) -> IndexerResult<usize> {
+        let mut conn = self.pool.acquire().await?;
+        Ok(queries::prune_object_writes(
+            &mut conn,
+            &self.namespace,
+            &self.identifier,
+            block_height,
+        )
+        .await?)
+    }
+
+    /// Record a block that was skipped or dead-lettered by this indexer.
+    ///
+    /// This runs outside of any open transaction, so the record survives the
+    /// rollback of the block it describes.
+    pub async fn put_failed_block(
         &self,
+        block_height: u64,
+        strategy: &ErrorStrategy,
+        error: String,
+        block_data: Option<Vec<u8>>,
+    ) -> IndexerResult<usize> {
+        let mut conn = self.pool.acquire().await?;
+        let block = FailedBlock {
+            namespace: self.namespace.clone(),
+            identifier: self.identifier.clone(),
+            block_height,
+            strategy: strategy.as_ref().to_string(),
+            error,
+            block_data,
+        };
+
+        Ok(queries::put_failed_block(&mut conn, &block).await?)
+    }
+
+    /// Build a multi-row upsert that replaces the stored row whose `key`
+    /// columns match. `created_at` is left to its column default, so it is
+    /// only set on insert; the `implicit` updates (e.g. of `updated_at`) are
+    /// applied on every write.
+    ///
+    /// If `written_at_block` is given, it is the column of the block that rows
+    /// were written at, and a stored row is only replaced by one written at the
+    /// same or a later block.
+    fn upsert_query(
         table: &str,
+        key: &[String],
         columns: &[String],
-        inserts: Vec<String>,
-        updates: Vec<String>,
+        rows: &[String],
+        implicit: &[String],
+        written_at_block: Option<&str>,
     ) -> String {
+        let mut updates: Vec<_> = columns
+            .iter()
+            .filter(|colname| !key.contains(colname))
+            .map(|colname| format!("{colname} = excluded.{colname}"))
+            .collect();
+        updates.extend_from_slice(implicit);
+
+        let condition = written_at_block
+            .map(|colname| {
+                format!(" WHERE {table}.{colname} IS NULL OR {table}.{colname} <= excluded.{colname}")
+            })
+            .unwrap_or_default();
+
         format!(
             "INSERT INTO {}
                 ({})
              VALUES
-                ({}, $1)
-             ON CONFLICT(id)
-             DO UPDATE SET {}",
+                {}
+             ON CONFLICT({})
+             DO UPDATE SET {}{}",
             table,
             columns.join(", "),
-            inserts.join(", "),
+            rows.join(", "),
+            key.join(", "),
             updates.join(", "),
+            condition,
         )
     }
 
+    /// Value of the upsert key of an object, from among its `columns`, if its
+    /// table has one.
+    fn upsert_key_value(&self, table: &str, columns: &[FtColumn]) -> Option<String> {
+        let values = self.upsert_keys.get(table)?.iter().map(|key| {
+            self.schema[table]
+                .iter()
+                .position(|colname| colname == key)
+                .map(|position| columns[position].query_fragment())
+        });
+
+        values
+            .collect::<Option<Vec<_>>>()
+            .map(|values| values.join(", "))
+    }
+
     fn namespace(&self) -> String {
         format!("{}_{}", self.namespace, self.identifier)
     }
 
-    fn get_query(&self, table: &str, object_id: u64) -> String {
-        format!("SELECT object from {table} where id = {object_id}")
+    /// Whether the handler with the given name is dispatched to.
+    pub fn handler_enabled(&self, name: &str) -> bool {
+        self.enabled_handlers
+            .as_ref()
+            .map_or(true, |names| names.contains(name))
     }
 
+    /// Increment the handler metric `name` of this indexer by `value`.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub fn metric_inc(&self, name: &str, value: u64) {
+        #[cfg(feature = "metrics")]
+        METRICS
+            .executor
+            .handler_metrics
+            .with_label_values(&[&self.namespace, &self.identifier, name])
+            .inc_by(value);
+    }
+
+    fn get_query(&self, table: &str) -> String {
+        if self.soft_deletable.contains(table) {
+            format!("SELECT object from {table} where id = $1 AND {DELETED_AT_COLUMN} IS NULL")
+        } else {
+            format!("SELECT object from {table} where id = $1")
+        }
+    }
+
+    /// Placeholders of one row of an upsert, numbered from `first`. On
+    /// Postgres, each value is cast to the type of its column, since values
+    /// such as `numeric` and array literals are bound as text.
+    fn row_placeholders(
+        db_type: &DbType,
+        columns: &[String],
+        value_types: &[String],
+        first: usize,
+    ) -> String {
+        let placeholders: Vec<_> = columns
+            .iter()
+            .zip(value_types)
+            .enumerate()
+            .map(|(i, (colname, sql_type))| match db_type {
+                DbType::Postgres if colname != "object" => {
+                    format!("CAST(${} AS {sql_type})", first + i)
+                }
+                _ => format!("${}", first + i),
+            })
+            .collect();
+
+        format!("({})", placeholders.join(", "))
+    }
+
+    /// ID of an object, from among its `columns`.
+    fn object_id(&self, table: &str, columns: &[FtColumn]) -> u64 {
+        let id_position = self.schema[table]
+            .iter()
+            .position(|colname| colname == &IdCol::to_lowercase_string())
+            .unwrap_or_default();
+
+        match columns[id_position] {
+            FtColumn::ID(Some(id)) => id,
+            _ => panic!("Schema fields of type ID cannot be nullable"),
+        }
+    }
+
+    /// Buffer an upsert of an object, to be written when the open transaction
+    /// commits. Saving an object that is already buffered replaces it.
+    ///
+    /// Objects with a null value for a non-null field of the schema are
+    /// rejected, rather than failing the whole transaction when it commits.
     pub async fn put_object(
         &mut self,
         type_id: i64,
         columns: Vec<FtColumn>,
         bytes: Vec<u8>,
-    ) {
+    ) -> IndexerResult<()> {
+        let table = self
+            .tables
+            .get(&type_id)
+            .ok_or(IndexerError::UnknownTypeId(type_id))?;
+
+        let db_type = self.pool.database_type();
+        let mut params: Vec<_> =
+            columns.iter().map(|col| col.bind_param(&db_type)).collect();
+        if let Some(defaults) = self.column_defaults.get(table) {
+            for (colname, param) in self.schema[table].iter().zip(params.iter_mut()) {
+                if matches!(param, QueryParam::Null) {
+                    if let Some(default) = defaults.get(colname) {
+                        *param = default.clone();
+                    }
+                }
+            }
+        }
+        if let Some(required) = self.required_columns.get(table) {
+            if let Some((colname, _)) =
+                self.schema[table]
+                    .iter()
+                    .zip(&params)
+                    .find(|(colname, param)| {
+                        matches!(param, QueryParam::Null) && required.contains(*colname)
+                    })
+            {
+                return Err(IndexerError::NullRequiredColumn(
+                    table.to_string(),
+                    colname.to_string(),
+                ));
+            }
+        }
+
+        let id = self.object_id(table, &columns);
+        let key = self.upsert_key_value(table, &columns);
+        let webhook_object = self
+            .webhooks
+            .contains_key(&type_id)
+            .then(|| object_json(&self.schema[table], &columns, &db_type));
+
+        #[cfg(feature = "metrics")]
+        METRICS
+            .executor
+            .objects_written
+            .with_label_values(&[&self.namespace, &self.identifier])
+            .inc();
+
+        let pending = self.pending.entry(type_id).or_default();
+        // Saving a removed object restores it.
+        pending.deleted.remove(&id);
+
+        // An object with the same upsert key as a buffered one replaces it,
+        // as it would replace the stored one.
+        let position = pending.positions.get(&id).copied().or_else(|| {
+            key.as_ref()
+                .and_then(|key| pending.key_positions.get(key).copied())
+        });
+        let position = match position {
+            Some(position) => {
+                let replaced = pending.rows[position].0;
+                if replaced != id {
+                    pending.positions.remove(&replaced);
+                }
+                if key.is_some() {
+                    pending.key_positions.retain(|_, p| *p != position);
+                }
+                pending.rows[position] = (id, params, bytes);
+                position
+            }
+            None => {
+                pending.rows.push((id, params, bytes));
+                pending.rows.len() - 1
+            }
+        };
+        pending.positions.insert(id, position);
+        if let Some(key) = key {
+            pending.key_positions.insert(key, position);
+        }
+        if let Some(object) = webhook_object {
+            pending.webhook_objects.insert(position, object);
+        }
+
+        Ok(())
+    }
+
+    /// Buffer the removal of an object, to be soft-deleted when the open
+    /// transaction commits. The object's row is kept, with its `deleted_at` set.
+    pub async fn delete_object(&mut self, type_id: i64, columns: Vec<FtColumn>) {
         let table = match self.tables.get(&type_id) {
             Some(t) => t,
             None => {
@@ -107,84 +664,361 @@ impl Database {
             }
         };
 
-        let inserts: Vec<_> = columns.iter().map(|col| col.query_fragment()).collect();
-        let updates: Vec<_> = self.schema[table]
-            .iter()
-            .zip(columns.iter())
-            .filter_map(|(colname, value)| {
-                if colname == &IdCol::to_lowercase_string() {
-                    None
-                } else {
-                    Some(format!("{} = {}", colname, value.query_fragment()))
-                }
-            })
-            .collect();
-
-        let columns = self.schema[table].clone();
+        if !self.soft_deletable.contains(table) {
+            error!("Table {table} has no {DELETED_AT_COLUMN} column; objects can't be removed.");
+            return;
+        }
 
-        let query_text = self.upsert_query(table, &columns, inserts, updates);
+        let id = self.object_id(table, &columns);
+        self.pending.entry(type_id).or_default().deleted.insert(id);
+    }
 
+    /// Write all buffered objects, with one multi-row upsert per table.
+    ///
+    /// If reorg tracking is enabled, the IDs of the written objects are also
+    /// recorded at the current block height.
+    async fn flush_objects(&mut self) -> IndexerResult<()> {
         let conn = self
             .stashed
             .as_mut()
-            .expect("No transaction has been opened.");
+            .ok_or(IndexerError::NoTransactionError)?;
+
+        let db_type = self.pool.database_type();
+        for (type_id, mut pending) in self.pending.drain() {
+            let table = &self.tables[&type_id];
+
+            // Objects that were removed after being saved are not posted.
+            if let (Some(url), Some(entity)) =
+                (self.webhooks.get(&type_id), self.entities.get(&type_id))
+            {
+                let mut objects: Vec<_> = pending.webhook_objects.drain().collect();
+                objects.sort_unstable_by_key(|(position, _)| *position);
+
+                for (position, object) in objects {
+                    if pending.deleted.contains(&pending.rows[position].0) {
+                        continue;
+                    }
+                    self.webhook_events.push((
+                        url.clone(),
+                        WebhookEvent {
+                            namespace: self.namespace.clone(),
+                            identifier: self.identifier.clone(),
+                            entity: entity.clone(),
+                            block_height: self.block_height,
+                            object,
+                        },
+                    ));
+                }
+            }
+
+            let block_recorded = self.block_recorded.contains(table);
+            let only_newer = self.only_newer.contains(table);
+            let mut columns = self.schema[table].clone();
+            let mut value_types = self.value_types[table].clone();
+            if block_recorded {
+                columns.push(WRITTEN_AT_BLOCK_COLUMN.to_string());
+                value_types.push("bigint".to_string());
+            }
+            let column_names: Vec<_> =
+                columns.iter().map(|c| self.naming.column_name(c)).collect();
+
+            // Each row binds its values, then its serialized object, then the
+            // block it was written at, if its table records one.
+            let row_params = columns.len();
+            let batch_size = MAX_PUT_OBJECTS_BATCH.min(MAX_QUERY_PARAMS / row_params);
+
+            // Writes are recorded before they are made, so that the rows they
+            // replace are recorded with them.
+            if self.max_reorg_depth > 0 {
+                let object_ids: Vec<i64> = pending
+                    .positions
+                    .keys()
+                    .chain(pending.deleted.iter())
+                    .map(|id| *id as i64)
+                    .collect();
+
+                for chunk in object_ids.chunks(MAX_PUT_OBJECTS_BATCH) {
+                    queries::put_object_writes(
+                        conn,
+                        &self.namespace,
+                        &self.identifier,
+                        self.block_height,
+                        table,
+                        chunk.to_vec(),
+                    )
+                    .await?;
+                }
+            }
+
+            self.written_objects += pending.rows.len() as u64;
+            let mut rows = pending.rows.into_iter().peekable();
+            while rows.peek().is_some() {
+                let mut values = Vec::new();
+                let mut params = Vec::new();
+                for (i, (_, row, bytes)) in rows.by_ref().take(batch_size).enumerate() {
+                    values.push(Self::row_placeholders(
+                        &db_type,
+                        &columns,
+                        &value_types,
+                        i * row_params + 1,
+                    ));
+                    params.extend(row);
+                    params.push(QueryParam::Bytes(bytes));
+                    if block_recorded {
+                        params.push(QueryParam::Int(self.block_height as i64));
+                    }
+                }
 
-        queries::put_object(conn, query_text, bytes)
-            .await
-            .expect("Failed to put object.");
+                let mut implicit = Vec::new();
+                if self.timestamped.contains(table) {
+                    let now = db_type.current_timestamp();
+                    implicit.push(format!("{UPDATED_AT_COLUMN} = {now}"));
+                }
+                if self.soft_deletable.contains(table) {
+                    implicit.push(format!("{DELETED_AT_COLUMN} = NULL"));
+                }
+                let key: Vec<_> = self
+                    .upsert_keys
+                    .get(table)
+                    .map(|key| key.iter().map(|c| self.naming.column_name(c)).collect())
+                    .unwrap_or_else(|| vec![IdCol::to_lowercase_string()]);
+                let written_at_block =
+                    only_newer.then(|| self.naming.column_name(WRITTEN_AT_BLOCK_COLUMN));
+                let query = Self::upsert_query(
+                    table,
+                    &key,
+                    &column_names,
+                    &values,
+                    &implicit,
+                    written_at_block.as_deref(),
+                );
+                queries::put_objects(conn, query, params)
+                    .await
+                    .map_err(|e| write_error(table, e))?;
+            }
+
+            let removed_at_block = block_recorded.then_some(self.block_height as i64);
+            for id in pending.deleted.iter() {
+                queries::soft_delete_object(conn, table, *id as i64, removed_at_block)
+                    .await?;
+            }
+
+            if let (Some(_), Some(entity)) = (&self.notifier, self.entities.get(&type_id))
+            {
+                let mut object_ids: Vec<u64> = pending
+                    .positions
+                    .keys()
+                    .chain(pending.deleted.iter())
+                    .copied()
+                    .collect();
+                object_ids.sort_unstable();
+                object_ids.dedup();
+
+                self.notifications.push(EntityNotification {
+                    namespace: self.namespace.clone(),
+                    identifier: self.identifier.clone(),
+                    entity: entity.clone(),
+                    object_ids,
+                });
+            }
+        }
+
+        Ok(())
     }
 
     pub async fn get_object(&mut self, type_id: i64, object_id: u64) -> Option<Vec<u8>> {
+        if let Some(pending) = self.pending.get(&type_id) {
+            if pending.deleted.contains(&object_id) {
+                return None;
+            }
+            if let Some(position) = pending.positions.get(&object_id) {
+                return Some(pending.rows[*position].2.clone());
+            }
+        }
+
         let table = &self.tables[&type_id];
-        let query = self.get_query(table, object_id);
+        let query = self.get_query(table);
         let conn = self
             .stashed
             .as_mut()
             .expect("No transaction has been opened.");
 
-        match queries::get_object(conn, query).await {
+        match queries::get_object(conn, query, object_id as i64).await {
             Ok(v) => Some(v),
             Err(_e) => None,
         }
     }
 
+    /// Return the first object of the given type whose `column` equals `value`.
+    ///
+    /// Unlike `get_object`, which looks objects up by ID, any column of the
+    /// entity may be matched. The value is bound to the query as a parameter.
+    pub async fn get_object_by(
+        &mut self,
+        type_id: i64,
+        column: &str,
+        value: FtColumn,
+    ) -> IndexerResult<Option<Vec<u8>>> {
+        // Buffered objects must be visible to the lookup.
+        self.flush_objects().await?;
+
+        let table = self
+            .tables
+            .get(&type_id)
+            .ok_or(IndexerError::UnknownTypeId(type_id))?;
+
+        let is_column = column != "object"
+            && self
+                .schema
+                .get(table)
+                .map_or(false, |columns| columns.iter().any(|c| c == column));
+
+        if !is_column {
+            return Err(IndexerError::UnknownColumn(format!("{table}.{column}")));
+        }
+
+        let param = value.query_param().ok_or_else(|| {
+            IndexerError::UnsupportedColumnValue(format!("{table}.{column}"), value)
+        })?;
+
+        let not_deleted = if self.soft_deletable.contains(table) {
+            format!(" AND {DELETED_AT_COLUMN} IS NULL")
+        } else {
+            String::new()
+        };
+        let query = format!(
+            "SELECT object FROM {table} WHERE {} = $1{not_deleted} LIMIT 1",
+            self.naming.column_name(column)
+        );
+
+        let conn = self
+            .stashed
+            .as_mut()
+            .ok_or(IndexerError::NoTransactionError)?;
+
+        Ok(queries::get_object_by(conn, query, vec![param]).await?)
+    }
+
+    /// Return the object of the given type whose key columns equal `key`, in
+    /// the order they are declared by its `@key` (or its upsert key, if it
+    /// has one instead).
+    pub async fn get_object_by_key(
+        &mut self,
+        type_id: i64,
+        key: Vec<FtColumn>,
+    ) -> IndexerResult<Option<Vec<u8>>> {
+        // Buffered objects must be visible to the lookup.
+        self.flush_objects().await?;
+
+        let table = self
+            .tables
+            .get(&type_id)
+            .ok_or(IndexerError::UnknownTypeId(type_id))?;
+
+        let columns = match self.upsert_keys.get(table) {
+            Some(columns) if columns.len() == key.len() => columns,
+            _ => return Err(IndexerError::InvalidKey(table.clone(), key.len())),
+        };
+
+        let mut conditions = Vec::new();
+        let mut params = Vec::new();
+        for (i, (column, value)) in columns.iter().zip(key).enumerate() {
+            let param = value.query_param().ok_or_else(|| {
+                IndexerError::UnsupportedColumnValue(format!("{table}.{column}"), value)
+            })?;
+            conditions.push(format!("{} = ${}", self.naming.column_name(column), i + 1));
+            params.push(param);
+        }
+        if self.soft_deletable.contains(table) {
+            conditions.push(format!("{DELETED_AT_COLUMN} IS NULL"));
+        }
+        let query = format!(
+            "SELECT object FROM {table} WHERE {}",
+            conditions.join(" AND ")
+        );
+
+        let conn = self
+            .stashed
+            .as_mut()
+            .ok_or(IndexerError::NoTransactionError)?;
+
+        Ok(queries::get_object_by(conn, query, params).await?)
+    }
+
+    /// Return the IDs of objects of the given type whose `column` equals `value`.
+    ///
+    /// Only indexed columns may be searched, and at most
+    /// `defaults::MAX_FIND_IDS_RESULTS` IDs are returned. The value is bound
+    /// to the query as a parameter.
+    pub async fn find_ids(
+        &mut self,
+        type_id: i64,
+        column: &str,
+        value: FtColumn,
+    ) -> IndexerResult<Vec<u64>> {
+        // Buffered objects must be visible to the lookup.
+        self.flush_objects().await?;
+
+        let table = self
+            .tables
+            .get(&type_id)
+            .ok_or(IndexerError::UnknownTypeId(type_id))?;
+
+        let is_indexed = self.indexed_columns.get(table).map_or(false, |columns| {
+            columns.contains(&self.naming.stored_column_name(column))
+        });
+
+        if !is_indexed {
+            return Err(IndexerError::ColumnNotIndexed(format!("{table}.{column}")));
+        }
+
+        let not_deleted = if self.soft_deletable.contains(table) {
+            format!(" AND {DELETED_AT_COLUMN} IS NULL")
+        } else {
+            String::new()
+        };
+        // A null value matches nothing with `=`, so it is looked up with
+        // `IS NULL` instead.
+        let (condition, params) = match value.bind_param(&self.pool.database_type()) {
+            QueryParam::Null => ("IS NULL", vec![]),
+            param => ("= $1", vec![param]),
+        };
+        let query = format!(
+            "SELECT id FROM {table} WHERE {} {condition}{not_deleted} LIMIT {}",
+            self.naming.column_name(column),
+            defaults::MAX_FIND_IDS_RESULTS
+        );
+
+        let conn = self
+            .stashed
+            .as_mut()
+            .ok_or(IndexerError::NoTransactionError)?;
+
+        Ok(queries::get_object_ids(conn, query, params)
+            .await?
+            .into_iter()
+            .map(|id| id as u64)
+            .collect())
+    }
+
     pub async fn load_schema(
         &mut self,
         manifest: &Manifest,
         instance: Option<&Instance>,
     ) -> IndexerResult<()> {
+        self.enabled_handlers = manifest
+            .enabled_handlers
+            .as_ref()
+            .map(|names| names.iter().cloned().collect());
+
+        let mut conn = self.pool.acquire().await?;
         match manifest.is_native() {
             true => {
                 self.namespace = manifest.namespace.clone();
                 self.identifier = manifest.identifier.clone();
-
-                let mut conn = self.pool.acquire().await?;
                 self.version =
                     queries::type_id_latest(&mut conn, &self.namespace, &self.identifier)
                         .await?;
-
-                let results = queries::columns_get_schema(
-                    &mut conn,
-                    &self.namespace,
-                    &self.identifier,
-                    &self.version,
-                )
-                .await?;
-
-                for column in results {
-                    let table = &format!("{}.{}", self.namespace(), &column.table_name);
-
-                    self.tables
-                        .entry(column.type_id)
-                        .or_insert_with(|| table.to_string());
-
-                    let columns = self
-                        .schema
-                        .entry(table.to_string())
-                        .or_insert_with(Vec::new);
-
-                    columns.push(column.column_name);
-                }
             }
             false => {
                 let instance = instance.unwrap();
@@ -192,33 +1026,165 @@ impl Database {
                 self.namespace = ffi::get_namespace(instance)?;
                 self.identifier = ffi::get_identifier(instance)?;
                 self.version = ffi::get_version(instance)?;
+            }
+        }
 
-                let mut conn = self.pool.acquire().await?;
-                let results = queries::columns_get_schema(
-                    &mut conn,
-                    &self.namespace,
-                    &self.identifier,
-                    &self.version,
-                )
+        let results = queries::columns_get_schema(
+            &mut conn,
+            &self.namespace,
+            &self.identifier,
+            &self.version,
+        )
+        .await?;
+
+        for column in results {
+            let table = &self
+                .pool
+                .database_type()
+                .table_name(&self.namespace(), &column.table_name);
+
+            self.tables
+                .entry(column.type_id)
+                .or_insert_with(|| table.to_string());
+            self.entities
+                .entry(column.type_id)
+                .or_insert_with(|| column.table_name.clone());
+
+            // Write and removal times are set by the database, not by the indexer.
+            if column.column_name == UPDATED_AT_COLUMN {
+                self.timestamped.insert(table.to_string());
+            }
+            if column.column_name == DELETED_AT_COLUMN {
+                self.soft_deletable.insert(table.to_string());
+            }
+            if column.column_name == WRITTEN_AT_BLOCK_COLUMN {
+                self.block_recorded.insert(table.to_string());
+            }
+            if [
+                CREATED_AT_COLUMN,
+                UPDATED_AT_COLUMN,
+                DELETED_AT_COLUMN,
+                WRITTEN_AT_BLOCK_COLUMN,
+            ]
+            .contains(&column.column_name.as_str())
+            {
+                continue;
+            }
+
+            let value_type = column.value_sql_type(&self.pool.database_type());
+            self.value_types
+                .entry(table.to_string())
+                .or_default()
+                .push(value_type);
+
+            let columns = self
+                .schema
+                .entry(table.to_string())
+                .or_insert_with(Vec::new);
+
+            columns.push(column.column_name);
+        }
+
+        // Upsert keys and naming aren't recorded with the columns, only in
+        // the schema.
+        let schema = match queries::graph_root_latest(
+            &mut conn,
+            &self.namespace,
+            &self.identifier,
+        )
+        .await
+        {
+            Ok(root) => root.schema,
+            Err(sqlx::Error::RowNotFound) => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        self.naming = schema_naming_strategy(&schema);
+
+        // Tables are named by the schema's naming strategy, but entities are
+        // announced by their lowercase name.
+        let table_entities = schema_table_entities(&schema);
+        for entity in self.entities.values_mut() {
+            if let Some(name) = table_entities.get(entity.as_str()) {
+                *entity = name.clone();
+            }
+        }
+
+        let indexed =
+            queries::indexed_columns(&mut conn, &self.namespace, &self.identifier)
                 .await?;
 
-                for column in results {
-                    let table = &format!("{}.{}", self.namespace(), &column.table_name);
+        for column in indexed {
+            let table = self
+                .pool
+                .database_type()
+                .table_name(&self.namespace(), &self.naming.quote(&column.table_name));
+
+            self.indexed_columns
+                .entry(table)
+                .or_default()
+                .insert(self.naming.stored_column_name(&column.column_name));
+        }
+
+        for (table_name, key) in schema_upsert_keys(&schema) {
+            let table = self
+                .pool
+                .database_type()
+                .table_name(&self.namespace(), &table_name);
+
+            if self.schema.get(&table).map_or(false, |columns| {
+                key.iter().all(|column| columns.contains(column))
+            }) {
+                self.upsert_keys.insert(table, key);
+            }
+        }
+
+        for (table_name, fields) in schema_required_fields(&schema) {
+            let table = self
+                .pool
+                .database_type()
+                .table_name(&self.namespace(), &table_name);
+            self.required_columns.insert(table, fields);
+        }
+
+        // Rows of `@onlyNewer` tables can only be compared by their block if
+        // the table records one.
+        for table_name in schema_only_newer_tables(&schema) {
+            let table = self
+                .pool
+                .database_type()
+                .table_name(&self.namespace(), &table_name);
+            if self.block_recorded.contains(&table) {
+                self.only_newer.insert(table);
+            }
+        }
 
-                    self.tables
-                        .entry(column.type_id)
-                        .or_insert_with(|| table.to_string());
+        for (table_name, defaults) in schema_default_values(&schema) {
+            let table = self
+                .pool
+                .database_type()
+                .table_name(&self.namespace(), &table_name);
+            self.column_defaults.insert(table, defaults);
+        }
 
-                    let columns = self
-                        .schema
-                        .entry(table.to_string())
-                        .or_insert_with(Vec::new);
+        for (entity, url) in manifest.webhooks.iter().flatten() {
+            let type_id = self
+                .entities
+                .iter()
+                .find(|(_, name)| name.eq_ignore_ascii_case(entity))
+                .map(|(type_id, _)| *type_id);
 
-                    columns.push(column.column_name);
+            match type_id {
+                Some(type_id) => {
+                    self.webhooks.insert(type_id, url.clone());
                 }
+                None => warn!("Webhook for unknown entity {entity} is ignored."),
             }
         }
 
+        if !self.webhooks.is_empty() && self.webhook_queue.is_none() {
+            self.webhook_queue = Some(WebhookQueue::spawn());
+        }
+
         Ok(())
     }
 }
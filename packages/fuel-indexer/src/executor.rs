@@ -1,21 +1,39 @@
 use crate::ffi;
-use crate::{database::Database, IndexerError, IndexerResult};
+use crate::{database::Database, ErrorStrategy, IndexerError, IndexerResult};
 use async_std::sync::{Arc, Mutex};
 use async_trait::async_trait;
-use fuel_indexer_schema::utils::serialize;
+use fuel_indexer_database::{queries, ConnectionPoolConfig};
+use fuel_indexer_schema::utils::{
+    inject_native_entities_into_schema, schema_version, serialize,
+};
 use fuel_indexer_types::abi::BlockData;
-use futures::Future;
+use futures::{future::BoxFuture, Future, FutureExt};
 use std::path::Path;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::{
+    any::Any,
+    collections::{HashSet, VecDeque},
+    panic::AssertUnwindSafe,
+};
 use thiserror::Error;
 use tokio::task::spawn_blocking;
-use tracing::error;
+use tracing::{error, warn};
 use wasmer::{
-    imports, Instance, LazyInit, Memory, Module, NativeFunc, RuntimeError, Store,
-    WasmerEnv,
+    imports,
+    vm::{
+        self, MemoryError, MemoryStyle, TableStyle, VMMemoryDefinition, VMTableDefinition,
+    },
+    wasmparser::Operator,
+    BaseTunables, CompilerConfig, Instance, LazyInit, Memory, MemoryType, Module,
+    NativeFunc, Pages, RuntimeError, Store, TableType, Target, Tunables, WasmerEnv,
+    WASM_MAX_PAGES, WASM_PAGE_SIZE,
 };
 use wasmer_compiler_cranelift::Cranelift;
 use wasmer_engine_universal::Universal;
+use wasmer_middlewares::metering::{
+    get_remaining_points, set_remaining_points, Metering, MeteringPoints,
+};
 
 use async_std::{fs::File, io::ReadExt};
 use chrono::{TimeZone, Utc};
@@ -24,28 +42,156 @@ use fuel_core_client::client::{
     FuelClient, PageDirection, PaginatedResult, PaginationRequest,
 };
 use fuel_indexer_lib::{
-    config::FuelNodeConfig,
+    config::{FuelNodeConfig, IndexerConfig},
     defaults::{
-        DELAY_FOR_EMPTY_PAGE, DELAY_FOR_SERVICE_ERR, INDEX_FAILED_CALLS,
-        MAX_EMPTY_BLOCK_REQUESTS,
+        DELAY_FOR_SERVICE_ERR, ENTITY_NOTIFICATION_CHANNEL_SIZE, INDEX_FAILED_CALLS,
+        MAX_EMPTY_BLOCK_REQUESTS, MAX_NODE_RETRY_DELAY_SECS,
+        NODE_HEALTH_CHECK_TIMEOUT_SECS, PAUSE_POLL_INTERVAL_MS,
     },
-    manifest::Manifest,
+    manifest::{CommitMode, Manifest},
+    utils::{EntityNotification, FuelNodeHealthResponse, ServiceStatus},
 };
 use fuel_indexer_types::{
     abi::TransactionData,
-    tx::{TransactionStatus, TxId},
-    Bytes32,
+    tx::{Receipt, TransactionStatus, TxId},
+    Bytes32, ContractId,
 };
 use std::marker::{Send, Sync};
 use std::str::FromStr;
 use tokio::{
+    sync::{broadcast, mpsc, oneshot, OwnedSemaphorePermit, Semaphore},
     task::JoinHandle,
-    time::{sleep, Duration},
+    time::{sleep, Duration, Instant},
 };
-use tracing::{debug, info};
+use tracing::{debug, info, info_span, Instrument};
+
+#[cfg(feature = "metrics")]
+use fuel_indexer_metrics::METRICS;
+
+/// Limits on the resources that each WASM indexer can use, so that a runaway
+/// module fails its blocks instead of taking down the service.
+///
+/// These are set by the service rather than the manifest, since the manifest is
+/// uploaded along with the module that is being limited.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct WasmLimits {
+    /// Max megabytes of memory.
+    pub memory_mb: Option<u64>,
+    /// Max instructions run while handling a batch of blocks.
+    pub fuel: Option<u64>,
+}
+
+impl WasmLimits {
+    pub fn from_config(config: &IndexerConfig) -> Self {
+        Self {
+            memory_mb: config.wasm_memory_limit_mb,
+            fuel: config.wasm_fuel_limit,
+        }
+    }
+
+    /// The memory limit in WASM pages.
+    fn memory_pages(&self) -> Option<Pages> {
+        self.memory_mb.map(|mb| {
+            let pages = mb.saturating_mul(1024 * 1024) / WASM_PAGE_SIZE as u64;
+            Pages(pages.min(WASM_MAX_PAGES as u64) as u32)
+        })
+    }
+
+    /// A store that compiles modules to count the instructions they run, and
+    /// that caps the memories of their instances.
+    fn store(&self) -> Store {
+        let mut compiler = Cranelift::default();
+        if let Some(fuel) = self.fuel {
+            compiler.push_middleware(Arc::new(Metering::new(fuel, |_: &Operator| 1)));
+        }
+        let engine = Universal::new(compiler).engine();
+
+        match self.memory_pages() {
+            Some(limit) => {
+                let base = BaseTunables::for_target(&Target::default());
+                Store::new_with_tunables(&engine, LimitingTunables { base, limit })
+            }
+            None => Store::new(&engine),
+        }
+    }
+}
+
+/// Tunables that cap every memory of an instance at `limit` pages, so that
+/// growing past it fails like it would on a full heap.
+struct LimitingTunables<T: Tunables> {
+    base: T,
+    limit: Pages,
+}
+
+impl<T: Tunables> LimitingTunables<T> {
+    fn adjust_memory(&self, requested: &MemoryType) -> MemoryType {
+        let mut adjusted = *requested;
+        adjusted.maximum = Some(
+            requested
+                .maximum
+                .map_or(self.limit, |maximum| maximum.min(self.limit)),
+        );
+        adjusted
+    }
+
+    fn validate_memory(&self, ty: &MemoryType) -> Result<(), MemoryError> {
+        if ty.minimum > self.limit {
+            return Err(MemoryError::Generic(format!(
+                "Module needs {} pages of memory, but is limited to {}.",
+                ty.minimum.0, self.limit.0
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<T: Tunables> Tunables for LimitingTunables<T> {
+    fn memory_style(&self, memory: &MemoryType) -> MemoryStyle {
+        self.base.memory_style(&self.adjust_memory(memory))
+    }
+
+    fn table_style(&self, table: &TableType) -> TableStyle {
+        self.base.table_style(table)
+    }
+
+    fn create_host_memory(
+        &self,
+        ty: &MemoryType,
+        style: &MemoryStyle,
+    ) -> Result<Arc<dyn vm::Memory>, MemoryError> {
+        let adjusted = self.adjust_memory(ty);
+        self.validate_memory(&adjusted)?;
+        self.base.create_host_memory(&adjusted, style)
+    }
+
+    unsafe fn create_vm_memory(
+        &self,
+        ty: &MemoryType,
+        style: &MemoryStyle,
+        vm_definition_location: NonNull<VMMemoryDefinition>,
+    ) -> Result<Arc<dyn vm::Memory>, MemoryError> {
+        let adjusted = self.adjust_memory(ty);
+        self.validate_memory(&adjusted)?;
+        self.base
+            .create_vm_memory(&adjusted, style, vm_definition_location)
+    }
+
+    fn create_host_table(
+        &self,
+        ty: &TableType,
+        style: &TableStyle,
+    ) -> Result<Arc<dyn vm::Table>, String> {
+        self.base.create_host_table(ty, style)
+    }
 
-fn compiler() -> Cranelift {
-    Cranelift::default()
+    unsafe fn create_vm_table(
+        &self,
+        ty: &TableType,
+        style: &TableStyle,
+        vm_definition_location: NonNull<VMTableDefinition>,
+    ) -> Result<Arc<dyn vm::Table>, String> {
+        self.base.create_vm_table(ty, style, vm_definition_location)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -72,64 +218,713 @@ impl ExecutorSource {
     }
 }
 
-pub fn run_executor<T: 'static + Executor + Send + Sync>(
-    fuel_node_addr: &str,
-    mut executor: T,
-    start_block: &u64,
-    kill_switch: Arc<AtomicBool>,
-    stop_idle_indexers: bool,
-) -> impl Future<Output = ()> {
-    let mut next_cursor = if *start_block > 1 {
-        let decremented = start_block - 1;
-        Some(decremented.to_string())
+/// Settings that a service starts each of its executors with, rather than
+/// ones set by an indexer's manifest.
+#[derive(Debug, Clone)]
+pub struct ExecutorOptions {
+    pub fuel_node: FuelNodeConfig,
+    pub database_url: String,
+    pub pool_config: ConnectionPoolConfig,
+    pub stop_idle_indexers: bool,
+    pub max_reorg_depth: u64,
+    pub stop_drain_timeout_secs: u64,
+    pub node_retry_attempts: usize,
+    pub node_retry_base_delay_ms: u64,
+    pub commit_window: Option<CommitWindow>,
+    pub read_ahead_blocks: usize,
+    /// Slots that the executors of every indexer take turns handling batches in.
+    pub scheduler: BatchScheduler,
+    pub wasm_limits: WasmLimits,
+    /// Where executors send the entities they commit.
+    pub notifier: broadcast::Sender<EntityNotification>,
+}
+
+impl ExecutorOptions {
+    pub fn from_config(config: &IndexerConfig) -> Self {
+        Self {
+            fuel_node: config.fuel_node.clone(),
+            database_url: config.database.to_string(),
+            pool_config: ConnectionPoolConfig::from(config),
+            stop_idle_indexers: config.stop_idle_indexers,
+            max_reorg_depth: config.max_reorg_depth,
+            stop_drain_timeout_secs: config.stop_drain_timeout_secs,
+            node_retry_attempts: config.node_retry_attempts,
+            node_retry_base_delay_ms: config.node_retry_base_delay_ms,
+            commit_window: CommitWindow::from_config(config),
+            read_ahead_blocks: config.read_ahead_blocks,
+            scheduler: BatchScheduler::from_config(config),
+            wasm_limits: WasmLimits::from_config(config),
+            notifier: broadcast::channel(ENTITY_NOTIFICATION_CHANNEL_SIZE).0,
+        }
+    }
+}
+
+/// What the executor loop should do after a batch of blocks has been handled.
+#[derive(Debug, Eq, PartialEq)]
+pub enum BatchOutcome {
+    /// Move on to the next page of blocks.
+    Advance,
+    /// Fetch and handle the same page of blocks again.
+    Retry,
+    /// Stop the executor.
+    Stop,
+}
+
+/// Block range `[from, to]` that an indexer has been asked to replay. The
+/// executor takes it between batches.
+pub type ReplaySlot = Arc<Mutex<Option<(u64, u64)>>>;
+
+/// How an executor that replaces the running version of its indexer takes
+/// over from it. The executor reports that it is up once its module is
+/// instantiated and its first page of blocks is fetched, and only handles
+/// blocks once it is let through, after the previous version has stopped, so
+/// that the two never write to the indexer's tables at the same time.
+#[derive(Debug)]
+pub struct ReloadHandshake {
+    ready: oneshot::Sender<()>,
+    proceed: oneshot::Receiver<()>,
+}
+
+impl ReloadHandshake {
+    /// A handshake for the executor, along with a receiver that resolves once
+    /// the executor is up, and a sender that lets it through. Dropping the
+    /// sender stops the executor instead.
+    pub fn new() -> (Self, oneshot::Receiver<()>, oneshot::Sender<()>) {
+        let (ready, ready_rx) = oneshot::channel();
+        let (proceed_tx, proceed) = oneshot::channel();
+        (Self { ready, proceed }, ready_rx, proceed_tx)
+    }
+
+    /// Report that the executor is up, and wait to be let through. Returns
+    /// whether the executor may go on.
+    async fn wait_for_turn(self) -> bool {
+        self.ready.send(()).is_ok() && self.proceed.await.is_ok()
+    }
+}
+
+/// Handle a batch of blocks, applying the indexer's `ErrorStrategy` if the
+/// handler fails.
+///
+/// `retry_count` is the number of times this batch has already been retried.
+///
+/// Under `SkipBlock` and `DeadLetter`, a failed batch is replayed one block at a
+/// time so that only the blocks that actually fail are recorded and skipped.
+/// Each replayed block is committed on its own, so a crash part-way through a
+/// replay will reprocess the already-committed blocks of that batch on restart.
+pub async fn handle_blocks<T: Executor>(
+    executor: &mut T,
+    blocks: Vec<BlockData>,
+    strategy: &ErrorStrategy,
+    retry_count: usize,
+) -> BatchOutcome {
+    let replay = if strategy.skips_blocks() {
+        blocks.clone()
     } else {
-        None
+        Vec::new()
     };
-    info!("Subscribing to Fuel node at {fuel_node_addr}");
 
-    let client = FuelClient::from_str(fuel_node_addr).unwrap_or_else(|e| {
-        panic!("Unable to connect to Fuel node at '{fuel_node_addr}': {e}",)
-    });
+    let e = match executor.handle_events(blocks).await {
+        Ok(()) => return BatchOutcome::Advance,
+        Err(e) => e,
+    };
 
-    async move {
-        let mut retry_count = 0;
+    match strategy {
+        ErrorStrategy::Abort | ErrorStrategy::Retry(_) => {
+            let can_retry = match strategy {
+                ErrorStrategy::Retry(max_retries) => retry_count < *max_retries,
+                _ => retry_count + 1 < INDEX_FAILED_CALLS,
+            };
 
-        // If we're testing or running on CI, we don't want indexers to run forever. But in production
-        // let the index operators decide if they way to stop idle indexers. Maybe we can eventually
-        // make this MAX_EMPTY_BLOCK_REQUESTS value configurable
-        let max_empty_block_reqs = if stop_idle_indexers {
-            MAX_EMPTY_BLOCK_REQUESTS
+            if can_retry {
+                error!("Indexer executor failed {e:?}, retrying.");
+                BatchOutcome::Retry
+            } else {
+                error!("Indexer failed after retries, giving up. <('.')>");
+                BatchOutcome::Stop
+            }
+        }
+        ErrorStrategy::SkipBlock | ErrorStrategy::DeadLetter => {
+            warn!("Indexer executor failed {e:?}, handling blocks individually.");
+
+            for block in replay {
+                let height = block.height;
+                let block_data = match strategy {
+                    ErrorStrategy::DeadLetter => Some(serialize(&block)),
+                    _ => None,
+                };
+
+                if let Err(e) = executor.handle_events(vec![block]).await {
+                    error!(
+                        "Block({height}) failed with {e:?}, applying {} strategy.",
+                        strategy.as_ref()
+                    );
+
+                    if let Err(e) = executor
+                        .record_failed_block(height, strategy, e, block_data)
+                        .await
+                    {
+                        error!("Failed to record Block({height}): {e:?}, giving up.");
+                        return BatchOutcome::Stop;
+                    }
+                }
+            }
+
+            BatchOutcome::Advance
+        }
+    }
+}
+
+/// Exponential backoff with full jitter between attempts to reach the Fuel node.
+pub struct Backoff {
+    /// Number of consecutive failed attempts.
+    pub attempt: usize,
+    pub max_attempts: usize,
+    base_delay: Duration,
+}
+
+impl Backoff {
+    pub fn new(max_attempts: usize, base_delay: Duration) -> Self {
+        Self {
+            attempt: 0,
+            max_attempts,
+            base_delay,
+        }
+    }
+
+    /// Record a failed attempt, returning how long to wait before the next one,
+    /// or `None` once `max_attempts` attempts have failed.
+    pub fn next_delay(&mut self) -> Option<Duration> {
+        self.attempt += 1;
+        if self.attempt >= self.max_attempts {
+            return None;
+        }
+
+        let exponent = (self.attempt - 1).min(16) as u32;
+        let ceiling = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(Duration::from_secs(MAX_NODE_RETRY_DELAY_SECS));
+        Some(ceiling.mul_f64(rand::random::<f64>()))
+    }
+
+    /// Start over after a successful attempt.
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+/// Describe the payload of a caught panic.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "Box<dyn Any>".to_string(),
+        },
+    }
+}
+
+/// Drop the blocks of a page that are past `end_block`, returning whether the
+/// page reached the end of the indexer's block range.
+pub fn truncate_to_end_block(
+    blocks: &mut Vec<BlockData>,
+    end_block: Option<u64>,
+) -> bool {
+    match end_block {
+        Some(end_block) => {
+            let range_complete = blocks.iter().any(|block| block.height >= end_block);
+            blocks.retain(|block| block.height <= end_block);
+            range_complete
+        }
+        None => false,
+    }
+}
+
+/// The contract that a receipt belongs to, if any. A call belongs to the
+/// contract that is called.
+fn receipt_contract_id(receipt: &Receipt) -> Option<&ContractId> {
+    match receipt {
+        Receipt::Call { to, .. } => Some(to),
+        Receipt::Return { id, .. }
+        | Receipt::ReturnData { id, .. }
+        | Receipt::Panic { id, .. }
+        | Receipt::Revert { id, .. }
+        | Receipt::Log { id, .. }
+        | Receipt::LogData { id, .. }
+        | Receipt::Transfer { id, .. }
+        | Receipt::TransferOut { id, .. } => Some(id),
+        Receipt::ScriptResult { .. } | Receipt::MessageOut { .. } => None,
+    }
+}
+
+/// Drop the receipts of contracts other than `contract_ids` from each
+/// transaction of `blocks`, so that they are never decoded by the indexer.
+/// Receipts that don't belong to a contract are kept.
+pub fn retain_contract_receipts(
+    blocks: &mut [BlockData],
+    contract_ids: &HashSet<ContractId>,
+) {
+    for tx in blocks
+        .iter_mut()
+        .flat_map(|block| block.transactions.iter_mut())
+    {
+        tx.receipts.retain(|receipt| {
+            receipt_contract_id(receipt).map_or(true, |id| contract_ids.contains(id))
+        });
+    }
+}
+
+/// The most recently processed blocks of an indexer, used to detect chain
+/// reorganizations and to find how far back an indexer must be rolled back.
+#[derive(Debug, Default)]
+pub struct BlockHistory {
+    /// Max number of blocks kept. Nothing is kept if 0.
+    max_depth: usize,
+    /// Height and ID of each kept block, oldest first.
+    blocks: VecDeque<(u64, Bytes32)>,
+    /// Height of the first block of each batch with kept blocks, oldest first.
+    batch_starts: VecDeque<u64>,
+}
+
+impl BlockHistory {
+    pub fn new(max_depth: u64) -> Self {
+        Self {
+            max_depth: max_depth as usize,
+            ..Default::default()
+        }
+    }
+
+    /// The most recently processed block.
+    pub fn last(&self) -> Option<(u64, Bytes32)> {
+        self.blocks.back().copied()
+    }
+
+    /// Kept blocks, newest first.
+    pub fn newest_first(&self) -> impl Iterator<Item = &(u64, Bytes32)> {
+        self.blocks.iter().rev()
+    }
+
+    /// Record a batch of processed blocks.
+    ///
+    /// If older blocks had to be dropped, returns the height below which
+    /// objects can no longer be reverted.
+    pub fn push(&mut self, blocks: &[(u64, Bytes32)]) -> Option<u64> {
+        if self.max_depth == 0 || blocks.is_empty() {
+            return None;
+        }
+
+        self.batch_starts.push_back(blocks[0].0);
+        self.blocks.extend(blocks.iter().copied());
+
+        if self.blocks.len() <= self.max_depth {
+            return None;
+        }
+
+        self.blocks.drain(..self.blocks.len() - self.max_depth);
+        let oldest = self.blocks.front().map(|(height, _)| *height)?;
+        while self.batch_starts.len() > 1 && self.batch_starts[1] <= oldest {
+            self.batch_starts.pop_front();
+        }
+
+        self.batch_starts.front().copied()
+    }
+
+    /// Forget the blocks from `first_orphan` onwards, returning the height from
+    /// which blocks must be indexed again.
+    ///
+    /// Objects are attributed to the first block of the batch that wrote them,
+    /// so rolling back always restarts from the beginning of a batch.
+    pub fn rewind(&mut self, first_orphan: u64) -> u64 {
+        let start = self
+            .batch_starts
+            .iter()
+            .rev()
+            .find(|start| **start <= first_orphan)
+            .copied()
+            .unwrap_or(first_orphan);
+
+        self.blocks.retain(|(height, _)| *height < start);
+        self.batch_starts.retain(|height| *height < start);
+
+        start
+    }
+}
+
+/// Limits on how many blocks an indexer commits in one transaction while it
+/// catches up to the chain head.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct CommitWindow {
+    /// Max number of blocks in a transaction.
+    pub max_blocks: Option<usize>,
+    /// Max time between fetching the first block of a transaction and
+    /// committing it.
+    pub max_age: Option<Duration>,
+}
+
+impl CommitWindow {
+    /// The window set by `config`, or `None` if blocks are committed as they
+    /// are fetched.
+    pub fn from_config(config: &IndexerConfig) -> Option<Self> {
+        let window = Self {
+            max_blocks: config.commit_window_blocks.map(|blocks| blocks.max(1)),
+            max_age: config.commit_window_ms.map(Duration::from_millis),
+        };
+        (window != Self::default()).then_some(window)
+    }
+}
+
+/// Shares the handling of batches fairly between the indexers of a service.
+///
+/// At most `max_concurrent_batches` batches are handled at once, across all
+/// indexers, and waiting indexers take turns in the order they asked, so one
+/// indexer catching up on many blocks can't hold the database and CPU to itself.
+/// Clones share the same slots.
+#[derive(Debug, Clone, Default)]
+pub struct BatchScheduler {
+    slots: Option<Arc<Semaphore>>,
+}
+
+impl BatchScheduler {
+    /// A scheduler of `max_concurrent_batches` slots, or one that never makes
+    /// indexers wait if it's zero.
+    pub fn new(max_concurrent_batches: usize) -> Self {
+        Self {
+            slots: (max_concurrent_batches > 0)
+                .then(|| Arc::new(Semaphore::new(max_concurrent_batches))),
+        }
+    }
+
+    pub fn from_config(config: &IndexerConfig) -> Self {
+        Self::new(config.max_concurrent_batches)
+    }
+
+    /// Wait for a slot to handle a batch in, which is freed once the returned
+    /// permit is dropped.
+    pub async fn acquire(&self) -> Option<OwnedSemaphorePermit> {
+        match &self.slots {
+            // The semaphore is never closed.
+            Some(slots) => slots.clone().acquire_owned().await.ok(),
+            None => None,
+        }
+    }
+}
+
+/// Fetched blocks that are held back until their `CommitWindow` is full.
+#[derive(Debug)]
+pub struct PendingBlocks {
+    window: CommitWindow,
+    blocks: Vec<BlockData>,
+    /// When the first held block was fetched.
+    opened: Option<Instant>,
+}
+
+impl PendingBlocks {
+    pub fn new(window: CommitWindow) -> Self {
+        Self {
+            window,
+            blocks: Vec::new(),
+            opened: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Hold `blocks`, and take the batches that should be handled now.
+    ///
+    /// While `catching_up`, blocks are held until the window is full, then
+    /// handled in a single batch. Otherwise, every held block is handled in a
+    /// batch of its own.
+    pub fn take_batches(
+        &mut self,
+        blocks: Vec<BlockData>,
+        catching_up: bool,
+    ) -> Vec<Vec<BlockData>> {
+        if !blocks.is_empty() && self.opened.is_none() {
+            self.opened = Some(Instant::now());
+        }
+        self.blocks.extend(blocks);
+
+        if self.blocks.is_empty() || (catching_up && !self.is_full()) {
+            return vec![];
+        }
+
+        let blocks = std::mem::take(&mut self.blocks);
+        self.opened = None;
+
+        if catching_up {
+            vec![blocks]
         } else {
-            usize::MAX
+            blocks.into_iter().map(|block| vec![block]).collect()
+        }
+    }
+
+    /// Drop the held blocks, so that they are fetched again.
+    pub fn clear(&mut self) {
+        self.blocks.clear();
+        self.opened = None;
+    }
+
+    fn is_full(&self) -> bool {
+        let too_many = self
+            .window
+            .max_blocks
+            .map_or(false, |max_blocks| self.blocks.len() >= max_blocks);
+        let too_old = match (self.window.max_age, self.opened) {
+            (Some(max_age), Some(opened)) => opened.elapsed() >= max_age,
+            _ => false,
         };
-        let mut num_empty_block_reqs = 0;
+
+        too_many || too_old
+    }
+}
+
+/// Walk back through `history` until a block that is still on the canonical
+/// chain is found, returning the height of the first orphaned block after it.
+///
+/// Returns `None` if none of the kept blocks are canonical, i.e. the
+/// reorganization is deeper than the history.
+async fn find_first_orphan(
+    client: &FuelClient,
+    history: &BlockHistory,
+) -> std::io::Result<Option<u64>> {
+    for (height, id) in history.newest_first() {
+        let canonical = client.block_by_height(*height).await?;
+        if canonical.map(|block| Bytes32::from(block.id)) == Some(*id) {
+            return Ok(Some(height + 1));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Count the blocks of a handled batch, and update how far the indexer is
+/// behind the Fuel node.
+#[cfg(feature = "metrics")]
+async fn record_progress(
+    client: &FuelClient,
+    labels: &[String; 2],
+    processed: &[(u64, Bytes32)],
+) {
+    let labels = [labels[0].as_str(), labels[1].as_str()];
+    METRICS
+        .executor
+        .blocks_processed
+        .with_label_values(&labels)
+        .inc_by(processed.len() as u64);
+
+    if let Some((last_height, _)) = processed.last() {
+        match client.chain_info().await {
+            Ok(info) => {
+                let head_height = info.latest_block.header.height.0;
+                METRICS
+                    .executor
+                    .lag_blocks
+                    .with_label_values(&labels)
+                    .set(head_height.saturating_sub(*last_height) as i64);
+            }
+            Err(e) => debug!("Failed to retrieve chain info: {e}"),
+        }
+    }
+}
+
+/// An item of a `BlockQueue`. Blocks are queued one at a time, so that the
+/// queue is bounded by a number of blocks rather than of pages.
+#[derive(Debug)]
+enum QueuedBlock {
+    Block(BlockData),
+    /// Marks the end of a page of blocks.
+    PageEnd {
+        cursor: Option<String>,
+        has_next_page: bool,
+        range_complete: bool,
+    },
+}
+
+/// A page of blocks taken from a `BlockQueue`.
+#[derive(Debug)]
+pub struct FetchedPage {
+    pub blocks: Vec<BlockData>,
+    /// Cursor of the last block of the page, or `None` if there were no new
+    /// blocks to fetch.
+    pub cursor: Option<String>,
+    pub has_next_page: bool,
+    /// Whether the page reached the end of the indexer's block range.
+    pub range_complete: bool,
+}
+
+/// The Fuel nodes an executor may fetch from, in order of preference, and
+/// the one it currently fetches from.
+#[derive(Clone)]
+struct FuelNodes {
+    nodes: Arc<Vec<(FuelNodeConfig, FuelClient)>>,
+    current: Arc<AtomicUsize>,
+}
+
+impl FuelNodes {
+    fn new(configs: Vec<FuelNodeConfig>) -> Self {
+        let nodes = configs
+            .into_iter()
+            .map(|config| {
+                let addr = config.to_string();
+                let client = FuelClient::from_str(&addr).unwrap_or_else(|e| {
+                    panic!("Unable to connect to Fuel node at '{addr}': {e}",)
+                });
+                (config, client)
+            })
+            .collect();
+
+        Self {
+            nodes: Arc::new(nodes),
+            current: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Client of the node currently fetched from.
+    fn client(&self) -> FuelClient {
+        self.nodes[self.current.load(Ordering::SeqCst)].1.clone()
+    }
+
+    /// Switch to the next node, in order, whose health check passes. Returns
+    /// `false` if there is none, in which case the current node is kept.
+    async fn fail_over(&self) -> bool {
+        let current = self.current.load(Ordering::SeqCst);
+
+        for offset in 1..self.nodes.len() {
+            let next = (current + offset) % self.nodes.len();
+            let (config, _) = &self.nodes[next];
+
+            if is_healthy(config).await {
+                warn!("Failing over to Fuel node at {}.", config.to_string());
+                self.current.store(next, Ordering::SeqCst);
+                return true;
+            }
+
+            warn!(
+                "Fuel node at {} failed its health check, skipping it.",
+                config.to_string()
+            );
+        }
+
+        false
+    }
+}
+
+/// Whether the Fuel node reports itself as up at its `health_check_uri`.
+async fn is_healthy(config: &FuelNodeConfig) -> bool {
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(NODE_HEALTH_CHECK_TIMEOUT_SECS))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Failed to build health check client: {e}");
+            return false;
+        }
+    };
+
+    let response = client
+        .get(config.clone().health_check_uri().to_string())
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+
+    match response {
+        Ok(response) => matches!(
+            response
+                .json::<FuelNodeHealthResponse>()
+                .await
+                .map(ServiceStatus::from),
+            Ok(ServiceStatus::OK)
+        ),
+        Err(e) => {
+            debug!(
+                "Health check of Fuel node at {} failed: {e}",
+                config.to_string()
+            );
+            false
+        }
+    }
+}
+
+/// Fetches pages of blocks from the Fuel node into a `BlockQueue`, failing
+/// over to the next healthy node once one can't be reached.
+#[derive(Clone)]
+struct BlockFetcher {
+    nodes: FuelNodes,
+    block_window: usize,
+    poll_interval: Duration,
+    end_block: Option<u64>,
+    contract_ids: Option<HashSet<ContractId>>,
+    node_retry_attempts: usize,
+    node_retry_base_delay: Duration,
+    kill_switch: Arc<AtomicBool>,
+}
+
+impl BlockFetcher {
+    /// Fetch pages of blocks after `cursor` into `sender`, until the block
+    /// range is complete, the Fuel node can't be reached, or the queue is
+    /// dropped.
+    async fn run(self, mut cursor: Option<String>, sender: mpsc::Sender<QueuedBlock>) {
+        // The full window is only requested while catching up to the chain
+        // head. Once caught up, blocks are requested one at a time.
+        let mut results = self.block_window;
+        let mut backoff =
+            Backoff::new(self.node_retry_attempts, self.node_retry_base_delay);
 
         loop {
-            debug!("Fetching paginated results from {next_cursor:?}",);
+            let client = &self.nodes.client();
+            debug!("Fetching paginated results from {cursor:?}",);
 
-            let PaginatedResult {
-                cursor, results, ..
-            } = client
+            let page = client
                 .blocks(PaginationRequest {
-                    cursor: next_cursor.clone(),
-                    results: 10,
+                    cursor: cursor.clone(),
+                    results,
                     direction: PageDirection::Forward,
                 })
-                .await
-                .unwrap_or_else(|e| {
-                    error!("Failed to retrieve blocks: {e}",);
-                    PaginatedResult {
-                        cursor: None,
-                        results: vec![],
-                        has_next_page: false,
-                        has_previous_page: false,
+                .await;
+
+            let PaginatedResult {
+                cursor: next_cursor,
+                results: blocks,
+                has_next_page,
+                ..
+            } = match page {
+                Ok(page) => {
+                    backoff.reset();
+                    page
+                }
+                Err(e) => match backoff.next_delay() {
+                    Some(delay) => {
+                        warn!(
+                            "Failed to retrieve blocks (attempt {}/{}): {e}, retrying in {delay:?}.",
+                            backoff.attempt, backoff.max_attempts
+                        );
+                        sleep(delay).await;
+                        continue;
                     }
-                });
+                    None => {
+                        if self.nodes.fail_over().await {
+                            backoff.reset();
+                            continue;
+                        }
+                        error!("Failed to retrieve blocks after {} attempts: {e}, giving up. <('.')>", backoff.max_attempts);
+                        self.kill_switch.store(true, Ordering::SeqCst);
+                        return;
+                    }
+                },
+            };
 
-            debug!("Processing {} results", results.len());
+            debug!("Fetched {} results", blocks.len());
+            results = if has_next_page { self.block_window } else { 1 };
 
             let mut block_info = Vec::new();
-            for block in results.into_iter() {
+            for block in blocks.into_iter() {
                 let producer = block.block_producer().map(|pk| pk.hash());
 
                 // NOTE: for now assuming we have a single contract instance,
@@ -232,50 +1027,509 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
                 block_info.push(block);
             }
 
-            let result = executor.handle_events(block_info).await;
-
-            if let Err(e) = result {
-                error!("Indexer executor failed {e:?}, retrying.");
-                sleep(Duration::from_secs(DELAY_FOR_SERVICE_ERR)).await;
-                retry_count += 1;
-                if retry_count < INDEX_FAILED_CALLS {
-                    continue;
-                } else {
-                    error!("Indexer failed after retries, giving up. <('.')>");
-                    break;
-                }
+            let range_complete = truncate_to_end_block(&mut block_info, self.end_block);
+            if let Some(contract_ids) = &self.contract_ids {
+                retain_contract_receipts(&mut block_info, contract_ids);
             }
 
-            if cursor.is_none() {
-                info!("No new blocks to process, sleeping.");
-                sleep(Duration::from_secs(DELAY_FOR_EMPTY_PAGE)).await;
-
-                num_empty_block_reqs += 1;
-
-                if num_empty_block_reqs == max_empty_block_reqs {
-                    error!("No blocks being produced, giving up. <('.')>");
-                    break;
+            // Sending waits while the queue is full, so fetching never gets
+            // more than the queue's capacity ahead of the executor.
+            for block in block_info {
+                if sender.send(QueuedBlock::Block(block)).await.is_err() {
+                    return;
                 }
-            } else {
-                next_cursor = cursor;
-                num_empty_block_reqs = 0;
             }
 
-            if kill_switch.load(Ordering::SeqCst) {
-                break;
+            let page_end = QueuedBlock::PageEnd {
+                cursor: next_cursor.clone(),
+                has_next_page,
+                range_complete,
+            };
+            if sender.send(page_end).await.is_err() || range_complete {
+                return;
             }
 
-            retry_count = 0;
+            match next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => {
+                    info!("No new blocks to process, sleeping.");
+                    sleep(self.poll_interval).await;
+                }
+            }
         }
     }
 }
 
-#[async_trait]
-pub trait Executor
-where
+/// Blocks fetched ahead of an executor by a background task, so that fetching
+/// and handling blocks can run at their own pace.
+///
+/// The queue holds at most `capacity` blocks: the task waits while it is full,
+/// and the executor waits while it is empty. Dropping the queue stops the task
+/// and discards the blocks fetched ahead.
+pub struct BlockQueue {
+    receiver: mpsc::Receiver<QueuedBlock>,
+    task: JoinHandle<()>,
+}
+
+impl BlockQueue {
+    fn spawn(fetcher: BlockFetcher, cursor: Option<String>, capacity: usize) -> Self {
+        let (sender, receiver) = mpsc::channel(capacity.max(1));
+        let task = tokio::spawn(fetcher.run(cursor, sender).in_current_span());
+        Self { receiver, task }
+    }
+
+    /// Wait for the next page of fetched blocks, or `None` once the fetching
+    /// task has stopped.
+    pub async fn next_page(&mut self) -> Option<FetchedPage> {
+        let mut blocks = Vec::new();
+        loop {
+            match self.receiver.recv().await? {
+                QueuedBlock::Block(block) => blocks.push(block),
+                QueuedBlock::PageEnd {
+                    cursor,
+                    has_next_page,
+                    range_complete,
+                } => {
+                    return Some(FetchedPage {
+                        blocks,
+                        cursor,
+                        has_next_page,
+                        range_complete,
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Drop for BlockQueue {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Run `executor` over the blocks of its indexer from `start_block`, returning
+/// the run along with the kill switch that stops it.
+pub fn run_executor<T: 'static + Executor + Send + Sync>(
+    mut executor: T,
+    manifest: &Manifest,
+    start_block: u64,
+    options: ExecutorOptions,
+    pause_switch: Arc<AtomicBool>,
+    replay_slot: ReplaySlot,
+    mut handshake: Option<ReloadHandshake>,
+) -> IndexerResult<(impl Future<Output = ()>, Arc<AtomicBool>)> {
+    let ExecutorOptions {
+        fuel_node,
+        stop_idle_indexers,
+        max_reorg_depth,
+        stop_drain_timeout_secs,
+        node_retry_attempts,
+        node_retry_base_delay_ms,
+        commit_window,
+        read_ahead_blocks,
+        scheduler,
+        ..
+    } = options;
+    let error_strategy = manifest.error_strategy.clone();
+    let (end_block, commit_mode) = (manifest.end_block, manifest.commit_mode);
+    let (block_window, poll_interval_ms) =
+        (manifest.block_window(), manifest.poll_interval_ms());
+    let contract_ids = manifest.contract_ids()?;
+    let kill_switch = Arc::new(AtomicBool::new(false));
+    #[cfg(feature = "metrics")]
+    let labels = [manifest.namespace.clone(), manifest.identifier.clone()];
+    let span = info_span!("indexer", uid = %manifest.uid());
+    let _entered = span.enter();
+    let killer = kill_switch.clone();
+    let start_cursor = if start_block > 1 {
+        let decremented = start_block - 1;
+        Some(decremented.to_string())
+    } else {
+        None
+    };
+    let fuel_nodes = fuel_node.nodes();
+    info!("Subscribing to Fuel node at {}", fuel_nodes[0].to_string());
+
+    let nodes = FuelNodes::new(fuel_nodes);
+
+    let fetcher = BlockFetcher {
+        nodes: nodes.clone(),
+        block_window,
+        poll_interval: Duration::from_millis(poll_interval_ms),
+        end_block,
+        contract_ids,
+        node_retry_attempts,
+        node_retry_base_delay: Duration::from_millis(node_retry_base_delay_ms),
+        kill_switch: kill_switch.clone(),
+    };
+
+    let stop_switch = kill_switch.clone();
+
+    let run = async move {
+        if let Some(end_block) = end_block {
+            if start_block > end_block {
+                info!("Start block {start_block} is past end block {end_block}, nothing to index.");
+                kill_switch.store(true, Ordering::SeqCst);
+                return;
+            }
+        }
+
+        let mut retry_count = 0;
+
+        // If we're testing or running on CI, we don't want indexers to run forever. But in production
+        // let the index operators decide if they way to stop idle indexers. Maybe we can eventually
+        // make this MAX_EMPTY_BLOCK_REQUESTS value configurable
+        let max_empty_block_reqs = if stop_idle_indexers {
+            MAX_EMPTY_BLOCK_REQUESTS
+        } else {
+            usize::MAX
+        };
+        let mut num_empty_block_reqs = 0;
+        let mut history = BlockHistory::new(max_reorg_depth);
+        // Blocks fetched while catching up are only held back if each window
+        // would otherwise be committed as a whole.
+        let mut pending = match commit_mode {
+            CommitMode::Batch => commit_window.map(PendingBlocks::new),
+            CommitMode::Block => None,
+        };
+        // Where to fetch from if held or failed blocks must be fetched again.
+        let mut committed_cursor = start_cursor.clone();
+        let mut queue =
+            BlockQueue::spawn(fetcher.clone(), start_cursor, read_ahead_blocks);
+
+        loop {
+            // Stop between batches, so that no block is left half-handled.
+            if kill_switch.load(Ordering::SeqCst) {
+                info!("Indexer stopped gracefully.");
+                break;
+            }
+
+            // A paused indexer holds off between batches, keeping its place,
+            // until it is resumed or stopped.
+            if pause_switch.load(Ordering::SeqCst) {
+                info!("Indexer paused.");
+                while pause_switch.load(Ordering::SeqCst)
+                    && !kill_switch.load(Ordering::SeqCst)
+                {
+                    sleep(Duration::from_millis(PAUSE_POLL_INTERVAL_MS)).await;
+                }
+                if !kill_switch.load(Ordering::SeqCst) {
+                    info!("Indexer resumed.");
+                }
+                continue;
+            }
+
+            let replay = replay_slot.lock().await.take();
+            if let Some(range) = replay {
+                replay_blocks(
+                    &mut executor,
+                    &fetcher,
+                    range,
+                    &error_strategy,
+                    read_ahead_blocks,
+                    &scheduler,
+                    &kill_switch,
+                )
+                .await;
+                continue;
+            }
+
+            if let Some((height, id)) = history.last() {
+                let client = nodes.client();
+                let reorged = match client.block_by_height(height).await {
+                    Ok(block) => block.map(|block| Bytes32::from(block.id)) != Some(id),
+                    Err(e) => {
+                        error!("Failed to retrieve Block({height}): {e}");
+                        false
+                    }
+                };
+
+                if reorged {
+                    warn!("Block({height}) is no longer on the canonical chain, rolling back.");
+
+                    let first_orphan = match find_first_orphan(&client, &history).await {
+                        Ok(Some(first_orphan)) => first_orphan,
+                        Ok(None) => {
+                            error!("Chain reorganization is deeper than max reorg depth of {max_reorg_depth} blocks, giving up. <('.')>");
+                            break;
+                        }
+                        Err(e) => {
+                            error!("Failed to retrieve blocks: {e}");
+                            sleep(Duration::from_secs(DELAY_FOR_SERVICE_ERR)).await;
+                            continue;
+                        }
+                    };
+
+                    let revert_height = history.rewind(first_orphan);
+                    if let Err(e) = executor.revert_to_block(revert_height).await {
+                        error!("Failed to roll back to Block({revert_height}): {e:?}, giving up.");
+                        break;
+                    }
+
+                    info!("Rolled back to Block({revert_height}).");
+                    committed_cursor = if revert_height > 1 {
+                        Some((revert_height - 1).to_string())
+                    } else {
+                        None
+                    };
+                    // Blocks fetched ahead may be orphaned too.
+                    queue = BlockQueue::spawn(
+                        fetcher.clone(),
+                        committed_cursor.clone(),
+                        read_ahead_blocks,
+                    );
+                    if let Some(pending) = &mut pending {
+                        pending.clear();
+                    }
+                    retry_count = 0;
+                    continue;
+                }
+            }
+
+            // The fetching task only stops early if the Fuel node can't be reached.
+            let FetchedPage {
+                blocks: block_info,
+                cursor,
+                has_next_page,
+                range_complete,
+            } = match queue.next_page().await {
+                Some(page) => page,
+                None => break,
+            };
+
+            // A reloaded executor holds off until the version it replaces has
+            // stopped.
+            if let Some(handshake) = handshake.take() {
+                if !handshake.wait_for_turn().await || kill_switch.load(Ordering::SeqCst)
+                {
+                    info!("Reload was called off before handling any blocks.");
+                    break;
+                }
+            }
+
+            debug!("Processing {} results", block_info.len());
+
+            let batches = match (&mut pending, commit_mode) {
+                (Some(pending), _) => {
+                    let batches = pending
+                        .take_batches(block_info, has_next_page && !range_complete);
+                    if !pending.is_empty() {
+                        debug!(
+                            "Holding {} blocks until the commit window is full",
+                            pending.len()
+                        );
+                    }
+                    batches
+                }
+                (None, CommitMode::Batch) => vec![block_info],
+                (None, CommitMode::Block) => {
+                    block_info.into_iter().map(|block| vec![block]).collect()
+                }
+            };
+
+            let mut outcome = BatchOutcome::Advance;
+            for blocks in batches {
+                let processed: Vec<_> = blocks
+                    .iter()
+                    .map(|block| (block.height, block.id))
+                    .collect();
+
+                #[cfg(feature = "metrics")]
+                let handler_timer = METRICS
+                    .executor
+                    .handler_duration_seconds
+                    .with_label_values(&[&labels[0], &labels[1]])
+                    .start_timer();
+
+                let block_height = processed.first().map(|(height, _)| *height);
+                let slot = scheduler.acquire().await;
+                outcome =
+                    handle_blocks(&mut executor, blocks, &error_strategy, retry_count)
+                        .instrument(info_span!("batch", block_height))
+                        .await;
+                drop(slot);
+
+                #[cfg(feature = "metrics")]
+                handler_timer.observe_duration();
+
+                if outcome != BatchOutcome::Advance {
+                    break;
+                }
+
+                #[cfg(feature = "metrics")]
+                record_progress(&nodes.client(), &labels, &processed).await;
+
+                if let Some(prune_height) = history.push(&processed) {
+                    if let Err(e) = executor.prune_object_writes(prune_height).await {
+                        error!("Failed to prune object writes: {e:?}");
+                    }
+                }
+
+                // Blocks that are already committed aren't fetched again if a
+                // later block of the window has to be retried.
+                if let Some((height, _)) = processed.last() {
+                    committed_cursor = Some(height.to_string());
+                    retry_count = 0;
+                }
+            }
+
+            match outcome {
+                BatchOutcome::Advance => {}
+                BatchOutcome::Retry => {
+                    // The failed batch may include blocks of earlier pages, and
+                    // the blocks fetched ahead of it must be handled after it.
+                    queue = BlockQueue::spawn(
+                        fetcher.clone(),
+                        committed_cursor.clone(),
+                        read_ahead_blocks,
+                    );
+                    if let Some(pending) = &mut pending {
+                        pending.clear();
+                    }
+                    sleep(Duration::from_secs(DELAY_FOR_SERVICE_ERR)).await;
+                    retry_count += 1;
+                    continue;
+                }
+                BatchOutcome::Stop => break,
+            }
+
+            if range_complete {
+                info!(
+                    "Indexer completed block range [{start_block}, {}].",
+                    end_block.unwrap_or_default()
+                );
+                kill_switch.store(true, Ordering::SeqCst);
+                break;
+            }
+
+            if cursor.is_none() {
+                num_empty_block_reqs += 1;
+
+                if num_empty_block_reqs == max_empty_block_reqs {
+                    error!("No blocks being produced, giving up. <('.')>");
+                    break;
+                }
+            } else {
+                num_empty_block_reqs = 0;
+            }
+
+            retry_count = 0;
+        }
+    };
+
+    // A stopped executor has `stop_drain_timeout_secs` to finish the batch it is
+    // handling. After that it is dropped, leaving its open transaction to be
+    // rolled back by the database.
+    let run = async move {
+        tokio::pin!(run);
+        tokio::select! {
+            _ = &mut run => {}
+            _ = stop_deadline(stop_switch, Duration::from_secs(stop_drain_timeout_secs)) => {
+                warn!("Indexer did not stop within {stop_drain_timeout_secs}s, killing it.");
+            }
+        }
+    }
+    .instrument(span.clone());
+
+    Ok((run, killer))
+}
+
+/// Handle blocks `[from, to]` again, after the indexer has committed them.
+///
+/// Objects are upserted, so a replay rewrites the objects derived from those
+/// blocks using the indexer's current module. The indexer's recorded progress
+/// is left as it is. A replay that fails is given up on, keeping the blocks
+/// replayed before the failure.
+async fn replay_blocks<T: Executor>(
+    executor: &mut T,
+    fetcher: &BlockFetcher,
+    (from, to): (u64, u64),
+    error_strategy: &ErrorStrategy,
+    read_ahead_blocks: usize,
+    scheduler: &BatchScheduler,
+    kill_switch: &AtomicBool,
+) {
+    info!("Replaying blocks [{from}, {to}].");
+
+    let fetcher = BlockFetcher {
+        end_block: Some(to),
+        ..fetcher.clone()
+    };
+    let cursor = (from > 1).then(|| (from - 1).to_string());
+    let mut queue = BlockQueue::spawn(fetcher, cursor, read_ahead_blocks);
+
+    executor.set_replaying(true).await;
+    let result = loop {
+        if kill_switch.load(Ordering::SeqCst) {
+            break Err("the indexer was stopped".to_string());
+        }
+
+        let page = match queue.next_page().await {
+            Some(page) => page,
+            None => break Err("blocks could not be fetched".to_string()),
+        };
+
+        if let Some(height) = page.blocks.last().map(|block| block.height) {
+            let slot = scheduler.acquire().await;
+            let outcome = handle_blocks(executor, page.blocks, error_strategy, 0)
+                .instrument(info_span!("replay", block_height = height))
+                .await;
+            drop(slot);
+            if outcome != BatchOutcome::Advance {
+                break Err(format!("blocks up to Block({height}) failed"));
+            }
+            info!("Replayed blocks [{from}, {height}] of [{from}, {to}].");
+        }
+
+        if page.range_complete || page.cursor.is_none() {
+            break Ok(());
+        }
+    };
+    executor.set_replaying(false).await;
+
+    match result {
+        Ok(()) => info!("Replayed blocks [{from}, {to}]."),
+        Err(e) => error!("Gave up replaying blocks [{from}, {to}]: {e}."),
+    }
+}
+
+/// Resolve `drain_timeout` after `kill_switch` is set.
+async fn stop_deadline(kill_switch: Arc<AtomicBool>, drain_timeout: Duration) {
+    while !kill_switch.load(Ordering::SeqCst) {
+        sleep(Duration::from_millis(100)).await;
+    }
+    sleep(drain_timeout).await;
+}
+
+#[async_trait]
+pub trait Executor
+where
     Self: Sized,
 {
     async fn handle_events(&mut self, blocks: Vec<BlockData>) -> IndexerResult<()>;
+
+    /// Persist a block that was skipped or dead-lettered under the indexer's
+    /// `ErrorStrategy`.
+    async fn record_failed_block(
+        &mut self,
+        block_height: u64,
+        strategy: &ErrorStrategy,
+        error: IndexerError,
+        block_data: Option<Vec<u8>>,
+    ) -> IndexerResult<()>;
+
+    /// Delete the objects written at or above `block_height`, so that those
+    /// blocks can be indexed again after a chain reorganization.
+    async fn revert_to_block(&mut self, block_height: u64) -> IndexerResult<()>;
+
+    /// Forget the objects written below `block_height`, which are past the
+    /// max reorg depth and can no longer be reverted.
+    async fn prune_object_writes(&mut self, block_height: u64) -> IndexerResult<()>;
+
+    /// Whether the blocks handled next are replayed, in which case they are
+    /// not recorded as the indexer's progress.
+    async fn set_replaying(&mut self, replaying: bool);
 }
 
 #[derive(Error, Debug)]
@@ -296,8 +1550,13 @@ pub struct IndexEnv {
 }
 
 impl IndexEnv {
-    pub async fn new(db_conn: String) -> IndexerResult<IndexEnv> {
-        let db = Arc::new(Mutex::new(Database::new(&db_conn).await?));
+    pub async fn new(
+        db_conn: String,
+        pool_config: ConnectionPoolConfig,
+    ) -> IndexerResult<IndexEnv> {
+        let db = Arc::new(Mutex::new(
+            Database::with_pool_config(&db_conn, pool_config).await?,
+        ));
         Ok(IndexEnv {
             memory: Default::default(),
             alloc: Default::default(),
@@ -318,13 +1577,22 @@ unsafe impl<F: Future<Output = IndexerResult<()>> + Send> Send
 }
 
 #[allow(dead_code)]
+/// The compiled-in `handle_events` of a native indexer, with its future boxed
+/// so that the handlers of different indexers can be kept together.
+pub type NativeHandler = Arc<
+    dyn Fn(Vec<BlockData>, Arc<Mutex<Database>>) -> BoxFuture<'static, IndexerResult<()>>
+        + Send
+        + Sync,
+>;
+
 pub struct NativeIndexExecutor<F>
 where
     F: Future<Output = IndexerResult<()>> + Send,
 {
     db: Arc<Mutex<Database>>,
     manifest: Manifest,
-    handle_events_fn: fn(Vec<BlockData>, Arc<Mutex<Database>>) -> F,
+    handle_events_fn:
+        Arc<dyn Fn(Vec<BlockData>, Arc<Mutex<Database>>) -> F + Send + Sync>,
 }
 
 impl<F> NativeIndexExecutor<F>
@@ -333,35 +1601,54 @@ where
 {
     pub async fn new(
         db_conn: &str,
+        pool_config: ConnectionPoolConfig,
         manifest: Manifest,
-        handle_events_fn: fn(Vec<BlockData>, Arc<Mutex<Database>>) -> F,
+        handle_events_fn: impl Fn(Vec<BlockData>, Arc<Mutex<Database>>) -> F
+            + Send
+            + Sync
+            + 'static,
     ) -> IndexerResult<Self> {
-        let db = Arc::new(Mutex::new(Database::new(db_conn).await?));
+        let db = Arc::new(Mutex::new(
+            Database::with_pool_config(db_conn, pool_config).await?,
+        ));
         db.lock().await.load_schema(&manifest, None).await?;
         Ok(Self {
             db,
             manifest,
-            handle_events_fn,
+            handle_events_fn: Arc::new(handle_events_fn),
         })
     }
 
     pub async fn create<T: Future<Output = IndexerResult<()>> + Send + 'static>(
-        db_url: &str,
-        fuel_node: &FuelNodeConfig,
+        options: &ExecutorOptions,
         manifest: Manifest,
-        stop_idle_indexers: bool,
         start_block: u64,
-        handle_events: fn(Vec<BlockData>, Arc<Mutex<Database>>) -> T,
+        pause_switch: Arc<AtomicBool>,
+        replay_slot: ReplaySlot,
+        handle_events: impl Fn(Vec<BlockData>, Arc<Mutex<Database>>) -> T
+            + Send
+            + Sync
+            + 'static,
     ) -> IndexerResult<(JoinHandle<()>, ExecutorSource, Arc<AtomicBool>)> {
-        let executor = NativeIndexExecutor::new(db_url, manifest, handle_events).await?;
-        let kill_switch = Arc::new(AtomicBool::new(false));
-        let handle = tokio::spawn(run_executor(
-            &fuel_node.to_string(),
+        let executor = NativeIndexExecutor::new(
+            &options.database_url,
+            options.pool_config,
+            manifest.clone(),
+            handle_events,
+        )
+        .await?;
+        executor.db.lock().await.max_reorg_depth = options.max_reorg_depth;
+        executor.db.lock().await.notifier = Some(options.notifier.clone());
+        let (run, kill_switch) = run_executor(
             executor,
-            &start_block,
-            kill_switch.clone(),
-            stop_idle_indexers,
-        ));
+            &manifest,
+            start_block,
+            options.clone(),
+            pause_switch,
+            replay_slot,
+            None,
+        )?;
+        let handle = tokio::spawn(run);
         Ok((handle, ExecutorSource::Manifest, kill_switch))
     }
 }
@@ -371,18 +1658,108 @@ impl<F> Executor for NativeIndexExecutor<F>
 where
     F: Future<Output = IndexerResult<()>> + Send,
 {
-    async fn handle_events(&mut self, blocks: Vec<BlockData>) -> IndexerResult<()> {
+    async fn handle_events(&mut self, mut blocks: Vec<BlockData>) -> IndexerResult<()> {
+        if let Some(height) = self.db.lock().await.committed_through().await? {
+            warn!(
+                "Blocks through Block({height}) were already committed, skipping them."
+            );
+            blocks.retain(|block| block.height > height);
+            if blocks.is_empty() {
+                return Ok(());
+            }
+        }
+        if let Some(block) = blocks.first() {
+            self.db.lock().await.set_block_height(block.height);
+        }
         self.db.lock().await.start_transaction().await?;
-        let res = (self.handle_events_fn)(blocks, self.db.clone()).await;
-        if let Err(e) = res {
-            error!("NativeIndexExecutor handle_events failed: {}.", e);
-            self.db.lock().await.revert_transaction().await?;
-            return Err(IndexerError::NativeExecutionRuntimeError);
-        } else {
-            self.db.lock().await.commit_transaction().await?;
+        // Progress is only counted once the transaction it commits with is
+        // open, so a batch that fails before then isn't counted twice when it
+        // is retried.
+        if let Some(block) = blocks.last() {
+            self.db.lock().await.set_last_block_height(block.height);
+        }
+        self.db
+            .lock()
+            .await
+            .add_processed_blocks(blocks.len() as u64);
+        let res = AssertUnwindSafe((self.handle_events_fn)(blocks, self.db.clone()))
+            .catch_unwind()
+            .await;
+        match res {
+            Ok(Ok(())) => {
+                self.db.lock().await.commit_transaction().await?;
+            }
+            Ok(Err(e)) => {
+                error!("NativeIndexExecutor handle_events failed: {}.", e);
+                self.db.lock().await.revert_transaction().await?;
+                return Err(IndexerError::NativeExecutionRuntimeError);
+            }
+            Err(payload) => {
+                let e = panic_message(payload);
+                error!("NativeIndexExecutor handle_events panicked: {e}.");
+                self.db.lock().await.revert_transaction().await?;
+                return Err(IndexerError::HandlerPanic(e));
+            }
         }
         Ok(())
     }
+
+    async fn record_failed_block(
+        &mut self,
+        block_height: u64,
+        strategy: &ErrorStrategy,
+        error: IndexerError,
+        block_data: Option<Vec<u8>>,
+    ) -> IndexerResult<()> {
+        self.db
+            .lock()
+            .await
+            .put_failed_block(block_height, strategy, error.to_string(), block_data)
+            .await?;
+        Ok(())
+    }
+
+    async fn revert_to_block(&mut self, block_height: u64) -> IndexerResult<()> {
+        self.db.lock().await.revert_to_block(block_height).await?;
+        Ok(())
+    }
+
+    async fn prune_object_writes(&mut self, block_height: u64) -> IndexerResult<()> {
+        self.db
+            .lock()
+            .await
+            .prune_object_writes(block_height)
+            .await?;
+        Ok(())
+    }
+
+    async fn set_replaying(&mut self, replaying: bool) {
+        self.db.lock().await.replaying = replaying;
+    }
+}
+
+/// Check that every handler that `manifest` enables is one of the module's.
+#[allow(clippy::result_large_err)]
+fn check_enabled_handlers(instance: &Instance, manifest: &Manifest) -> IndexerResult<()> {
+    if manifest.enabled_handlers.is_none() {
+        return Ok(());
+    }
+
+    let handlers = ffi::get_handlers(instance).map_err(|_| {
+        IndexerError::InvalidManifest(
+            "`enabled_handlers` is set, but the module was built without a list of its handlers. Rebuild it.".to_string(),
+        )
+    })?;
+
+    let unknown = manifest.unknown_handlers(&handlers);
+    if !unknown.is_empty() {
+        return Err(IndexerError::InvalidManifest(format!(
+            "Unknown handlers in `enabled_handlers`: {}.",
+            unknown.join(", ")
+        )));
+    }
+
+    Ok(())
 }
 
 /// Responsible for loading a single indexer module, triggering events.
@@ -392,31 +1769,59 @@ pub struct WasmIndexExecutor {
     _module: Module,
     _store: Store,
     db: Arc<Mutex<Database>>,
+    limits: WasmLimits,
 }
 
 impl WasmIndexExecutor {
     pub async fn new(
         db_conn: String,
+        pool_config: ConnectionPoolConfig,
         manifest: Manifest,
         wasm_bytes: impl AsRef<[u8]>,
+        limits: WasmLimits,
     ) -> IndexerResult<Self> {
-        let store = Store::new(&Universal::new(compiler()).engine());
+        let (store, module, instance, env) =
+            Self::instantiate(db_conn, pool_config, wasm_bytes, limits).await?;
+        env.db
+            .lock()
+            .await
+            .load_schema(&manifest, Some(&instance))
+            .await?;
+
+        if let Some(handlers) = &manifest.enabled_handlers {
+            check_enabled_handlers(&instance, &manifest)?;
+            ffi::set_enabled_handlers(&instance, handlers)?;
+        }
+
+        Ok(WasmIndexExecutor {
+            instance,
+            _module: module,
+            _store: store,
+            db: env.db.clone(),
+            limits,
+        })
+    }
+
+    /// Instantiate a WASM module with the host functions it can call, checking
+    /// that it exports a handler.
+    async fn instantiate(
+        db_conn: String,
+        pool_config: ConnectionPoolConfig,
+        wasm_bytes: impl AsRef<[u8]>,
+        limits: WasmLimits,
+    ) -> IndexerResult<(Store, Module, Instance, IndexEnv)> {
+        let store = limits.store();
         let module = Module::new(&store, &wasm_bytes)?;
 
         let mut import_object = imports! {};
 
-        let mut env = IndexEnv::new(db_conn).await?;
+        let mut env = IndexEnv::new(db_conn, pool_config).await?;
         let exports = ffi::get_exports(&env, &store);
 
         import_object.register("env", exports);
 
         let instance = Instance::new(&module, &import_object)?;
         env.init_with_instance(&instance)?;
-        env.db
-            .lock()
-            .await
-            .load_schema(&manifest, Some(&instance))
-            .await?;
 
         if !instance
             .exports
@@ -425,81 +1830,170 @@ impl WasmIndexExecutor {
             return Err(IndexerError::MissingHandler);
         }
 
-        Ok(WasmIndexExecutor {
-            instance,
-            _module: module,
-            _store: store,
-            db: env.db.clone(),
-        })
+        Ok((store, module, instance, env))
+    }
+
+    /// Give the module its full fuel limit, if it has one.
+    fn refuel(&self) {
+        if let Some(fuel) = self.limits.fuel {
+            set_remaining_points(&self.instance, fuel);
+        }
+    }
+
+    /// The limit that the module ran into, if a call to it failed because of one.
+    fn exceeded_limit(&self) -> Option<IndexerError> {
+        if let Some(fuel) = self.limits.fuel {
+            if let MeteringPoints::Exhausted = get_remaining_points(&self.instance) {
+                return Some(IndexerError::WasmFuelExhausted(fuel));
+            }
+        }
+
+        let (memory_mb, limit) = (self.limits.memory_mb?, self.limits.memory_pages()?);
+        let memory = self.instance.exports.get_memory("memory").ok()?;
+        (memory.size() >= limit)
+            .then_some(IndexerError::WasmMemoryLimitExceeded(memory_mb))
+    }
+
+    /// Check that a WASM module can be run as the given indexer, without running it.
+    ///
+    /// The module is instantiated, and the namespace and identifier that it exports
+    /// must be the indexer's. The schema version it was built with must be that of
+    /// `schema`, if given, or else already be registered for the indexer. The
+    /// handlers enabled by `manifest`, if given, must be the module's.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn validate(
+        db_conn: String,
+        pool_config: ConnectionPoolConfig,
+        namespace: &str,
+        identifier: &str,
+        wasm_bytes: impl AsRef<[u8]>,
+        schema: Option<&str>,
+        manifest: Option<&Manifest>,
+        limits: WasmLimits,
+    ) -> IndexerResult<()> {
+        let (_store, _module, instance, env) =
+            Self::instantiate(db_conn, pool_config, wasm_bytes, limits).await?;
+
+        let module_namespace = ffi::get_namespace(&instance)?;
+        let module_identifier = ffi::get_identifier(&instance)?;
+        let version = ffi::get_version(&instance)?;
+
+        if module_namespace != namespace || module_identifier != identifier {
+            return Err(IndexerError::InvalidModule(format!(
+                "Module was built for Indexer({module_namespace}.{module_identifier}), not Indexer({namespace}.{identifier})."
+            )));
+        }
+
+        match schema {
+            Some(schema) => {
+                let schema_version =
+                    schema_version(&inject_native_entities_into_schema(schema));
+                if schema_version != version {
+                    return Err(IndexerError::InvalidModule(format!(
+                        "Module was built with schema version {version}, but the uploaded schema is version {schema_version}."
+                    )));
+                }
+            }
+            None => {
+                let mut conn = env.db.lock().await.pool.acquire().await?;
+                if !queries::schema_exists(&mut conn, namespace, identifier, &version)
+                    .await?
+                {
+                    return Err(IndexerError::InvalidModule(format!(
+                        "Module was built with schema version {version}, which is not registered for Indexer({namespace}.{identifier})."
+                    )));
+                }
+            }
+        }
+
+        if let Some(manifest) = manifest {
+            check_enabled_handlers(&instance, manifest)?;
+        }
+
+        Ok(())
     }
 
     /// Restore index from wasm file
     pub async fn from_file(db_conn: String, manifest_path: &Path) -> IndexerResult<Self> {
         let manifest = Manifest::from_file(manifest_path)?;
         let bytes = manifest.module_bytes()?;
-        Self::new(db_conn, manifest, bytes).await
+        Self::new(
+            db_conn,
+            ConnectionPoolConfig::default(),
+            manifest,
+            bytes,
+            WasmLimits::default(),
+        )
+        .await
     }
 
     pub async fn create(
-        fuel_node: &FuelNodeConfig,
-        db_url: &str,
+        options: &ExecutorOptions,
         manifest: &Manifest,
         exec_source: ExecutorSource,
-        stop_idle_indexers: bool,
-        start_block: &u64,
+        start_block: u64,
+        handshake: Option<ReloadHandshake>,
+        pause_switch: Arc<AtomicBool>,
+        replay_slot: ReplaySlot,
     ) -> IndexerResult<(JoinHandle<()>, ExecutorSource, Arc<AtomicBool>)> {
-        let killer = Arc::new(AtomicBool::new(false));
-
-        match &exec_source {
+        let bytes = match exec_source {
             ExecutorSource::Manifest => match &manifest.module {
                 crate::Module::Wasm(ref module) => {
                     let mut bytes = Vec::<u8>::new();
                     let mut file = File::open(module).await?;
                     file.read_to_end(&mut bytes).await?;
-
-                    let executor = WasmIndexExecutor::new(
-                        db_url.to_string(),
-                        manifest.to_owned(),
-                        bytes.clone(),
-                    )
-                    .await?;
-                    let handle = tokio::spawn(run_executor(
-                        &fuel_node.to_string(),
-                        executor,
-                        start_block,
-                        killer.clone(),
-                        stop_idle_indexers,
-                    ));
-
-                    Ok((handle, ExecutorSource::Registry(bytes), killer))
+                    bytes
                 }
                 crate::Module::Native => {
-                    Err(IndexerError::NativeExecutionInstantiationError)
+                    return Err(IndexerError::NativeExecutionInstantiationError)
                 }
             },
-            ExecutorSource::Registry(bytes) => {
-                let executor =
-                    WasmIndexExecutor::new(db_url.into(), manifest.to_owned(), bytes)
-                        .await?;
-                let handle = tokio::spawn(run_executor(
-                    &fuel_node.to_string(),
-                    executor,
-                    start_block,
-                    killer.clone(),
-                    stop_idle_indexers,
-                ));
+            ExecutorSource::Registry(bytes) => bytes,
+        };
 
-                Ok((handle, exec_source, killer))
-            }
-        }
+        let executor = WasmIndexExecutor::new(
+            options.database_url.clone(),
+            options.pool_config,
+            manifest.to_owned(),
+            bytes.clone(),
+            options.wasm_limits,
+        )
+        .await?;
+        executor.db.lock().await.max_reorg_depth = options.max_reorg_depth;
+        executor.db.lock().await.notifier = Some(options.notifier.clone());
+        let (run, killer) = run_executor(
+            executor,
+            manifest,
+            start_block,
+            options.clone(),
+            pause_switch,
+            replay_slot,
+            handshake,
+        )?;
+        let handle = tokio::spawn(run);
+
+        Ok((handle, ExecutorSource::Registry(bytes), killer))
     }
 }
 
 #[async_trait]
 impl Executor for WasmIndexExecutor {
     /// Trigger a WASM event handler, passing in a serialized event struct.
-    async fn handle_events(&mut self, blocks: Vec<BlockData>) -> IndexerResult<()> {
+    async fn handle_events(&mut self, mut blocks: Vec<BlockData>) -> IndexerResult<()> {
+        if let Some(height) = self.db.lock().await.committed_through().await? {
+            warn!(
+                "Blocks through Block({height}) were already committed, skipping them."
+            );
+            blocks.retain(|block| block.height > height);
+            if blocks.is_empty() {
+                return Ok(());
+            }
+        }
+        if let Some(block) = blocks.first() {
+            self.db.lock().await.set_block_height(block.height);
+        }
         let bytes = serialize(&blocks);
+        self.refuel();
         let arg = ffi::WasmArg::new(&self.instance, bytes)?;
 
         let fun = self
@@ -508,11 +2002,33 @@ impl Executor for WasmIndexExecutor {
             .get_native_function::<(u32, u32), ()>(ffi::MODULE_ENTRYPOINT)?;
 
         self.db.lock().await.start_transaction().await?;
+        // Counted like in `NativeIndexExecutor::handle_events`.
+        if let Some(block) = blocks.last() {
+            self.db.lock().await.set_last_block_height(block.height);
+        }
+        self.db
+            .lock()
+            .await
+            .add_processed_blocks(blocks.len() as u64);
 
         let ptr = arg.get_ptr();
         let len = arg.get_len();
 
-        let res = spawn_blocking(move || fun.call(ptr, len)).await?;
+        // A host function panicking while the module runs surfaces as a failed
+        // join, and is handled like any other failed block.
+        let res = match spawn_blocking(move || fun.call(ptr, len)).await {
+            Ok(res) => res,
+            Err(e) if e.is_panic() => {
+                let e = panic_message(e.into_panic());
+                error!("WasmIndexExecutor handle_events panicked: {e}.");
+                self.db.lock().await.revert_transaction().await?;
+                return Err(IndexerError::HandlerPanic(e));
+            }
+            Err(e) => {
+                self.db.lock().await.revert_transaction().await?;
+                return Err(e.into());
+            }
+        };
 
         if let Err(e) = res {
             error!("WasmIndexExecutor handle_events failed: {}.", e.message());
@@ -526,11 +2042,654 @@ impl Executor for WasmIndexExecutor {
                 );
             }
 
+            let exceeded = self.exceeded_limit();
+            // Leave the module enough fuel to free its argument.
+            self.refuel();
+
             self.db.lock().await.revert_transaction().await?;
-            return Err(IndexerError::RuntimeError(e));
+            return Err(exceeded.unwrap_or(IndexerError::RuntimeError(e)));
         } else {
             self.db.lock().await.commit_transaction().await?;
         }
         Ok(())
     }
+
+    async fn record_failed_block(
+        &mut self,
+        block_height: u64,
+        strategy: &ErrorStrategy,
+        error: IndexerError,
+        block_data: Option<Vec<u8>>,
+    ) -> IndexerResult<()> {
+        self.db
+            .lock()
+            .await
+            .put_failed_block(block_height, strategy, error.to_string(), block_data)
+            .await?;
+        Ok(())
+    }
+
+    async fn revert_to_block(&mut self, block_height: u64) -> IndexerResult<()> {
+        self.db.lock().await.revert_to_block(block_height).await?;
+        Ok(())
+    }
+
+    async fn prune_object_writes(&mut self, block_height: u64) -> IndexerResult<()> {
+        self.db
+            .lock()
+            .await
+            .prune_object_writes(block_height)
+            .await?;
+        Ok(())
+    }
+
+    async fn set_replaying(&mut self, replaying: bool) {
+        self.db.lock().await.replaying = replaying;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_indexer_lib::defaults;
+    use fuel_indexer_types::{tx::ScriptExecutionResult, Bech32ContractId};
+
+    /// Fails any batch containing `failing_height`, committing nothing from it.
+    struct FailingExecutor {
+        failing_height: u64,
+        committed: Vec<u64>,
+        recorded: Vec<(u64, Option<Vec<u8>>)>,
+        calls: usize,
+    }
+
+    impl FailingExecutor {
+        fn new(failing_height: u64) -> Self {
+            Self {
+                failing_height,
+                committed: Vec::new(),
+                recorded: Vec::new(),
+                calls: 0,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Executor for FailingExecutor {
+        async fn handle_events(&mut self, blocks: Vec<BlockData>) -> IndexerResult<()> {
+            self.calls += 1;
+            if blocks.iter().any(|b| b.height == self.failing_height) {
+                return Err(IndexerError::NativeExecutionRuntimeError);
+            }
+            self.committed.extend(blocks.iter().map(|b| b.height));
+            Ok(())
+        }
+
+        async fn record_failed_block(
+            &mut self,
+            block_height: u64,
+            _strategy: &ErrorStrategy,
+            _error: IndexerError,
+            block_data: Option<Vec<u8>>,
+        ) -> IndexerResult<()> {
+            self.recorded.push((block_height, block_data));
+            Ok(())
+        }
+
+        async fn revert_to_block(&mut self, block_height: u64) -> IndexerResult<()> {
+            self.committed.retain(|height| *height < block_height);
+            Ok(())
+        }
+
+        async fn prune_object_writes(&mut self, _block_height: u64) -> IndexerResult<()> {
+            Ok(())
+        }
+
+        async fn set_replaying(&mut self, _replaying: bool) {}
+    }
+
+    fn blocks(heights: &[u64]) -> Vec<BlockData> {
+        heights
+            .iter()
+            .map(|height| BlockData {
+                height: *height,
+                id: Bytes32::default(),
+                producer: None,
+                time: 0,
+                transactions: vec![],
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_stop_deadline_starts_when_kill_switch_is_set() {
+        let kill_switch = Arc::new(AtomicBool::new(false));
+
+        let deadline = stop_deadline(kill_switch.clone(), Duration::from_millis(10));
+        assert!(tokio::time::timeout(Duration::from_millis(300), deadline)
+            .await
+            .is_err());
+
+        kill_switch.store(true, Ordering::SeqCst);
+        let deadline = stop_deadline(kill_switch, Duration::from_millis(10));
+        assert!(tokio::time::timeout(Duration::from_secs(1), deadline)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_fail_over_keeps_current_node_without_a_healthy_fallback() {
+        let node = |port: &str| FuelNodeConfig {
+            host: "127.0.0.1".to_string(),
+            port: port.to_string(),
+            fallbacks: Vec::new(),
+        };
+
+        let nodes = FuelNodes::new(vec![node("1")]);
+        assert!(!nodes.fail_over().await);
+
+        let nodes = FuelNodes::new(vec![node("1"), node("2"), node("3")]);
+        assert!(!nodes.fail_over().await);
+        assert_eq!(nodes.current.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_abort_strategy_retries_then_stops() {
+        let mut executor = FailingExecutor::new(2);
+        let strategy = ErrorStrategy::Abort;
+
+        let outcome =
+            handle_blocks(&mut executor, blocks(&[1, 2, 3]), &strategy, 0).await;
+        assert_eq!(outcome, BatchOutcome::Retry);
+
+        let outcome = handle_blocks(
+            &mut executor,
+            blocks(&[1, 2, 3]),
+            &strategy,
+            INDEX_FAILED_CALLS - 1,
+        )
+        .await;
+        assert_eq!(outcome, BatchOutcome::Stop);
+        assert!(executor.committed.is_empty());
+        assert!(executor.recorded.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_retry_strategy_stops_after_configured_retries() {
+        let mut executor = FailingExecutor::new(2);
+        let strategy = ErrorStrategy::Retry(2);
+
+        let mut retry_count = 0;
+        while handle_blocks(&mut executor, blocks(&[1, 2, 3]), &strategy, retry_count)
+            .await
+            == BatchOutcome::Retry
+        {
+            retry_count += 1;
+        }
+
+        assert_eq!(retry_count, 2);
+        assert_eq!(executor.calls, 3);
+        assert!(executor.committed.is_empty());
+        assert!(executor.recorded.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_skip_block_strategy_skips_only_failing_block() {
+        let mut executor = FailingExecutor::new(2);
+
+        let outcome = handle_blocks(
+            &mut executor,
+            blocks(&[1, 2, 3]),
+            &ErrorStrategy::SkipBlock,
+            0,
+        )
+        .await;
+
+        assert_eq!(outcome, BatchOutcome::Advance);
+        assert_eq!(executor.committed, vec![1, 3]);
+        assert_eq!(executor.recorded, vec![(2, None)]);
+    }
+
+    #[tokio::test]
+    async fn test_dead_letter_strategy_persists_failing_block_data() {
+        let mut executor = FailingExecutor::new(2);
+        let batch = blocks(&[1, 2, 3]);
+        let expected = serialize(&batch[1]);
+
+        let outcome =
+            handle_blocks(&mut executor, batch, &ErrorStrategy::DeadLetter, 0).await;
+
+        assert_eq!(outcome, BatchOutcome::Advance);
+        assert_eq!(executor.committed, vec![1, 3]);
+        assert_eq!(executor.recorded, vec![(2, Some(expected))]);
+    }
+
+    #[test]
+    fn test_error_strategy_parses_from_manifest() {
+        let manifest = r#"
+namespace: test_namespace
+identifier: test_index
+graphql_schema: schema.graphql
+module:
+  wasm: module.wasm
+error_strategy:
+  retry: 3
+"#;
+        let manifest = Manifest::from_str(manifest).unwrap();
+        assert_eq!(manifest.error_strategy, ErrorStrategy::Retry(3));
+
+        let manifest = Manifest::from_str(
+            "namespace: a\nidentifier: b\ngraphql_schema: c\nmodule: native\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.error_strategy, ErrorStrategy::Abort);
+
+        let manifest = Manifest::from_str(
+            "namespace: a\nidentifier: b\ngraphql_schema: c\nmodule: native\non_error: skip\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.error_strategy, ErrorStrategy::SkipBlock);
+    }
+
+    #[test]
+    fn test_block_window_and_commit_mode_parse_from_manifest() {
+        let manifest = Manifest::from_str(
+            "namespace: a\nidentifier: b\ngraphql_schema: c\nmodule: native\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.block_window(), defaults::BLOCK_WINDOW);
+        assert_eq!(manifest.commit_mode, CommitMode::Batch);
+
+        let manifest = Manifest::from_str(
+            "namespace: a\nidentifier: b\ngraphql_schema: c\nmodule: native\nblock_window: 100\ncommit_mode: block\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.block_window(), 100);
+        assert_eq!(manifest.commit_mode, CommitMode::Block);
+
+        // A window must hold at least one block.
+        let manifest = Manifest::from_str(
+            "namespace: a\nidentifier: b\ngraphql_schema: c\nmodule: native\nblock_window: 0\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.block_window(), 1);
+    }
+
+    #[test]
+    fn test_poll_interval_parses_from_manifest() {
+        let manifest = Manifest::from_str(
+            "namespace: a\nidentifier: b\ngraphql_schema: c\nmodule: native\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.poll_interval_ms(), defaults::POLL_INTERVAL_MS);
+
+        let manifest = Manifest::from_str(
+            "namespace: a\nidentifier: b\ngraphql_schema: c\nmodule: native\npoll_interval_ms: 250\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.poll_interval_ms(), 250);
+    }
+
+    #[test]
+    fn test_enabled_handlers_parse_from_manifest() {
+        let handlers = vec!["one".to_string(), "two".to_string()];
+
+        let manifest = Manifest::from_str(
+            "namespace: a\nidentifier: b\ngraphql_schema: c\nmodule: native\n",
+        )
+        .unwrap();
+        assert!(manifest.handler_enabled("one"));
+        assert!(manifest.unknown_handlers(&handlers).is_empty());
+
+        let manifest = Manifest::from_str(
+            "namespace: a\nidentifier: b\ngraphql_schema: c\nmodule: native\nenabled_handlers: [two, three]\n",
+        )
+        .unwrap();
+        assert!(!manifest.handler_enabled("one"));
+        assert!(manifest.handler_enabled("two"));
+        assert_eq!(
+            manifest.unknown_handlers(&handlers),
+            vec!["three".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_backoff_grows_until_attempts_run_out_and_resets() {
+        let base_delay = Duration::from_millis(100);
+        let mut backoff = Backoff::new(4, base_delay);
+
+        for attempt in 0..3 {
+            let delay = backoff.next_delay().unwrap();
+            assert!(delay <= base_delay * (1 << attempt));
+        }
+        assert_eq!(backoff.next_delay(), None);
+
+        backoff.reset();
+        assert!(backoff.next_delay().unwrap() <= base_delay);
+    }
+
+    #[test]
+    fn test_backoff_delay_is_capped() {
+        let mut backoff = Backoff::new(usize::MAX, Duration::from_secs(1));
+        for _ in 0..100 {
+            let delay = backoff.next_delay().unwrap();
+            assert!(delay <= Duration::from_secs(MAX_NODE_RETRY_DELAY_SECS));
+        }
+    }
+
+    #[test]
+    fn test_panic_message_describes_panic_payloads() {
+        let payload = std::panic::catch_unwind(|| panic!("bad block {}", 7)).unwrap_err();
+        assert_eq!(panic_message(payload), "bad block 7");
+
+        let payload = std::panic::catch_unwind(|| panic!("bad block")).unwrap_err();
+        assert_eq!(panic_message(payload), "bad block");
+    }
+
+    #[test]
+    fn test_truncate_to_end_block_drops_blocks_past_end_block() {
+        let mut page = blocks(&[4, 5, 6]);
+        assert!(!truncate_to_end_block(&mut page, Some(7)));
+        assert_eq!(page.len(), 3);
+
+        let mut page = blocks(&[4, 5, 6]);
+        assert!(truncate_to_end_block(&mut page, Some(6)));
+        assert_eq!(page.len(), 3);
+
+        let mut page = blocks(&[4, 5, 6]);
+        assert!(truncate_to_end_block(&mut page, Some(4)));
+        assert_eq!(page.iter().map(|b| b.height).collect::<Vec<_>>(), vec![4]);
+
+        let mut page = blocks(&[4, 5, 6]);
+        assert!(!truncate_to_end_block(&mut page, None));
+        assert_eq!(page.len(), 3);
+    }
+
+    #[test]
+    fn test_contract_id_parses_one_or_many_from_manifest() {
+        let ids = [[1u8; 32], [2u8; 32]].map(ContractId::from);
+        let bech32 = ids.map(|id| Bech32ContractId::from(id).to_string());
+        let manifest = |contract_id: &str| {
+            Manifest::from_str(&format!(
+                "namespace: a\nidentifier: b\ngraphql_schema: c\nmodule: native\n{contract_id}"
+            ))
+            .unwrap()
+        };
+
+        assert_eq!(manifest("").contract_ids().unwrap(), None);
+        assert_eq!(
+            manifest("contract_id: \"\"\n").contract_ids().unwrap(),
+            None
+        );
+        assert_eq!(
+            manifest(&format!("contract_id: {}\n", bech32[0]))
+                .contract_ids()
+                .unwrap(),
+            Some(HashSet::from([ids[0]]))
+        );
+        assert_eq!(
+            manifest(&format!("contract_id: [{}, {}]\n", bech32[0], bech32[1]))
+                .contract_ids()
+                .unwrap(),
+            Some(HashSet::from(ids))
+        );
+        assert!(manifest("contract_id: not-a-contract\n")
+            .contract_ids()
+            .is_err());
+    }
+
+    #[test]
+    fn test_retain_contract_receipts_drops_other_contracts() {
+        let (ours, theirs) = (ContractId::from([1u8; 32]), ContractId::from([2u8; 32]));
+        let log = |id| Receipt::log(id, 0, 0, 0, 0, 0, 0);
+        let call = |id, to| Receipt::call(id, to, 0, Default::default(), 0, 0, 0, 0, 0);
+
+        let mut page = blocks(&[1]);
+        page[0].transactions.push(TransactionData {
+            transaction: Default::default(),
+            status: Default::default(),
+            receipts: vec![
+                call(ContractId::default(), ours),
+                call(ours, theirs),
+                log(ours),
+                log(theirs),
+                Receipt::script_result(ScriptExecutionResult::Success, 0),
+            ],
+            id: TxId::default(),
+        });
+
+        retain_contract_receipts(&mut page, &HashSet::from([ours]));
+        assert_eq!(
+            page[0].transactions[0].receipts,
+            vec![
+                call(ContractId::default(), ours),
+                log(ours),
+                Receipt::script_result(ScriptExecutionResult::Success, 0),
+            ]
+        );
+    }
+
+    fn block_ids(heights: &[u64]) -> Vec<(u64, Bytes32)> {
+        heights
+            .iter()
+            .map(|height| (*height, Bytes32::from([*height as u8; 32])))
+            .collect()
+    }
+
+    #[test]
+    fn test_block_history_keeps_max_depth_blocks() {
+        let mut history = BlockHistory::new(5);
+        assert_eq!(history.push(&block_ids(&[1, 2, 3])), None);
+        assert_eq!(history.push(&block_ids(&[4, 5])), None);
+
+        // Block 1 is dropped, but block 2 is still kept, so the batch starting
+        // at block 1 can still be reverted.
+        assert_eq!(history.push(&block_ids(&[6])), Some(1));
+        assert_eq!(history.push(&block_ids(&[7, 8])), Some(4));
+        assert_eq!(history.last(), Some((8, Bytes32::from([8; 32]))));
+        assert_eq!(history.newest_first().count(), 5);
+
+        let mut disabled = BlockHistory::new(0);
+        assert_eq!(disabled.push(&block_ids(&[1, 2, 3])), None);
+        assert_eq!(disabled.last(), None);
+    }
+
+    #[test]
+    fn test_block_history_rewinds_to_start_of_orphaned_batch() {
+        let mut history = BlockHistory::new(10);
+        history.push(&block_ids(&[1, 2, 3]));
+        history.push(&block_ids(&[4, 5, 6]));
+        history.push(&block_ids(&[7, 8]));
+
+        assert_eq!(history.rewind(5), 4);
+        assert_eq!(history.last(), Some((3, Bytes32::from([3; 32]))));
+
+        assert_eq!(history.rewind(1), 1);
+        assert_eq!(history.last(), None);
+    }
+
+    fn heights(batches: &[Vec<BlockData>]) -> Vec<Vec<u64>> {
+        batches
+            .iter()
+            .map(|batch| batch.iter().map(|b| b.height).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_pending_blocks_commit_full_windows_while_catching_up() {
+        let mut pending = PendingBlocks::new(CommitWindow {
+            max_blocks: Some(5),
+            max_age: None,
+        });
+
+        assert!(pending.take_batches(blocks(&[1, 2, 3]), true).is_empty());
+        assert_eq!(
+            heights(&pending.take_batches(blocks(&[4, 5, 6]), true)),
+            vec![vec![1, 2, 3, 4, 5, 6]]
+        );
+        assert!(pending.is_empty());
+
+        // Once caught up, every block is committed on its own.
+        assert!(pending.take_batches(blocks(&[7, 8]), true).is_empty());
+        assert_eq!(
+            heights(&pending.take_batches(blocks(&[9]), false)),
+            vec![vec![7], vec![8], vec![9]]
+        );
+
+        pending.take_batches(blocks(&[10]), true);
+        pending.clear();
+        assert!(pending.take_batches(vec![], false).is_empty());
+    }
+
+    #[test]
+    fn test_pending_blocks_commit_windows_past_max_age() {
+        let mut pending = PendingBlocks::new(CommitWindow {
+            max_blocks: None,
+            max_age: Some(Duration::ZERO),
+        });
+        assert_eq!(
+            heights(&pending.take_batches(blocks(&[1, 2]), true)),
+            vec![vec![1, 2]]
+        );
+
+        let config = IndexerConfig::default();
+        assert_eq!(CommitWindow::from_config(&config), None);
+
+        let config = IndexerConfig {
+            commit_window_blocks: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(
+            CommitWindow::from_config(&config),
+            Some(CommitWindow {
+                max_blocks: Some(1),
+                max_age: None,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_scheduler_hands_out_slots_in_turn() {
+        let scheduler = BatchScheduler::new(1);
+        let slot = scheduler.acquire().await;
+        assert!(slot.is_some());
+
+        let (handled, mut order) = mpsc::unbounded_channel();
+        let mut tasks = Vec::new();
+        for indexer in [1, 2] {
+            let (scheduler, handled) = (scheduler.clone(), handled.clone());
+            tasks.push(tokio::spawn(async move {
+                let _slot = scheduler.acquire().await;
+                handled.send(indexer).unwrap();
+            }));
+            tokio::task::yield_now().await;
+        }
+
+        // Both wait for the held slot, and get it in the order they asked.
+        assert!(order.try_recv().is_err());
+        drop(slot);
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(order.recv().await, Some(1));
+        assert_eq!(order.recv().await, Some(2));
+
+        // Without a limit, nothing waits.
+        assert!(BatchScheduler::new(0).acquire().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reload_handshake_waits_to_be_let_through() {
+        let (handshake, ready, proceed) = ReloadHandshake::new();
+        let turn = tokio::spawn(handshake.wait_for_turn());
+
+        // The executor is up, but holds off until it is let through.
+        ready.await.unwrap();
+        tokio::task::yield_now().await;
+        assert!(!turn.is_finished());
+        proceed.send(()).unwrap();
+        assert!(turn.await.unwrap());
+
+        // It doesn't go on if the reload is called off.
+        let (handshake, ready, proceed) = ReloadHandshake::new();
+        drop(proceed);
+        let turn = tokio::spawn(handshake.wait_for_turn());
+        ready.await.unwrap();
+        assert!(!turn.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_block_queue_reads_ahead_up_to_capacity() {
+        let (sender, receiver) = mpsc::channel(2);
+        let (sent, mut sent_rx) = mpsc::unbounded_channel();
+        let task = tokio::spawn(async move {
+            for block in blocks(&[1, 2, 3, 4]) {
+                let height = block.height;
+                sender.send(QueuedBlock::Block(block)).await.unwrap();
+                sent.send(height).unwrap();
+            }
+            let page_end = QueuedBlock::PageEnd {
+                cursor: Some("4".to_string()),
+                has_next_page: false,
+                range_complete: false,
+            };
+            sender.send(page_end).await.unwrap();
+        });
+        let mut queue = BlockQueue { receiver, task };
+
+        // Fetching pauses once the queue is full.
+        assert_eq!(sent_rx.recv().await, Some(1));
+        assert_eq!(sent_rx.recv().await, Some(2));
+        sleep(Duration::from_millis(50)).await;
+        assert!(sent_rx.try_recv().is_err());
+
+        // Pages larger than the queue are still taken whole.
+        let page = queue.next_page().await.unwrap();
+        assert_eq!(heights(&[page.blocks]), vec![vec![1, 2, 3, 4]]);
+        assert_eq!(page.cursor, Some("4".to_string()));
+        assert!(queue.next_page().await.is_none());
+    }
+
+    #[test]
+    fn test_wasm_limits_stop_runaway_modules() {
+        let limits = WasmLimits {
+            memory_mb: Some(1),
+            fuel: Some(1_000),
+        };
+        let store = limits.store();
+
+        let module = Module::new(
+            &store,
+            r#"
+            (module
+              (memory (export "memory") 1)
+              (func (export "spin") (loop (br 0)))
+              (func (export "grow") (param i32) (result i32)
+                (memory.grow (local.get 0))))
+            "#,
+        )
+        .unwrap();
+        let instance = Instance::new(&module, &imports! {}).unwrap();
+
+        let spin = instance
+            .exports
+            .get_native_function::<(), ()>("spin")
+            .unwrap();
+        assert!(spin.call().is_err());
+        assert!(matches!(
+            get_remaining_points(&instance),
+            MeteringPoints::Exhausted
+        ));
+
+        set_remaining_points(&instance, 1_000);
+        let grow = instance
+            .exports
+            .get_native_function::<u32, i32>("grow")
+            .unwrap();
+        // 1 MB is 16 pages, one of which the module starts with.
+        assert_eq!(grow.call(15).unwrap(), 1);
+        assert_eq!(grow.call(1).unwrap(), -1);
+
+        let module = Module::new(&store, "(module (memory 17))").unwrap();
+        assert!(Instance::new(&module, &imports! {}).is_err());
+    }
 }
@@ -1,4 +1,4 @@
-use fuel_indexer_schema::FtColumn;
+use fuel_indexer_schema::{utils::serialize, FtColumn};
 use fuel_indexer_types::ffi::{
     LOG_LEVEL_DEBUG, LOG_LEVEL_ERROR, LOG_LEVEL_INFO, LOG_LEVEL_TRACE, LOG_LEVEL_WARN,
 };
@@ -86,6 +86,44 @@ pub(crate) fn get_version(instance: &Instance) -> Result<String, FFIError> {
     Ok(version)
 }
 
+/// Names of the handlers that the module dispatches to.
+pub(crate) fn get_handlers(instance: &Instance) -> Result<Vec<String>, FFIError> {
+    let exports = &instance.exports;
+    let memory = exports.get_memory("memory")?;
+
+    let ptr = exports.get_function("get_handlers_ptr")?.call(&[])?[0]
+        .i32()
+        .ok_or_else(|| FFIError::None("get_handlers".to_string()))? as u32;
+
+    let len = exports.get_function("get_handlers_len")?.call(&[])?[0]
+        .i32()
+        .ok_or_else(|| FFIError::None("get_handlers".to_string()))? as u32;
+
+    let handlers = get_string(memory, ptr, len)?;
+
+    Ok(handlers
+        .split(',')
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Restrict the module to dispatching to the given handlers.
+#[allow(clippy::result_large_err)]
+pub(crate) fn set_enabled_handlers(
+    instance: &Instance,
+    handlers: &[String],
+) -> IndexerResult<()> {
+    let arg = WasmArg::new(instance, serialize(&handlers))?;
+
+    instance
+        .exports
+        .get_native_function::<(u32, u32), ()>("set_enabled_handlers")?
+        .call(arg.get_ptr(), arg.get_len())?;
+
+    Ok(())
+}
+
 fn get_string(mem: &Memory, ptr: u32, len: u32) -> Result<String, FFIError> {
     let result = WasmPtr::<u8, wasmer::Array>::new(ptr)
         .get_utf8_string(mem, len)
@@ -114,6 +152,14 @@ fn log_data(env: &IndexEnv, ptr: u32, len: u32, log_level: u32) {
     }
 }
 
+fn metric_inc(env: &IndexEnv, ptr: u32, len: u32, value: u64) {
+    let mem = env.memory_ref().expect("Memory uninitialized.");
+    let name = get_string(mem, ptr, len).expect("Metric name could not be fetched.");
+
+    let rt = tokio::runtime::Runtime::new().expect("Could not create tokio runtime.");
+    rt.block_on(async { env.db.lock().await.metric_inc(&name, value) });
+}
+
 fn get_object(env: &IndexEnv, type_id: i64, ptr: u32, len_ptr: u32) -> u32 {
     let mem = env.memory_ref().expect("Memory uninitialized.");
 
@@ -145,7 +191,155 @@ fn get_object(env: &IndexEnv, type_id: i64, ptr: u32, len_ptr: u32) -> u32 {
     }
 }
 
-fn put_object(env: &IndexEnv, type_id: i64, ptr: u32, len: u32) {
+fn get_object_by(
+    env: &IndexEnv,
+    type_id: i64,
+    ptr: u32,
+    len: u32,
+    len_ptr: u32,
+) -> Result<u32, RuntimeError> {
+    let mem = env.memory_ref().expect("Memory uninitialized.");
+
+    let mut bytes = Vec::with_capacity(len as usize);
+    let range = ptr as usize..ptr as usize + len as usize;
+
+    unsafe {
+        bytes.extend_from_slice(&mem.data_unchecked()[range]);
+    }
+
+    let (column, value): (String, FtColumn) =
+        bincode::deserialize(&bytes).expect("Serde error.");
+
+    let rt = tokio::runtime::Runtime::new().expect("Could not create tokio runtime.");
+    let bytes = rt
+        .block_on(async {
+            env.db
+                .lock()
+                .await
+                .get_object_by(type_id, &column, value)
+                .await
+        })
+        .map_err(|e| RuntimeError::new(e.to_string()))?;
+
+    match bytes {
+        Some(bytes) => {
+            let alloc_fn = env.alloc_ref().expect("Alloc export is missing.");
+
+            let size = bytes.len() as u32;
+            let result = alloc_fn.call(size).expect("Alloc failed.");
+            let range = result as usize..result as usize + size as usize;
+
+            WasmPtr::<u32>::new(len_ptr)
+                .deref(mem)
+                .expect("Failed to deref WasmPtr.")
+                .set(size);
+
+            unsafe {
+                mem.data_unchecked_mut()[range].copy_from_slice(&bytes);
+            }
+
+            Ok(result)
+        }
+        None => Ok(0),
+    }
+}
+
+fn get_object_by_key(
+    env: &IndexEnv,
+    type_id: i64,
+    ptr: u32,
+    len: u32,
+    len_ptr: u32,
+) -> Result<u32, RuntimeError> {
+    let mem = env.memory_ref().expect("Memory uninitialized.");
+
+    let mut bytes = Vec::with_capacity(len as usize);
+    let range = ptr as usize..ptr as usize + len as usize;
+
+    unsafe {
+        bytes.extend_from_slice(&mem.data_unchecked()[range]);
+    }
+
+    let key: Vec<FtColumn> = bincode::deserialize(&bytes).expect("Serde error.");
+
+    let rt = tokio::runtime::Runtime::new().expect("Could not create tokio runtime.");
+    let bytes = rt
+        .block_on(async { env.db.lock().await.get_object_by_key(type_id, key).await })
+        .map_err(|e| RuntimeError::new(e.to_string()))?;
+
+    match bytes {
+        Some(bytes) => {
+            let alloc_fn = env.alloc_ref().expect("Alloc export is missing.");
+
+            let size = bytes.len() as u32;
+            let result = alloc_fn.call(size).expect("Alloc failed.");
+            let range = result as usize..result as usize + size as usize;
+
+            WasmPtr::<u32>::new(len_ptr)
+                .deref(mem)
+                .expect("Failed to deref WasmPtr.")
+                .set(size);
+
+            unsafe {
+                mem.data_unchecked_mut()[range].copy_from_slice(&bytes);
+            }
+
+            Ok(result)
+        }
+        None => Ok(0),
+    }
+}
+
+fn find_ids(
+    env: &IndexEnv,
+    type_id: i64,
+    ptr: u32,
+    len: u32,
+    len_ptr: u32,
+) -> Result<u32, RuntimeError> {
+    let mem = env.memory_ref().expect("Memory uninitialized.");
+
+    let mut bytes = Vec::with_capacity(len as usize);
+    let range = ptr as usize..ptr as usize + len as usize;
+
+    unsafe {
+        bytes.extend_from_slice(&mem.data_unchecked()[range]);
+    }
+
+    let (column, value): (String, FtColumn) =
+        bincode::deserialize(&bytes).expect("Serde error.");
+
+    let rt = tokio::runtime::Runtime::new().expect("Could not create tokio runtime.");
+    let ids = rt
+        .block_on(async { env.db.lock().await.find_ids(type_id, &column, value).await })
+        .map_err(|e| RuntimeError::new(e.to_string()))?;
+
+    let bytes = bincode::serialize(&ids).expect("Serde error.");
+
+    let alloc_fn = env.alloc_ref().expect("Alloc export is missing.");
+
+    let size = bytes.len() as u32;
+    let result = alloc_fn.call(size).expect("Alloc failed.");
+    let range = result as usize..result as usize + size as usize;
+
+    WasmPtr::<u32>::new(len_ptr)
+        .deref(mem)
+        .expect("Failed to deref WasmPtr.")
+        .set(size);
+
+    unsafe {
+        mem.data_unchecked_mut()[range].copy_from_slice(&bytes);
+    }
+
+    Ok(result)
+}
+
+fn put_object(
+    env: &IndexEnv,
+    type_id: i64,
+    ptr: u32,
+    len: u32,
+) -> Result<(), RuntimeError> {
     let mem = env.memory_ref().expect("Memory uninitialized.");
 
     let mut bytes = Vec::with_capacity(len as usize);
@@ -165,14 +359,36 @@ fn put_object(env: &IndexEnv, type_id: i64, ptr: u32, len: u32) {
             .await
             .put_object(type_id, columns, bytes)
             .await
-    });
+    })
+    .map_err(|e| RuntimeError::new(e.to_string()))
+}
+
+fn delete_object(env: &IndexEnv, type_id: i64, ptr: u32, len: u32) {
+    let mem = env.memory_ref().expect("Memory uninitialized.");
+
+    let mut bytes = Vec::with_capacity(len as usize);
+    let range = ptr as usize..ptr as usize + len as usize;
+
+    unsafe {
+        bytes.extend_from_slice(&mem.data_unchecked()[range]);
+    }
+
+    let columns: Vec<FtColumn> = bincode::deserialize(&bytes).expect("Serde error.");
+
+    let rt = tokio::runtime::Runtime::new().expect("Could not create tokio runtime.");
+    rt.block_on(async { env.db.lock().await.delete_object(type_id, columns).await });
 }
 
 pub fn get_exports(env: &IndexEnv, store: &Store) -> Exports {
     let mut exports = Exports::new();
     declare_export!(get_object, exports, store, env);
+    declare_export!(get_object_by, exports, store, env);
+    declare_export!(get_object_by_key, exports, store, env);
     declare_export!(put_object, exports, store, env);
+    declare_export!(delete_object, exports, store, env);
+    declare_export!(find_ids, exports, store, env);
     declare_export!(log_data, exports, store, env);
+    declare_export!(metric_inc, exports, store, env);
     exports
 }
 
@@ -6,13 +6,18 @@ mod database;
 pub mod executor;
 pub mod ffi;
 mod service;
+pub mod snapshot;
+pub mod testing;
+mod webhook;
 
 pub use database::Database;
-pub use executor::{Executor, IndexEnv, NativeIndexExecutor, WasmIndexExecutor};
+pub use executor::{
+    Executor, IndexEnv, NativeIndexExecutor, WasmIndexExecutor, WasmLimits,
+};
 pub use fuel_indexer_database::IndexerDatabaseError;
 pub use fuel_indexer_lib::{
     config::IndexerConfig,
-    manifest::{Manifest, ManifestError, Module},
+    manifest::{ErrorStrategy, Manifest, ManifestError, Module},
 };
 pub use fuel_indexer_schema::{db::IndexerSchemaError, FtColumn};
 pub use service::IndexerService;
@@ -68,14 +73,44 @@ pub enum IndexerError {
     InvalidPortNumber(#[from] core::num::ParseIntError),
     #[error("No transaction is open.")]
     NoTransactionError,
+    #[error("TypeId({0}) not found in schema.")]
+    UnknownTypeId(i64),
+    #[error("Column {0} is not indexed.")]
+    ColumnNotIndexed(String),
+    #[error("Column {0} not found in schema.")]
+    UnknownColumn(String),
+    #[error("Column {0} can't be matched against {1:?}.")]
+    UnsupportedColumnValue(String, FtColumn),
+    #[error("Entity {0} has no key of {1} columns.")]
+    InvalidKey(String, usize),
+    #[error("Unique constraint of {0} violated: {1}")]
+    UniqueViolation(String, String),
+    #[error("Column {1} of {0} is non-null, but was saved as null.")]
+    NullRequiredColumn(String, String),
     #[error("Unknown error")]
     Unknown,
     #[error("Indexer schema error: {0:?}")]
     SchemaError(#[from] IndexerSchemaError),
     #[error("Manifest error: {0:?}")]
     ManifestError(#[from] ManifestError),
+    #[error("Invalid manifest: {0}")]
+    InvalidManifest(String),
+    #[error("Invalid WASM module: {0}")]
+    InvalidModule(String),
     #[error("Error creating native executor.")]
     NativeExecutionInstantiationError,
     #[error("Native execution runtime error.")]
     NativeExecutionRuntimeError,
+    #[error("No handler is registered for NativeIndex({0}).")]
+    NativeHandlerNotFound(String),
+    #[error("Indexer({0}) is already registered; replace it to register it again.")]
+    IndexerAlreadyRegistered(String),
+    #[error("Handler panicked: {0}")]
+    HandlerPanic(String),
+    #[error("WASM module ran out of fuel after {0} instructions.")]
+    WasmFuelExhausted(u64),
+    #[error("WASM module exceeded its memory limit of {0} MB.")]
+    WasmMemoryLimitExceeded(u64),
+    #[error("Snapshot error: {0}")]
+    SnapshotError(String),
 }
@@ -1,36 +1,56 @@
 use crate::{
-    executor::{ExecutorSource, NativeIndexExecutor, WasmIndexExecutor},
-    Database, IndexerConfig, IndexerError, IndexerResult, Manifest,
+    executor::{
+        ExecutorOptions, ExecutorSource, NativeHandler, NativeIndexExecutor,
+        ReloadHandshake, ReplaySlot, WasmIndexExecutor,
+    },
+    Database, IndexerConfig, IndexerError, IndexerResult, Manifest, Module,
 };
 use async_std::sync::{Arc, Mutex};
 use fuel_indexer_database::{
     queries, types::IndexAssetType, IndexerConnection, IndexerConnectionPool,
 };
-use fuel_indexer_lib::{defaults, utils::ServiceRequest};
-use fuel_indexer_schema::db::manager::SchemaManager;
+use fuel_indexer_lib::{
+    defaults,
+    utils::{AssetReloadRequest, IndexReplayRequest, IndexRevertRequest, ServiceRequest},
+};
+use fuel_indexer_schema::{db::manager::SchemaManager, utils::schema_type_errors};
 use fuel_indexer_types::abi::BlockData;
 use futures::{
+    future::BoxFuture,
     stream::{FuturesUnordered, StreamExt},
-    Future,
+    Future, FutureExt,
 };
 use std::collections::HashMap;
 use std::marker::Send;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::{
-    sync::mpsc::Receiver,
+    sync::{mpsc::Receiver, oneshot},
     task::JoinHandle,
     time::{sleep, Duration},
 };
-use tracing::{debug, error, info, warn};
+use tracing::{error, info, info_span, warn, Instrument, Span};
+
+/// The task of a started executor, with its kill switch, pause switch and
+/// replay slot.
+type StartedExecutor = (JoinHandle<()>, Arc<AtomicBool>, Arc<AtomicBool>, ReplaySlot);
 
 pub struct IndexerService {
     config: IndexerConfig,
     pool: IndexerConnectionPool,
     manager: SchemaManager,
-    database_url: String,
+    /// Settings shared by the executors of every indexer.
+    options: ExecutorOptions,
     handles: HashMap<String, JoinHandle<()>>,
     rx: Option<Receiver<ServiceRequest>>,
     killers: HashMap<String, Arc<AtomicBool>>,
+    /// Pause switch of each indexer. Executors started by a reload or revert
+    /// share the switch of the executor they replace, so a paused indexer
+    /// stays paused until it is resumed.
+    pausers: HashMap<String, Arc<AtomicBool>>,
+    /// Replay slot of each indexer, shared like its pause switch.
+    replayers: HashMap<String, ReplaySlot>,
+    alive: HashMap<String, Arc<AtomicBool>>,
+    native_handlers: HashMap<String, NativeHandler>,
 }
 
 impl IndexerService {
@@ -39,26 +59,110 @@ impl IndexerService {
         pool: IndexerConnectionPool,
         rx: Option<Receiver<ServiceRequest>>,
     ) -> IndexerResult<IndexerService> {
-        let database_url = config.database.to_string();
-
         let manager = SchemaManager::new(pool.clone());
+        let options = ExecutorOptions::from_config(&config);
 
         Ok(IndexerService {
             config,
             pool,
             manager,
-            database_url,
+            options,
             handles: HashMap::default(),
             killers: HashMap::default(),
+            pausers: HashMap::default(),
+            replayers: HashMap::default(),
+            alive: HashMap::default(),
+            native_handlers: HashMap::default(),
             rx,
         })
     }
 
+    /// Register and start the indexer of `manifest`.
+    ///
+    /// Fails if an indexer of the same namespace and identifier is running in
+    /// this service, or is in the registry with other assets, since both
+    /// would write to the same tables; `replace_index_from_manifest` replaces
+    /// it instead. An indexer registered with the same assets is restarted.
     pub async fn register_index_from_manifest(
         &mut self,
         manifest: Manifest,
     ) -> IndexerResult<()> {
-        let database_url = self.database_url.clone();
+        let uid = manifest.uid();
+        let running = self
+            .alive
+            .get(&uid)
+            .map_or(false, |alive| alive.load(Ordering::SeqCst));
+        if running {
+            return Err(IndexerError::IndexerAlreadyRegistered(uid));
+        }
+
+        let registered = {
+            let mut conn = self.pool.acquire().await?;
+            queries::index_is_registered(
+                &mut conn,
+                &manifest.namespace,
+                &manifest.identifier,
+            )
+            .await?
+        };
+        if let Some(index) = registered {
+            if !self.has_latest_assets(index.id, &manifest).await? {
+                return Err(IndexerError::IndexerAlreadyRegistered(uid));
+            }
+        }
+
+        self.start_index_from_manifest(manifest).await
+    }
+
+    /// Whether the latest assets registered to the indexer `index_id` are the
+    /// manifest, schema and module of `manifest`.
+    async fn has_latest_assets(
+        &self,
+        index_id: i64,
+        manifest: &Manifest,
+    ) -> IndexerResult<bool> {
+        let mut conn = self.pool.acquire().await?;
+        let assets = match queries::latest_assets_for_index(&mut conn, &index_id).await {
+            Ok(assets) => assets,
+            Err(sqlx::Error::RowNotFound) => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(assets.manifest.bytes == manifest.to_bytes()?
+            && assets.schema.bytes == manifest.graphql_schema()?.into_bytes()
+            && (manifest.is_native() || assets.wasm.bytes == manifest.module_bytes()?))
+    }
+
+    /// Register and start the indexer of `manifest`, replacing an indexer of
+    /// the same namespace and identifier. If this service is running that
+    /// indexer, it is stopped after its current block before the new one starts.
+    pub async fn replace_index_from_manifest(
+        &mut self,
+        manifest: Manifest,
+    ) -> IndexerResult<()> {
+        let uid = manifest.uid();
+        if let Some(killer) = self.killers.remove(&uid) {
+            info!(
+                uid = %uid,
+                "Stopping Indexer({uid}) after its current block, or in {}s at the latest, to replace it.",
+                self.options.stop_drain_timeout_secs
+            );
+            killer.store(true, Ordering::SeqCst);
+        }
+        if let Some(handle) = self.handles.remove(&uid) {
+            let _ = handle.await;
+        }
+
+        self.start_index_from_manifest(manifest).await
+    }
+
+    async fn start_index_from_manifest(
+        &mut self,
+        manifest: Manifest,
+    ) -> IndexerResult<()> {
+        let schema = manifest.graphql_schema()?;
+        validate_manifest(&manifest, &schema)?;
+
         let mut conn = self.pool.acquire().await?;
         let index = queries::register_index(
             &mut conn,
@@ -68,7 +172,6 @@ impl IndexerService {
         )
         .await?;
 
-        let schema = manifest.graphql_schema()?;
         let schema_bytes = schema.as_bytes().to_vec();
 
         self.manager
@@ -82,13 +185,16 @@ impl IndexerService {
 
         let mut conn = self.pool.acquire().await?;
         let start_block = get_start_block(&mut conn, &manifest).await?;
+        let pauser = Arc::new(AtomicBool::new(false));
+        let replayer = ReplaySlot::default();
         let (handle, exec_source, killer) = WasmIndexExecutor::create(
-            &self.config.fuel_node.clone(),
-            &database_url,
+            &self.options,
             &manifest,
             ExecutorSource::Manifest,
-            self.config.stop_idle_indexers,
-            &start_block,
+            start_block,
+            None,
+            pauser.clone(),
+            replayer.clone(),
         )
         .await?;
 
@@ -100,6 +206,7 @@ impl IndexerService {
 
         while let Some((asset_type, bytes)) = items.pop() {
             info!(
+                uid = %index.uid(),
                 "Registering Asset({:?}) for Index({})",
                 asset_type,
                 index.uid()
@@ -118,39 +225,113 @@ impl IndexerService {
             }
         }
 
-        info!("Registered Index({})", &manifest.uid());
-        self.handles.insert(manifest.uid(), handle);
-        self.killers.insert(manifest.uid(), killer);
+        info!(uid = %manifest.uid(), "Registered Index({})", &manifest.uid());
+        self.track_executor(manifest.uid(), handle, killer, pauser, replayer);
 
         Ok(())
     }
 
+    /// Start an executor for each indexer in the registry, setting up at most
+    /// `registry_startup_concurrency` of them at once.
+    ///
+    /// An indexer that fails to start is logged and skipped, so that it does
+    /// not keep the others from starting.
     pub async fn register_indices_from_registry(&mut self) -> IndexerResult<()> {
-        let mut conn = self.pool.acquire().await?;
-        let indices = queries::registered_indices(&mut conn).await?;
-        for index in indices {
-            let assets = queries::latest_assets_for_index(&mut conn, &index.id).await?;
-            let manifest = Manifest::from_slice(&assets.manifest.bytes)?;
-
-            let start_block = get_start_block(&mut conn, &manifest).await.unwrap_or(1);
-            let (handle, _module_bytes, killer) = WasmIndexExecutor::create(
-                &self.config.fuel_node,
-                &self.config.database.to_string(),
-                &manifest,
-                ExecutorSource::Registry(assets.wasm.bytes),
-                self.config.stop_idle_indexers,
-                &start_block,
-            )
-            .await?;
+        let indices = {
+            let mut conn = self.pool.acquire().await?;
+            queries::registered_indices(&mut conn).await?
+        };
 
-            info!("Registered Index({})", manifest.uid());
-            self.handles.insert(manifest.uid(), handle);
-            self.killers.insert(manifest.uid(), killer);
+        let service = &*self;
+        let concurrency = service.config.registry_startup_concurrency.max(1);
+        let mut executors = futures::stream::iter(indices.into_iter().enumerate())
+            .map(|(position, index)| async move {
+                let uid = index.uid();
+                (
+                    position,
+                    uid,
+                    service.create_registry_executor(index.id).await,
+                )
+            })
+            .buffer_unordered(concurrency)
+            .collect::<Vec<_>>()
+            .await;
+
+        // Track executors in registry order, whatever order they started in.
+        executors.sort_by_key(|(position, ..)| *position);
+
+        for (_, uid, result) in executors {
+            match result {
+                Ok((handle, killer, pauser, replayer)) => {
+                    info!(uid = %uid, "Registered Index({uid})");
+                    self.track_executor(uid, handle, killer, pauser, replayer);
+                }
+                Err(e) => error!(uid = %uid, "Failed to register Index({uid}): {e}"),
+            }
         }
 
         Ok(())
     }
 
+    /// Start an executor for the latest assets of a registered indexer.
+    async fn create_registry_executor(
+        &self,
+        index_id: i64,
+    ) -> IndexerResult<StartedExecutor> {
+        let mut conn = self.pool.acquire().await?;
+        let manifest_asset = queries::latest_asset_for_index(
+            &mut conn,
+            &index_id,
+            IndexAssetType::Manifest,
+        )
+        .await?;
+        let manifest = Manifest::from_slice(&manifest_asset.bytes)?;
+
+        let start_block = get_start_block(&mut conn, &manifest).await.unwrap_or(1);
+
+        // Native indexers have no WASM module, only a handler compiled into
+        // this binary.
+        if let Module::Native = manifest.module {
+            drop(conn);
+            return self.create_native_executor(manifest, start_block).await;
+        }
+
+        let assets = queries::latest_assets_for_index(&mut conn, &index_id).await?;
+
+        // Return the connection before the executor opens its own.
+        drop(conn);
+
+        let pauser = Arc::new(AtomicBool::new(false));
+        let replayer = ReplaySlot::default();
+        let (handle, _module_bytes, killer) = WasmIndexExecutor::create(
+            &self.options,
+            &manifest,
+            ExecutorSource::Registry(assets.wasm.bytes),
+            start_block,
+            None,
+            pauser.clone(),
+            replayer.clone(),
+        )
+        .await?;
+
+        Ok((handle, killer, pauser, replayer))
+    }
+
+    /// Make `handle_events` the handler of the native indexer `uid`, so that
+    /// `register_indices_from_registry` can restart that indexer.
+    pub fn register_native_handler<
+        T: Future<Output = IndexerResult<()>> + Send + 'static,
+    >(
+        &mut self,
+        uid: &str,
+        handle_events: fn(Vec<BlockData>, Arc<Mutex<Database>>) -> T,
+    ) {
+        self.native_handlers.insert(
+            uid.to_string(),
+            Arc::new(move |blocks, db| handle_events(blocks, db).boxed()),
+        );
+    }
+
     pub async fn register_native_index<
         T: Future<Output = IndexerResult<()>> + Send + 'static,
     >(
@@ -158,16 +339,18 @@ impl IndexerService {
         manifest: Manifest,
         handle_events: fn(Vec<BlockData>, Arc<Mutex<Database>>) -> T,
     ) -> IndexerResult<()> {
+        let schema = manifest.graphql_schema()?;
+        validate_manifest(&manifest, &schema)?;
+
         let mut conn = self.pool.acquire().await?;
-        let _index = queries::register_index(
+        let index = queries::register_index(
             &mut conn,
             &manifest.namespace,
             &manifest.identifier,
             None,
         )
         .await?;
-        let schema = manifest.graphql_schema()?;
-        let _schema_bytes = schema.as_bytes().to_vec();
+        let schema_bytes = schema.as_bytes().to_vec();
 
         self.manager
             .new_schema(
@@ -178,32 +361,112 @@ impl IndexerService {
             )
             .await?;
 
+        // There is no module to register, but the manifest and schema let the
+        // indexer be restarted from the registry.
+        let items = vec![
+            (IndexAssetType::Manifest, manifest.to_bytes()?),
+            (IndexAssetType::Schema, schema_bytes),
+        ];
+
+        for (asset_type, bytes) in items {
+            info!(
+                uid = %index.uid(),
+                "Registering Asset({:?}) for NativeIndex({})",
+                asset_type,
+                index.uid()
+            );
+
+            queries::register_index_asset(
+                &mut conn,
+                &manifest.namespace,
+                &manifest.identifier,
+                bytes,
+                asset_type,
+                None,
+            )
+            .await?;
+        }
+
         let start_block = get_start_block(&mut conn, &manifest).await.unwrap_or(1);
+        drop(conn);
+
         let uid = manifest.uid();
-        let (handle, _module_bytes, killer) = NativeIndexExecutor::<T>::create(
-            &self.database_url,
-            &self.config.fuel_node,
-            manifest,
-            self.config.stop_idle_indexers,
-            start_block,
-            handle_events,
-        )
-        .await?;
+        self.register_native_handler(&uid, handle_events);
+        let (handle, killer, pauser, replayer) =
+            self.create_native_executor(manifest, start_block).await?;
 
-        info!("Registered NativeIndex({})", uid);
+        info!(uid = %uid, "Registered NativeIndex({})", uid);
 
-        self.handles.insert(uid.clone(), handle);
-        self.killers.insert(uid, killer);
+        self.track_executor(uid, handle, killer, pauser, replayer);
         Ok(())
     }
 
+    /// Start an executor for a native indexer, using its registered handler.
+    async fn create_native_executor(
+        &self,
+        manifest: Manifest,
+        start_block: u64,
+    ) -> IndexerResult<StartedExecutor> {
+        let handler = self
+            .native_handlers
+            .get(&manifest.uid())
+            .cloned()
+            .ok_or_else(|| IndexerError::NativeHandlerNotFound(manifest.uid()))?;
+
+        let pauser = Arc::new(AtomicBool::new(false));
+        let replayer = ReplaySlot::default();
+        let (handle, _module_bytes, killer) =
+            NativeIndexExecutor::<BoxFuture<'static, IndexerResult<()>>>::create(
+                &self.options,
+                manifest,
+                start_block,
+                pauser.clone(),
+                replayer.clone(),
+                move |blocks, db| handler(blocks, db),
+            )
+            .await?;
+
+        Ok((handle, killer, pauser, replayer))
+    }
+
+    fn track_executor(
+        &mut self,
+        uid: String,
+        handle: JoinHandle<()>,
+        killer: Arc<AtomicBool>,
+        pauser: Arc<AtomicBool>,
+        replayer: ReplaySlot,
+    ) {
+        let alive = Arc::new(AtomicBool::new(true));
+        self.handles
+            .insert(uid.clone(), watch_executor(handle, alive.clone()));
+        self.killers.insert(uid.clone(), killer);
+        self.pausers.insert(uid.clone(), pauser);
+        self.replayers.insert(uid.clone(), replayer);
+        self.alive.insert(uid, alive);
+    }
+
+    /// Run until every indexer has retired, or until the process receives
+    /// SIGTERM or ctrl-c.
     pub async fn run(self) {
+        self.run_until(shutdown_signal()).await
+    }
+
+    /// Run until every indexer has retired, or until `shutdown` resolves.
+    ///
+    /// On shutdown, every indexer is stopped after its current block, and this
+    /// returns once all of them have retired. Indexers that don't stop within
+    /// `stop_drain_timeout_secs` are dropped, rolling back their open transaction.
+    pub async fn run_until(self, shutdown: impl Future<Output = ()>) {
         let IndexerService {
             handles,
             rx,
             pool,
-            config,
+            options,
             killers,
+            pausers,
+            replayers,
+            alive,
             ..
         } = self;
 
@@ -211,192 +474,628 @@ impl IndexerService {
             handles.into_values(),
         )));
 
-        let _ = tokio::spawn(create_service_task(
+        let killers = Arc::new(Mutex::new(killers));
+
+        let mut service_task = tokio::spawn(create_service_task(
             rx,
-            config.clone(),
-            pool.clone(),
-            futs.clone(),
-            killers,
-        ))
-        .await
-        .unwrap();
+            ServiceTaskSettings {
+                options,
+                pool: pool.clone(),
+                futs: futs.clone(),
+                killers: killers.clone(),
+                pausers,
+                replayers,
+                alive,
+            },
+        ));
+
+        let retired = async {
+            let _ = (&mut service_task).await.unwrap();
+            retire_executors(&futs).await;
+        };
+
+        tokio::select! {
+            _ = retired => return,
+            _ = shutdown => {}
+        }
+
+        info!("Shutting down, stopping all indexers after their current block.");
 
-        while let Some(fut) = futs.lock().await.next().await {
-            info!("Retired a future {fut:?}");
+        // No more indexers may be started once shutdown has begun.
+        service_task.abort();
+        let _ = service_task.await;
+
+        for killer in killers.lock().await.values() {
+            killer.store(true, Ordering::SeqCst);
         }
+
+        retire_executors(&futs).await;
+
+        info!("All indexers stopped.");
     }
 }
 
-async fn create_service_task(
-    rx: Option<Receiver<ServiceRequest>>,
-    config: IndexerConfig,
+/// Check that a manifest's ABIs and schema are consistent before any of its
+/// assets are written, so that mistakes surface at registration time rather
+/// than inside the running indexer.
+#[allow(clippy::result_large_err)]
+fn validate_manifest(manifest: &Manifest, schema: &str) -> IndexerResult<()> {
+    let errors: Vec<String> = manifest
+        .field_errors()
+        .into_iter()
+        .chain(manifest.abi_type_errors())
+        .chain(schema_type_errors(schema))
+        .chain(manifest.schema_errors(schema))
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(IndexerError::InvalidManifest(errors.join(" ")))
+    }
+}
+
+/// Await every executor being tracked until all of them have completed.
+async fn retire_executors(futs: &Mutex<FuturesUnordered<JoinHandle<()>>>) {
+    while let Some(fut) = futs.lock().await.next().await {
+        info!("Retired a future {fut:?}");
+    }
+}
+
+/// Resolve once the process receives SIGTERM or ctrl-c.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!("Failed to listen for ctrl-c: {e}");
+            futures::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                error!("Failed to listen for SIGTERM: {e}");
+                futures::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = futures::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+/// What the service task starts executors with, and the switches of the
+/// executors it tracks.
+struct ServiceTaskSettings {
+    options: ExecutorOptions,
     pool: IndexerConnectionPool,
     futs: Arc<Mutex<FuturesUnordered<JoinHandle<()>>>>,
-    mut killers: HashMap<String, Arc<AtomicBool>>,
+    killers: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+    pausers: HashMap<String, Arc<AtomicBool>>,
+    replayers: HashMap<String, ReplaySlot>,
+    alive: HashMap<String, Arc<AtomicBool>>,
+}
+
+async fn create_service_task(
+    rx: Option<Receiver<ServiceRequest>>,
+    settings: ServiceTaskSettings,
 ) -> IndexerResult<()> {
+    let ServiceTaskSettings {
+        options,
+        pool,
+        futs,
+        killers,
+        mut pausers,
+        mut replayers,
+        mut alive,
+    } = settings;
+
     if let Some(mut rx) = rx {
+        // Reloaded executors that have yet to fetch their first page of
+        // blocks, and the killer of the latest one of each indexer.
+        let mut reloads: FuturesUnordered<BoxFuture<'static, (PendingReload, bool)>> =
+            FuturesUnordered::new();
+        let mut reloading: HashMap<String, Arc<AtomicBool>> = HashMap::new();
+
         loop {
-            let futs = futs.lock().await;
-            match rx.try_recv() {
-                Ok(service_request) => match service_request {
+            let service_request = tokio::select! {
+                request = rx.recv() => match request {
+                    Some(request) => request,
+                    None => break,
+                },
+                Some((reload, is_ready)) = reloads.next() => {
+                    let span = request_span(reload.request_id.as_deref());
+                    finish_reload(reload, is_ready, &mut reloading, &killers, &mut alive)
+                        .instrument(span)
+                        .await;
+                    continue;
+                }
+            };
+
+            let span = request_span(service_request.request_id());
+            async {
+                match service_request {
                     ServiceRequest::AssetReload(request) => {
-                        let mut conn = pool
-                            .acquire()
+                        let uid = format!("{}.{}", request.namespace, request.identifier);
+
+                        let pauser = pausers.entry(uid.clone()).or_default().clone();
+                        let replayer = replayers.entry(uid.clone()).or_default().clone();
+
+                        match reload_executor(&options, &pool, &request, pauser, replayer)
                             .await
-                            .expect("Failed to acquire connection from pool");
-
-                        match queries::index_id_for(
-                            &mut conn,
-                            &request.namespace,
-                            &request.identifier,
-                        )
-                        .await
                         {
-                            Ok(id) => {
-                                let assets =
-                                    queries::latest_assets_for_index(&mut conn, &id)
-                                        .await
-                                        .expect(
-                                            "Could not get latest assets for indexer",
-                                        );
-
-                                let manifest: Manifest =
-                                    serde_yaml::from_slice(&assets.manifest.bytes)
-                                        .expect("Failed to deserialize manifest");
-
-                                let start_block =
-                                    get_start_block(&mut conn, &manifest).await?;
-                                let (handle, _module_bytes, killer) = WasmIndexExecutor::create(
-                                    &config.fuel_node,
-                                    &config.database.to_string(),
-                                    &manifest,
-                                    ExecutorSource::Registry(assets.wasm.bytes),
-                                    config.stop_idle_indexers,
-                                    &start_block,
-                                )
-                                .await
-                                .expect(
-                                    "Failed to spawn executor from index asset registry",
-                                );
+                            Ok((handle, killer, ready, proceed)) => {
+                                let is_alive = Arc::new(AtomicBool::new(true));
+                                futs.lock()
+                                    .await
+                                    .push(watch_executor(handle, is_alive.clone()));
 
-                                futs.push(handle);
-
-                                if let Some(killer_for_prev_executor) =
-                                    killers.insert(manifest.uid(), killer)
+                                if let Some(superseded) =
+                                    reloading.insert(uid.clone(), killer.clone())
                                 {
-                                    let uid = manifest.uid();
-                                    info!("Indexer({uid}) was replaced. Stopping previous version of Indexer({uid}).");
-                                    killer_for_prev_executor
-                                        .store(true, Ordering::SeqCst);
+                                    info!(uid = %uid, "Indexer({uid}) was reloaded again before its previous reload was up. Stopping the previous reload.");
+                                    superseded.store(true, Ordering::SeqCst);
                                 }
+
+                                // The running version keeps handling blocks until
+                                // the new one is up.
+                                let reload = PendingReload {
+                                    uid,
+                                    killer: Some(killer),
+                                    proceed: Some(proceed),
+                                    alive: is_alive,
+                                    request_id: request.request_id,
+                                };
+                                reloads.push(
+                                    async move {
+                                        let is_ready = ready.await.is_ok();
+                                        (reload, is_ready)
+                                    }
+                                    .boxed(),
+                                );
                             }
                             Err(e) => {
-                                error!(
-                                    "Failed to find Indexer({}.{}): {}",
-                                    &request.namespace, &request.identifier, e
-                                );
-
-                                continue;
+                                error!(uid = %uid, "Failed to reload Indexer({uid}): {e}");
                             }
                         }
                     }
+                    ServiceRequest::AssetValidate(request) => {
+                        let db_url = options.database_url.clone();
+                        let pool_config = options.pool_config;
+                        let wasm_limits = options.wasm_limits;
+
+                        // Compiling a module can take a while, so don't hold up
+                        // other requests while doing so.
+                        tokio::spawn(async move {
+                            let result = match request
+                                .manifest
+                                .as_deref()
+                                .map(Manifest::from_slice)
+                                .transpose()
+                            {
+                                Ok(manifest) => WasmIndexExecutor::validate(
+                                    db_url,
+                                    pool_config,
+                                    &request.namespace,
+                                    &request.identifier,
+                                    &request.wasm,
+                                    request.schema.as_deref(),
+                                    manifest.as_ref(),
+                                    wasm_limits,
+                                )
+                                .await
+                                .map_err(|e| e.to_string()),
+                                Err(e) => Err(format!("Invalid manifest: {e}")),
+                            };
+
+                            if request.reply.send(result).is_err() {
+                                warn!("Asset validation requester went away before a reply.");
+                            }
+                        }.in_current_span());
+                    }
                     ServiceRequest::IndexStop(request) => {
                         let uid = format!("{}.{}", request.namespace, request.identifier);
 
-                        if let Some(killer) = killers.remove(&uid) {
+                        if let Some(reload) = reloading.remove(&uid) {
+                            reload.store(true, Ordering::SeqCst);
+                        }
+
+                        if let Some(killer) = killers.lock().await.remove(&uid) {
+                            info!(
+                                uid = %uid,
+                                "Stopping Indexer({uid}) after its current block, or in {}s at the latest.",
+                                options.stop_drain_timeout_secs
+                            );
                             killer.store(true, Ordering::SeqCst);
                         } else {
-                            warn!("Stop Indexer: No indexer with the name Index({uid})");
+                            warn!(uid = %uid, "Stop Indexer: No indexer with the name Index({uid})");
+                        }
+                        pausers.remove(&uid);
+                        replayers.remove(&uid);
+                    }
+                    ServiceRequest::IndexPause(request) => {
+                        let uid = format!("{}.{}", request.namespace, request.identifier);
+
+                        match pausers.get(&uid) {
+                            Some(pauser) => {
+                                info!(uid = %uid, "Pausing Indexer({uid}) after its current block.");
+                                pauser.store(true, Ordering::SeqCst);
+                            }
+                            None => {
+                                warn!(uid = %uid, "Pause Indexer: Indexer({uid}) not found.")
+                            }
+                        }
+                    }
+                    ServiceRequest::IndexResume(request) => {
+                        let uid = format!("{}.{}", request.namespace, request.identifier);
+
+                        match pausers.get(&uid) {
+                            Some(pauser) => {
+                                info!(uid = %uid, "Resuming Indexer({uid}).");
+                                pauser.store(false, Ordering::SeqCst);
+                            }
+                            None => {
+                                warn!(uid = %uid, "Resume Indexer: Indexer({uid}) not found.")
+                            }
+                        }
+                    }
+                    ServiceRequest::IndexReplay(IndexReplayRequest {
+                        namespace,
+                        identifier,
+                        from,
+                        to,
+                        ..
+                    }) => {
+                        let uid = format!("{namespace}.{identifier}");
+
+                        match replayers.get(&uid) {
+                            Some(replayer) => {
+                                info!(uid = %uid, "Replaying blocks [{from}, {to}] of Indexer({uid}) after its current block.");
+                                if let Some((from, to)) =
+                                    replayer.lock().await.replace((from, to))
+                                {
+                                    warn!(uid = %uid, "Replay of blocks [{from}, {to}] of Indexer({uid}) was replaced before it started.");
+                                }
+                            }
+                            None => {
+                                warn!(uid = %uid, "Replay Indexer: Indexer({uid}) not found.")
+                            }
                         }
                     }
                     ServiceRequest::IndexRevert(request) => {
                         let uid = format!("{}.{}", request.namespace, request.identifier);
 
-                        if let Some(killer) = killers.get(&uid) {
+                        if let Some(reload) = reloading.remove(&uid) {
+                            reload.store(true, Ordering::SeqCst);
+                        }
+
+                        if let Some(killer) = killers.lock().await.get(&uid) {
                             killer.store(true, Ordering::SeqCst);
                         } else {
-                            warn!("Revert Indexer: Indexer({uid}) not found.");
+                            warn!(uid = %uid, "Revert Indexer: Indexer({uid}) not found.");
                         }
 
-                        let mut conn = pool
-                            .acquire()
-                            .await
-                            .expect("Failed to acquire connection from pool");
+                        let pauser = pausers.entry(uid.clone()).or_default().clone();
+                        let replayer = replayers.entry(uid.clone()).or_default().clone();
 
-                        let _ = queries::start_transaction(&mut conn)
+                        match revert_executor(&options, &pool, request, pauser, replayer)
                             .await
-                            .expect("Failed to start transaction");
-
-                        let latest_assets = queries::latest_assets_for_index(
-                            &mut conn,
-                            &request.penultimate_asset_id,
-                        )
-                        .await
-                        .expect("Could not get latest assets for index");
-
-                        if let Err(_e) = queries::remove_asset_by_version(
-                            &mut conn,
-                            &latest_assets.manifest.id,
-                            &latest_assets.wasm.version,
-                            IndexAssetType::Wasm,
-                        )
-                        .await
                         {
-                            error!("Failed to remove asset by version");
-                            queries::revert_transaction(&mut conn)
-                                .await
-                                .expect("Failed to revert transaction");
-                        } else {
-                            queries::commit_transaction(&mut conn)
-                                .await
-                                .expect("Failed to commit transaction");
+                            Ok((handle, killer, ready, proceed)) => {
+                                let is_alive = Arc::new(AtomicBool::new(true));
+                                futs.lock()
+                                    .await
+                                    .push(watch_executor(handle, is_alive.clone()));
+                                let previous = alive.insert(uid.clone(), is_alive);
+                                killers.lock().await.insert(uid, killer);
+
+                                // As with a reload, the reverted version doesn't
+                                // handle blocks until the running one has stopped.
+                                tokio::spawn(
+                                    async move {
+                                        if ready.await.is_ok() {
+                                            wait_for_stop(previous).await;
+                                            let _ = proceed.send(());
+                                        }
+                                    }
+                                    .in_current_span(),
+                                );
+                            }
+                            Err(e) => {
+                                error!(uid = %uid, "Failed to revert Indexer({uid}): {e}");
+                            }
                         }
+                    }
+                    ServiceRequest::IndexStatus(request) => {
+                        let statuses = alive
+                            .iter()
+                            .map(|(uid, is_alive)| {
+                                (uid.clone(), is_alive.load(Ordering::SeqCst))
+                            })
+                            .collect();
 
-                        let manifest =
-                            Manifest::from_slice(&latest_assets.manifest.bytes)
-                                .expect("Failed to deserialize manifest");
-
-                        let start_block = get_start_block(&mut conn, &manifest).await?;
-                        let (handle, _module_bytes, killer) = WasmIndexExecutor::create(
-                            &config.fuel_node,
-                            &config.database.to_string(),
-                            &manifest,
-                            ExecutorSource::Registry(request.penultimate_asset_bytes),
-                            config.stop_idle_indexers,
-                            &start_block,
-                        )
-                        .await
-                        .expect("Failed to spawn executor from index asset registry");
-
-                        futs.push(handle);
-                        killers.insert(manifest.uid(), killer);
+                        if request.reply.send(statuses).is_err() {
+                            warn!("Indexer status requester went away before a reply.");
+                        }
+                    }
+                    ServiceRequest::EntitySubscribe(request) => {
+                        if request.reply.send(options.notifier.subscribe()).is_err() {
+                            warn!("Entity subscriber went away before a reply.");
+                        }
                     }
-                },
-                Err(e) => {
-                    debug!("No service request to handle: {e:?}");
-                    sleep(Duration::from_secs(defaults::IDLE_SERVICE_WAIT_SECS)).await;
                 }
             }
+            .instrument(span)
+            .await;
+        }
+
+        info!("Service request channel closed, no longer handling requests.");
+
+        while let Some((reload, is_ready)) = reloads.next().await {
+            let span = request_span(reload.request_id.as_deref());
+            finish_reload(reload, is_ready, &mut reloading, &killers, &mut alive)
+                .instrument(span)
+                .await;
         }
     }
     Ok(())
 }
 
+/// Span that a service request is handled in, tagged with the correlation ID
+/// of the API request that triggered it, if any. Executors started while
+/// handling the request open their spans within it, so their logs carry the
+/// ID too.
+fn request_span(request_id: Option<&str>) -> Span {
+    info_span!("service_request", request_id)
+}
+
+/// An executor started by a reload, which is stopped if this is dropped
+/// before the executor replaces the running version of its indexer.
+struct PendingReload {
+    uid: String,
+    killer: Option<Arc<AtomicBool>>,
+    /// Lets the executor handle blocks, which it does not do until the running
+    /// version has stopped.
+    proceed: Option<oneshot::Sender<()>>,
+    alive: Arc<AtomicBool>,
+    /// Correlation ID of the API request that triggered the reload, if any.
+    request_id: Option<String>,
+}
+
+impl Drop for PendingReload {
+    fn drop(&mut self) {
+        if let Some(killer) = &self.killer {
+            killer.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Replace the running version of a reloaded indexer with its new executor,
+/// if the new executor is up. Otherwise, the running version is kept and the
+/// new executor stopped.
+///
+/// The new executor is let through to handle blocks once the running version
+/// has stopped, so that only one of them writes to the indexer's tables.
+async fn finish_reload(
+    mut reload: PendingReload,
+    is_ready: bool,
+    reloading: &mut HashMap<String, Arc<AtomicBool>>,
+    killers: &Mutex<HashMap<String, Arc<AtomicBool>>>,
+    alive: &mut HashMap<String, Arc<AtomicBool>>,
+) {
+    let uid = reload.uid.clone();
+    let killer = match reload.killer.take() {
+        Some(killer) => killer,
+        None => return,
+    };
+
+    // A reload that was superseded, or whose indexer was stopped or reverted
+    // in the meantime, was already told to stop.
+    match reloading.get(&uid) {
+        Some(latest) if Arc::ptr_eq(latest, &killer) => {
+            reloading.remove(&uid);
+        }
+        _ => {
+            killer.store(true, Ordering::SeqCst);
+            return;
+        }
+    }
+
+    if !is_ready {
+        error!(uid = %uid, "Failed to reload Indexer({uid}): the new version stopped before fetching any blocks. Keeping the previous version of Indexer({uid}).");
+        killer.store(true, Ordering::SeqCst);
+        return;
+    }
+
+    let previous = alive.insert(uid.clone(), reload.alive.clone());
+
+    if let Some(killer_for_prev_executor) =
+        killers.lock().await.insert(uid.clone(), killer)
+    {
+        info!(uid = %uid, "Indexer({uid}) was replaced. Stopping previous version of Indexer({uid}).");
+        killer_for_prev_executor.store(true, Ordering::SeqCst);
+    }
+
+    if let Some(proceed) = reload.proceed.take() {
+        tokio::spawn(
+            async move {
+                wait_for_stop(previous).await;
+                // The new executor may have been stopped in the meantime.
+                let _ = proceed.send(());
+            }
+            .in_current_span(),
+        );
+    }
+}
+
+/// Wait for the previous executor of an indexer, if any, to stop.
+async fn wait_for_stop(previous: Option<Arc<AtomicBool>>) {
+    if let Some(previous) = previous {
+        while previous.load(Ordering::SeqCst) {
+            sleep(Duration::from_millis(defaults::RELOAD_POLL_INTERVAL_MS)).await;
+        }
+    }
+}
+
+/// Start an executor for the latest assets of an indexer whose assets were
+/// just uploaded, along with a receiver that resolves once the executor has
+/// fetched its first page of blocks, and a sender that lets it handle them.
+async fn reload_executor(
+    options: &ExecutorOptions,
+    pool: &IndexerConnectionPool,
+    request: &AssetReloadRequest,
+    pauser: Arc<AtomicBool>,
+    replayer: ReplaySlot,
+) -> IndexerResult<(
+    JoinHandle<()>,
+    Arc<AtomicBool>,
+    oneshot::Receiver<()>,
+    oneshot::Sender<()>,
+)> {
+    let mut conn = pool.acquire().await?;
+    let index_id =
+        queries::index_id_for(&mut conn, &request.namespace, &request.identifier).await?;
+    let assets = queries::latest_assets_for_index(&mut conn, &index_id).await?;
+    let manifest = Manifest::from_slice(&assets.manifest.bytes)?;
+    let start_block = get_start_block(&mut conn, &manifest).await?;
+
+    // Return the connection before the executor opens its own.
+    drop(conn);
+
+    let (handshake, ready, proceed) = ReloadHandshake::new();
+    let (handle, _module_bytes, killer) = WasmIndexExecutor::create(
+        options,
+        &manifest,
+        ExecutorSource::Registry(assets.wasm.bytes),
+        start_block,
+        Some(handshake),
+        pauser,
+        replayer,
+    )
+    .await?;
+
+    Ok((handle, killer, ready, proceed))
+}
+
+/// Remove an indexer's latest WASM module, and start an executor for the
+/// module that preceded it, along with a receiver that resolves once the
+/// executor has fetched its first page of blocks, and a sender that lets it
+/// handle them.
+async fn revert_executor(
+    options: &ExecutorOptions,
+    pool: &IndexerConnectionPool,
+    request: IndexRevertRequest,
+    pauser: Arc<AtomicBool>,
+    replayer: ReplaySlot,
+) -> IndexerResult<(
+    JoinHandle<()>,
+    Arc<AtomicBool>,
+    oneshot::Receiver<()>,
+    oneshot::Sender<()>,
+)> {
+    let uid = format!("{}.{}", request.namespace, request.identifier);
+    let mut conn = pool.acquire().await?;
+
+    let latest_assets =
+        queries::latest_assets_for_index(&mut conn, &request.penultimate_asset_id)
+            .await?;
+
+    queries::start_transaction(&mut conn).await?;
+
+    if let Err(e) = queries::remove_asset_by_version(
+        &mut conn,
+        &latest_assets.manifest.id,
+        &latest_assets.wasm.version,
+        IndexAssetType::Wasm,
+    )
+    .await
+    {
+        error!(uid = %uid, "Failed to remove asset by version: {e}");
+        queries::revert_transaction(&mut conn).await?;
+    } else {
+        queries::commit_transaction(&mut conn).await?;
+    }
+
+    let manifest = Manifest::from_slice(&latest_assets.manifest.bytes)?;
+    let start_block = get_start_block(&mut conn, &manifest).await?;
+
+    // Return the connection before the executor opens its own.
+    drop(conn);
+
+    let (handshake, ready, proceed) = ReloadHandshake::new();
+    let (handle, _module_bytes, killer) = WasmIndexExecutor::create(
+        options,
+        &manifest,
+        ExecutorSource::Registry(request.penultimate_asset_bytes),
+        start_block,
+        Some(handshake),
+        pauser,
+        replayer,
+    )
+    .await?;
+
+    Ok((handle, killer, ready, proceed))
+}
+
+/// Await an executor in the background, flagging it as no longer alive once
+/// its future completes, whether it stopped, finished or panicked.
+fn watch_executor(handle: JoinHandle<()>, alive: Arc<AtomicBool>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let _ = handle.await;
+        alive.store(false, Ordering::SeqCst);
+    })
+}
+
 async fn get_start_block(
     conn: &mut IndexerConnection,
     manifest: &Manifest,
 ) -> Result<u64, IndexerError> {
-    match &manifest.resumable {
-        Some(_) => {
-            let last = queries::last_block_height_for_indexer(
+    // The last committed block height is always recorded, but it is only used
+    // to pick up where the indexer left off if the indexer is resumable.
+    let last = match manifest.resumable {
+        Some(true) => {
+            queries::last_block_height_for_indexer(
                 conn,
                 &manifest.namespace,
                 &manifest.identifier,
             )
-            .await?;
-            info!("Resuming index from block {}", last);
-            Ok(last)
+            .await?
+        }
+        _ => None,
+    };
+
+    let start_block = match last {
+        Some(last) => {
+            info!(
+                uid = %manifest.uid(),
+                block_height = last + 1,
+                "Resuming index from block {}",
+                last + 1
+            );
+            last + 1
+        }
+        None => manifest.start_block.unwrap_or(1),
+    };
+
+    if let Some(end_block) = manifest.end_block {
+        if start_block > end_block {
+            info!(
+                uid = %manifest.uid(),
+                "Index({}) has already completed its block range ending at {end_block}.",
+                manifest.uid()
+            );
         }
-        None => Ok(manifest.start_block.unwrap_or(1)),
     }
+
+    Ok(start_block)
 }
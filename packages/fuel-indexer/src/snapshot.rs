@@ -0,0 +1,268 @@
+//! Export of an indexer's indexed state to a portable file, and import of that
+//! file into another database.
+//!
+//! A snapshot is newline-delimited JSON. The first line is a [`SnapshotHeader`]
+//! holding the indexer's GraphQL schema and the last block it committed. Each
+//! following line holds one row of one of the indexer's tables, keyed by the
+//! `type_id` of the table's type:
+//!
+//! ```text
+//! {"namespace":"fuel","identifier":"explorer","version":"...","schema":"...","last_block_height":1234}
+//! {"type_id":-4145438814509139062,"row":{"id":1,"account":"...","object":"\\x..."}}
+//! ```
+//!
+//! Snapshots are only supported on Postgres.
+
+use crate::{IndexerError, IndexerResult};
+use fuel_indexer_database::{
+    queries, DbType, IndexerConnection, IndexerConnectionPool, IndexerDatabaseError,
+};
+use fuel_indexer_schema::db::manager::SchemaManager;
+use fuel_indexer_types::graphql::{GraphqlObject, IndexMetadata};
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::{
+    collections::HashMap,
+    io::{BufRead, Write},
+};
+use tracing::info;
+
+/// Number of rows read from a table at a time during export.
+const EXPORT_PAGE_SIZE: i64 = 1000;
+
+/// Number of rows inserted into a table at a time during import.
+const IMPORT_BATCH_SIZE: usize = 1000;
+
+/// First line of a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotHeader {
+    pub namespace: String,
+    pub identifier: String,
+    pub version: String,
+    /// The indexer's GraphQL schema, without the entities injected by the service.
+    pub schema: String,
+    pub last_block_height: Option<u64>,
+}
+
+/// Summary of an exported or imported snapshot.
+#[derive(Debug)]
+pub struct SnapshotSummary {
+    pub header: SnapshotHeader,
+    pub rows: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotRow<'a> {
+    type_id: i64,
+    #[serde(borrow)]
+    row: &'a RawValue,
+}
+
+#[allow(clippy::result_large_err)]
+fn ensure_postgres(pool: &IndexerConnectionPool) -> IndexerResult<()> {
+    match pool.database_type() {
+        DbType::Postgres => Ok(()),
+        db_type => Err(IndexerDatabaseError::BackendNotSupported(format!(
+            "Snapshots are not supported on {db_type:?}."
+        ))
+        .into()),
+    }
+}
+
+fn invalid_snapshot(line: usize, e: impl std::fmt::Display) -> IndexerError {
+    IndexerError::SnapshotError(format!("Line {line}: {e}"))
+}
+
+/// Tables of the indexer's latest schema, by `type_id`.
+async fn tables_by_type_id(
+    conn: &mut IndexerConnection,
+    db_type: &DbType,
+    namespace: &str,
+    identifier: &str,
+    version: &str,
+) -> IndexerResult<HashMap<i64, String>> {
+    let schema = format!("{namespace}_{identifier}");
+    Ok(
+        queries::type_id_list_by_name(conn, namespace, version, identifier)
+            .await?
+            .into_iter()
+            .map(|t| (t.id, db_type.table_name(&schema, &t.table_name)))
+            .collect(),
+    )
+}
+
+/// Write every row of the indexer `namespace.identifier` to `writer`.
+///
+/// Stop the indexer first, so that the rows and the last block height agree.
+pub async fn export_indexer<W: Write>(
+    pool: &IndexerConnectionPool,
+    namespace: &str,
+    identifier: &str,
+    writer: &mut W,
+) -> IndexerResult<SnapshotSummary> {
+    ensure_postgres(pool)?;
+    let mut conn = pool.acquire().await?;
+
+    let root = match queries::graph_root_latest(&mut conn, namespace, identifier).await {
+        Ok(root) => root,
+        Err(sqlx::Error::RowNotFound) => {
+            return Err(IndexerError::SnapshotError(format!(
+                "Indexer({namespace}.{identifier}) has no schema."
+            )))
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let header = SnapshotHeader {
+        namespace: namespace.to_string(),
+        identifier: identifier.to_string(),
+        schema: root
+            .schema
+            .strip_suffix(IndexMetadata::schema_fragment())
+            .unwrap_or(&root.schema)
+            .to_string(),
+        version: root.version,
+        last_block_height: queries::last_block_height_for_indexer(
+            &mut conn, namespace, identifier,
+        )
+        .await?,
+    };
+    serde_json::to_writer(&mut *writer, &header)
+        .map_err(|e| IndexerError::SnapshotError(e.to_string()))?;
+    writeln!(writer)?;
+
+    let tables = tables_by_type_id(
+        &mut conn,
+        &pool.database_type(),
+        namespace,
+        identifier,
+        &header.version,
+    )
+    .await?;
+
+    let mut rows = 0;
+    for (type_id, table) in tables {
+        let mut after = None;
+        loop {
+            let page =
+                queries::table_rows_as_json(&mut conn, &table, after, EXPORT_PAGE_SIZE)
+                    .await?;
+            for (_, row) in page.iter() {
+                // Rows are written as they came from the database, so that
+                // no precision is lost to a round trip through `f64`.
+                writeln!(writer, "{{\"type_id\":{type_id},\"row\":{row}}}")?;
+            }
+            rows += page.len();
+            match page.last() {
+                Some((id, _)) if page.len() as i64 == EXPORT_PAGE_SIZE => {
+                    after = Some(*id)
+                }
+                _ => break,
+            }
+        }
+        info!("Exported {table}.");
+    }
+    writer.flush()?;
+
+    Ok(SnapshotSummary { header, rows })
+}
+
+/// Recreate the indexer described by the snapshot in `reader`, and load its rows.
+///
+/// The rows are loaded in a single transaction, so a failed import leaves the
+/// indexer's tables as they were. The indexer resumes from the snapshot's last
+/// block height once it is registered, if its manifest is `resumable`.
+pub async fn import_indexer<R: BufRead>(
+    pool: &IndexerConnectionPool,
+    reader: R,
+) -> IndexerResult<SnapshotSummary> {
+    ensure_postgres(pool)?;
+    let mut conn = pool.acquire().await?;
+
+    let mut lines = reader.lines();
+    let header: SnapshotHeader = match lines.next() {
+        Some(line) => serde_json::from_str(&line?).map_err(|e| invalid_snapshot(1, e))?,
+        None => return Err(IndexerError::SnapshotError("Snapshot is empty.".into())),
+    };
+
+    queries::start_transaction(&mut conn).await?;
+    match load_rows(pool, &mut conn, &header, lines).await {
+        Ok(rows) => {
+            queries::commit_transaction(&mut conn).await?;
+            Ok(SnapshotSummary { header, rows })
+        }
+        Err(e) => {
+            queries::revert_transaction(&mut conn).await?;
+            Err(e)
+        }
+    }
+}
+
+async fn load_rows<R: BufRead>(
+    pool: &IndexerConnectionPool,
+    conn: &mut IndexerConnection,
+    header: &SnapshotHeader,
+    lines: std::io::Lines<R>,
+) -> IndexerResult<usize> {
+    let SnapshotHeader {
+        namespace,
+        identifier,
+        version,
+        schema,
+        last_block_height,
+    } = header;
+
+    SchemaManager::new(pool.clone())
+        .new_schema(namespace, identifier, schema, conn)
+        .await?;
+
+    let root = queries::graph_root_latest(conn, namespace, identifier).await?;
+    if &root.version != version {
+        return Err(IndexerError::SnapshotError(format!(
+            "Schema version {} does not match the snapshot's version {version}.",
+            root.version
+        )));
+    }
+
+    let tables =
+        tables_by_type_id(conn, &pool.database_type(), namespace, identifier, version)
+            .await?;
+
+    let mut rows = 0;
+    // Rows are inserted in batches of consecutive rows of the same table, as
+    // they are written table by table on export.
+    let mut batch = Vec::new();
+    let mut batch_table: Option<&String> = None;
+    // The header is the first line.
+    for (i, line) in lines.enumerate().map(|(i, line)| (i + 2, line)) {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        let SnapshotRow { type_id, row } =
+            serde_json::from_str(&line).map_err(|e| invalid_snapshot(i, e))?;
+        let table = tables
+            .get(&type_id)
+            .ok_or(IndexerError::UnknownTypeId(type_id))?;
+
+        if batch_table != Some(table) || batch.len() == IMPORT_BATCH_SIZE {
+            if let Some(batch_table) = batch_table {
+                rows += queries::insert_json_rows(conn, batch_table, &batch).await?;
+                batch.clear();
+            }
+            batch_table = Some(table);
+        }
+        batch.push(row.get().to_string());
+    }
+
+    if let Some(batch_table) = batch_table {
+        rows += queries::insert_json_rows(conn, batch_table, &batch).await?;
+    }
+
+    if let Some(block_height) = last_block_height {
+        queries::set_last_block_height(conn, namespace, identifier, *block_height)
+            .await?;
+    }
+
+    Ok(rows)
+}
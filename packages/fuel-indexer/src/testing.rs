@@ -0,0 +1,274 @@
+//! Run the handlers of a native indexer against synthetic blocks, without a
+//! Fuel node or a database server.
+//!
+//! A [`TestIndexer`] keeps the indexer's objects in an in-memory SQLite
+//! database, and reads back the objects its handlers committed.
+//!
+//! ```ignore
+//! let mut indexer = TestIndexer::new(manifest, SCHEMA, handle_events).await?;
+//! indexer
+//!     .handle_events(vec![testing::block(1, vec![testing::log_data(rb, data)])])
+//!     .await?;
+//!
+//! let greeters = indexer.objects("Greeter").await?;
+//! assert_eq!(greeters[0]["name"], "Alice");
+//! ```
+
+use crate::{
+    executor::{Executor, NativeIndexExecutor},
+    Database, IndexerResult, Manifest,
+};
+use async_std::sync::{Arc, Mutex};
+use fuel_indexer_database::{
+    queries,
+    types::{
+        DbType, CREATED_AT_COLUMN, DELETED_AT_COLUMN, UPDATED_AT_COLUMN,
+        WRITTEN_AT_BLOCK_COLUMN,
+    },
+    ConnectionPoolConfig, IndexerConnection, IndexerConnectionPool,
+};
+use fuel_indexer_schema::db::manager::SchemaManager;
+use fuel_indexer_types::{
+    abi::{BlockData, TransactionData},
+    tx::{Receipt, Transaction, TransactionStatus},
+};
+use serde_json::Value;
+use std::{
+    future::Future,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+/// Columns that every table has, which aren't fields of the entity.
+const BOOKKEEPING_COLUMNS: [&str; 5] = [
+    "object",
+    CREATED_AT_COLUMN,
+    UPDATED_AT_COLUMN,
+    DELETED_AT_COLUMN,
+    WRITTEN_AT_BLOCK_COLUMN,
+];
+
+/// Number of databases created by this process, to give each its own name.
+static DATABASES: AtomicUsize = AtomicUsize::new(0);
+
+/// A native indexer whose objects are kept in an in-memory SQLite database.
+pub struct TestIndexer<F>
+where
+    F: Future<Output = IndexerResult<()>> + Send,
+{
+    executor: NativeIndexExecutor<F>,
+    pool: IndexerConnectionPool,
+    // An in-memory database is dropped along with its last connection.
+    _conn: IndexerConnection,
+    namespace: String,
+    identifier: String,
+}
+
+impl<F> TestIndexer<F>
+where
+    F: Future<Output = IndexerResult<()>> + Send,
+{
+    /// Create the tables of `schema` in a fresh database, and register the
+    /// indexer of `manifest`, whose `handle_events` is usually the one generated
+    /// by the `#[indexer]` macro.
+    pub async fn new(
+        manifest: Manifest,
+        schema: &str,
+        handle_events: impl Fn(Vec<BlockData>, Arc<Mutex<Database>>) -> F
+            + Send
+            + Sync
+            + 'static,
+    ) -> IndexerResult<Self> {
+        let url = format!(
+            "sqlite:fuel-indexer-test-{}-{}?mode=memory&cache=shared",
+            std::process::id(),
+            DATABASES.fetch_add(1, Ordering::Relaxed)
+        );
+        let pool = IndexerConnectionPool::connect(&url).await?;
+        let mut conn = pool.acquire().await?;
+        queries::run_migration(&mut conn).await?;
+        queries::register_index(
+            &mut conn,
+            &manifest.namespace,
+            &manifest.identifier,
+            None,
+        )
+        .await?;
+        SchemaManager::new(pool.clone())
+            .new_schema(&manifest.namespace, &manifest.identifier, schema, &mut conn)
+            .await?;
+
+        let (namespace, identifier) =
+            (manifest.namespace.clone(), manifest.identifier.clone());
+        let executor = NativeIndexExecutor::new(
+            &url,
+            ConnectionPoolConfig::default(),
+            manifest,
+            handle_events,
+        )
+        .await?;
+
+        Ok(Self {
+            executor,
+            pool,
+            _conn: conn,
+            namespace,
+            identifier,
+        })
+    }
+
+    /// Run the handlers on `blocks`, and commit the objects they save, as the
+    /// indexer would for a batch of blocks fetched from a node.
+    pub async fn handle_events(&mut self, blocks: Vec<BlockData>) -> IndexerResult<()> {
+        self.executor.handle_events(blocks).await
+    }
+
+    /// The committed objects of `entity` that weren't removed, ordered by ID,
+    /// each as a JSON object of its fields.
+    pub async fn objects(&self, entity: &str) -> IndexerResult<Vec<Value>> {
+        let schema = SchemaManager::new(self.pool.clone())
+            .load_schema(&self.namespace, &self.identifier)
+            .await?;
+        let table = DbType::Sqlite.table_name(
+            &format!("{}_{}", self.namespace, self.identifier),
+            &schema.table_name(&entity.to_lowercase()),
+        );
+        let query = format!(
+            "SELECT * FROM {table} WHERE {} IS NULL ORDER BY {}",
+            schema.column_name(DELETED_AT_COLUMN),
+            schema.column_name("id")
+        );
+
+        let mut conn = self.pool.acquire().await?;
+        let rows = queries::run_read_only_query(
+            &mut conn,
+            &self.namespace,
+            &self.identifier,
+            query,
+            Duration::ZERO,
+        )
+        .await?;
+
+        Ok(rows
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|mut row| {
+                if let Some(columns) = row.as_object_mut() {
+                    columns
+                        .retain(|name, _| !BOOKKEEPING_COLUMNS.contains(&name.as_str()));
+                }
+                row
+            })
+            .collect())
+    }
+}
+
+/// A block at `height` with a single successful transaction, whose receipts
+/// are `receipts`.
+pub fn block(height: u64, receipts: Vec<Receipt>) -> BlockData {
+    BlockData {
+        height,
+        id: [0u8; 32].into(),
+        producer: None,
+        time: 0,
+        transactions: vec![TransactionData {
+            transaction: Transaction::default(),
+            status: TransactionStatus::default(),
+            receipts,
+            id: [0u8; 32].into(),
+        }],
+    }
+}
+
+/// A `LogData` receipt of `data`, the ABI encoding of a logged value, where
+/// `rb` is the log ID of the value's type in the contract's ABI.
+pub fn log_data(rb: u64, data: Vec<u8>) -> Receipt {
+    Receipt::LogData {
+        id: [0u8; 32].into(),
+        ra: 0,
+        rb,
+        ptr: 0,
+        len: data.len() as u64,
+        digest: [0u8; 32].into(),
+        data,
+        pc: 0,
+        is: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FtColumn;
+    use fuel_indexer_types::type_id;
+    use serde_json::json;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn test_test_indexer_reads_back_committed_objects() {
+        let manifest = Manifest::from_str(
+            "namespace: test_namespace\n\
+            identifier: testing\n\
+            graphql_schema: testing.graphql\n\
+            module: native\n",
+        )
+        .unwrap();
+        let schema = r#"
+            schema {
+                query: QueryRoot
+            }
+
+            type QueryRoot {
+                thing: Thing
+            }
+
+            type Thing {
+                id: ID!
+                receipts: UInt8!
+            }
+        "#;
+
+        let mut indexer = TestIndexer::new(
+            manifest,
+            schema,
+            |blocks: Vec<BlockData>, db: Arc<Mutex<Database>>| async move {
+                let thing = type_id("test_namespace_testing", "Thing");
+                for block in blocks {
+                    let receipts = block.transactions[0].receipts.len() as u64;
+                    db.lock()
+                        .await
+                        .put_object(
+                            thing,
+                            vec![
+                                FtColumn::ID(Some(block.height)),
+                                FtColumn::UInt8(Some(receipts)),
+                            ],
+                            vec![],
+                        )
+                        .await?;
+                }
+                Ok(())
+            },
+        )
+        .await
+        .unwrap();
+
+        indexer
+            .handle_events(vec![
+                block(1, vec![]),
+                block(2, vec![log_data(0, vec![1, 2, 3])]),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            indexer.objects("Thing").await.unwrap(),
+            vec![
+                json!({ "id": 1, "receipts": 0 }),
+                json!({ "id": 2, "receipts": 1 }),
+            ]
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use crate::executor::Backoff;
+use fuel_indexer_database::types::{DbType, QueryParam};
+use fuel_indexer_lib::defaults;
+use fuel_indexer_schema::FtColumn;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tokio::{
+    sync::mpsc,
+    time::{sleep, Duration},
+};
+use tracing::{error, warn};
+
+/// An object written by a committed transaction, as posted to the webhook of
+/// its entity.
+#[derive(Clone, Debug, Serialize)]
+pub struct WebhookEvent {
+    pub namespace: String,
+    pub identifier: String,
+    /// Lowercase name of the entity, as used for its table.
+    pub entity: String,
+    /// Height of the first block of the batch that wrote the object.
+    pub block_height: u64,
+    pub object: Value,
+}
+
+/// Queue of events to be posted to webhooks by a background task, so that a
+/// slow webhook doesn't hold up indexing.
+///
+/// Events are posted one at a time, in the order they were committed. The task
+/// stops once the queue is dropped and the events left in it are posted.
+#[derive(Clone, Debug)]
+pub struct WebhookQueue {
+    sender: mpsc::Sender<(String, WebhookEvent)>,
+}
+
+impl WebhookQueue {
+    pub fn spawn() -> Self {
+        let (sender, mut receiver) =
+            mpsc::channel::<(String, WebhookEvent)>(defaults::WEBHOOK_QUEUE_SIZE);
+
+        tokio::spawn(async move {
+            let client = reqwest::Client::builder()
+                .timeout(Duration::from_secs(defaults::WEBHOOK_TIMEOUT_SECS))
+                .build()
+                .expect("Failed to build webhook client.");
+
+            while let Some((url, event)) = receiver.recv().await {
+                deliver(&client, &url, &event).await;
+            }
+        });
+
+        Self { sender }
+    }
+
+    /// Queue `event` to be posted to `url`. If the queue is full, the event is
+    /// dropped rather than holding up the indexer.
+    pub fn send(&self, url: String, event: WebhookEvent) {
+        if let Err(e) = self.sender.try_send((url, event)) {
+            let (url, event) = match e {
+                mpsc::error::TrySendError::Full(item)
+                | mpsc::error::TrySendError::Closed(item) => item,
+            };
+            error!(
+                "Webhook queue is full, dropping {} object from Block({}) for {url}.",
+                event.entity, event.block_height
+            );
+        }
+    }
+}
+
+/// Post `event` to `url`, retrying with backoff until the webhook accepts it
+/// or `WEBHOOK_RETRY_ATTEMPTS` attempts have failed.
+async fn deliver(client: &reqwest::Client, url: &str, event: &WebhookEvent) {
+    let mut backoff = Backoff::new(
+        defaults::WEBHOOK_RETRY_ATTEMPTS,
+        Duration::from_millis(defaults::WEBHOOK_RETRY_BASE_DELAY_MS),
+    );
+
+    loop {
+        let result = client
+            .post(url)
+            .json(event)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        let e = match result {
+            Ok(_) => return,
+            Err(e) => e,
+        };
+
+        match backoff.next_delay() {
+            Some(delay) => {
+                warn!("Failed to post to webhook {url}: {e}. Retrying in {delay:?}.");
+                sleep(delay).await;
+            }
+            None => {
+                error!(
+                    "Failed to post to webhook {url} after {} attempts: {e}. Dropping {} object from Block({}).",
+                    backoff.attempt, event.entity, event.block_height
+                );
+                return;
+            }
+        }
+    }
+}
+
+/// An object as a JSON object, keyed by column name.
+pub fn object_json(columns: &[String], values: &[FtColumn], db_type: &DbType) -> Value {
+    let object: Map<String, Value> = columns
+        .iter()
+        .zip(values)
+        .map(|(name, value)| (name.clone(), column_json(value, db_type)))
+        .collect();
+
+    Value::Object(object)
+}
+
+/// A column value as JSON. Values are written as they are bound to queries, so
+/// that 128-bit integers and decimals are strings rather than lossy numbers,
+/// and bytes are hex strings.
+fn column_json(value: &FtColumn, db_type: &DbType) -> Value {
+    match value {
+        FtColumn::Array(items) => items.as_ref().map_or(Value::Null, |items| {
+            Value::Array(items.iter().map(|i| column_json(i, db_type)).collect())
+        }),
+        FtColumn::Json(Some(json)) => serde_json::from_str(&json.0)
+            .unwrap_or_else(|_| Value::String(json.0.clone())),
+        value => match value.bind_param(db_type) {
+            QueryParam::Int(v) => v.into(),
+            QueryParam::Float(v) => v.into(),
+            QueryParam::Boolean(v) => v.into(),
+            QueryParam::String(v) => v.into(),
+            QueryParam::Bytes(v) => hex::encode(v).into(),
+            QueryParam::Null => Value::Null,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_indexer_types::{Bytes32, Json};
+    use serde_json::json;
+
+    #[test]
+    fn test_object_json_keeps_values_exact() {
+        let columns: Vec<String> = ["id", "hash", "amount", "tags", "meta", "memo"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        let values = vec![
+            FtColumn::ID(Some(7)),
+            FtColumn::Bytes32(Some(Bytes32::from([0x01; 32]))),
+            FtColumn::UInt16(Some(u128::MAX)),
+            FtColumn::Array(Some(vec![
+                FtColumn::Charfield(Some("a".to_string())),
+                FtColumn::Charfield(Some("b".to_string())),
+            ])),
+            FtColumn::Json(Some(Json(r#"{"hello":"world"}"#.to_string()))),
+            FtColumn::Charfield(None),
+        ];
+
+        assert_eq!(
+            object_json(&columns, &values, &DbType::Postgres),
+            json!({
+                "id": 7,
+                "hash": "01".repeat(32),
+                "amount": u128::MAX.to_string(),
+                "tags": ["a", "b"],
+                "meta": {"hello": "world"},
+                "memo": null,
+            })
+        );
+    }
+}
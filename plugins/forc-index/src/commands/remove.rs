@@ -26,6 +26,14 @@ pub struct Command {
     #[clap(long, help = "Authentication header value.")]
     pub auth: Option<String>,
 
+    /// Drop the indexer's tables along with its registration.
+    #[clap(long, help = "Drop the indexer's tables along with its registration.")]
+    pub purge: bool,
+
+    /// List what would be dropped, without removing anything.
+    #[clap(long, help = "List what would be dropped, without removing anything.")]
+    pub dry_run: bool,
+
     /// Enable verbose output.
     #[clap(short, long, help = "Enable verbose output.")]
     pub verbose: bool,
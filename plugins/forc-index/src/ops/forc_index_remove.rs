@@ -14,6 +14,8 @@ pub fn init(command: RemoveCommand) -> anyhow::Result<()> {
         manifest,
         url,
         auth,
+        purge,
+        dry_run,
         verbose,
         ..
     } = command;
@@ -24,7 +26,7 @@ pub fn init(command: RemoveCommand) -> anyhow::Result<()> {
     let manifest: Manifest = Manifest::from_file(manifest_path.as_path())?;
 
     let target = format!(
-        "{url}/api/index/{}/{}",
+        "{url}/api/index/{}/{}?purge={purge}&dry_run={dry_run}",
         &manifest.namespace, &manifest.identifier
     );
 
@@ -60,6 +62,16 @@ pub fn init(command: RemoveCommand) -> anyhow::Result<()> {
         .json::<Map<String, Value>>()
         .expect("Failed to read JSON response.");
 
+    if dry_run {
+        info!(
+            "\n{}\n🔍 Removing indexer '{}.{}' would drop the tables above\n",
+            to_string_pretty(&res_json["dropped_tables"])?,
+            &manifest.namespace,
+            &manifest.identifier,
+        );
+        return Ok(());
+    }
+
     if verbose {
         info!(
             "\n{}\n✅ Successfully removed indexer '{}.{}' at {} \n",
@@ -10,6 +10,7 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
         config,
         fuel_node_host,
         fuel_node_port,
+        fuel_node_fallbacks,
         graphql_api_host,
         graphql_api_port,
         database,
@@ -21,12 +22,25 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
         run_migrations,
         metrics,
         manifest,
+        replace_indexer,
         embedded_database,
         auth_enabled,
         auth_strategy,
         jwt_secret,
         jwt_issuer,
         jwt_expiry,
+        api_key_hash,
+        admin_pubkeys,
+        signature_encoding,
+        cors_allowed_origins,
+        cors_allowed_methods,
+        cors_allowed_headers,
+        tls_cert,
+        tls_key,
+        commit_window_blocks,
+        commit_window_ms,
+        wasm_memory_limit_mb,
+        wasm_fuel_limit,
         verbose,
         ..
     } = command;
@@ -64,6 +78,9 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
 
     if let Some(m) = &manifest {
         cmd.arg("--manifest").arg(m);
+        if replace_indexer {
+            cmd.arg("--replace-indexer");
+        }
     }
 
     if let Some(c) = &config {
@@ -94,6 +111,49 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
             ("--jwt-secret", jwt_secret),
             ("--jwt-issuer", jwt_issuer),
             ("--jwt-expiry", jwt_expiry.map(|x| x.to_string())),
+            ("--api-key-hash", api_key_hash),
+            (
+                "--signature-encoding",
+                Some(signature_encoding.as_ref().to_string()),
+            ),
+            (
+                "--fuel-node-fallbacks",
+                (!fuel_node_fallbacks.is_empty()).then(|| fuel_node_fallbacks.join(",")),
+            ),
+            (
+                "--admin-pubkeys",
+                (!admin_pubkeys.is_empty()).then(|| admin_pubkeys.join(",")),
+            ),
+            (
+                "--cors-allowed-origins",
+                (!cors_allowed_origins.is_empty())
+                    .then(|| cors_allowed_origins.join(",")),
+            ),
+            (
+                "--cors-allowed-methods",
+                (!cors_allowed_methods.is_empty())
+                    .then(|| cors_allowed_methods.join(",")),
+            ),
+            (
+                "--cors-allowed-headers",
+                (!cors_allowed_headers.is_empty())
+                    .then(|| cors_allowed_headers.join(",")),
+            ),
+            ("--tls-cert", tls_cert.map(|x| x.display().to_string())),
+            ("--tls-key", tls_key.map(|x| x.display().to_string())),
+            (
+                "--commit-window-blocks",
+                commit_window_blocks.map(|x| x.to_string()),
+            ),
+            (
+                "--commit-window-ms",
+                commit_window_ms.map(|x| x.to_string()),
+            ),
+            (
+                "--wasm-memory-limit-mb",
+                wasm_memory_limit_mb.map(|x| x.to_string()),
+            ),
+            ("--wasm-fuel-limit", wasm_fuel_limit.map(|x| x.to_string())),
         ];
         for (opt, value) in options.iter() {
             if let Some(value) = value {
@@ -90,6 +90,9 @@ abi: ~
 # The particular start block after which you'd like your indexer to start indexing events.
 start_block: ~
 
+# The particular end block after which you'd like your indexer to stop indexing events.
+end_block: ~
+
 # The contract_id specifies which particular contract you would like your index to subscribe to.
 contract_id: ~
 
@@ -110,6 +113,10 @@ report_metrics: true
 # The resumable field contains a boolean that specifies whether or not the indexer should, synchronise
 # with the latest block if it has fallen out of sync. 
 resumable: ~
+
+# The error_strategy field specifies what the indexer should do when a handler fails: abort,
+# skip_block, dead_letter, or retry a fixed number of times (e.g., `retry: 3`).
+error_strategy: abort
 "#
     )
 }